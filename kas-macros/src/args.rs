@@ -161,11 +161,15 @@ mod kw {
     custom_keyword!(row);
     custom_keyword!(cspan);
     custom_keyword!(rspan);
+    custom_keyword!(colspan);
+    custom_keyword!(rowspan);
     custom_keyword!(widget);
     custom_keyword!(handler);
     custom_keyword!(msg);
     custom_keyword!(generics);
     custom_keyword!(frame);
+    custom_keyword!(margins);
+    custom_keyword!(spacing);
     custom_keyword!(single);
     custom_keyword!(horizontal);
     custom_keyword!(vertical);
@@ -173,6 +177,7 @@ mod kw {
     custom_keyword!(substitutions);
     custom_keyword!(halign);
     custom_keyword!(valign);
+    custom_keyword!(stretch);
 }
 
 #[derive(Debug)]
@@ -183,6 +188,7 @@ pub struct WidgetAttrArgs {
     pub rspan: Option<Lit>,
     pub halign: Option<Ident>,
     pub valign: Option<Ident>,
+    pub stretch: Option<Ident>,
     pub handler: Option<Ident>,
 }
 
@@ -228,13 +234,35 @@ impl WidgetAttrArgs {
             Ok(None)
         }
     }
+    // Vertical alignment is not mirrored, thus `top` and `bottom` are allowed
     pub fn valign_toks(&self) -> Result<Option<TokenStream>> {
-        if let Some(ref ident) = self.valign {
-            Ok(Some(Self::match_align(ident)?))
-        } else {
-            Ok(None)
+        match self.valign {
+            Some(ref ident) if ident == "top" => Ok(Some(quote! { kas::Align::Begin })),
+            Some(ref ident) if ident == "bottom" => Ok(Some(quote! { kas::Align::End })),
+            Some(ref ident) => Ok(Some(Self::match_align(ident)?)),
+            None => Ok(None),
         }
     }
+    pub fn stretch_toks(&self) -> Result<Option<TokenStream>> {
+        let ident = match self.stretch {
+            Some(ref ident) => ident,
+            None => return Ok(None),
+        };
+        Ok(Some(match ident {
+            ident if ident == "fixed" => quote! { kas::layout::StretchPolicy::Fixed },
+            ident if ident == "filler" => quote! { kas::layout::StretchPolicy::Filler },
+            ident if ident == "low_utility" => quote! { kas::layout::StretchPolicy::LowUtility },
+            ident if ident == "maximise" || ident == "maximize" => {
+                quote! { kas::layout::StretchPolicy::Maximise }
+            }
+            ident => {
+                return Err(Error::new(
+                    ident.span(),
+                    "expected one of `fixed`, `filler`, `low_utility`, `maximise`, `maximize`",
+                ));
+            }
+        }))
+    }
 }
 
 impl Parse for WidgetAttrArgs {
@@ -246,6 +274,7 @@ impl Parse for WidgetAttrArgs {
             rspan: None,
             halign: None,
             valign: None,
+            stretch: None,
             handler: None,
         };
         if input.is_empty() {
@@ -269,10 +298,18 @@ impl Parse for WidgetAttrArgs {
                 let _: kw::cspan = content.parse()?;
                 let _: Eq = content.parse()?;
                 args.cspan = Some(content.parse()?);
+            } else if args.cspan.is_none() && lookahead.peek(kw::colspan) {
+                let _: kw::colspan = content.parse()?;
+                let _: Eq = content.parse()?;
+                args.cspan = Some(content.parse()?);
             } else if args.rspan.is_none() && lookahead.peek(kw::rspan) {
                 let _: kw::rspan = content.parse()?;
                 let _: Eq = content.parse()?;
                 args.rspan = Some(content.parse()?);
+            } else if args.rspan.is_none() && lookahead.peek(kw::rowspan) {
+                let _: kw::rowspan = content.parse()?;
+                let _: Eq = content.parse()?;
+                args.rspan = Some(content.parse()?);
             } else if args.halign.is_none() && lookahead.peek(kw::halign) {
                 let _: kw::halign = content.parse()?;
                 let _: Eq = content.parse()?;
@@ -281,6 +318,10 @@ impl Parse for WidgetAttrArgs {
                 let _: kw::valign = content.parse()?;
                 let _: Eq = content.parse()?;
                 args.valign = Some(content.parse()?);
+            } else if args.stretch.is_none() && lookahead.peek(kw::stretch) {
+                let _: kw::stretch = content.parse()?;
+                let _: Eq = content.parse()?;
+                args.stretch = Some(content.parse()?);
             } else if args.handler.is_none() && lookahead.peek(kw::handler) {
                 let _: kw::handler = content.parse()?;
                 let _: Eq = content.parse()?;
@@ -307,6 +348,7 @@ impl ToTokens for WidgetAttrArgs {
             || self.rspan.is_some()
             || self.halign.is_some()
             || self.valign.is_some()
+            || self.stretch.is_some()
             || self.handler.is_some()
         {
             let comma = TokenTree::from(Punct::new(',', Spacing::Alone));
@@ -344,6 +386,12 @@ impl ToTokens for WidgetAttrArgs {
                 }
                 args.append_all(quote! { valign = #ident });
             }
+            if let Some(ref ident) = self.stretch {
+                if !args.is_empty() {
+                    args.append(comma.clone());
+                }
+                args.append_all(quote! { stretch = #ident });
+            }
             if let Some(ref ident) = self.handler {
                 if !args.is_empty() {
                     args.append(comma);
@@ -401,11 +449,32 @@ pub enum LayoutType {
     Grid,
 }
 
+/// Margin or spacing size
+pub enum MarginSize {
+    /// Use the theme's `outer_margin`
+    Theme,
+    /// Size in logical pixels
+    Fixed(Box<Expr>),
+}
+
+impl Parse for MarginSize {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(Eq) {
+            let _: Eq = input.parse()?;
+            Ok(MarginSize::Fixed(Box::new(input.parse()?)))
+        } else {
+            Ok(MarginSize::Theme)
+        }
+    }
+}
+
 pub struct LayoutArgs {
     pub span: Span,
     pub layout: LayoutType,
     pub is_frame: bool,
     pub area: Option<Ident>,
+    pub margins: Option<MarginSize>,
+    pub spacing: Option<MarginSize>,
 }
 
 impl Parse for LayoutArgs {
@@ -445,6 +514,8 @@ impl Parse for LayoutArgs {
 
         let mut is_frame = false;
         let mut area = None;
+        let mut margins = None;
+        let mut spacing = None;
 
         while !content.is_empty() {
             let lookahead = content.lookahead1();
@@ -455,6 +526,12 @@ impl Parse for LayoutArgs {
                 let _: kw::area = content.parse()?;
                 let _: Eq = content.parse()?;
                 area = Some(content.parse()?);
+            } else if margins.is_none() && lookahead.peek(kw::margins) {
+                let _: kw::margins = content.parse()?;
+                margins = Some(content.parse()?);
+            } else if spacing.is_none() && lookahead.peek(kw::spacing) {
+                let _: kw::spacing = content.parse()?;
+                spacing = Some(content.parse()?);
             } else {
                 return Err(lookahead.error());
             }
@@ -469,6 +546,8 @@ impl Parse for LayoutArgs {
             layout,
             is_frame,
             area,
+            margins,
+            spacing,
         })
     }
 }
@@ -5,7 +5,7 @@
 
 use std::cmp::Ordering;
 
-use crate::args::{Child, LayoutArgs, LayoutType};
+use crate::args::{Child, LayoutArgs, LayoutType, MarginSize};
 use proc_macro2::TokenStream;
 use quote::{quote, TokenStreamExt};
 use syn::parse::{Error, Result};
@@ -101,12 +101,16 @@ pub(crate) fn derive(
             }
         };
 
+        let rules = match args.stretch_toks()? {
+            Some(toks) => quote! { child.size_rules(size_handle, axis).with_stretch(#toks) },
+            None => quote! { child.size_rules(size_handle, axis) },
+        };
         size.append_all(quote! {
             let child = &mut self.#ident;
             solver.for_child(
                 &mut #data,
                 #child_info,
-                |axis| child.size_rules(size_handle, axis)
+                |axis| #rules
             );
         });
 
@@ -118,7 +122,8 @@ pub(crate) fn derive(
             set_rect.append_all(quote! { align.vert = Some(#toks); });
         }
         set_rect.append_all(quote! {
-            self.#ident.set_rect(size_handle, setter.child_rect(#child_info), align);
+            let child_rect = direction.mirror(setter.child_rect(#child_info), rect);
+            self.#ident.set_rect(size_handle, child_rect, align);
         });
 
         draw.append_all(quote! {
@@ -224,7 +229,7 @@ pub(crate) fn derive(
 
     let size_post = match layout.layout {
         LayoutType::Single | LayoutType::Horizontal | LayoutType::Vertical => quote! {
-            let mut rules = solver.finish(&mut #data, iter::empty(), iter::empty());
+            let rules = solver.finish(&mut #data, iter::empty(), iter::empty());
         },
         LayoutType::Grid => {
             let mut horiz = quote! {};
@@ -247,7 +252,7 @@ pub(crate) fn derive(
             }
 
             quote! {
-                let mut rules = solver.finish(&mut #data,
+                let rules = solver.finish(&mut #data,
                     iter::empty() #horiz, iter::empty() # vert);
             }
         }
@@ -263,6 +268,35 @@ pub(crate) fn derive(
 
     let is_frame = layout.is_frame;
 
+    let margin_size = |size: &MarginSize| match size {
+        MarginSize::Theme => quote! { size_handle.outer_margin() },
+        MarginSize::Fixed(expr) => quote! {
+            kas::geom::Size::uniform(((#expr) as f32 * size_handle.dpi_factor()).round() as u32)
+        },
+    };
+    let mut margins = quote! {
+        let mut margins = if #is_frame {
+            kas::layout::Margins::outer_frame(size_handle.outer_frame())
+        } else {
+            kas::layout::Margins::ZERO
+        };
+    };
+    if let Some(ref size) = layout.margins {
+        let size = margin_size(size);
+        margins.append_all(quote! {
+            let size = #size;
+            margins.first += size;
+            margins.last += size;
+        });
+    }
+    if let Some(ref size) = layout.spacing {
+        let size = margin_size(size);
+        margins.append_all(quote! {
+            margins.inter = #size;
+        });
+    }
+    let (cols, rows) = (cols as u32, rows as u32);
+
     let fns = quote! {
         fn size_rules(
             &mut self,
@@ -275,6 +309,9 @@ pub(crate) fn derive(
             use kas::WidgetCore;
             use kas::layout::RulesSolver;
 
+            #margins
+            axis.sub_other(margins.sum(axis.is_horizontal(), #cols, #rows));
+
             let mut solver = <Self as kas::LayoutData>::Solver::new(
                 axis,
                 #dim,
@@ -283,12 +320,7 @@ pub(crate) fn derive(
             #size
             #size_post
 
-            if #is_frame {
-                let sizes = size_handle.outer_frame();
-                rules = rules + axis.extract_size(sizes.0) + axis.extract_size(sizes.1);
-            }
-
-            rules
+            rules + margins.size_rules(axis, #cols, #rows)
         }
 
         fn set_rect(
@@ -298,14 +330,11 @@ pub(crate) fn derive(
             _: kas::AlignHints,
         ) {
             use kas::{WidgetCore, Widget};
-            use kas::layout::{Margins, RulesSetter};
+            use kas::layout::RulesSetter;
             self.core_data_mut().rect = rect;
 
-            let margins = if #is_frame {
-                Margins::outer_frame(size_handle.outer_frame())
-            } else {
-                Margins::ZERO
-            };
+            #margins
+            let direction = size_handle.layout_direction();
             let mut setter = <Self as kas::LayoutData>::Setter::new(
                 rect,
                 margins,
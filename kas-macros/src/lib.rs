@@ -131,6 +131,9 @@ pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
             fn as_widget(&self) -> &dyn kas::Widget { self }
             fn as_widget_mut(&mut self) -> &mut dyn kas::Widget { self }
 
+            fn as_any(&self) -> &dyn std::any::Any { self }
+            fn as_any_mut(&mut self) -> &mut dyn std::any::Any { self }
+
             fn len(&self) -> usize {
                 #count
             }
@@ -254,10 +257,10 @@ pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
                 fn handle(&mut self, mgr: &mut kas::event::Manager, id: kas::WidgetId, event: kas::event::Event)
                 -> kas::event::Response<Self::Msg>
                 {
-                    use kas::{WidgetCore, event::Response};
+                    use kas::WidgetCore;
                     #ev_to_num {
                         debug_assert!(id == self.id(), "Handler::handle: bad WidgetId");
-                        Response::Unhandled(event)
+                        kas::event::Manager::handle_generic(self, mgr, event)
                     }
                 }
             }
@@ -8,11 +8,16 @@
 //! TODO: move traits up to kas?
 
 use std::any::Any;
+use std::cell::RefCell;
 use std::f32::consts::FRAC_PI_2;
+use std::rc::Rc;
 use wgpu_glyph::GlyphBrushBuilder;
 
-use super::{Colour, Draw, DrawPipe, FlatRound, ShadedRound, ShadedSquare, Vec2};
+use super::{
+    Colour, Draw, DrawImage, DrawPipe, FlatRound, Images, ShadedRound, ShadedSquare, Vec2,
+};
 use crate::shared::SharedState;
+use kas::draw::{ImageData, Transform};
 use kas::geom::{Coord, Rect, Size};
 use kas::theme;
 
@@ -50,8 +55,13 @@ pub trait DrawExt: Draw {
 
 impl DrawPipe {
     /// Construct
-    // TODO: do we want to share state across windows? With glyph_brush this is
-    // not trivial but with our "pipes" it shouldn't be difficult.
+    ///
+    /// The glyph brush (including its glyph cache) is shared by all windows.
+    /// Text is queued while drawing a window and flushed by [`DrawPipe::render`]
+    /// for that window, thus sharing is safe as long as windows are drawn one
+    /// at a time.
+    // TODO: our "pipes" could also share their render pipelines; only the
+    // scale uniform and vertex buffers are specific to each window.
     pub fn new<T: theme::Theme<Self>>(
         shared: &mut SharedState<T>,
         tex_format: wgpu::TextureFormat,
@@ -65,8 +75,16 @@ impl DrawPipe {
         let f = a.0 / a.1;
         let norm = [dir.1.sin() * f, -dir.1.cos() * f, 1.0];
 
-        let glyph_brush = GlyphBrushBuilder::using_fonts(shared.theme.get_fonts())
-            .build(&mut shared.device, tex_format);
+        let glyph_brush = match shared.glyph_brush.as_ref() {
+            Some(glyph_brush) => glyph_brush.clone(),
+            None => {
+                let glyph_brush = GlyphBrushBuilder::using_fonts(shared.theme.get_fonts())
+                    .build(&mut shared.device, tex_format);
+                let glyph_brush = Rc::new(RefCell::new(glyph_brush));
+                shared.glyph_brush = Some(glyph_brush.clone());
+                glyph_brush
+            }
+        };
 
         let region = Rect {
             pos: Coord::ZERO,
@@ -77,8 +95,80 @@ impl DrawPipe {
             flat_round: FlatRound::new(shared, size),
             shaded_square: ShadedSquare::new(shared, size, norm),
             shaded_round: ShadedRound::new(shared, size, norm),
+            images: Images::new(shared, size),
             glyph_brush,
+            opacity: 1.0,
+            dimming: (Colour::grey(0.0), 0.0),
+            transform: Transform::IDENTITY,
+        }
+    }
+
+    /// Get the current opacity
+    ///
+    /// This is a factor in the range `[0, 1]` applied to the alpha component
+    /// of all colours subsequently drawn.
+    #[inline]
+    pub fn opacity(&self) -> f32 {
+        self.opacity
+    }
+
+    /// Set the opacity applied to subsequent draw operations
+    ///
+    /// The value is clamped to the range `[0, 1]`.
+    #[inline]
+    pub fn set_opacity(&mut self, opacity: f32) {
+        self.opacity = opacity.max(0.0).min(1.0);
+    }
+
+    /// Get the current dimming: `(colour, amount)`
+    #[inline]
+    pub fn dimming(&self) -> (Colour, f32) {
+        self.dimming
+    }
+
+    /// Set the dimming applied to subsequent draw operations
+    ///
+    /// All colours subsequently drawn are mixed with `colour`; `amount` is
+    /// the fraction of `colour` used and is clamped to the range `[0, 1]`.
+    /// Images are not affected.
+    #[inline]
+    pub fn set_dimming(&mut self, colour: Colour, amount: f32) {
+        self.dimming = (colour, amount.max(0.0).min(1.0));
+    }
+
+    /// Apply the current opacity and dimming to a colour
+    #[inline]
+    fn fade(&self, mut col: Colour) -> Colour {
+        col.a *= self.opacity;
+        let (dim, x) = self.dimming;
+        if x > 0.0 {
+            col.r += (dim.r - col.r) * x;
+            col.g += (dim.g - col.g) * x;
+            col.b += (dim.b - col.b) * x;
         }
+        col
+    }
+
+    /// Get the current transform
+    ///
+    /// This maps the coordinates of all subsequent draw operations (including
+    /// clip regions) to window coordinates.
+    #[inline]
+    pub fn transform(&self) -> Transform {
+        self.transform
+    }
+
+    /// Set the transform applied to subsequent draw operations
+    ///
+    /// Shapes and images are transformed exactly. Clip regions are replaced by
+    /// their (axis-aligned) bounding box. Text is positioned and scaled but
+    /// not rotated.
+    pub fn set_transform(&mut self, transform: Transform) {
+        self.transform = transform;
+        self.images.set_transform(transform);
+        self.shaded_square.set_transform(transform);
+        self.shaded_round.set_transform(transform);
+        self.flat_round.set_transform(transform);
     }
 
     /// Process window resize
@@ -89,6 +179,7 @@ impl DrawPipe {
         self.flat_round.resize(device, &mut encoder, size);
         self.shaded_square.resize(device, &mut encoder, size);
         self.shaded_round.resize(device, &mut encoder, size);
+        self.images.resize(device, &mut encoder, size);
         encoder.finish()
     }
 
@@ -103,7 +194,14 @@ impl DrawPipe {
         let mut encoder = device.create_command_encoder(&desc);
         let mut load_op = wgpu::LoadOp::Clear;
 
+        // Images must be uploaded before use in a render pass
+        self.images.prepare(device, &mut encoder);
+
         // We use a separate render pass for each clipped region.
+        // TODO: label each pass and pipe with debug groups (for GPU capture
+        // tools such as RenderDoc), with a toggle. This is blocked on a wgpu
+        // upgrade: wgpu 0.4 has no debug group or label API, and the
+        // corresponding wgpu-native 0.4 functions are unimplemented stubs.
         for (pass, region) in self.clip_regions.iter().enumerate() {
             let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
@@ -125,6 +223,7 @@ impl DrawPipe {
             self.flat_round.render(device, pass, &mut rpass);
             self.shaded_square.render(device, pass, &mut rpass);
             self.shaded_round.render(device, pass, &mut rpass);
+            self.images.render(device, pass, &mut rpass);
             drop(rpass);
 
             load_op = wgpu::LoadOp::Load;
@@ -133,11 +232,13 @@ impl DrawPipe {
         // Fonts use their own render pass(es).
         let size = self.clip_regions[0].size;
         self.glyph_brush
+            .borrow_mut()
             .draw_queued(device, &mut encoder, frame_view, size.0, size.1)
             .expect("glyph_brush.draw_queued");
 
         // Keep only first clip region (which is the entire window)
         self.clip_regions.truncate(1);
+        self.images.free_unused();
 
         encoder.finish()
     }
@@ -152,25 +253,42 @@ impl Draw for DrawPipe {
     }
 
     fn add_clip_region(&mut self, region: Rect) -> usize {
+        // Scissor rects must lie within the window
+        let region = self.transform.map_rect(region);
+        let window = self.clip_regions[0];
+        let pos = region.pos.max(window.pos);
+        let end = (region.pos + region.size).min(window.pos + window.size);
+        let size = Size((end.0 - pos.0).max(0) as u32, (end.1 - pos.1).max(0) as u32);
+
         let pass = self.clip_regions.len();
-        self.clip_regions.push(region);
+        self.clip_regions.push(Rect::new(pos, size));
         pass
     }
 
     #[inline]
     fn rect(&mut self, region: Self::Region, rect: Rect, col: Colour) {
+        let col = self.fade(col);
         self.shaded_square.rect(region, rect, col);
     }
 
     #[inline]
     fn frame(&mut self, region: Self::Region, outer: Rect, inner: Rect, col: Colour) {
+        let col = self.fade(col);
         self.shaded_square.frame(region, outer, inner, col);
     }
 }
 
+impl DrawImage for DrawPipe {
+    #[inline]
+    fn image(&mut self, pass: usize, image: &ImageData, rect: Rect) {
+        self.images.image(pass, image, rect, self.opacity);
+    }
+}
+
 impl DrawExt for DrawPipe {
     #[inline]
     fn rounded_frame(&mut self, pass: usize, outer: Rect, inner: Rect, col: Colour) {
+        let col = self.fade(col);
         self.flat_round.rounded_frame(pass, outer, inner, col);
     }
 
@@ -183,6 +301,7 @@ impl DrawExt for DrawPipe {
         style: ShadeStyle,
         col: Colour,
     ) {
+        let col = self.fade(col);
         match style {
             ShadeStyle::Square(norm) => self
                 .shaded_square
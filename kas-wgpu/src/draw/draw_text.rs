@@ -9,6 +9,7 @@ use std::borrow::Cow;
 use wgpu_glyph::{GlyphCruncher, VariedSection};
 
 use super::{DrawPipe, Vec2};
+use kas::draw::Transform;
 
 /// Abstraction over text rendering
 ///
@@ -45,7 +46,33 @@ impl DrawText for DrawPipe {
     where
         S: Into<Cow<'a, VariedSection<'a>>>,
     {
-        self.glyph_brush.queue(section)
+        let mut section = section.into();
+        if self.opacity < 1.0 {
+            for text in section.to_mut().text.iter_mut() {
+                text.color[3] *= self.opacity;
+            }
+        }
+        let (dim, x) = self.dimming;
+        if x > 0.0 {
+            for text in section.to_mut().text.iter_mut() {
+                text.color[0] += (dim.r - text.color[0]) * x;
+                text.color[1] += (dim.g - text.color[1]) * x;
+                text.color[2] += (dim.b - text.color[2]) * x;
+            }
+        }
+        let t = self.transform;
+        if t != Transform::IDENTITY {
+            let section = section.to_mut();
+            section.screen_position = t.map_point(section.screen_position);
+            // Glyphs are positioned and scaled, but not rotated
+            let scale = t.scale_factor();
+            section.bounds = (section.bounds.0 * scale, section.bounds.1 * scale);
+            for text in section.text.iter_mut() {
+                text.scale.x *= scale;
+                text.scale.y *= scale;
+            }
+        }
+        self.glyph_brush.borrow_mut().queue(section)
     }
 
     #[inline]
@@ -54,6 +81,7 @@ impl DrawText for DrawPipe {
         S: Into<Cow<'a, VariedSection<'a>>>,
     {
         self.glyph_brush
+            .borrow_mut()
             .glyph_bounds(section)
             .map(|rect| (Vec2(rect.min.x, rect.min.y), Vec2(rect.max.x, rect.max.y)))
     }
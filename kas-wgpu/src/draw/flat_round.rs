@@ -7,13 +7,14 @@
 
 use std::mem::size_of;
 
-use crate::draw::{Colour, Rgb, Vec2};
+use crate::draw::{Colour, Rgba, Vec2};
 use crate::shared::SharedState;
+use kas::draw::Transform;
 use kas::geom::{Rect, Size};
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug)]
-struct Vertex(Vec2, Rgb, Vec2, Vec2);
+struct Vertex(Vec2, Rgba, Vec2, Vec2);
 
 /// A pipeline for rendering rounded shapes
 pub struct FlatRound {
@@ -21,6 +22,7 @@ pub struct FlatRound {
     scale_buf: wgpu::Buffer,
     render_pipeline: wgpu::RenderPipeline,
     passes: Vec<Vec<Vertex>>,
+    transform: Transform,
 }
 
 impl FlatRound {
@@ -102,18 +104,18 @@ impl FlatRound {
                         shader_location: 0,
                     },
                     wgpu::VertexAttributeDescriptor {
-                        format: wgpu::VertexFormat::Float3,
+                        format: wgpu::VertexFormat::Float4,
                         offset: size_of::<Vec2>() as u64,
                         shader_location: 1,
                     },
                     wgpu::VertexAttributeDescriptor {
                         format: wgpu::VertexFormat::Float2,
-                        offset: (size_of::<Vec2>() + size_of::<Rgb>()) as u64,
+                        offset: (size_of::<Vec2>() + size_of::<Rgba>()) as u64,
                         shader_location: 2,
                     },
                     wgpu::VertexAttributeDescriptor {
                         format: wgpu::VertexFormat::Float2,
-                        offset: (2 * size_of::<Vec2>() + size_of::<Rgb>()) as u64,
+                        offset: (2 * size_of::<Vec2>() + size_of::<Rgba>()) as u64,
                         shader_location: 3,
                     },
                 ],
@@ -128,9 +130,15 @@ impl FlatRound {
             scale_buf,
             render_pipeline,
             passes: vec![],
+            transform: Transform::IDENTITY,
         }
     }
 
+    /// Set the transform applied to vertex positions
+    pub fn set_transform(&mut self, transform: Transform) {
+        self.transform = transform;
+    }
+
     pub fn resize(
         &mut self,
         device: &wgpu::Device,
@@ -265,6 +273,15 @@ impl FlatRound {
             self.passes.resize(pass + 8, vec![]);
         }
 
-        self.passes[pass].extend_from_slice(slice);
+        if self.transform == Transform::IDENTITY {
+            self.passes[pass].extend_from_slice(slice);
+        } else {
+            let t = self.transform;
+            self.passes[pass].extend(slice.iter().map(|v| {
+                let mut v = *v;
+                v.0 = t.map_point(v.0.into()).into();
+                v
+            }));
+        }
     }
 }
@@ -0,0 +1,347 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Image pipeline
+
+use std::collections::HashMap;
+use std::mem::size_of;
+
+use crate::draw::Vec2;
+use crate::shared::SharedState;
+use kas::draw::{ImageData, ImageId, Transform};
+use kas::geom::{Rect, Size};
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+struct Vertex(Vec2, Vec2, f32);
+
+/// An uploaded image
+struct Texture {
+    _texture: wgpu::Texture,
+    _view: wgpu::TextureView,
+    bind_group: wgpu::BindGroup,
+    used: bool,
+}
+
+/// A pipeline for rendering images
+///
+/// Image data is uploaded to a texture on first use. Textures not used while
+/// drawing a frame are freed after that frame is rendered.
+pub struct Images {
+    bind_group: wgpu::BindGroup,
+    scale_buf: wgpu::Buffer,
+    tex_bind_group_layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    render_pipeline: wgpu::RenderPipeline,
+    textures: HashMap<ImageId, Texture>,
+    pending: Vec<ImageData>,
+    passes: Vec<Vec<(ImageId, [Vertex; 6])>>,
+    transform: Transform,
+}
+
+impl Images {
+    /// Construct
+    pub fn new<T>(shared: &SharedState<T>, size: Size) -> Self {
+        let device = &shared.device;
+
+        type Scale = [f32; 2];
+        let scale_factor: Scale = [2.0 / size.0 as f32, 2.0 / size.1 as f32];
+        let scale_buf = device
+            .create_buffer_mapped(
+                scale_factor.len(),
+                wgpu::BufferUsage::UNIFORM | wgpu::BufferUsage::COPY_DST,
+            )
+            .fill_from_slice(&scale_factor);
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            bindings: &[wgpu::BindGroupLayoutBinding {
+                binding: 0,
+                visibility: wgpu::ShaderStage::VERTEX,
+                ty: wgpu::BindingType::UniformBuffer { dynamic: false },
+            }],
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &bind_group_layout,
+            bindings: &[wgpu::Binding {
+                binding: 0,
+                resource: wgpu::BindingResource::Buffer {
+                    buffer: &scale_buf,
+                    range: 0..(size_of::<Scale>() as u64),
+                },
+            }],
+        });
+
+        let tex_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                bindings: &[
+                    wgpu::BindGroupLayoutBinding {
+                        binding: 0,
+                        visibility: wgpu::ShaderStage::FRAGMENT,
+                        ty: wgpu::BindingType::SampledTexture {
+                            multisampled: false,
+                            dimension: wgpu::TextureViewDimension::D2,
+                        },
+                    },
+                    wgpu::BindGroupLayoutBinding {
+                        binding: 1,
+                        visibility: wgpu::ShaderStage::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler,
+                    },
+                ],
+            });
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            mipmap_filter: wgpu::FilterMode::Nearest,
+            lod_min_clamp: -100.0,
+            lod_max_clamp: 100.0,
+            compare_function: wgpu::CompareFunction::Always,
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            bind_group_layouts: &[&bind_group_layout, &tex_bind_group_layout],
+        });
+
+        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            layout: &pipeline_layout,
+            vertex_stage: wgpu::ProgrammableStageDescriptor {
+                module: &shared.shaders.vert_image,
+                entry_point: "main",
+            },
+            fragment_stage: Some(wgpu::ProgrammableStageDescriptor {
+                module: &shared.shaders.frag_image,
+                entry_point: "main",
+            }),
+            rasterization_state: Some(wgpu::RasterizationStateDescriptor {
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: wgpu::CullMode::None,
+                depth_bias: 0,
+                depth_bias_slope_scale: 0.0,
+                depth_bias_clamp: 0.0,
+            }),
+            primitive_topology: wgpu::PrimitiveTopology::TriangleList,
+            color_states: &[wgpu::ColorStateDescriptor {
+                format: wgpu::TextureFormat::Bgra8UnormSrgb,
+                color_blend: wgpu::BlendDescriptor {
+                    src_factor: wgpu::BlendFactor::SrcAlpha,
+                    dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+                    operation: wgpu::BlendOperation::Add,
+                },
+                alpha_blend: wgpu::BlendDescriptor {
+                    src_factor: wgpu::BlendFactor::Zero,
+                    dst_factor: wgpu::BlendFactor::One,
+                    operation: wgpu::BlendOperation::Add,
+                },
+                write_mask: wgpu::ColorWrite::ALL,
+            }],
+            depth_stencil_state: None,
+            index_format: wgpu::IndexFormat::Uint16,
+            vertex_buffers: &[wgpu::VertexBufferDescriptor {
+                stride: size_of::<Vertex>() as wgpu::BufferAddress,
+                step_mode: wgpu::InputStepMode::Vertex,
+                attributes: &[
+                    wgpu::VertexAttributeDescriptor {
+                        format: wgpu::VertexFormat::Float2,
+                        offset: 0,
+                        shader_location: 0,
+                    },
+                    wgpu::VertexAttributeDescriptor {
+                        format: wgpu::VertexFormat::Float2,
+                        offset: size_of::<Vec2>() as u64,
+                        shader_location: 1,
+                    },
+                    wgpu::VertexAttributeDescriptor {
+                        format: wgpu::VertexFormat::Float,
+                        offset: (2 * size_of::<Vec2>()) as u64,
+                        shader_location: 2,
+                    },
+                ],
+            }],
+            sample_count: 1,
+            sample_mask: !0,
+            alpha_to_coverage_enabled: false,
+        });
+
+        Images {
+            bind_group,
+            scale_buf,
+            tex_bind_group_layout,
+            sampler,
+            render_pipeline,
+            textures: HashMap::new(),
+            pending: vec![],
+            passes: vec![],
+            transform: Transform::IDENTITY,
+        }
+    }
+
+    /// Set the transform applied to vertex positions
+    pub fn set_transform(&mut self, transform: Transform) {
+        self.transform = transform;
+    }
+
+    pub fn resize(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        size: Size,
+    ) {
+        type Scale = [f32; 2];
+        let scale_factor: Scale = [2.0 / size.0 as f32, 2.0 / size.1 as f32];
+        let scale_buf = device
+            .create_buffer_mapped(scale_factor.len(), wgpu::BufferUsage::COPY_SRC)
+            .fill_from_slice(&scale_factor);
+        let byte_len = size_of::<Scale>() as u64;
+
+        encoder.copy_buffer_to_buffer(&scale_buf, 0, &self.scale_buf, 0, byte_len);
+    }
+
+    /// Upload pending images
+    ///
+    /// This must be called before any render pass using the images begins.
+    pub fn prepare(&mut self, device: &wgpu::Device, encoder: &mut wgpu::CommandEncoder) {
+        for image in self.pending.drain(..) {
+            let size = image.size();
+            let extent = wgpu::Extent3d {
+                width: size.0,
+                height: size.1,
+                depth: 1,
+            };
+            let texture = device.create_texture(&wgpu::TextureDescriptor {
+                size: extent,
+                array_layer_count: 1,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8UnormSrgb,
+                usage: wgpu::TextureUsage::SAMPLED | wgpu::TextureUsage::COPY_DST,
+            });
+
+            let data = image.rgba();
+            let buffer = device
+                .create_buffer_mapped(data.len(), wgpu::BufferUsage::COPY_SRC)
+                .fill_from_slice(data);
+            encoder.copy_buffer_to_texture(
+                wgpu::BufferCopyView {
+                    buffer: &buffer,
+                    offset: 0,
+                    row_pitch: 4 * size.0,
+                    image_height: size.1,
+                },
+                wgpu::TextureCopyView {
+                    texture: &texture,
+                    mip_level: 0,
+                    array_layer: 0,
+                    origin: wgpu::Origin3d {
+                        x: 0.0,
+                        y: 0.0,
+                        z: 0.0,
+                    },
+                },
+                extent,
+            );
+
+            let view = texture.create_default_view();
+            let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                layout: &self.tex_bind_group_layout,
+                bindings: &[
+                    wgpu::Binding {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(&view),
+                    },
+                    wgpu::Binding {
+                        binding: 1,
+                        resource: wgpu::BindingResource::Sampler(&self.sampler),
+                    },
+                ],
+            });
+
+            let texture = Texture {
+                _texture: texture,
+                _view: view,
+                bind_group,
+                used: true,
+            };
+            self.textures.insert(image.id(), texture);
+        }
+    }
+
+    /// Render queued images and clear the queue
+    pub fn render(&mut self, device: &wgpu::Device, pass: usize, rpass: &mut wgpu::RenderPass) {
+        if pass >= self.passes.len() || self.passes[pass].is_empty() {
+            return;
+        }
+        let queue = &mut self.passes[pass];
+        let v: Vec<Vertex> = queue.iter().flat_map(|(_, v)| v.iter().cloned()).collect();
+        let buffer = device
+            .create_buffer_mapped(v.len(), wgpu::BufferUsage::VERTEX)
+            .fill_from_slice(&v);
+
+        rpass.set_pipeline(&self.render_pipeline);
+        rpass.set_bind_group(0, &self.bind_group, &[]);
+        rpass.set_vertex_buffers(0, &[(&buffer, 0)]);
+        for (i, (id, _)) in queue.iter().enumerate() {
+            if let Some(texture) = self.textures.get(id) {
+                let start = 6 * i as u32;
+                rpass.set_bind_group(1, &texture.bind_group, &[]);
+                rpass.draw(start..(start + 6), 0..1);
+            }
+        }
+
+        queue.clear();
+    }
+
+    /// Free textures not used since the last call
+    pub fn free_unused(&mut self) {
+        self.textures.retain(|_, texture| texture.used);
+        for texture in self.textures.values_mut() {
+            texture.used = false;
+        }
+    }
+
+    /// Add an image to the buffer
+    pub fn image(&mut self, pass: usize, image: &ImageData, rect: Rect, alpha: f32) {
+        let aa = Vec2::from(rect.pos);
+        let bb = aa + Vec2::from(rect.size);
+        if !aa.lt(bb) || image.size().0 == 0 || image.size().1 == 0 {
+            // zero / negative size: nothing to draw
+            return;
+        }
+
+        let id = image.id();
+        match self.textures.get_mut(&id) {
+            Some(texture) => texture.used = true,
+            None => {
+                if !self.pending.iter().any(|pending| pending.id() == id) {
+                    self.pending.push(image.clone());
+                }
+            }
+        }
+
+        let t = self.transform;
+        let ab = Vec2::from(t.map_point((aa.0, bb.1)));
+        let ba = Vec2::from(t.map_point((bb.0, aa.1)));
+        let (aa, bb) = (
+            Vec2::from(t.map_point(aa.into())),
+            Vec2::from(t.map_point(bb.into())),
+        );
+        let (taa, tbb) = (Vec2(0.0, 0.0), Vec2(1.0, 1.0));
+        let (tab, tba) = (Vec2(0.0, 1.0), Vec2(1.0, 0.0));
+
+        if self.passes.len() <= pass {
+            // We only need one more, but no harm in adding extra
+            self.passes.resize(pass + 8, vec![]);
+        }
+
+        #[rustfmt::skip]
+        self.passes[pass].push((id, [
+            Vertex(aa, taa, alpha), Vertex(ba, tba, alpha), Vertex(ab, tab, alpha),
+            Vertex(ab, tab, alpha), Vertex(ba, tba, alpha), Vertex(bb, tbb, alpha),
+        ]));
+    }
+}
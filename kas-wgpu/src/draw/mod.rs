@@ -12,39 +12,47 @@
 mod draw_pipe;
 mod draw_text;
 mod flat_round;
+mod images;
 mod shaded_round;
 mod shaded_square;
 mod shaders;
 mod vector;
 
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use kas::draw::Transform;
 use kas::geom::Rect;
 use wgpu_glyph::GlyphBrush;
 
 pub(crate) use flat_round::FlatRound;
+pub(crate) use images::Images;
 pub(crate) use shaded_round::ShadedRound;
 pub(crate) use shaded_square::ShadedSquare;
 pub(crate) use shaders::ShaderManager;
 
 pub use draw_pipe::{DrawExt, ShadeStyle};
 pub use draw_text::DrawText;
-pub use kas::draw::{Colour, Draw};
+pub use kas::draw::{Colour, Draw, DrawImage};
 pub use vector::{Quad, Vec2};
 
-/// 3-part colour data
+/// 4-part colour data (with alpha)
 #[repr(C)]
 #[derive(Clone, Copy, Debug)]
-pub(crate) struct Rgb {
+pub(crate) struct Rgba {
     pub r: f32,
     pub g: f32,
     pub b: f32,
+    pub a: f32,
 }
 
-impl From<kas::draw::Colour> for Rgb {
+impl From<kas::draw::Colour> for Rgba {
     fn from(c: kas::draw::Colour) -> Self {
-        Rgb {
+        Rgba {
             r: c.r,
             g: c.g,
             b: c.b,
+            a: c.a,
         }
     }
 }
@@ -55,5 +63,9 @@ pub struct DrawPipe {
     flat_round: FlatRound,
     shaded_round: ShadedRound,
     shaded_square: ShadedSquare,
-    glyph_brush: GlyphBrush<'static, ()>,
+    images: Images,
+    glyph_brush: Rc<RefCell<GlyphBrush<'static, ()>>>,
+    opacity: f32,
+    dimming: (Colour, f32),
+    transform: Transform,
 }
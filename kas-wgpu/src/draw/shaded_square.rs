@@ -8,13 +8,14 @@
 use std::f32;
 use std::mem::size_of;
 
-use crate::draw::{Colour, Rgb, Vec2};
+use crate::draw::{Colour, Rgba, Vec2};
 use crate::shared::SharedState;
+use kas::draw::Transform;
 use kas::geom::{Rect, Size};
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug)]
-struct Vertex(Vec2, Rgb, Vec2);
+struct Vertex(Vec2, Rgba, Vec2);
 
 /// A pipeline for rendering with flat and square-corner shading
 pub struct ShadedSquare {
@@ -22,6 +23,7 @@ pub struct ShadedSquare {
     scale_buf: wgpu::Buffer,
     render_pipeline: wgpu::RenderPipeline,
     passes: Vec<Vec<Vertex>>,
+    transform: Transform,
 }
 
 impl ShadedSquare {
@@ -101,8 +103,16 @@ impl ShadedSquare {
             primitive_topology: wgpu::PrimitiveTopology::TriangleList,
             color_states: &[wgpu::ColorStateDescriptor {
                 format: wgpu::TextureFormat::Bgra8UnormSrgb,
-                color_blend: wgpu::BlendDescriptor::REPLACE,
-                alpha_blend: wgpu::BlendDescriptor::REPLACE,
+                color_blend: wgpu::BlendDescriptor {
+                    src_factor: wgpu::BlendFactor::SrcAlpha,
+                    dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+                    operation: wgpu::BlendOperation::Add,
+                },
+                alpha_blend: wgpu::BlendDescriptor {
+                    src_factor: wgpu::BlendFactor::Zero,
+                    dst_factor: wgpu::BlendFactor::One,
+                    operation: wgpu::BlendOperation::Add,
+                },
                 write_mask: wgpu::ColorWrite::ALL,
             }],
             depth_stencil_state: None,
@@ -117,13 +127,13 @@ impl ShadedSquare {
                         shader_location: 0,
                     },
                     wgpu::VertexAttributeDescriptor {
-                        format: wgpu::VertexFormat::Float3,
+                        format: wgpu::VertexFormat::Float4,
                         offset: size_of::<Vec2>() as u64,
                         shader_location: 1,
                     },
                     wgpu::VertexAttributeDescriptor {
                         format: wgpu::VertexFormat::Float2,
-                        offset: (size_of::<Vec2>() + size_of::<Rgb>()) as u64,
+                        offset: (size_of::<Vec2>() + size_of::<Rgba>()) as u64,
                         shader_location: 2,
                     },
                 ],
@@ -138,9 +148,15 @@ impl ShadedSquare {
             scale_buf,
             render_pipeline,
             passes: vec![],
+            transform: Transform::IDENTITY,
         }
     }
 
+    /// Set the transform applied to vertex positions
+    pub fn set_transform(&mut self, transform: Transform) {
+        self.transform = transform;
+    }
+
     pub fn resize(
         &mut self,
         device: &wgpu::Device,
@@ -274,6 +290,15 @@ impl ShadedSquare {
             self.passes.resize(pass + 8, vec![]);
         }
 
-        self.passes[pass].extend_from_slice(slice);
+        if self.transform == Transform::IDENTITY {
+            self.passes[pass].extend_from_slice(slice);
+        } else {
+            let t = self.transform;
+            self.passes[pass].extend(slice.iter().map(|v| {
+                let mut v = *v;
+                v.0 = t.map_point(v.0.into()).into();
+                v
+            }));
+        }
     }
 }
@@ -18,9 +18,11 @@ pub struct ShaderManager {
     pub vert_32: ShaderModule,
     pub vert_322: ShaderModule,
     pub vert_3222: ShaderModule,
+    pub vert_image: ShaderModule,
     pub frag_flat_round: ShaderModule,
     pub frag_shaded_square: ShaderModule,
     pub frag_shaded_round: ShaderModule,
+    pub frag_image: ShaderModule,
 }
 
 impl ShaderManager {
@@ -42,6 +44,11 @@ impl ShaderManager {
         let artifact = compiler.compile_into_spirv(source, Vertex, fname, "main", None)?;
         let vert_3222 = device.create_shader_module(&artifact.as_binary());
 
+        let fname = "shaders/image.vert";
+        let source = include_str!("shaders/image.vert");
+        let artifact = compiler.compile_into_spirv(source, Vertex, fname, "main", None)?;
+        let vert_image = device.create_shader_module(&artifact.as_binary());
+
         let fname = "shaders/flat_round.frag";
         let source = include_str!("shaders/flat_round.frag");
         let artifact = compiler.compile_into_spirv(source, Fragment, fname, "main", None)?;
@@ -57,13 +64,20 @@ impl ShaderManager {
         let artifact = compiler.compile_into_spirv(source, Fragment, fname, "main", None)?;
         let frag_shaded_round = device.create_shader_module(&artifact.as_binary());
 
+        let fname = "shaders/image.frag";
+        let source = include_str!("shaders/image.frag");
+        let artifact = compiler.compile_into_spirv(source, Fragment, fname, "main", None)?;
+        let frag_image = device.create_shader_module(&artifact.as_binary());
+
         Ok(ShaderManager {
             vert_32,
             vert_322,
             vert_3222,
+            vert_image,
             frag_flat_round,
             frag_shaded_square,
             frag_shaded_round,
+            frag_image,
         })
     }
 }
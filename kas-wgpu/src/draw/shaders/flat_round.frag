@@ -8,7 +8,7 @@
 
 precision mediump float;
 
-layout(location = 0) in vec3 fragColor;
+layout(location = 0) in vec4 fragColor;
 layout(location = 1) in vec2 dir;
 layout(location = 2) in vec2 off;
 
@@ -29,5 +29,5 @@ void main() {
         + sample_a(dir + off2)
         + sample_a(dir - off2);
 
-    outColor = vec4(fragColor, alpha);
+    outColor = vec4(fragColor.rgb, fragColor.a * alpha);
 }
@@ -7,10 +7,10 @@
 #extension GL_ARB_separate_shader_objects : enable
 
 layout(location = 0) in vec2 a_pos;
-layout(location = 1) in vec3 a_col;
+layout(location = 1) in vec4 a_col;
 layout(location = 2) in vec2 a1;
 
-layout(location = 0) out vec3 b_col;
+layout(location = 0) out vec4 b_col;
 layout(location = 1) out vec2 b1;
 
 layout(set = 0, binding = 0) uniform Locals {
@@ -7,19 +7,29 @@
 
 use log::{debug, error, trace};
 use smallvec::SmallVec;
+use std::any::Any;
 use std::collections::HashMap;
-use std::time::Instant;
+use std::mem::take;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
 
 use winit::event::{Event, StartCause};
 use winit::event_loop::{ControlFlow, EventLoopWindowTarget};
 use winit::window as ww;
 
+use kas::event::DragData;
+use kas::geom::Coord;
 use kas::{theme, TkAction};
 
 use crate::draw::DrawPipe;
+use crate::options::frame_time;
+use crate::power::PowerState;
 use crate::shared::{PendingAction, SharedState};
 use crate::{ProxyAction, Window, WindowId};
 
+/// Maximum delay between a drag ending and the target window being entered
+const DROP_TIMEOUT: Duration = Duration::from_millis(200);
+
 /// Event-loop data structure (i.e. all run-time state)
 pub(crate) struct Loop<T: theme::Theme<DrawPipe>> {
     /// Window states
@@ -30,6 +40,19 @@ pub(crate) struct Loop<T: theme::Theme<DrawPipe>> {
     shared: SharedState<T>,
     /// Timer resumes: (time, window index)
     resumes: Vec<(Instant, ww::WindowId)>,
+    /// Modal windows: owner (whose input is blocked) to a stack of modals
+    ///
+    /// Entries are removed when their stack becomes empty.
+    modals: HashMap<ww::WindowId, Vec<ww::WindowId>>,
+    /// The window which last received `CursorEntered` (unless since left)
+    hovered: Option<ww::WindowId>,
+    /// A drop awaiting `CursorEntered` on its target: (time, source, data,
+    /// screen coord)
+    ///
+    /// While a drag is in progress the cursor is grabbed by the source
+    /// window, thus the target window may only learn of the cursor after the
+    /// drag ends.
+    pending_drop: Option<(Instant, ww::WindowId, DragData, Coord)>,
 }
 
 impl<T: theme::Theme<DrawPipe>> Loop<T> {
@@ -43,6 +66,58 @@ impl<T: theme::Theme<DrawPipe>> Loop<T> {
             id_map,
             shared,
             resumes: vec![],
+            modals: HashMap::new(),
+            hovered: None,
+            pending_drop: None,
+        }
+    }
+
+    /// Deliver a drop to `target` if hovered at `screen` and able to receive it
+    ///
+    /// Window stacking order is unknown, thus only the hovered window may
+    /// receive a drop. Returns `data` if not delivered.
+    fn drop_on(
+        &mut self,
+        target: ww::WindowId,
+        source: ww::WindowId,
+        data: DragData,
+        screen: Coord,
+    ) -> Result<TkAction, DragData> {
+        if target == source || self.modals.contains_key(&target) {
+            return Err(data);
+        }
+        let window = match self.windows.get_mut(&target) {
+            Some(window) => window,
+            None => return Err(data),
+        };
+        match window.screen_rect() {
+            Some(rect) if rect.contains(screen) => {
+                Ok(window.handle_drop(&mut self.shared, data, screen - rect.pos))
+            }
+            _ => Err(data),
+        }
+    }
+
+    /// Create and init() a new window
+    fn add_window(
+        &mut self,
+        elwt: &EventLoopWindowTarget<ProxyAction>,
+        id: WindowId,
+        widget: Box<dyn kas::Window>,
+    ) -> Option<(ww::WindowId, TkAction)> {
+        debug!("Adding window {}", widget.title());
+        match Window::new(&mut self.shared, elwt, widget) {
+            Ok(mut window) => {
+                let wid = window.window.id();
+                let action = window.init(&mut self.shared);
+                self.id_map.insert(id, wid);
+                self.windows.insert(wid, window);
+                Some((wid, action))
+            }
+            Err(e) => {
+                error!("Unable to create window: {}", e);
+                None
+            }
         }
     }
 
@@ -72,6 +147,27 @@ impl<T: theme::Theme<DrawPipe>> Loop<T> {
 
         match event {
             WindowEvent { window_id, event } => {
+                match event {
+                    winit::event::WindowEvent::CursorEntered { .. } => {
+                        self.hovered = Some(window_id);
+                        if let Some((time, source, data, screen)) = self.pending_drop.take() {
+                            if time.elapsed() < DROP_TIMEOUT {
+                                if let Ok(action) = self.drop_on(window_id, source, data, screen) {
+                                    actions.push((window_id, action));
+                                }
+                            }
+                        }
+                    }
+                    winit::event::WindowEvent::CursorLeft { .. } => {
+                        if self.hovered == Some(window_id) {
+                            self.hovered = None;
+                        }
+                    }
+                    _ => (),
+                }
+                if self.modals.contains_key(&window_id) && is_input(&event) {
+                    return;
+                }
                 if let Some(window) = self.windows.get_mut(&window_id) {
                     let (action, resume) = window.handle_event(&mut self.shared, event);
                     actions.push((window_id, action));
@@ -100,6 +196,45 @@ impl<T: theme::Theme<DrawPipe>> Loop<T> {
                         .pending
                         .push(PendingAction::Update(handle, payload));
                 }
+                ProxyAction::SendTo(id, w_id, msg) => {
+                    let window = self.id_map.get(&id).cloned();
+                    if let Some(window) = window.and_then(|id| self.windows.get_mut(&id)) {
+                        let msg: Rc<dyn Any + Send> = Rc::from(msg);
+                        window.send_to(w_id, msg);
+                    }
+                }
+                ProxyAction::SetPresentMode(mode) => {
+                    self.shared.present_mode = mode;
+                    for (_, window) in self.windows.iter_mut() {
+                        window.update_present_mode(&mut self.shared);
+                    }
+                }
+                ProxyAction::SetFrameRateLimit(limit) => {
+                    self.shared.frame_time = frame_time(limit);
+                }
+                ProxyAction::SetUiScale(scale) => {
+                    self.shared.set_ui_scale(scale);
+                }
+                ProxyAction::SetFontSize(size) => {
+                    self.shared.adjust_theme(|theme| theme.set_font_size(size));
+                }
+                ProxyAction::SetColours(scheme) => {
+                    self.shared.adjust_theme(|theme| theme.set_colours(&scheme));
+                }
+                ProxyAction::SetColourScheme(scheme) => {
+                    self.shared
+                        .adjust_theme(|theme| theme.set_colour_scheme(scheme));
+                }
+                ProxyAction::SetTheme(name) => {
+                    self.shared.adjust_theme(|theme| theme.set_theme(&name));
+                }
+                ProxyAction::SetThemeConfig(config) => {
+                    self.shared
+                        .adjust_theme(|theme| theme.apply_config(&config));
+                }
+                ProxyAction::Poll(task) => {
+                    self.shared.pending.push(PendingAction::Poll(task));
+                }
             },
 
             NewEvents(cause) => {
@@ -155,52 +290,104 @@ impl<T: theme::Theme<DrawPipe>> Loop<T> {
 
             RedrawRequested(id) => {
                 if let Some(window) = self.windows.get_mut(&id) {
+                    if self.shared.stats.power_state() == PowerState::Idle {
+                        trace!("Drawing window while idle");
+                    }
                     window.do_draw(&mut self.shared);
                 }
             }
 
-            MainEventsCleared | RedrawEventsCleared | LoopDestroyed | Suspended | Resumed => return,
-        };
+            MainEventsCleared => {
+                // Input events are coalesced until all available input has
+                // been handled, thus we deliver these once per frame.
+                for (id, window) in self.windows.iter_mut() {
+                    let (action, resume) = window.flush_pending(&mut self.shared);
+                    actions.push((*id, action));
+                    if let Some(instant) = resume {
+                        add_resume(&mut self.resumes, instant, *id);
+                        have_new_resumes = true;
+                    }
+                }
+            }
 
-        // Create and init() any new windows.
-        while let Some(pending) = self.shared.pending.pop() {
-            match pending {
-                PendingAction::AddWindow(id, widget) => {
-                    debug!("Adding window {}", widget.title());
-                    match Window::new(&mut self.shared, elwt, widget) {
-                        Ok(mut window) => {
-                            let wid = window.window.id();
+            RedrawEventsCleared | LoopDestroyed | Suspended | Resumed => return,
+        };
 
-                            let action = window.init(&mut self.shared);
+        // Create and init() any new windows. Pending actions are handled in
+        // the order queued (including any queued while handling these).
+        while !self.shared.pending.is_empty() {
+            for pending in take(&mut self.shared.pending) {
+                match pending {
+                    PendingAction::AddWindow(id, widget) => {
+                        if let Some(item) = self.add_window(elwt, id, widget) {
+                            actions.push(item);
+                        }
+                    }
+                    PendingAction::AddModalWindow(id, widget, owner) => {
+                        if let Some((wid, action)) = self.add_window(elwt, id, widget) {
                             actions.push((wid, action));
-
-                            self.id_map.insert(id, wid);
-                            self.windows.insert(wid, window);
+                            self.modals.entry(owner).or_default().push(wid);
+                            if let Some(window) = self.windows.get_mut(&owner) {
+                                window.set_blocked(true);
+                            }
                         }
-                        Err(e) => {
-                            error!("Unable to create window: {}", e);
+                    }
+                    PendingAction::CloseWindow(id) => {
+                        if let Some(id) = self.id_map.get(&id) {
+                            actions.push((*id, TkAction::Close));
                         }
-                    };
-                }
-                PendingAction::CloseWindow(id) => {
-                    if let Some(id) = self.id_map.get(&id) {
-                        actions.push((*id, TkAction::Close));
                     }
-                }
-                PendingAction::ThemeResize => {
-                    for (_, window) in self.windows.iter_mut() {
-                        window.theme_resize(&self.shared);
+                    PendingAction::ThemeResize => {
+                        for (_, window) in self.windows.iter_mut() {
+                            window.theme_resize(&self.shared);
+                        }
                     }
-                }
-                PendingAction::RedrawAll => {
-                    for (_, window) in self.windows.iter_mut() {
-                        window.window.request_redraw();
+                    PendingAction::RedrawAll => {
+                        for (_, window) in self.windows.iter_mut() {
+                            window.redraw_all();
+                        }
                     }
-                }
-                PendingAction::Update(handle, payload) => {
-                    for (id, window) in self.windows.iter_mut() {
-                        let action = window.update_handle(&mut self.shared, handle, payload);
-                        actions.push((*id, action));
+                    PendingAction::Update(handle, payload) => {
+                        for (id, window) in self.windows.iter_mut() {
+                            let action = window.update_handle(&mut self.shared, handle, payload);
+                            actions.push((*id, action));
+                        }
+                    }
+                    PendingAction::Msg(msg) => {
+                        for (id, window) in self.windows.iter_mut() {
+                            let action = window.inspect_msg(&mut self.shared, &*msg);
+                            actions.push((*id, action));
+                        }
+                    }
+                    PendingAction::Drop(source, data, coord) => {
+                        // Translate to screen coordinates, then find the target
+                        let screen = match self.windows.get(&source).and_then(|w| w.screen_rect()) {
+                            Some(rect) => rect.pos + coord,
+                            None => continue,
+                        };
+                        let result = match self.hovered {
+                            Some(target) => self
+                                .drop_on(target, source, data, screen)
+                                .map(|action| (target, action)),
+                            None => Err(data),
+                        };
+                        match result {
+                            Ok(item) => actions.push(item),
+                            // Any previous pending drop is replaced
+                            Err(data) => {
+                                self.pending_drop = Some((Instant::now(), source, data, screen));
+                            }
+                        }
+                    }
+                    PendingAction::Poll(task) => {
+                        if let Some((id, w_id, output)) = self.shared.executor.poll(task) {
+                            if let Some(window) = self.windows.get_mut(&id) {
+                                // Delivered on the next frame
+                                window.send_to(w_id, output);
+                                add_resume(&mut self.resumes, Instant::now(), id);
+                                have_new_resumes = true;
+                            }
+                        }
                     }
                 }
             }
@@ -210,7 +397,7 @@ impl<T: theme::Theme<DrawPipe>> Loop<T> {
             match action {
                 TkAction::None => (),
                 TkAction::Redraw => {
-                    self.windows.get(&id).map(|w| w.window.request_redraw());
+                    self.windows.get(&id).map(|w| w.redraw_dirty());
                 }
                 TkAction::RegionMoved => {
                     if let Some(window) = self.windows.get_mut(&id) {
@@ -227,7 +414,22 @@ impl<T: theme::Theme<DrawPipe>> Loop<T> {
                     }
                 }
                 TkAction::Close => {
+                    self.modals.remove(&id);
+                    let mut unblocked = vec![];
+                    self.modals.retain(|owner, stack| {
+                        stack.retain(|modal| *modal != id);
+                        if stack.is_empty() {
+                            unblocked.push(*owner);
+                        }
+                        !stack.is_empty()
+                    });
+                    for owner in unblocked {
+                        if let Some(window) = self.windows.get_mut(&owner) {
+                            window.set_blocked(false);
+                        }
+                    }
                     if let Some(window) = self.windows.remove(&id) {
+                        self.shared.executor.close_window(id);
                         if window.handle_closure(&mut self.shared) == TkAction::CloseAll {
                             actions.push((id, TkAction::CloseAll));
                         }
@@ -259,6 +461,33 @@ impl<T: theme::Theme<DrawPipe>> Loop<T> {
             } else {
                 ControlFlow::Wait
             };
+
+            // With ControlFlow::Wait, the loop sleeps until the next OS event
+            // and (absent such events) draws no frames.
+            let state = match *control_flow {
+                ControlFlow::Wait | ControlFlow::Exit => PowerState::Idle,
+                _ => PowerState::Active,
+            };
+            self.shared.stats.set_power_state(state);
         }
     }
 }
+
+/// True for user input, which is blocked while a modal window is open
+fn is_input(event: &winit::event::WindowEvent) -> bool {
+    use winit::event::WindowEvent::*;
+    match event {
+        CloseRequested
+        | DroppedFile(_)
+        | HoveredFile(_)
+        | ReceivedCharacter(_)
+        | KeyboardInput { .. }
+        | CursorMoved { .. }
+        | MouseWheel { .. }
+        | MouseInput { .. }
+        | TouchpadPressure { .. }
+        | AxisMotion { .. }
+        | Touch(_) => true,
+        _ => false,
+    }
+}
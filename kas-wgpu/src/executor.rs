@@ -0,0 +1,122 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Executor for futures spawned via `Manager::spawn`
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::future::Future;
+use std::mem::ManuallyDrop;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+use kas::WidgetId;
+use winit::event_loop::EventLoopProxy;
+use winit::window as ww;
+
+use crate::ProxyAction;
+
+type Task = Pin<Box<dyn Future<Output = Rc<dyn Any>>>>;
+
+/// Wakes a task by requesting that the event loop polls it
+struct TaskWaker {
+    task: u64,
+    proxy: Mutex<EventLoopProxy<ProxyAction>>,
+}
+
+impl TaskWaker {
+    fn wake_by_ref(&self) {
+        if let Ok(proxy) = self.proxy.lock() {
+            // Fails only if the event loop terminated, in which case the task
+            // is of no further interest.
+            let _ = proxy.send_event(ProxyAction::Poll(self.task));
+        }
+    }
+
+    fn into_waker(self: Arc<Self>) -> Waker {
+        let raw = RawWaker::new(Arc::into_raw(self) as *const (), &VTABLE);
+        // Safe: the functions of VTABLE treat the data pointer as an
+        // Arc<TaskWaker>, thus are thread-safe and manage the reference count.
+        unsafe { Waker::from_raw(raw) }
+    }
+}
+
+static VTABLE: RawWakerVTable = RawWakerVTable::new(clone_waker, wake, wake_by_ref, drop_waker);
+
+unsafe fn clone_waker(data: *const ()) -> RawWaker {
+    let waker = ManuallyDrop::new(Arc::from_raw(data as *const TaskWaker));
+    let clone = Arc::clone(&*waker);
+    RawWaker::new(Arc::into_raw(clone) as *const (), &VTABLE)
+}
+
+unsafe fn wake(data: *const ()) {
+    let waker = Arc::from_raw(data as *const TaskWaker);
+    waker.wake_by_ref();
+}
+
+unsafe fn wake_by_ref(data: *const ()) {
+    (*(data as *const TaskWaker)).wake_by_ref();
+}
+
+unsafe fn drop_waker(data: *const ()) {
+    drop(Arc::from_raw(data as *const TaskWaker));
+}
+
+/// A single-threaded executor, polled by the event loop
+///
+/// Tasks are polled on the UI thread, thus should not block.
+#[derive(Default)]
+pub struct Executor {
+    proxy: Option<EventLoopProxy<ProxyAction>>,
+    tasks: HashMap<u64, (ww::WindowId, WidgetId, Task)>,
+    next_task: u64,
+}
+
+impl Executor {
+    /// Set the proxy used to wake tasks
+    ///
+    /// Tasks are not polled until this is set.
+    pub fn set_proxy(&mut self, proxy: EventLoopProxy<ProxyAction>) {
+        self.proxy = Some(proxy);
+    }
+
+    /// Add a task, returning its identifier
+    ///
+    /// The task should be polled (via [`Executor::poll`]) soon after.
+    pub fn spawn(&mut self, window: ww::WindowId, id: WidgetId, task: Task) -> u64 {
+        let key = self.next_task;
+        self.next_task += 1;
+        self.tasks.insert(key, (window, id, task));
+        key
+    }
+
+    /// Poll a task
+    ///
+    /// If the task completes, its window, target widget and output are
+    /// returned.
+    pub fn poll(&mut self, task: u64) -> Option<(ww::WindowId, WidgetId, Rc<dyn Any>)> {
+        let proxy = self.proxy.as_ref()?;
+        let entry = self.tasks.get_mut(&task)?;
+        let waker = Arc::new(TaskWaker {
+            task,
+            proxy: Mutex::new(proxy.clone()),
+        })
+        .into_waker();
+        match entry.2.as_mut().poll(&mut Context::from_waker(&waker)) {
+            Poll::Pending => None,
+            Poll::Ready(output) => {
+                let (window, id, _) = self.tasks.remove(&task).unwrap();
+                Some((window, id, output))
+            }
+        }
+    }
+
+    /// Drop all tasks of a window
+    pub fn close_window(&mut self, window: ww::WindowId) {
+        self.tasks.retain(|_, task| task.0 != window);
+    }
+}
@@ -7,70 +7,339 @@
 //!
 //! Optionally, this uses font-kit to find a suitable font. Since this is a
 //! large dependency, an alternative is provided.
+//!
+//! Fonts are loaded as faces, each in four styles: regular, bold, italic and
+//! bold italic, with font identifiers given by [`font_id`]. Where a style is
+//! not available, the regular font is used instead. Face 0 is the default
+//! system font; other faces are loaded from the [`FontConfig`]. A monospace
+//! system font is preferred for [`TextClass::Monospace`].
+//!
+//! Each [`TextClass`] has a chain of faces: text is drawn from the first face
+//! in the chain with a glyph for each character (see [`section_text`]).
 
 #[cfg(feature = "font-kit")]
 use font_kit::{
-    family_name::FamilyName, handle::Handle, properties::Properties, source::SystemSource,
+    family_name::FamilyName,
+    handle::Handle,
+    properties::{Properties, Style, Weight},
+    source::SystemSource,
 };
 
 use lazy_static::lazy_static;
-use wgpu_glyph::Font;
-// use wgpu_glyph::rusttype::FontCollection;
+use log::warn;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use wgpu_glyph::{Font, FontId, Scale, SectionText};
 
-#[cfg(feature = "font-kit")]
-use std::{fs::File, io::Read, sync::Arc};
+use kas::draw::Colour;
+use kas::theme::TextClass;
 
-#[cfg(feature = "font-kit")]
-struct FontCollectionBytes {
-    bytes: Vec<u8>,
-    index: u32,
+/// Source of a font
+#[derive(Clone, Debug)]
+pub enum FontSource {
+    /// A font family name, looked up from system fonts
+    ///
+    /// This requires the `font-kit` feature.
+    Family(String),
+    /// A font file
+    File(PathBuf),
+    /// Font data, e.g. as embedded via `include_bytes!`
+    Bytes(Vec<u8>),
 }
 
-#[cfg(feature = "font-kit")]
-impl FontCollectionBytes {
-    fn load() -> Self {
-        let handle = SystemSource::new()
-            .select_best_match(&[FamilyName::SansSerif], &Properties::new())
-            .unwrap();
-        match handle {
-            Handle::Path { path, font_index } => {
-                let mut bytes = vec![];
-                File::open(path).unwrap().read_to_end(&mut bytes).unwrap();
-                FontCollectionBytes {
-                    bytes,
-                    index: font_index,
-                }
-            }
-            Handle::Memory { bytes, font_index } => {
-                let bytes = Arc::try_unwrap(bytes).unwrap();
-                FontCollectionBytes {
-                    bytes,
-                    index: font_index,
+/// Font configuration
+///
+/// Fonts are given in order of preference: glyphs missing from one font are
+/// drawn from the next. The default system font is always the last fallback.
+///
+/// Fonts are loaded once, when the first window is created, thus this must be
+/// configured before then (see [`crate::Options::fonts`]).
+#[derive(Clone, Debug, Default)]
+pub struct FontConfig {
+    fonts: Vec<FontSource>,
+    classes: HashMap<TextClass, Vec<FontSource>>,
+}
+
+impl FontConfig {
+    /// Construct, using only the default system font
+    pub fn new() -> Self {
+        FontConfig::default()
+    }
+
+    /// Append a font used for all text (chain style)
+    pub fn with_font(mut self, source: FontSource) -> Self {
+        self.fonts.push(source);
+        self
+    }
+
+    /// Append a font used for text of the given `class` (chain style)
+    ///
+    /// Fonts given for a class are preferred over those given by
+    /// [`FontConfig::with_font`]. For example, a monospace family may be
+    /// used for [`TextClass::EditMulti`].
+    pub fn with_class_font(mut self, class: TextClass, source: FontSource) -> Self {
+        self.classes.entry(class).or_default().push(source);
+        self
+    }
+
+    /// True if no fonts are configured
+    pub fn is_empty(&self) -> bool {
+        self.fonts.is_empty() && self.classes.is_empty()
+    }
+}
+
+/// Font faces ([`font_id`] order) and fallback chains per class
+struct Fonts {
+    fonts: Vec<Font<'static>>,
+    default: Vec<usize>,
+    classes: HashMap<TextClass, Vec<usize>>,
+}
+
+impl Fonts {
+    fn load(config: FontConfig) -> Self {
+        let mut fonts = Fonts {
+            fonts: default_face().to_vec(),
+            default: vec![],
+            classes: HashMap::new(),
+        };
+
+        let mut load = |sources: &[FontSource]| -> Vec<usize> {
+            let mut chain = vec![];
+            for source in sources {
+                if let Some(face) = load_face(source) {
+                    chain.push(fonts.fonts.len() / 4);
+                    fonts.fonts.extend(face.iter().cloned());
                 }
             }
+            chain
+        };
+        let mut default = load(&config.fonts);
+        let mut classes: HashMap<_, _> = config
+            .classes
+            .iter()
+            .map(|(class, sources)| (*class, load(sources)))
+            .collect();
+
+        // Configured monospace fonts are preferred over the system font
+        if let Some(face) = monospace_face() {
+            let chain = classes.entry(TextClass::Monospace).or_default();
+            chain.push(fonts.fonts.len() / 4);
+            fonts.fonts.extend(face.iter().cloned());
+        }
+
+        default.push(0);
+        for (class, mut chain) in classes {
+            chain.extend_from_slice(&default);
+            fonts.classes.insert(class, chain);
         }
+        fonts.default = default;
+        fonts
     }
-    fn font<'a>(&'a self) -> Font<'a> {
-        // FontCollection is in next version of rusttype
-        assert!(self.index == 0, "Font collections not yet supported");
-        Font::from_bytes(&self.bytes).unwrap()
+
+    fn chain(&self, class: TextClass) -> &[usize] {
+        self.classes.get(&class).unwrap_or(&self.default)
+    }
+
+    fn has_glyph(&self, face: usize, c: char) -> bool {
+        self.fonts[4 * face].glyph(c).id().0 != 0
     }
 }
 
 #[cfg(feature = "font-kit")]
-lazy_static! {
-    static ref FCB: FontCollectionBytes = FontCollectionBytes::load();
-    static ref FONT: Font<'static> = FCB.font();
+fn load_family(family: FamilyName, bold: bool, italic: bool) -> Option<Font<'static>> {
+    let mut properties = Properties::new();
+    if bold {
+        properties.weight(Weight::BOLD);
+    }
+    if italic {
+        properties.style(Style::Italic);
+    }
+    let handle = SystemSource::new()
+        .select_best_match(&[family], &properties)
+        .ok()?;
+    let (bytes, index) = match handle {
+        Handle::Path { path, font_index } => (fs::read(path).ok()?, font_index),
+        Handle::Memory { bytes, font_index } => ((*bytes).clone(), font_index),
+    };
+    // FontCollection is in next version of rusttype
+    if index != 0 {
+        warn!("Font collections not yet supported");
+        return None;
+    }
+    Font::from_bytes(bytes).ok()
+}
+
+#[cfg(feature = "font-kit")]
+fn load_styles(family: FamilyName) -> Option<[Font<'static>; 4]> {
+    let regular = load_family(family.clone(), false, false)?;
+    let style = |bold, italic| load_family(family.clone(), bold, italic);
+    Some([
+        regular.clone(),
+        style(true, false).unwrap_or_else(|| regular.clone()),
+        style(false, true).unwrap_or_else(|| regular.clone()),
+        style(true, true).unwrap_or(regular),
+    ])
+}
+
+#[cfg(feature = "font-kit")]
+fn default_face() -> [Font<'static>; 4] {
+    load_styles(FamilyName::SansSerif).expect("no sans-serif system font found")
+}
+
+#[cfg(feature = "font-kit")]
+fn monospace_face() -> Option<[Font<'static>; 4]> {
+    let face = load_styles(FamilyName::Monospace);
+    if face.is_none() {
+        warn!("No monospace system font found");
+    }
+    face
 }
 
 #[cfg(not(feature = "font-kit"))]
 const BYTES: &'static [u8] = include_bytes!("/usr/share/fonts/dejavu/DejaVuSerif.ttf");
 
 #[cfg(not(feature = "font-kit"))]
+const MONO_BYTES: &'static [u8] = include_bytes!("/usr/share/fonts/dejavu/DejaVuSansMono.ttf");
+
+#[cfg(not(feature = "font-kit"))]
+fn default_face() -> [Font<'static>; 4] {
+    let font = Font::from_bytes(BYTES).unwrap();
+    [font.clone(), font.clone(), font.clone(), font]
+}
+
+#[cfg(not(feature = "font-kit"))]
+fn monospace_face() -> Option<[Font<'static>; 4]> {
+    let font = Font::from_bytes(MONO_BYTES).ok()?;
+    Some([font.clone(), font.clone(), font.clone(), font])
+}
+
+fn load_face(source: &FontSource) -> Option<[Font<'static>; 4]> {
+    let bytes = match source {
+        #[cfg(feature = "font-kit")]
+        FontSource::Family(name) => {
+            let face = load_styles(FamilyName::Title(name.clone()));
+            if face.is_none() {
+                warn!("Font family \"{}\" not found", name);
+            }
+            return face;
+        }
+        #[cfg(not(feature = "font-kit"))]
+        FontSource::Family(name) => {
+            warn!("Font family \"{}\": lookup requires font-kit", name);
+            return None;
+        }
+        FontSource::File(path) => match fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                warn!("Failed to read font {}: {}", path.display(), e);
+                return None;
+            }
+        },
+        FontSource::Bytes(bytes) => bytes.clone(),
+    };
+    match Font::from_bytes(bytes) {
+        Ok(font) => Some([font.clone(), font.clone(), font.clone(), font]),
+        Err(e) => {
+            warn!("Failed to load font: {}", e);
+            None
+        }
+    }
+}
+
 lazy_static! {
-    static ref FONT: Font<'static> = Font::from_bytes(BYTES).unwrap();
+    static ref CONFIG: Mutex<FontConfig> = Mutex::new(FontConfig::new());
+    static ref FONTS: Fonts = {
+        LOADED.store(true, Ordering::Relaxed);
+        let config = CONFIG.lock().map(|mut c| std::mem::take(&mut *c));
+        Fonts::load(config.unwrap_or_default())
+    };
 }
 
-pub(crate) fn get_font() -> Font<'static> {
-    FONT.clone()
+static LOADED: AtomicBool = AtomicBool::new(false);
+
+/// Set the font configuration
+///
+/// This has no effect once fonts are loaded.
+pub(crate) fn configure(config: FontConfig) {
+    if LOADED.load(Ordering::Relaxed) {
+        if !config.is_empty() {
+            warn!("Fonts already loaded: configuration ignored");
+        }
+        return;
+    }
+    if let Ok(mut c) = CONFIG.lock() {
+        *c = config;
+    }
+}
+
+/// Get fonts, in the order given by [`font_id`]
+pub(crate) fn get_fonts() -> Vec<Font<'static>> {
+    FONTS.fonts.clone()
+}
+
+/// Get the identifier of the font for the given face and style
+pub(crate) fn font_id(face: usize, bold: bool, italic: bool) -> FontId {
+    FontId(4 * face + bold as usize + 2 * italic as usize)
+}
+
+/// Get the advance width of `c`, as drawn by [`section_text`]
+pub(crate) fn advance_width(c: char, class: TextClass, scale: Scale) -> f32 {
+    let fonts: &'static Fonts = &FONTS;
+    let chain = fonts.chain(class);
+    let face = chain
+        .iter()
+        .cloned()
+        .find(|f| fonts.has_glyph(*f, c))
+        .unwrap_or(chain[0]);
+    let glyph = fonts.fonts[4 * face].glyph(c).scaled(scale);
+    glyph.h_metrics().advance_width
+}
+
+/// Convert text to glyph sections, selecting a font for each character
+///
+/// Each character is drawn from the first face in the chain of `class` with
+/// a glyph for this character, or from the first face if none has a glyph.
+pub(crate) fn section_text<'a>(
+    text: &'a str,
+    class: TextClass,
+    bold: bool,
+    italic: bool,
+    scale: Scale,
+    col: Colour,
+) -> impl Iterator<Item = SectionText<'a>> {
+    let fonts: &'static Fonts = &FONTS;
+    let chain = fonts.chain(class);
+
+    let mut runs = vec![];
+    let mut start = 0;
+    let mut face = chain[0];
+    for (i, c) in text.char_indices() {
+        // Spaces and control characters continue the current run
+        if c.is_whitespace() || c.is_control() {
+            continue;
+        }
+        let f = chain
+            .iter()
+            .cloned()
+            .find(|f| fonts.has_glyph(*f, c))
+            .unwrap_or(chain[0]);
+        if f != face {
+            if i > start {
+                runs.push((start..i, face));
+            }
+            start = i;
+            face = f;
+        }
+    }
+    runs.push((start..text.len(), face));
+
+    runs.into_iter().map(move |(range, face)| SectionText {
+        text: &text[range],
+        scale,
+        color: col.into(),
+        font_id: font_id(face, bold, italic),
+    })
 }
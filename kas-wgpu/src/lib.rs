@@ -7,25 +7,37 @@
 
 pub mod draw;
 mod event_loop;
+mod executor;
 mod font;
 mod options;
-mod resources;
+mod power;
 mod shared;
+pub mod snapshot;
 pub mod theme;
+mod watch;
 mod window;
 
-use std::{error, fmt};
+use log::warn;
+use std::any::Any;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::{error, fmt, io};
 
+use kas::data::WindowPlacements;
 use kas::event::UpdateHandle;
-use kas::WindowId;
+use kas::theme::{ColourScheme, ThemeConfig};
+use kas::{WidgetId, WindowId};
 use winit::error::OsError;
 use winit::event_loop::{EventLoop, EventLoopProxy};
 
 use crate::draw::DrawPipe;
+use crate::power::LoopStats;
 use crate::shared::SharedState;
 use window::Window;
 
-pub use options::Options;
+pub use font::{FontConfig, FontSource};
+pub use options::{Options, TouchMode};
+pub use power::PowerState;
 
 pub use kas;
 pub use wgpu_glyph as glyph;
@@ -47,6 +59,8 @@ pub enum Error {
     ShaderCompilation(shaderc::Error),
     /// OS error during window creation
     Window(OsError),
+    /// Failed to read back a rendered image
+    MapRead,
 }
 
 impl fmt::Display for Error {
@@ -55,6 +69,7 @@ impl fmt::Display for Error {
             Error::NoAdapter => write!(f, "no suitable graphics adapter found"),
             Error::ShaderCompilation(e) => write!(f, "shader compilation failed: {}", e),
             Error::Window(e) => write!(f, "window creation error: {}", e),
+            Error::MapRead => write!(f, "failed to read rendered image"),
         }
     }
 }
@@ -90,12 +105,85 @@ impl<T: kas::theme::Theme<DrawPipe> + 'static> Toolkit<T> {
     }
 
     /// Construct an instance with custom options
-    pub fn new_custom(theme: T, options: Options) -> Result<Self, Error> {
-        Ok(Toolkit {
-            el: EventLoop::with_user_event(),
+    pub fn new_custom(mut theme: T, mut options: Options) -> Result<Self, Error> {
+        font::configure(std::mem::take(&mut options.fonts));
+        if options.touch_mode == TouchMode::On {
+            let _ = theme.set_touch_mode(true);
+        }
+        let _ = theme.set_layout_direction(options.layout_direction);
+        if let Some(name) = options.theme.as_ref() {
+            let _ = theme.set_theme(name);
+        }
+        if let Some(scheme) = options.colours.as_ref() {
+            let _ = theme.set_colours(scheme);
+        }
+        let theme_config = options.theme_config.clone();
+        let reload = options.reload_theme_config;
+        let el = EventLoop::with_user_event();
+        let mut shared = SharedState::new(theme, options)?;
+        shared.executor.set_proxy(el.create_proxy());
+        let mut toolkit = Toolkit {
+            el,
             windows: vec![],
-            shared: SharedState::new(theme, options)?,
-        })
+            shared,
+        };
+        if let Some(path) = theme_config {
+            toolkit.load_theme_config(path, reload);
+        }
+        Ok(toolkit)
+    }
+
+    /// Load theme configuration from the file at `path`
+    ///
+    /// See [`kas::theme::ThemeConfig`] for the file format. If `reload` is
+    /// true, the file is watched for changes, which are applied to all
+    /// windows; this is intended for use during development.
+    pub fn load_theme_config<P: Into<PathBuf>>(&mut self, path: P, reload: bool) {
+        let path = path.into();
+        match ThemeConfig::load(&path) {
+            Ok(config) => {
+                let _ = self.shared.theme.apply_config(&config);
+            }
+            Err(e) => warn!("Unable to load theme config: {}", e),
+        }
+        if reload {
+            watch::watch_theme_config(path, self.el.create_proxy());
+        }
+    }
+
+    /// Save and restore window placement using the file at `path`
+    ///
+    /// Placements are loaded from `path` (if it exists) and saved to it
+    /// whenever a named window (see [`kas::Window::name`]) is closed. Saved
+    /// placement is applied before a window is first drawn, thus this should
+    /// be called before adding windows.
+    pub fn persist_window_placements<P: Into<PathBuf>>(&mut self, path: P) {
+        let path = path.into();
+        match WindowPlacements::load(&path) {
+            Ok(placements) => self.shared.placements = placements,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => (),
+            Err(e) => warn!("Unable to load window placements: {}", e),
+        }
+        self.shared.placements_path = Some(path);
+    }
+
+    /// Set window placements
+    ///
+    /// This may be used to restore placements persisted by other means (e.g.
+    /// deserialised from application settings). As with
+    /// [`Toolkit::persist_window_placements`], this should be called before
+    /// adding windows.
+    pub fn set_window_placements(&mut self, placements: WindowPlacements) {
+        self.shared.placements = placements;
+    }
+
+    /// Record input events of the next window added to the file at `path`
+    ///
+    /// Events are recorded with timing and saved when the window is closed.
+    /// See [`kas::remote::EventRecording`] for the file format and replay.
+    #[cfg(feature = "record")]
+    pub fn record_events<P: Into<PathBuf>>(&mut self, path: P) {
+        self.shared.record_path = Some(path.into());
     }
 
     /// Assume ownership of and display a window
@@ -119,6 +207,7 @@ impl<T: kas::theme::Theme<DrawPipe> + 'static> Toolkit<T> {
     pub fn create_proxy(&self) -> ToolkitProxy {
         ToolkitProxy {
             proxy: self.el.create_proxy(),
+            stats: self.shared.stats.clone(),
         }
     }
 
@@ -132,9 +221,14 @@ impl<T: kas::theme::Theme<DrawPipe> + 'static> Toolkit<T> {
 
 /// A proxy allowing control of a [`Toolkit`] from another thread.
 ///
-/// Created by [`Toolkit::create_proxy`].
+/// Created by [`Toolkit::create_proxy`]. The proxy may be cloned and moved to
+/// other threads; for example, a background computation may report progress
+/// via [`ToolkitProxy::send_to`] or [`ToolkitProxy::trigger_update`]. Each
+/// call wakes the event loop.
+#[derive(Clone)]
 pub struct ToolkitProxy {
     proxy: EventLoopProxy<ProxyAction>,
+    stats: Arc<LoopStats>,
 }
 
 /// Error type returned by [`ToolkitProxy`] functions.
@@ -143,6 +237,14 @@ pub struct ToolkitProxy {
 pub struct ClosedError;
 
 impl ToolkitProxy {
+    /// Get a proxy for a specific window
+    pub fn window(&self, id: WindowId) -> WindowProxy {
+        WindowProxy {
+            proxy: self.clone(),
+            id,
+        }
+    }
+
     /// Close a specific window.
     pub fn close(&self, id: WindowId) -> Result<(), ClosedError> {
         self.proxy
@@ -163,6 +265,147 @@ impl ToolkitProxy {
             .send_event(ProxyAction::Update(handle, payload))
             .map_err(|_| ClosedError)
     }
+
+    /// Send a message to a widget
+    ///
+    /// The message is delivered to widget `id` of `window` as
+    /// [`kas::event::Event::Message`]; see [`kas::event::Manager::send_to`].
+    pub fn send_to<M>(&self, window: WindowId, id: WidgetId, msg: M) -> Result<(), ClosedError>
+    where
+        M: fmt::Debug + Send + 'static,
+    {
+        self.proxy
+            .send_event(ProxyAction::SendTo(window, id, Box::new(msg)))
+            .map_err(|_| ClosedError)
+    }
+
+    /// Set the swap-chain present mode for all windows
+    ///
+    /// See [`Options::present_mode`].
+    pub fn set_present_mode(&self, mode: wgpu::PresentMode) -> Result<(), ClosedError> {
+        self.proxy
+            .send_event(ProxyAction::SetPresentMode(mode))
+            .map_err(|_| ClosedError)
+    }
+
+    /// Set or clear the frame rate limit
+    ///
+    /// See [`Options::frame_rate_limit`].
+    pub fn set_frame_rate_limit(&self, limit: Option<u32>) -> Result<(), ClosedError> {
+        self.proxy
+            .send_event(ProxyAction::SetFrameRateLimit(limit))
+            .map_err(|_| ClosedError)
+    }
+
+    /// Set the global UI scale
+    ///
+    /// This multiplies the scale factor of every window (see
+    /// [`Options::ui_scale`]). Windows are resized.
+    pub fn set_ui_scale(&self, scale: f32) -> Result<(), ClosedError> {
+        self.proxy
+            .send_event(ProxyAction::SetUiScale(scale))
+            .map_err(|_| ClosedError)
+    }
+
+    /// Set the base font size of the theme
+    ///
+    /// See [`kas::theme::ThemeApi::set_font_size`]. Windows are resized.
+    pub fn set_font_size(&self, size: f32) -> Result<(), ClosedError> {
+        self.proxy
+            .send_event(ProxyAction::SetFontSize(size))
+            .map_err(|_| ClosedError)
+    }
+
+    /// Change the colour scheme of all windows
+    ///
+    /// See [`kas::theme::ThemeApi::set_colours`]. Windows are redrawn.
+    pub fn set_colours<S: Into<String>>(&self, scheme: S) -> Result<(), ClosedError> {
+        self.proxy
+            .send_event(ProxyAction::SetColours(scheme.into()))
+            .map_err(|_| ClosedError)
+    }
+
+    /// Change the colour scheme of all windows to a custom palette
+    ///
+    /// See [`kas::theme::ThemeApi::set_colour_scheme`]. Windows are redrawn.
+    pub fn set_colour_scheme(&self, scheme: ColourScheme) -> Result<(), ClosedError> {
+        self.proxy
+            .send_event(ProxyAction::SetColourScheme(scheme))
+            .map_err(|_| ClosedError)
+    }
+
+    /// Apply theme configuration to all windows
+    ///
+    /// See [`kas::theme::ThemeApi::apply_config`]. Windows are resized.
+    pub fn set_theme_config(&self, config: ThemeConfig) -> Result<(), ClosedError> {
+        self.proxy
+            .send_event(ProxyAction::SetThemeConfig(config))
+            .map_err(|_| ClosedError)
+    }
+
+    /// Change the theme of all windows
+    ///
+    /// See [`kas::theme::ThemeApi::set_theme`]. Where the new theme uses
+    /// different metrics, windows are resized without being recreated.
+    pub fn set_theme<S: Into<String>>(&self, theme: S) -> Result<(), ClosedError> {
+        self.proxy
+            .send_event(ProxyAction::SetTheme(theme.into()))
+            .map_err(|_| ClosedError)
+    }
+
+    /// Get the current power state of the event loop
+    pub fn power_state(&self) -> PowerState {
+        self.stats.power_state()
+    }
+
+    /// Get the number of frames rendered (over all windows) since start
+    ///
+    /// While the [`PowerState`] is [`PowerState::Idle`], this should only
+    /// increase in response to input or OS redraw requests.
+    pub fn frame_count(&self) -> u64 {
+        self.stats.frames()
+    }
+}
+
+/// A proxy allowing control of a window from another thread.
+///
+/// Created by [`ToolkitProxy::window`]. Like [`ToolkitProxy`], this may be
+/// cloned and moved to other threads.
+#[derive(Clone)]
+pub struct WindowProxy {
+    proxy: ToolkitProxy,
+    id: WindowId,
+}
+
+impl WindowProxy {
+    /// Get the window's identifier
+    #[inline]
+    pub fn id(&self) -> WindowId {
+        self.id
+    }
+
+    /// Close the window.
+    pub fn close(&self) -> Result<(), ClosedError> {
+        self.proxy.close(self.id)
+    }
+
+    /// Send a message to a widget of this window
+    ///
+    /// See [`ToolkitProxy::send_to`].
+    pub fn send_to<M>(&self, id: WidgetId, msg: M) -> Result<(), ClosedError>
+    where
+        M: fmt::Debug + Send + 'static,
+    {
+        self.proxy.send_to(self.id, id, msg)
+    }
+
+    /// Trigger an update handle
+    ///
+    /// See [`ToolkitProxy::trigger_update`]. Note that updates are not limited
+    /// to this window.
+    pub fn trigger_update(&self, handle: UpdateHandle, payload: u64) -> Result<(), ClosedError> {
+        self.proxy.trigger_update(handle, payload)
+    }
 }
 
 #[derive(Debug)]
@@ -170,4 +413,15 @@ enum ProxyAction {
     CloseAll,
     Close(WindowId),
     Update(UpdateHandle, u64),
+    SendTo(WindowId, WidgetId, Box<dyn Any + Send>),
+    SetPresentMode(wgpu::PresentMode),
+    SetFrameRateLimit(Option<u32>),
+    SetUiScale(f32),
+    SetFontSize(f32),
+    SetColours(String),
+    SetColourScheme(ColourScheme),
+    SetTheme(String),
+    SetThemeConfig(ThemeConfig),
+    /// Poll a task of the executor (sent by its waker)
+    Poll(u64),
 }
@@ -5,9 +5,27 @@
 
 //! Options
 
+use kas::event::{Keymap, Shortcut, VirtualKeyCode};
+use kas::LayoutDirection;
 use log::warn;
 use std::env::var;
-use wgpu::{BackendBit, PowerPreference};
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+use wgpu::{BackendBit, PowerPreference, PresentMode};
+
+use crate::font::{FontConfig, FontSource};
+
+/// Theme touch mode selection
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TouchMode {
+    /// Always use normal (mouse) metrics
+    Off,
+    /// Always use touch metrics
+    On,
+    /// Switch according to the last input device
+    Auto,
+}
 
 /// Toolkit options
 pub struct Options {
@@ -15,6 +33,55 @@ pub struct Options {
     pub power_preference: PowerPreference,
     /// Adapter backend. Default value: PRIMARY (Vulkan/Metal/DX12).
     pub backends: BackendBit,
+    /// Swap-chain present mode. Default value: `Vsync`.
+    ///
+    /// (Mailbox mode is not yet supported by `wgpu`.)
+    pub present_mode: PresentMode,
+    /// Maximum frame rate while animations are running. Default value: none.
+    ///
+    /// This limits the rate at which timer updates (see
+    /// [`kas::event::Manager::update_on_timer`]) are delivered, and thus the
+    /// rate of redraws caused by these.
+    pub frame_rate_limit: Option<u32>,
+    /// Theme touch mode (see [`kas::theme::ThemeApi::set_touch_mode`]).
+    /// Default value: `Auto`.
+    pub touch_mode: TouchMode,
+    /// Layout direction (see [`kas::theme::ThemeApi::set_layout_direction`]).
+    /// Default value: `LeftToRight`.
+    pub layout_direction: LayoutDirection,
+    /// Theme name passed to [`kas::theme::ThemeApi::set_theme`], if any.
+    /// Default value: none.
+    pub theme: Option<String>,
+    /// Colour scheme passed to [`kas::theme::ThemeApi::set_colours`], if any.
+    /// Default value: none.
+    pub colours: Option<String>,
+    /// Theme configuration file (see [`kas::theme::ThemeConfig`]), if any.
+    /// Default value: none.
+    pub theme_config: Option<PathBuf>,
+    /// Whether to reload the theme configuration file when changed.
+    /// Default value: `true` in debug builds, otherwise `false`.
+    pub reload_theme_config: bool,
+    /// Global UI scale, multiplying the scale factor of each window.
+    /// Default value: 1.
+    pub ui_scale: f32,
+    /// Whether `Ctrl+=`, `Ctrl+-` and `Ctrl+0` increase, decrease and reset
+    /// the UI scale. Default value: `true`.
+    pub zoom_keys: bool,
+    /// Fonts, with fallbacks (see [`FontConfig`]).
+    /// Default value: the system's sans-serif font only.
+    pub fonts: FontConfig,
+    /// Keymap overriding default shortcuts (see [`kas::event::Keymap`]).
+    /// Default value: empty.
+    pub keymap: Keymap,
+    /// Maximum interval between presses of a double-click.
+    /// Default value: 500ms.
+    pub click_time: Duration,
+    /// Maximum distance between presses of a double-click, in logical pixels.
+    /// Default value: 4.
+    pub click_distance: u32,
+    /// Shortcut toggling the widget inspector (see [`kas::layout::Inspector`]).
+    /// Default value: `F12` in debug builds, otherwise none.
+    pub inspector_key: Option<Shortcut>,
 }
 
 impl Options {
@@ -23,6 +90,25 @@ impl Options {
         Options {
             power_preference: PowerPreference::LowPower,
             backends: BackendBit::PRIMARY,
+            present_mode: PresentMode::Vsync,
+            frame_rate_limit: None,
+            touch_mode: TouchMode::Auto,
+            layout_direction: LayoutDirection::LeftToRight,
+            theme: None,
+            colours: None,
+            theme_config: None,
+            reload_theme_config: cfg!(debug_assertions),
+            ui_scale: 1.0,
+            zoom_keys: true,
+            fonts: FontConfig::new(),
+            keymap: Keymap::new(),
+            click_time: Duration::from_millis(500),
+            click_distance: 4,
+            inspector_key: if cfg!(debug_assertions) {
+                Some(Shortcut::new(VirtualKeyCode::F12))
+            } else {
+                None
+            },
         }
     }
 
@@ -49,6 +135,72 @@ impl Options {
     /// -   `DX12`
     /// -   `PRIMARY`: any of Vulkan, Metal or DX12
     /// -   `SECONDARY`: any of GL or DX11
+    ///
+    /// ### Present mode
+    ///
+    /// The `KAS_PRESENT_MODE` variable supports:
+    ///
+    /// -   `Vsync`
+    /// -   `NoVsync`
+    ///
+    /// ### Frame rate limit
+    ///
+    /// The `KAS_FRAME_RATE_LIMIT` variable may be set to a maximum number of
+    /// frames per second (while animating), or to `0` for no limit.
+    ///
+    /// ### Touch mode
+    ///
+    /// The `KAS_TOUCH_MODE` variable supports:
+    ///
+    /// -   `Off`
+    /// -   `On`
+    /// -   `Auto`
+    ///
+    /// ### Layout direction
+    ///
+    /// The `KAS_LAYOUT_DIRECTION` variable supports:
+    ///
+    /// -   `LTR`: left-to-right
+    /// -   `RTL`: right-to-left
+    ///
+    /// ### Theme
+    ///
+    /// The `KAS_THEME` variable may name a theme (e.g. `flat` or `shaded` for
+    /// [`crate::theme::MultiTheme`]) and `KAS_COLOURS` a colour scheme (e.g.
+    /// `default`, `light` or `dark`). These names are case-sensitive.
+    ///
+    /// The `KAS_THEME_CONFIG` variable may be set to the path of a theme
+    /// configuration file (see [`kas::theme::ThemeConfig::parse`]), applied
+    /// after the above. `KAS_THEME_CONFIG_RELOAD` supports `On` and `Off`,
+    /// controlling whether this file is reloaded when changed.
+    ///
+    /// ### UI scale
+    ///
+    /// The `KAS_UI_SCALE` variable may be set to a scale factor applied to all
+    /// windows (e.g. `1.5`). `KAS_ZOOM_KEYS` supports `On` and `Off`,
+    /// enabling or disabling the keys adjusting this scale at run-time.
+    ///
+    /// ### Fonts
+    ///
+    /// The `KAS_FONTS` variable may be set to a comma-separated list of font
+    /// family names, in order of preference (see [`FontConfig::with_font`]).
+    ///
+    /// ### Keymap
+    ///
+    /// The `KAS_KEYMAP` variable may be set to the path of a keymap file (see
+    /// [`kas::event::Keymap::parse`]).
+    ///
+    /// ### Double-click
+    ///
+    /// The `KAS_CLICK_TIME` variable may be set to the maximum interval between
+    /// presses of a double-click in milliseconds, and `KAS_CLICK_DISTANCE` to
+    /// the maximum distance between these presses in logical pixels.
+    ///
+    /// ### Inspector
+    ///
+    /// The `KAS_INSPECTOR_KEY` variable may be set to a shortcut (see
+    /// [`kas::event::Shortcut::parse`]) toggling the widget inspector, or to
+    /// `None` to disable it.
     pub fn from_env() -> Self {
         let mut options = Options::new();
 
@@ -85,6 +237,142 @@ impl Options {
             }
         }
 
+        if let Ok(mut v) = var("KAS_PRESENT_MODE") {
+            v.make_ascii_uppercase();
+            options.present_mode = match v.as_str() {
+                "VSYNC" => PresentMode::Vsync,
+                "NOVSYNC" => PresentMode::NoVsync,
+                other => {
+                    warn!("Unexpected environment value: KAS_PRESENT_MODE={}", other);
+                    options.present_mode
+                }
+            }
+        }
+
+        if let Ok(v) = var("KAS_FRAME_RATE_LIMIT") {
+            options.frame_rate_limit = match v.parse::<u32>() {
+                Ok(0) => None,
+                Ok(limit) => Some(limit),
+                Err(_) => {
+                    warn!("Unexpected environment value: KAS_FRAME_RATE_LIMIT={}", v);
+                    options.frame_rate_limit
+                }
+            }
+        }
+
+        if let Ok(mut v) = var("KAS_TOUCH_MODE") {
+            v.make_ascii_uppercase();
+            options.touch_mode = match v.as_str() {
+                "OFF" => TouchMode::Off,
+                "ON" => TouchMode::On,
+                "AUTO" => TouchMode::Auto,
+                other => {
+                    warn!("Unexpected environment value: KAS_TOUCH_MODE={}", other);
+                    options.touch_mode
+                }
+            }
+        }
+
+        if let Ok(mut v) = var("KAS_LAYOUT_DIRECTION") {
+            v.make_ascii_uppercase();
+            options.layout_direction = match v.as_str() {
+                "LTR" => LayoutDirection::LeftToRight,
+                "RTL" => LayoutDirection::RightToLeft,
+                other => {
+                    warn!(
+                        "Unexpected environment value: KAS_LAYOUT_DIRECTION={}",
+                        other
+                    );
+                    options.layout_direction
+                }
+            }
+        }
+
+        if let Ok(v) = var("KAS_THEME") {
+            options.theme = Some(v);
+        }
+
+        if let Ok(v) = var("KAS_COLOURS") {
+            options.colours = Some(v);
+        }
+
+        if let Ok(path) = var("KAS_THEME_CONFIG") {
+            options.theme_config = Some(path.into());
+        }
+
+        if let Ok(mut v) = var("KAS_THEME_CONFIG_RELOAD") {
+            v.make_ascii_uppercase();
+            options.reload_theme_config = match v.as_str() {
+                "OFF" => false,
+                "ON" => true,
+                other => {
+                    warn!(
+                        "Unexpected environment value: KAS_THEME_CONFIG_RELOAD={}",
+                        other
+                    );
+                    options.reload_theme_config
+                }
+            }
+        }
+
+        if let Ok(v) = var("KAS_UI_SCALE") {
+            match v.parse::<f32>() {
+                Ok(scale) if scale > 0.0 && scale.is_finite() => options.ui_scale = scale,
+                _ => warn!("Unexpected environment value: KAS_UI_SCALE={}", v),
+            }
+        }
+
+        if let Ok(mut v) = var("KAS_ZOOM_KEYS") {
+            v.make_ascii_uppercase();
+            options.zoom_keys = match v.as_str() {
+                "OFF" => false,
+                "ON" => true,
+                other => {
+                    warn!("Unexpected environment value: KAS_ZOOM_KEYS={}", other);
+                    options.zoom_keys
+                }
+            }
+        }
+
+        if let Ok(v) = var("KAS_FONTS") {
+            for family in v.split(',').map(|s| s.trim()).filter(|s| !s.is_empty()) {
+                let source = FontSource::Family(family.to_string());
+                options.fonts = options.fonts.with_font(source);
+            }
+        }
+
+        if let Ok(path) = var("KAS_KEYMAP") {
+            match fs::read_to_string(&path) {
+                Ok(text) => options.keymap = Keymap::parse(&text),
+                Err(e) => warn!("Failed to read keymap {}: {}", path, e),
+            }
+        }
+
+        if let Ok(v) = var("KAS_CLICK_TIME") {
+            match v.parse::<u64>() {
+                Ok(ms) => options.click_time = Duration::from_millis(ms),
+                Err(_) => warn!("Unexpected environment value: KAS_CLICK_TIME={}", v),
+            }
+        }
+
+        if let Ok(v) = var("KAS_CLICK_DISTANCE") {
+            match v.parse::<u32>() {
+                Ok(dist) => options.click_distance = dist,
+                Err(_) => warn!("Unexpected environment value: KAS_CLICK_DISTANCE={}", v),
+            }
+        }
+
+        if let Ok(v) = var("KAS_INSPECTOR_KEY") {
+            if v.eq_ignore_ascii_case("none") {
+                options.inspector_key = None;
+            } else {
+                match Shortcut::parse(&v) {
+                    Some(shortcut) => options.inspector_key = Some(shortcut),
+                    None => warn!("Unexpected environment value: KAS_INSPECTOR_KEY={}", v),
+                }
+            }
+        }
+
         options
     }
 
@@ -95,3 +383,10 @@ impl Options {
         }
     }
 }
+
+/// Convert a frame rate limit to a minimum frame time
+pub(crate) fn frame_time(frame_rate_limit: Option<u32>) -> Option<Duration> {
+    frame_rate_limit
+        .filter(|limit| *limit > 0)
+        .map(|limit| Duration::from_secs(1) / limit)
+}
@@ -0,0 +1,58 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Power state tracking
+
+use log::debug;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+/// Power state of the event loop
+///
+/// See [`ToolkitProxy::power_state`](crate::ToolkitProxy::power_state).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PowerState {
+    /// The event loop sleeps until the next OS event
+    ///
+    /// No animations or timers are active; frames are only drawn in response
+    /// to input or OS requests (e.g. when a window is exposed).
+    Idle,
+    /// The event loop will wake for a scheduled timer update
+    ///
+    /// This is the case while widgets are animating or have scheduled an
+    /// update (see [`kas::event::Manager::update_on_timer`]).
+    Active,
+}
+
+/// Event-loop statistics, readable from any thread
+#[derive(Debug, Default)]
+pub(crate) struct LoopStats {
+    active: AtomicBool,
+    frames: AtomicU64,
+}
+
+impl LoopStats {
+    pub fn power_state(&self) -> PowerState {
+        if self.active.load(Ordering::Relaxed) {
+            PowerState::Active
+        } else {
+            PowerState::Idle
+        }
+    }
+
+    pub fn set_power_state(&self, state: PowerState) {
+        let active = state == PowerState::Active;
+        if self.active.swap(active, Ordering::Relaxed) != active {
+            debug!("Power state: {:?}", state);
+        }
+    }
+
+    pub fn frames(&self) -> u64 {
+        self.frames.load(Ordering::Relaxed)
+    }
+
+    pub fn count_frame(&self) {
+        self.frames.fetch_add(1, Ordering::Relaxed);
+    }
+}
@@ -5,25 +5,79 @@
 
 //! Shared state
 
-use log::{info, warn};
+use log::{debug, info, warn};
+use std::any::Any;
+use std::cell::RefCell;
 use std::num::NonZeroU32;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
 
 use crate::draw::ShaderManager;
-use crate::{Error, Options, WindowId};
-use kas::event::UpdateHandle;
+use crate::executor::Executor;
+use crate::options::frame_time;
+use crate::power::LoopStats;
+use crate::{Error, Options, TouchMode, WindowId};
+use kas::data::{WindowPlacement, WindowPlacements};
+use kas::event::{DragData, Keymap, Priority, Shortcut, UpdateHandle};
+use kas::geom::Coord;
+use kas::theme::{ThemeAction, ThemeApi};
+use wgpu_glyph::GlyphBrush;
 
 #[cfg(feature = "clipboard")]
 use clipboard::{ClipboardContext, ClipboardProvider};
 
+#[cfg(feature = "tts")]
+use tts::TTS;
+
+/// Limits of the global UI scale
+const UI_SCALE_RANGE: (f32, f32) = (0.25, 4.0);
+
+fn clamp_ui_scale(scale: f32) -> f32 {
+    if scale.is_finite() {
+        scale.max(UI_SCALE_RANGE.0).min(UI_SCALE_RANGE.1)
+    } else {
+        1.0
+    }
+}
+
 /// State shared between windows
 pub struct SharedState<T> {
     #[cfg(feature = "clipboard")]
     clipboard: Option<ClipboardContext>,
+    #[cfg(feature = "tts")]
+    tts: Option<TTS>,
     pub device: wgpu::Device,
     pub queue: wgpu::Queue,
     pub shaders: ShaderManager,
     pub theme: T,
     pub pending: Vec<PendingAction>,
+    pub present_mode: wgpu::PresentMode,
+    pub frame_time: Option<Duration>,
+    /// Whether windows switch theme touch mode automatically
+    pub touch_mode_auto: bool,
+    /// Keymap, shared by all windows
+    pub keymap: Rc<Keymap>,
+    /// Double-click thresholds: time and distance
+    pub click_time: Duration,
+    pub click_distance: u32,
+    /// Shortcut toggling the widget inspector
+    pub inspector_key: Option<Shortcut>,
+    /// Global UI scale, multiplying the scale factor of each window
+    pub ui_scale: f32,
+    /// Whether Ctrl+= / Ctrl+- / Ctrl+0 adjust the UI scale
+    pub zoom_keys: bool,
+    pub stats: Arc<LoopStats>,
+    pub placements: WindowPlacements,
+    pub placements_path: Option<PathBuf>,
+    /// Record input to this path (taken by the next window constructed)
+    #[cfg(feature = "record")]
+    pub record_path: Option<PathBuf>,
+    /// Glyph brush, shared by all windows (constructed with the first window)
+    pub glyph_brush: Option<Rc<RefCell<GlyphBrush<'static, ()>>>>,
+    /// Executor for tasks spawned by widgets
+    pub executor: Executor,
     window_id: u32,
 }
 
@@ -39,6 +93,15 @@ impl<T> SharedState<T> {
             }
         };
 
+        #[cfg(feature = "tts")]
+        let tts = match TTS::default() {
+            Ok(tts) => Some(tts),
+            Err(e) => {
+                warn!("Unable to open text-to-speech: {:?}", e);
+                None
+            }
+        };
+
         let adapter_options = options.adapter_options();
 
         let adapter = match wgpu::Adapter::request(&adapter_options) {
@@ -55,19 +118,70 @@ impl<T> SharedState<T> {
         });
 
         let shaders = ShaderManager::new(&device)?;
+        let frame_time = frame_time(options.frame_rate_limit);
 
         Ok(SharedState {
             #[cfg(feature = "clipboard")]
             clipboard,
+            #[cfg(feature = "tts")]
+            tts,
             device,
             queue,
             shaders,
             theme,
             pending: vec![],
+            present_mode: options.present_mode,
+            frame_time,
+            touch_mode_auto: options.touch_mode == TouchMode::Auto,
+            keymap: Rc::new(options.keymap),
+            click_time: options.click_time,
+            click_distance: options.click_distance,
+            inspector_key: options.inspector_key,
+            ui_scale: clamp_ui_scale(options.ui_scale),
+            zoom_keys: options.zoom_keys,
+            stats: Default::default(),
+            placements: Default::default(),
+            placements_path: None,
+            #[cfg(feature = "record")]
+            record_path: None,
+            glyph_brush: None,
+            executor: Default::default(),
             window_id: 0,
         })
     }
 
+    /// Record the placement of a named window, saving if enabled
+    pub fn set_placement(&mut self, name: &str, placement: WindowPlacement) {
+        self.placements.set(name, placement);
+        if let Some(path) = self.placements_path.as_ref() {
+            if let Err(e) = self.placements.save(path) {
+                warn!("Unable to save window placements: {}", e);
+            }
+        }
+    }
+
+    /// Set the global UI scale, scheduling a resize of all windows
+    pub fn set_ui_scale(&mut self, scale: f32) {
+        let scale = clamp_ui_scale(scale);
+        if scale != self.ui_scale {
+            debug!("Setting UI scale: {}", scale);
+            self.ui_scale = scale;
+            self.pending.push(PendingAction::ThemeResize);
+        }
+    }
+
+    /// Adjust the theme, scheduling any resize or redraw required
+    pub fn adjust_theme<F: FnOnce(&mut T) -> ThemeAction>(&mut self, f: F)
+    where
+        T: ThemeApi,
+    {
+        match f(&mut self.theme) {
+            ThemeAction::None => (),
+            ThemeAction::RedrawAll => self.pending.push(PendingAction::RedrawAll),
+            ThemeAction::ThemeResize => self.pending.push(PendingAction::ThemeResize),
+        }
+    }
+
     pub fn next_window_id(&mut self) -> WindowId {
         self.window_id += 1;
         WindowId::new(NonZeroU32::new(self.window_id).unwrap())
@@ -103,12 +217,37 @@ impl<T> SharedState<T> {
                 .unwrap_or_else(|e| warn!("Failed to set clipboard contents: {:?}", e))
         });
     }
+
+    #[cfg(not(feature = "tts"))]
+    #[inline]
+    pub fn announce(&mut self, text: &str, _priority: Priority) {
+        debug!("Announce: {}", text);
+    }
+
+    #[cfg(feature = "tts")]
+    pub fn announce(&mut self, text: &str, priority: Priority) {
+        let interrupt = priority == Priority::Assertive;
+        if let Some(tts) = self.tts.as_mut() {
+            if let Err(e) = tts.speak(text, interrupt) {
+                warn!("Failed to announce text: {:?}", e);
+            }
+        }
+    }
 }
 
 pub enum PendingAction {
     AddWindow(WindowId, Box<dyn kas::Window>),
+    /// Add a window, blocking input to the owner (last field) until closed
+    AddModalWindow(WindowId, Box<dyn kas::Window>, winit::window::WindowId),
     CloseWindow(WindowId),
     ThemeResize,
     RedrawAll,
     Update(UpdateHandle, u64),
+    /// Message pushed to the inspectors of all windows
+    Msg(Rc<dyn Any>),
+    /// Data dropped outside the source window (first field), at the given
+    /// coordinate relative to that window
+    Drop(winit::window::WindowId, DragData, Coord),
+    /// Poll a task of the executor
+    Poll(u64),
 }
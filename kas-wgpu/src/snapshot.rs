@@ -0,0 +1,428 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Snapshot testing
+//!
+//! This module supports visual regression tests: a widget tree is rendered
+//! off-screen to an [`Image`], which is compared against a stored reference.
+//!
+//! ```no_run
+//! use kas::widget::{Label, Window};
+//! use kas_wgpu::snapshot::{assert_snapshot, render};
+//!
+//! let window = Window::new("Snapshot", Label::new("Hello"));
+//! let theme = kas_wgpu::theme::FlatTheme::new();
+//! let image = render(theme, Box::new(window), kas::geom::Size(200, 100)).unwrap();
+//! assert_snapshot(&image, "tests/snapshots/label.ppm", 2);
+//! ```
+//!
+//! References are stored in binary PPM format. Where the reference file does
+//! not exist or the `KAS_UPDATE_SNAPSHOTS` environment variable is set, the
+//! reference is (re-)written instead of compared.
+
+use log::info;
+use std::any::Any;
+use std::env::var;
+use std::fs;
+use std::future::Future;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+use kas::event::{CursorIcon, DragData, ManagerState, Priority, UpdateHandle};
+use kas::geom::{Coord, Rect, Size};
+use kas::theme::{SizeHandle, Theme, ThemeAction, ThemeApi, Window as _};
+use kas::{WidgetId, WindowId};
+
+use crate::draw::DrawPipe;
+use crate::shared::SharedState;
+use crate::window::to_wgpu_color;
+use crate::{Error, Options};
+
+/// An RGB image with 8 bits per channel (sRGB)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    size: Size,
+    data: Vec<u8>,
+}
+
+impl Image {
+    /// Construct from RGB data
+    ///
+    /// Panics if `data.len()` does not match `size`.
+    pub fn new(size: Size, data: Vec<u8>) -> Self {
+        assert_eq!(data.len(), 3 * size.0 as usize * size.1 as usize);
+        Image { size, data }
+    }
+
+    /// Image dimensions
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    /// Raw RGB data, row-major without padding
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Get the pixel at the given coordinates
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 3] {
+        let i = 3 * (y as usize * self.size.0 as usize + x as usize);
+        [self.data[i], self.data[i + 1], self.data[i + 2]]
+    }
+
+    /// Read from a binary PPM (P6) file
+    pub fn read_ppm<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let mut buf = vec![];
+        fs::File::open(path)?.read_to_end(&mut buf)?;
+
+        // Header: magic, width, height, maxval, separated by whitespace,
+        // followed by a single whitespace character and the data
+        let invalid = |msg| io::Error::new(io::ErrorKind::InvalidData, msg);
+        let mut fields = [0u32; 3];
+        let mut pos = 2;
+        if !buf.starts_with(b"P6") {
+            return Err(invalid("not a binary PPM file"));
+        }
+        for field in fields.iter_mut() {
+            while pos < buf.len() && buf[pos].is_ascii_whitespace() {
+                pos += 1;
+            }
+            let start = pos;
+            while pos < buf.len() && buf[pos].is_ascii_digit() {
+                pos += 1;
+            }
+            *field = std::str::from_utf8(&buf[start..pos])
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .ok_or_else(|| invalid("bad PPM header"))?;
+        }
+        if fields[2] != 255 {
+            return Err(invalid("unsupported PPM bit depth"));
+        } else if pos >= buf.len() {
+            return Err(invalid("missing PPM data"));
+        }
+        let data = buf.split_off(pos + 1);
+        let size = Size(fields[0], fields[1]);
+        if data.len() != 3 * size.0 as usize * size.1 as usize {
+            return Err(invalid("bad PPM data length"));
+        }
+        Ok(Image { size, data })
+    }
+
+    /// Write to a binary PPM (P6) file
+    pub fn write_ppm<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        if let Some(dir) = path.as_ref().parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = fs::File::create(path)?;
+        write!(file, "P6\n{} {}\n255\n", self.size.0, self.size.1)?;
+        file.write_all(&self.data)
+    }
+}
+
+/// Result of comparing two images
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Comparison {
+    /// All pixels are within tolerance
+    Match,
+    /// Image sizes differ
+    SizeMismatch,
+    /// Some pixels differ by more than the tolerance
+    Differ {
+        /// Number of pixels exceeding the tolerance
+        pixels: usize,
+        /// Maximum difference in any channel
+        max_diff: u8,
+    },
+}
+
+/// Compare two images
+///
+/// Images match if no colour channel of any pixel differs by more than
+/// `tolerance`.
+pub fn compare(image: &Image, reference: &Image, tolerance: u8) -> Comparison {
+    if image.size != reference.size {
+        return Comparison::SizeMismatch;
+    }
+    let mut pixels = 0;
+    let mut max_diff = 0;
+    for (a, b) in image.data.chunks(3).zip(reference.data.chunks(3)) {
+        let diff = a
+            .iter()
+            .zip(b.iter())
+            .map(|(x, y)| (*x as i16 - *y as i16).abs() as u8)
+            .max()
+            .unwrap_or(0);
+        if diff > tolerance {
+            pixels += 1;
+            max_diff = max_diff.max(diff);
+        }
+    }
+    if pixels == 0 {
+        Comparison::Match
+    } else {
+        Comparison::Differ { pixels, max_diff }
+    }
+}
+
+/// Compare `image` against the reference stored at `path`
+///
+/// If the reference does not exist or the `KAS_UPDATE_SNAPSHOTS` environment
+/// variable is set, `image` is written to `path` instead.
+///
+/// On mismatch, `image` is written alongside the reference (with extension
+/// `actual.ppm`) for inspection, and this function panics.
+pub fn assert_snapshot<P: AsRef<Path>>(image: &Image, path: P, tolerance: u8) {
+    let path = path.as_ref();
+    if var("KAS_UPDATE_SNAPSHOTS").is_ok() || !path.exists() {
+        info!("Writing snapshot reference: {}", path.display());
+        image
+            .write_ppm(path)
+            .unwrap_or_else(|e| panic!("failed to write {}: {}", path.display(), e));
+        return;
+    }
+
+    let reference = Image::read_ppm(path)
+        .unwrap_or_else(|e| panic!("failed to read {}: {}", path.display(), e));
+    let result = compare(image, &reference, tolerance);
+    if result != Comparison::Match {
+        let actual = PathBuf::from(path).with_extension("actual.ppm");
+        let _ = image.write_ppm(&actual);
+        panic!(
+            "snapshot {} does not match ({:?}); see {}",
+            path.display(),
+            result,
+            actual.display()
+        );
+    }
+}
+
+/// Render a window off-screen
+///
+/// The window is sized to `size` (in physical pixels) with a scale factor of
+/// 1, configured, and drawn once using the given `theme`. Graphics adapter
+/// selection uses [`Options::from_env`].
+pub fn render<T: Theme<DrawPipe>>(
+    theme: T,
+    mut widget: Box<dyn kas::Window>,
+    size: Size,
+) -> Result<Image, Error> {
+    let mut shared = SharedState::new(theme, Options::from_env())?;
+    let format = wgpu::TextureFormat::Bgra8UnormSrgb;
+
+    let mut draw_pipe = DrawPipe::new(&mut shared, format, size);
+    let mut theme_window = shared.theme.new_window(&mut draw_pipe, 1.0);
+    let mut mgr = ManagerState::new(1.0);
+
+    let mut size_handle = unsafe { theme_window.size_handle(&mut draw_pipe) };
+    widget.resize(&mut size_handle, size);
+    drop(size_handle);
+    mgr.configure(&mut HeadlessTkWindow(&mut shared), &mut *widget);
+
+    let rect = Rect {
+        pos: Coord::ZERO,
+        size,
+    };
+    let mut draw_handle = unsafe {
+        shared
+            .theme
+            .draw_handle(&mut draw_pipe, &mut theme_window, rect)
+    };
+    let mut tkw = HeadlessTkWindow(&mut shared);
+    widget.draw(&mut draw_handle, &mgr.manager(&mut tkw));
+    drop(draw_handle);
+
+    let extent = wgpu::Extent3d {
+        width: size.0,
+        height: size.1,
+        depth: 1,
+    };
+    let texture = shared.device.create_texture(&wgpu::TextureDescriptor {
+        size: extent,
+        array_layer_count: 1,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage: wgpu::TextureUsage::OUTPUT_ATTACHMENT | wgpu::TextureUsage::COPY_SRC,
+    });
+    let view = texture.create_default_view();
+
+    let clear_color = to_wgpu_color(shared.theme.clear_colour());
+    let render_buf = draw_pipe.render(&mut shared.device, &view, clear_color);
+
+    // Rows of the copy destination must be aligned to 256 bytes
+    let row_pitch = (4 * size.0 + 255) & !255;
+    let buf_size = row_pitch as u64 * size.1 as u64;
+    let buffer = shared.device.create_buffer(&wgpu::BufferDescriptor {
+        size: buf_size,
+        usage: wgpu::BufferUsage::MAP_READ | wgpu::BufferUsage::COPY_DST,
+    });
+    let desc = wgpu::CommandEncoderDescriptor { todo: 0 };
+    let mut encoder = shared.device.create_command_encoder(&desc);
+    encoder.copy_texture_to_buffer(
+        wgpu::TextureCopyView {
+            texture: &texture,
+            mip_level: 0,
+            array_layer: 0,
+            origin: wgpu::Origin3d {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            },
+        },
+        wgpu::BufferCopyView {
+            buffer: &buffer,
+            offset: 0,
+            row_pitch,
+            image_height: size.1,
+        },
+        extent,
+    );
+    shared.queue.submit(&[render_buf, encoder.finish()]);
+
+    let result = Arc::new(Mutex::new(None));
+    let result2 = result.clone();
+    buffer.map_read_async(0, buf_size, move |r: wgpu::BufferMapAsyncResult<&[u8]>| {
+        if let Ok(mapping) = r {
+            *result2.lock().unwrap() = Some(mapping.data.to_vec());
+        }
+    });
+    shared.device.poll(true);
+
+    let raw = result.lock().unwrap().take().ok_or(Error::MapRead)?;
+    let mut data = Vec::with_capacity(3 * size.0 as usize * size.1 as usize);
+    for row in raw.chunks(row_pitch as usize) {
+        for bgra in row[0..4 * size.0 as usize].chunks(4) {
+            data.extend_from_slice(&[bgra[2], bgra[1], bgra[0]]);
+        }
+    }
+    Ok(Image { size, data })
+}
+
+/// Toolkit interface for off-screen rendering
+///
+/// Window management, cursors and size handles are not supported.
+struct HeadlessTkWindow<'a, T>(&'a mut SharedState<T>);
+
+impl<'a, T: Theme<DrawPipe>> kas::TkWindow for HeadlessTkWindow<'a, T> {
+    fn add_window(&mut self, _: Box<dyn kas::Window>) -> WindowId {
+        self.0.next_window_id()
+    }
+
+    fn add_modal_window(&mut self, _: Box<dyn kas::Window>) -> WindowId {
+        self.0.next_window_id()
+    }
+
+    fn close_window(&mut self, _: WindowId) {}
+
+    fn trigger_update(&mut self, _: UpdateHandle, _: u64) {}
+
+    fn push_msg(&mut self, _: Rc<dyn Any>) {}
+
+    fn spawn(&mut self, _: WidgetId, _: Pin<Box<dyn Future<Output = Rc<dyn Any>>>>) {}
+
+    #[inline]
+    fn get_clipboard(&mut self) -> Option<String> {
+        self.0.get_clipboard()
+    }
+
+    #[inline]
+    fn set_clipboard(&mut self, content: String) {
+        self.0.set_clipboard(content);
+    }
+
+    fn adjust_theme(&mut self, f: &mut dyn FnMut(&mut dyn ThemeApi) -> ThemeAction) {
+        f(&mut self.0.theme);
+    }
+
+    fn set_cursor_icon(&mut self, _: CursorIcon) {}
+
+    fn set_ime_position(&mut self, _: Coord) {}
+
+    #[inline]
+    fn announce(&mut self, text: &str, priority: Priority) {
+        self.0.announce(text, priority);
+    }
+
+    fn screen_areas(&self) -> Vec<Rect> {
+        vec![]
+    }
+
+    fn size_handle(&mut self, _: &mut dyn FnMut(&mut dyn SizeHandle)) {}
+
+    fn drop_outside(&mut self, _: DragData, _: Coord) {}
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use kas::widget::{CheckBox, Window};
+
+    fn image(size: Size, rgb: [u8; 3]) -> Image {
+        let n = size.0 as usize * size.1 as usize;
+        Image::new(size, rgb.iter().cloned().cycle().take(3 * n).collect())
+    }
+
+    #[test]
+    fn compare_images() {
+        let size = Size(4, 3);
+        let a = image(size, [10, 20, 30]);
+        assert_eq!(compare(&a, &a, 0), Comparison::Match);
+        assert_eq!(
+            compare(&a, &image(size, [12, 20, 30]), 2),
+            Comparison::Match
+        );
+        let differ = Comparison::Differ {
+            pixels: 12,
+            max_diff: 3,
+        };
+        assert_eq!(compare(&a, &image(size, [10, 23, 30]), 2), differ);
+        assert_eq!(
+            compare(&a, &image(Size(3, 4), [10, 20, 30]), 255),
+            Comparison::SizeMismatch
+        );
+    }
+
+    #[test]
+    fn ppm_round_trip() {
+        let mut data = vec![];
+        for i in 0..(3 * 5 * 2) {
+            data.push(i as u8 * 8);
+        }
+        let image = Image::new(Size(5, 2), data);
+        let path = std::env::temp_dir().join(format!("kas-snapshot-{}.ppm", std::process::id()));
+        image.write_ppm(&path).unwrap();
+        let read = Image::read_ppm(&path);
+        let _ = fs::remove_file(&path);
+        assert_eq!(read.unwrap(), image);
+        assert_eq!(image.pixel(1, 1), [144, 152, 160]);
+    }
+
+    // wgpu 0.4 aborts where no graphics adapter is available, thus this test
+    // must be run explicitly: cargo test -- --ignored
+    #[test]
+    #[ignore]
+    fn render_check_box() {
+        let size = Size(100, 40);
+        let render_state = |state| {
+            let window = Window::new("Snapshot", CheckBox::new("").state(state));
+            render(crate::theme::FlatTheme::new(), Box::new(window), size).unwrap()
+        };
+        let unchecked = render_state(false);
+        let checked = render_state(true);
+        assert_eq!(unchecked.size(), size);
+
+        // Only the check mark differs
+        match compare(&checked, &unchecked, 0) {
+            Comparison::Differ { pixels, .. } => assert!(pixels < (size.0 * size.1) as usize / 2),
+            result => panic!("unexpected comparison result: {:?}", result),
+        }
+        assert_eq!(compare(&render_state(true), &checked, 0), Comparison::Match);
+    }
+}
@@ -0,0 +1,44 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Bidirectional text support
+
+use std::borrow::Cow;
+
+use kas::LayoutDirection;
+use unicode_bidi::{BidiInfo, Level};
+
+/// Reorder `text` from logical to visual order
+///
+/// Each line is reordered independently using the Unicode Bidirectional
+/// Algorithm. Where the direction of a line cannot be determined from its
+/// content, the layout `direction` is used.
+///
+/// Text which is not wrapped before reordering may be wrapped incorrectly.
+/// Since positions within the result do not match the input, this should not
+/// be used for editable text.
+pub fn visual_order(text: &str, direction: LayoutDirection) -> Cow<'_, str> {
+    let level = match direction {
+        LayoutDirection::LeftToRight => Level::ltr(),
+        LayoutDirection::RightToLeft => Level::rtl(),
+    };
+    let info = BidiInfo::new(text, Some(level));
+    if !info.has_rtl() {
+        return Cow::Borrowed(text);
+    }
+
+    let mut result = String::with_capacity(text.len());
+    for (i, line) in text.split('\n').enumerate() {
+        if i > 0 {
+            result.push('\n');
+        }
+        let info = BidiInfo::new(line, Some(level));
+        match info.paragraphs.first() {
+            Some(para) => result.push_str(&info.reorder_line(para, 0..line.len())),
+            None => result.push_str(line),
+        }
+    }
+    Cow::Owned(result)
+}
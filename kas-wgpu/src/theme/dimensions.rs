@@ -10,14 +10,19 @@
 use std::any::Any;
 use std::f32;
 
-use wgpu_glyph::{Layout, Scale, Section};
+use wgpu_glyph::{Layout, Scale, SectionText, VariedSection};
 
+use kas::draw::Colour;
 use kas::geom::Size;
 use kas::layout::{AxisInfo, SizeRules, StretchPolicy};
-use kas::theme::{self, TextClass};
-use kas::Direction::{self, Horizontal, Vertical};
+use kas::text::RichText;
+use kas::theme::{self, TextClass, ThemeConfig};
+use kas::Direction::{Horizontal, Vertical};
+use kas::LayoutDirection;
 
+use super::rich;
 use crate::draw::{DrawPipe, DrawText};
+use crate::font;
 
 /// Parameterisation of [`Dimensions`]
 ///
@@ -33,10 +38,30 @@ pub struct DimensionsParams {
     pub button_frame: f32,
     /// Scrollbar width & min length
     pub scrollbar_size: f32,
+    /// Minimum size of checkboxes and scrollbar width (e.g. for touch input)
+    pub min_target: f32,
+    /// Width of the text caret (edit cursor)
+    pub caret_width: f32,
+}
+
+impl DimensionsParams {
+    /// Override parameters given by `config`
+    pub fn with_config(self, config: &ThemeConfig) -> Self {
+        DimensionsParams {
+            margin: config.margin.unwrap_or(self.margin),
+            frame_size: config.frame_size.unwrap_or(self.frame_size),
+            button_frame: config.button_frame.unwrap_or(self.button_frame),
+            scrollbar_size: config.scrollbar_size.unwrap_or(self.scrollbar_size),
+            min_target: self.min_target,
+            caret_width: config.caret_width.unwrap_or(self.caret_width),
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
 pub struct Dimensions {
+    pub dpi_factor: f32,
+    pub direction: LayoutDirection,
     pub font_scale: f32,
     pub line_height: u32,
     pub min_line_length: u32,
@@ -46,15 +71,25 @@ pub struct Dimensions {
     pub button_frame: u32,
     pub checkbox: u32,
     pub scrollbar: u32,
+    pub caret_width: u32,
 }
 
 impl Dimensions {
-    pub fn new(params: DimensionsParams, font_size: f32, dpi_factor: f32) -> Self {
+    pub fn new(
+        params: DimensionsParams,
+        font_size: f32,
+        dpi_factor: f32,
+        direction: LayoutDirection,
+    ) -> Self {
         let font_scale = font_size * dpi_factor;
         let line_height = font_scale.round() as u32;
         let margin = (params.margin * dpi_factor).round() as u32;
         let frame = (params.frame_size * dpi_factor).round() as u32;
+        let min_target = (params.min_target * dpi_factor).round() as u32;
+        let scrollbar = (params.scrollbar_size * dpi_factor).round() as u32;
         Dimensions {
+            dpi_factor,
+            direction,
             font_scale,
             line_height,
             min_line_length: line_height * 10,
@@ -62,8 +97,9 @@ impl Dimensions {
             margin,
             frame,
             button_frame: (params.button_frame * dpi_factor).round() as u32,
-            checkbox: (font_scale * 0.7).round() as u32 + 2 * (margin + frame),
-            scrollbar: (params.scrollbar_size * dpi_factor).round() as u32,
+            checkbox: ((font_scale * 0.7).round() as u32 + 2 * (margin + frame)).max(min_target),
+            scrollbar: scrollbar.max(min_target),
+            caret_width: ((params.caret_width * dpi_factor).round() as u32).max(1),
         }
     }
 }
@@ -73,9 +109,14 @@ pub struct DimensionsWindow {
 }
 
 impl DimensionsWindow {
-    pub fn new(dims: DimensionsParams, font_size: f32, dpi_factor: f32) -> Self {
+    pub fn new(
+        dims: DimensionsParams,
+        font_size: f32,
+        dpi_factor: f32,
+        direction: LayoutDirection,
+    ) -> Self {
         DimensionsWindow {
-            dims: Dimensions::new(dims, font_size, dpi_factor),
+            dims: Dimensions::new(dims, font_size, dpi_factor, direction),
         }
     }
 }
@@ -103,9 +144,83 @@ impl<'a> SizeHandle<'a> {
     pub fn new(draw: &'a mut DrawPipe, dims: &'a Dimensions) -> Self {
         SizeHandle { draw, dims }
     }
+
+    // Size rules for text sections, as in text_bound
+    fn text_rules(
+        &mut self,
+        parts: Vec<SectionText>,
+        class: TextClass,
+        axis: AxisInfo,
+    ) -> SizeRules {
+        let layout = match class {
+            TextClass::Label | TextClass::EditMulti | TextClass::Monospace => {
+                Layout::default_wrap()
+            }
+            TextClass::Button | TextClass::Edit | TextClass::Placeholder => {
+                Layout::default_single_line()
+            }
+        };
+        let mut bounds = (f32::INFINITY, f32::INFINITY);
+        if let Some(size) = axis.size_other_if_fixed(Horizontal) {
+            bounds.1 = size as f32;
+        } else if let Some(size) = axis.size_other_if_fixed(Vertical) {
+            if class != TextClass::Monospace {
+                bounds.0 = size as f32;
+            }
+        }
+
+        let bounds = self.draw.glyph_bounds(VariedSection {
+            text: parts,
+            screen_position: (0.0, 0.0),
+            bounds,
+            layout,
+            ..VariedSection::default()
+        });
+        let bound = bounds
+            .map(|(min, max)| {
+                let size = max - min;
+                let bound = if axis.is_horizontal() { size.0 } else { size.1 };
+                bound as u32
+            })
+            .unwrap_or(0);
+
+        let line_height = self.dims.line_height;
+        if axis.is_horizontal() {
+            let min = match class {
+                TextClass::Edit | TextClass::EditMulti => self.dims.min_line_length,
+                _ => bound.min(self.dims.min_line_length),
+            };
+            let ideal = match class {
+                TextClass::Monospace => bound,
+                _ => bound.min(self.dims.max_line_length),
+            };
+            SizeRules::new(min, ideal, StretchPolicy::LowUtility)
+        } else {
+            let min = match class {
+                TextClass::EditMulti => line_height * 3,
+                _ => line_height,
+            };
+            let ideal = bound.max(line_height);
+            let stretch = match class {
+                TextClass::Button | TextClass::Edit | TextClass::Placeholder => {
+                    StretchPolicy::Fixed
+                }
+                _ => StretchPolicy::Filler,
+            };
+            SizeRules::new(min, ideal, stretch)
+        }
+    }
 }
 
 impl<'a> theme::SizeHandle for SizeHandle<'a> {
+    fn dpi_factor(&self) -> f32 {
+        self.dims.dpi_factor
+    }
+
+    fn layout_direction(&self) -> LayoutDirection {
+        self.dims.direction
+    }
+
     fn outer_frame(&self) -> (Size, Size) {
         let f = self.dims.frame as u32;
         (Size::uniform(f), Size::uniform(f))
@@ -123,59 +238,53 @@ impl<'a> theme::SizeHandle for SizeHandle<'a> {
         self.dims.line_height
     }
 
+    fn char_width(&self, class: TextClass) -> f32 {
+        font::advance_width('0', class, Scale::uniform(self.dims.font_scale))
+    }
+
     fn text_bound(&mut self, text: &str, class: TextClass, axis: AxisInfo) -> SizeRules {
-        let font_scale = self.dims.font_scale;
-        let line_height = self.dims.line_height;
-        let draw = &mut self.draw;
-        let mut bound = |dir: Direction| -> u32 {
-            let layout = match class {
-                TextClass::Label | TextClass::EditMulti => Layout::default_wrap(),
-                TextClass::Button | TextClass::Edit => Layout::default_single_line(),
-            };
-            let mut bounds = (f32::INFINITY, f32::INFINITY);
-            if let Some(size) = axis.size_other_if_fixed(Horizontal) {
-                bounds.1 = size as f32;
-            } else if let Some(size) = axis.size_other_if_fixed(Vertical) {
-                bounds.0 = size as f32;
+        let scale = Scale::uniform(self.dims.font_scale);
+        let parts = font::section_text(text, class, false, false, scale, Colour::default());
+        self.text_rules(parts.collect(), class, axis)
+    }
+
+    fn rich_text_bound(&mut self, text: &RichText, class: TextClass, axis: AxisInfo) -> SizeRules {
+        let parts = rich::section_text(text, class, self.dims.font_scale, Colour::default());
+        self.text_rules(parts, class, axis)
+    }
+
+    fn text_size(&mut self, text: &str, class: TextClass, wrap_width: Option<u32>) -> Size {
+        let layout = match class {
+            TextClass::Label | TextClass::EditMulti | TextClass::Monospace => {
+                Layout::default_wrap()
+            }
+            TextClass::Button | TextClass::Edit | TextClass::Placeholder => {
+                Layout::default_single_line()
             }
+        };
+        let bounds = (
+            wrap_width
+                .filter(|_| class != TextClass::Monospace)
+                .map(|w| w as f32)
+                .unwrap_or(f32::INFINITY),
+            f32::INFINITY,
+        );
 
-            let bounds = draw.glyph_bounds(Section {
-                text,
+        let scale = Scale::uniform(self.dims.font_scale);
+        let parts = font::section_text(text, class, false, false, scale, Colour::default());
+        self.draw
+            .glyph_bounds(VariedSection {
+                text: parts.collect(),
                 screen_position: (0.0, 0.0),
-                scale: Scale::uniform(font_scale),
                 bounds,
                 layout,
-                ..Section::default()
-            });
-
-            bounds
-                .map(|(min, max)| match dir {
-                    Horizontal => (max - min).0,
-                    Vertical => (max - min).1,
-                } as u32)
-                .unwrap_or(0)
-        };
-
-        if axis.is_horizontal() {
-            let bound = bound(Horizontal);
-            let min = match class {
-                TextClass::Edit | TextClass::EditMulti => self.dims.min_line_length,
-                _ => bound.min(self.dims.min_line_length),
-            };
-            let ideal = bound.min(self.dims.max_line_length);
-            SizeRules::new(min, ideal, StretchPolicy::LowUtility)
-        } else {
-            let min = match class {
-                TextClass::EditMulti => line_height * 3,
-                _ => line_height,
-            };
-            let ideal = bound(Vertical).max(line_height);
-            let stretch = match class {
-                TextClass::Button | TextClass::Edit => StretchPolicy::Fixed,
-                _ => StretchPolicy::Filler,
-            };
-            SizeRules::new(min, ideal, stretch)
-        }
+                ..VariedSection::default()
+            })
+            .map(|(min, max)| {
+                let size = max - min;
+                Size(size.0.ceil() as u32, size.1.ceil() as u32)
+            })
+            .unwrap_or(Size::ZERO)
     }
 
     fn button_surround(&self) -> (Size, Size) {
@@ -197,8 +306,16 @@ impl<'a> theme::SizeHandle for SizeHandle<'a> {
         self.checkbox()
     }
 
+    fn icon(&self) -> Size {
+        Size::uniform(self.dims.line_height)
+    }
+
     fn scrollbar(&self) -> (u32, u32, u32) {
         let s = self.dims.scrollbar as u32;
         (s, s, 2 * s)
     }
+
+    fn separator(&self) -> u32 {
+        self.dims.frame
+    }
 }
@@ -8,24 +8,32 @@
 //! Widget size and appearance can be modified through themes.
 
 use std::f32;
-use wgpu_glyph::{Font, HorizontalAlign, Layout, Scale, Section, VerticalAlign};
+use std::ops::Range;
+use wgpu_glyph::Font;
 
-use kas::draw::{Colour, Draw};
+use kas::draw::{Colour, Draw, DrawImage, ImageData, Transform};
 use kas::event::HighlightState;
-use kas::geom::{Coord, Rect};
-use kas::theme::{self, TextClass, TextProperties, ThemeAction, ThemeApi};
-use kas::Align;
-use kas::Direction;
+use kas::geom::{Coord, Rect, Size};
+use kas::text::RichText;
+use kas::theme::{
+    self, ColourScheme, TextProperties, ThemeAction, ThemeApi, ThemeConfig, UnderlineStyle,
+    ValidationState,
+};
+use kas::{Direction, LayoutDirection};
 
+use super::text::TextHandle;
 use super::{Dimensions, DimensionsParams, DimensionsWindow};
-use crate::draw::{DrawExt, DrawPipe, DrawText, Vec2};
-use crate::resources::colours::ThemeColours;
+use crate::draw::{DrawExt, DrawPipe};
 
 /// A simple flat theme.
 #[derive(Clone, Debug)]
 pub struct FlatTheme {
     font_size: f32,
-    cols: ThemeColours,
+    cols: ColourScheme,
+    touch: bool,
+    direction: LayoutDirection,
+    /// Overrides of dimension parameters
+    config: ThemeConfig,
 }
 
 impl FlatTheme {
@@ -33,9 +41,28 @@ impl FlatTheme {
     pub fn new() -> Self {
         FlatTheme {
             font_size: 18.0,
-            cols: ThemeColours::new(),
+            cols: ColourScheme::new(),
+            touch: false,
+            direction: LayoutDirection::default(),
+            config: ThemeConfig::new(),
         }
     }
+
+    /// Set the colour scheme (chain style)
+    pub fn with_colours(mut self, scheme: ColourScheme) -> Self {
+        self.cols = scheme;
+        self
+    }
+
+    /// Get the colour scheme
+    pub fn colours(&self) -> &ColourScheme {
+        &self.cols
+    }
+
+    fn dims(&self) -> DimensionsParams {
+        let dims = if self.touch { TOUCH_DIMS } else { DIMS };
+        dims.with_config(&self.config)
+    }
 }
 
 const DIMS: DimensionsParams = DimensionsParams {
@@ -43,12 +70,27 @@ const DIMS: DimensionsParams = DimensionsParams {
     frame_size: 4.0,
     button_frame: 6.0,
     scrollbar_size: 8.0,
+    min_target: 0.0,
+    caret_width: 1.5,
 };
 
+/// Dimensions used in touch mode
+const TOUCH_DIMS: DimensionsParams = DimensionsParams {
+    margin: 4.0,
+    frame_size: 4.0,
+    button_frame: 12.0,
+    scrollbar_size: 16.0,
+    min_target: 32.0,
+    caret_width: 2.0,
+};
+
+/// Fraction by which a window is darkened while blocked by a modal window
+const DIM_AMOUNT: f32 = 0.4;
+
 pub struct DrawHandle<'a> {
     draw: &'a mut DrawPipe,
     window: &'a mut DimensionsWindow,
-    cols: &'a ThemeColours,
+    cols: &'a ColourScheme,
     rect: Rect,
     offset: Coord,
     pass: usize,
@@ -59,11 +101,11 @@ impl theme::Theme<DrawPipe> for FlatTheme {
     type DrawHandle = DrawHandle<'static>;
 
     fn new_window(&self, _draw: &mut DrawPipe, dpi_factor: f32) -> Self::Window {
-        DimensionsWindow::new(DIMS, self.font_size, dpi_factor)
+        DimensionsWindow::new(self.dims(), self.font_size, dpi_factor, self.direction)
     }
 
     fn update_window(&self, window: &mut Self::Window, dpi_factor: f32) {
-        window.dims = Dimensions::new(DIMS, self.font_size, dpi_factor);
+        window.dims = Dimensions::new(self.dims(), self.font_size, dpi_factor, self.direction);
     }
 
     unsafe fn draw_handle<'a>(
@@ -77,7 +119,7 @@ impl theme::Theme<DrawPipe> for FlatTheme {
         DrawHandle {
             draw: transmute::<&'a mut DrawPipe, &'static mut DrawPipe>(draw),
             window: transmute::<&'a mut Self::Window, &'static mut Self::Window>(window),
-            cols: transmute::<&'a ThemeColours, &'static ThemeColours>(&self.cols),
+            cols: transmute::<&'a ColourScheme, &'static ColourScheme>(&self.cols),
             rect,
             offset: Coord::ZERO,
             pass: 0,
@@ -85,7 +127,7 @@ impl theme::Theme<DrawPipe> for FlatTheme {
     }
 
     fn get_fonts<'a>(&self) -> Vec<Font<'a>> {
-        vec![crate::font::get_font()]
+        crate::font::get_fonts()
     }
 
     fn light_direction(&self) -> (f32, f32) {
@@ -104,13 +146,59 @@ impl ThemeApi for FlatTheme {
     }
 
     fn set_colours(&mut self, scheme: &str) -> ThemeAction {
-        if let Some(scheme) = ThemeColours::open(scheme) {
+        if let Some(scheme) = ColourScheme::open(scheme) {
             self.cols = scheme;
             ThemeAction::RedrawAll
         } else {
             ThemeAction::None
         }
     }
+
+    fn set_colour_scheme(&mut self, scheme: ColourScheme) -> ThemeAction {
+        self.cols = scheme;
+        ThemeAction::RedrawAll
+    }
+
+    fn apply_config(&mut self, config: &ThemeConfig) -> ThemeAction {
+        if let Some(size) = config.font_size {
+            self.font_size = size;
+        }
+        if let Some(scheme) = config.colours.as_ref() {
+            self.cols = scheme.clone();
+        }
+        self.config = config.clone();
+        ThemeAction::ThemeResize
+    }
+
+    fn set_touch_mode(&mut self, touch: bool) -> ThemeAction {
+        if touch != self.touch {
+            self.touch = touch;
+            ThemeAction::ThemeResize
+        } else {
+            ThemeAction::None
+        }
+    }
+
+    fn set_layout_direction(&mut self, direction: LayoutDirection) -> ThemeAction {
+        if direction != self.direction {
+            self.direction = direction;
+            ThemeAction::ThemeResize
+        } else {
+            ThemeAction::None
+        }
+    }
+}
+
+impl<'a> DrawHandle<'a> {
+    fn text_handle(&mut self) -> TextHandle<'_> {
+        TextHandle {
+            draw: self.draw,
+            dims: &self.window.dims,
+            cols: self.cols,
+            offset: self.offset,
+            pass: self.pass,
+        }
+    }
 }
 
 impl<'a> theme::DrawHandle for DrawHandle<'a> {
@@ -133,11 +221,60 @@ impl<'a> theme::DrawHandle for DrawHandle<'a> {
         f(&mut handle);
     }
 
+    fn transform_region(
+        &mut self,
+        rect: Rect,
+        transform: Transform,
+        f: &mut dyn FnMut(&mut dyn theme::DrawHandle),
+    ) {
+        let pass = self.draw.add_clip_region(rect + self.offset);
+        let old = self.draw.transform();
+        let offset = Transform::translation(self.offset.0 as f32, self.offset.1 as f32);
+        self.draw.set_transform(transform.then(offset).then(old));
+        let mut handle = DrawHandle {
+            draw: self.draw,
+            window: self.window,
+            cols: self.cols,
+            rect: transform.inverse().map_rect(rect),
+            offset: Coord::ZERO,
+            pass,
+        };
+        f(&mut handle);
+        self.draw.set_transform(old);
+    }
+
+    fn with_opacity(&mut self, opacity: f32, f: &mut dyn FnMut(&mut dyn theme::DrawHandle)) {
+        let old = self.draw.opacity();
+        self.draw.set_opacity(old * opacity);
+        f(self);
+        self.draw.set_opacity(old);
+    }
+
+    fn with_dimming(&mut self, f: &mut dyn FnMut(&mut dyn theme::DrawHandle)) {
+        let old = self.draw.dimming();
+        self.draw.set_dimming(Colour::grey(0.0), DIM_AMOUNT);
+        // The window background is not drawn by widgets
+        let col = self.cols.background;
+        self.draw.rect(self.pass, self.rect, col);
+        f(self);
+        self.draw.set_dimming(old.0, old.1);
+    }
+
     fn target_rect(&self) -> Rect {
         // Translate to local coordinates
         self.rect - self.offset
     }
 
+    fn rect(&mut self, rect: Rect, col: Colour) {
+        let rect = rect + self.offset;
+        self.draw.rect(self.pass, rect, col);
+    }
+
+    fn frame(&mut self, outer: Rect, inner: Rect, col: Colour) {
+        let (outer, inner) = (outer + self.offset, inner + self.offset);
+        self.draw.frame(self.pass, outer, inner, col);
+    }
+
     fn outer_frame(&mut self, rect: Rect) {
         let outer = rect + self.offset;
         let inner = outer.shrink(self.window.dims.frame);
@@ -146,43 +283,83 @@ impl<'a> theme::DrawHandle for DrawHandle<'a> {
     }
 
     fn text(&mut self, rect: Rect, text: &str, props: TextProperties) {
-        let bounds = Coord::from(rect.size);
+        self.text_handle().text(rect, text, props);
+    }
 
-        let col = match props.class {
-            TextClass::Label => self.cols.label_text,
-            TextClass::Button => self.cols.button_text,
-            TextClass::Edit | TextClass::EditMulti => self.cols.text,
-        };
+    fn text_selected(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        range: Range<usize>,
+        props: TextProperties,
+    ) {
+        self.text_handle().text_selected(rect, text, range, props);
+    }
 
-        // TODO: support justified alignment
-        let (h_align, h_offset) = match props.horiz {
-            Align::Begin | Align::Stretch => (HorizontalAlign::Left, 0),
-            Align::Centre => (HorizontalAlign::Center, bounds.0 / 2),
-            Align::End => (HorizontalAlign::Right, bounds.0),
-        };
-        let (v_align, v_offset) = match props.vert {
-            Align::Begin | Align::Stretch => (VerticalAlign::Top, 0),
-            Align::Centre => (VerticalAlign::Center, bounds.1 / 2),
-            Align::End => (VerticalAlign::Bottom, bounds.1),
-        };
+    fn rich_text(&mut self, rect: Rect, text: &RichText, props: TextProperties) {
+        self.text_handle().rich_text(rect, text, props);
+    }
 
-        let text_pos = rect.pos + self.offset + Coord(h_offset, v_offset);
+    fn text_underline(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        range: Range<usize>,
+        style: UnderlineStyle,
+        props: TextProperties,
+    ) {
+        self.text_handle()
+            .text_underline(rect, text, range, style, props);
+    }
 
-        let layout = match props.class {
-            TextClass::Label | TextClass::EditMulti => Layout::default_wrap(),
-            TextClass::Button | TextClass::Edit => Layout::default_single_line(),
-        };
-        let layout = layout.h_align(h_align).v_align(v_align);
+    fn text_selection(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        range: Range<usize>,
+        props: TextProperties,
+    ) {
+        self.text_handle().text_selection(rect, text, range, props);
+    }
+
+    fn text_caret(&mut self, rect: Rect, text: &str, index: usize, props: TextProperties) {
+        self.text_handle().text_caret(rect, text, index, props);
+    }
 
-        self.draw.draw_text(Section {
-            text,
-            screen_position: Vec2::from(text_pos).into(),
-            color: col.into(),
-            scale: Scale::uniform(self.window.dims.font_scale),
-            bounds: Vec2::from(bounds).into(),
-            layout,
-            ..Section::default()
-        });
+    fn selection_box(&mut self, rect: Rect) {
+        let rect = rect + self.offset;
+        self.draw.rect(self.pass, rect, self.cols.selection);
+    }
+
+    fn scroll_edge(&mut self, rect: Rect) {
+        let rect = rect + self.offset;
+        self.draw.rect(self.pass, rect, self.cols.key_nav_focus);
+    }
+
+    fn image(&mut self, rect: Rect, image: &ImageData) {
+        let rect = rect + self.offset;
+        self.draw.image(self.pass, image, rect);
+    }
+
+    fn interaction_frame(&mut self, rect: Rect, highlights: HighlightState) {
+        if let Some(col) = self.cols.interaction_state(highlights) {
+            let outer = rect + self.offset;
+            let inner = outer.shrink(self.window.dims.margin);
+            self.draw.frame(self.pass, outer, inner, col);
+        }
+    }
+
+    fn separator(&mut self, rect: Rect) {
+        let rect = rect + self.offset;
+        self.draw.rect(self.pass, rect, self.cols.frame);
+    }
+
+    fn popup_box(&mut self, rect: Rect) {
+        let outer = rect + self.offset;
+        let inner = outer.shrink(self.window.dims.frame);
+        self.draw
+            .rounded_frame(self.pass, outer, inner, self.cols.frame);
+        self.draw.rect(self.pass, inner, self.cols.background);
     }
 
     fn button(&mut self, rect: Rect, highlights: HighlightState) {
@@ -202,7 +379,7 @@ impl<'a> theme::DrawHandle for DrawHandle<'a> {
         self.draw.rect(self.pass, inner, col);
     }
 
-    fn edit_box(&mut self, rect: Rect, highlights: HighlightState) {
+    fn edit_box(&mut self, rect: Rect, error: bool, highlights: HighlightState) {
         let mut outer = rect + self.offset;
 
         let mut inner = outer.shrink(self.window.dims.frame);
@@ -215,10 +392,24 @@ impl<'a> theme::DrawHandle for DrawHandle<'a> {
             self.draw.frame(self.pass, outer, inner, col);
         }
 
-        self.draw.rect(self.pass, inner, self.cols.text_area);
+        let col = self.cols.text_area_state(error);
+        self.draw.rect(self.pass, inner, col);
     }
 
-    fn checkbox(&mut self, rect: Rect, checked: bool, highlights: HighlightState) {
+    fn validation_frame(&mut self, rect: Rect, state: ValidationState) {
+        if let Some(col) = self.cols.validation_state(state) {
+            let outer = rect + self.offset;
+            let inner = outer.shrink(self.window.dims.frame);
+            self.draw.rounded_frame(self.pass, outer, inner, col);
+        }
+    }
+
+    fn validation_message(&mut self, rect: Rect, text: &str, state: ValidationState) {
+        self.text_handle().validation_message(rect, text, state);
+    }
+
+    fn checkbox(&mut self, rect: Rect, state: Option<bool>, highlights: HighlightState) {
+        let checked = state != Some(false);
         let mut outer = rect + self.offset;
 
         let mut inner = outer.shrink(self.window.dims.frame);
@@ -239,13 +430,24 @@ impl<'a> theme::DrawHandle for DrawHandle<'a> {
             .cols
             .check_mark_state(highlights, checked)
             .unwrap_or(self.cols.text_area);
-        self.draw.rect(self.pass, inner, col);
+        if state.is_none() {
+            // Indeterminate state: draw a bar across the middle
+            self.draw.rect(self.pass, inner, self.cols.text_area);
+            let h = inner.size.1 / 3;
+            let bar = Rect {
+                pos: inner.pos + Coord(0, h as i32),
+                size: inner.size - Size(0, 2 * h),
+            };
+            self.draw.rect(self.pass, bar, col);
+        } else {
+            self.draw.rect(self.pass, inner, col);
+        }
     }
 
     #[inline]
     fn radiobox(&mut self, rect: Rect, checked: bool, highlights: HighlightState) {
         // TODO: distinct
-        self.checkbox(rect, checked, highlights);
+        self.checkbox(rect, Some(checked), highlights);
     }
 
     fn scrollbar(
@@ -5,10 +5,13 @@
 
 //! Themes
 
+mod bidi;
 mod dimensions;
 mod flat_theme;
 mod multi_theme;
+mod rich;
 mod shaded_theme;
+mod text;
 
 pub(crate) use dimensions::{Dimensions, DimensionsParams, DimensionsWindow};
 
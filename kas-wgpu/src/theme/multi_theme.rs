@@ -6,13 +6,18 @@
 //! Wrapper around mutliple themes, supporting run-time switching
 
 use std::f32;
+use std::ops::Range;
 use wgpu_glyph::Font;
 
-use kas::draw::Colour;
+use kas::draw::{Colour, ImageData, Transform};
 use kas::event::HighlightState;
 use kas::geom::{Coord, Rect};
-use kas::theme::{self, TextProperties, ThemeAction, ThemeApi};
-use kas::Direction;
+use kas::text::RichText;
+use kas::theme::{
+    self, ColourScheme, TextProperties, ThemeAction, ThemeApi, ThemeConfig, UnderlineStyle,
+    ValidationState,
+};
+use kas::{Direction, LayoutDirection};
 
 use super::{DimensionsWindow, FlatTheme, ShadedTheme};
 use crate::draw::DrawPipe;
@@ -40,6 +45,15 @@ impl MultiTheme {
             shaded: ShadedTheme::new(),
         }
     }
+
+    /// Set the colour scheme of all themes (chain style)
+    pub fn with_colours(self, scheme: ColourScheme) -> Self {
+        MultiTheme {
+            which: self.which,
+            flat: self.flat.with_colours(scheme.clone()),
+            shaded: self.shaded.with_colours(scheme),
+        }
+    }
 }
 
 pub enum WhichDrawHandle {
@@ -116,6 +130,26 @@ impl ThemeApi for MultiTheme {
         self.shaded.set_colours(scheme)
     }
 
+    fn set_colour_scheme(&mut self, scheme: ColourScheme) -> ThemeAction {
+        let _ = self.flat.set_colour_scheme(scheme.clone());
+        self.shaded.set_colour_scheme(scheme)
+    }
+
+    fn apply_config(&mut self, config: &ThemeConfig) -> ThemeAction {
+        let _ = self.flat.apply_config(config);
+        self.shaded.apply_config(config)
+    }
+
+    fn set_touch_mode(&mut self, touch: bool) -> ThemeAction {
+        let _ = self.flat.set_touch_mode(touch);
+        self.shaded.set_touch_mode(touch)
+    }
+
+    fn set_layout_direction(&mut self, direction: LayoutDirection) -> ThemeAction {
+        let _ = self.flat.set_layout_direction(direction);
+        self.shaded.set_layout_direction(direction)
+    }
+
     fn set_theme(&mut self, theme: &str) -> ThemeAction {
         match theme {
             "flat" if self.which != WhichTheme::Flat => {
@@ -144,6 +178,32 @@ impl theme::DrawHandle for WhichDrawHandle {
         }
     }
 
+    fn transform_region(
+        &mut self,
+        rect: Rect,
+        transform: Transform,
+        f: &mut dyn FnMut(&mut dyn theme::DrawHandle),
+    ) {
+        match self {
+            WhichDrawHandle::Flat(handle) => handle.transform_region(rect, transform, f),
+            WhichDrawHandle::Shaded(handle) => handle.transform_region(rect, transform, f),
+        }
+    }
+
+    fn with_opacity(&mut self, opacity: f32, f: &mut dyn FnMut(&mut dyn theme::DrawHandle)) {
+        match self {
+            WhichDrawHandle::Flat(handle) => handle.with_opacity(opacity, f),
+            WhichDrawHandle::Shaded(handle) => handle.with_opacity(opacity, f),
+        }
+    }
+
+    fn with_dimming(&mut self, f: &mut dyn FnMut(&mut dyn theme::DrawHandle)) {
+        match self {
+            WhichDrawHandle::Flat(handle) => handle.with_dimming(f),
+            WhichDrawHandle::Shaded(handle) => handle.with_dimming(f),
+        }
+    }
+
     fn target_rect(&self) -> Rect {
         match self {
             WhichDrawHandle::Flat(handle) => handle.target_rect(),
@@ -151,6 +211,20 @@ impl theme::DrawHandle for WhichDrawHandle {
         }
     }
 
+    fn rect(&mut self, rect: Rect, col: Colour) {
+        match self {
+            WhichDrawHandle::Flat(handle) => handle.rect(rect, col),
+            WhichDrawHandle::Shaded(handle) => handle.rect(rect, col),
+        }
+    }
+
+    fn frame(&mut self, outer: Rect, inner: Rect, col: Colour) {
+        match self {
+            WhichDrawHandle::Flat(handle) => handle.frame(outer, inner, col),
+            WhichDrawHandle::Shaded(handle) => handle.frame(outer, inner, col),
+        }
+    }
+
     fn outer_frame(&mut self, rect: Rect) {
         match self {
             WhichDrawHandle::Flat(handle) => handle.outer_frame(rect),
@@ -165,6 +239,104 @@ impl theme::DrawHandle for WhichDrawHandle {
         }
     }
 
+    fn rich_text(&mut self, rect: Rect, text: &RichText, props: TextProperties) {
+        match self {
+            WhichDrawHandle::Flat(handle) => handle.rich_text(rect, text, props),
+            WhichDrawHandle::Shaded(handle) => handle.rich_text(rect, text, props),
+        }
+    }
+
+    fn text_selected(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        range: Range<usize>,
+        props: TextProperties,
+    ) {
+        match self {
+            WhichDrawHandle::Flat(handle) => handle.text_selected(rect, text, range, props),
+            WhichDrawHandle::Shaded(handle) => handle.text_selected(rect, text, range, props),
+        }
+    }
+
+    fn text_underline(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        range: Range<usize>,
+        style: UnderlineStyle,
+        props: TextProperties,
+    ) {
+        match self {
+            WhichDrawHandle::Flat(handle) => handle.text_underline(rect, text, range, style, props),
+            WhichDrawHandle::Shaded(handle) => {
+                handle.text_underline(rect, text, range, style, props)
+            }
+        }
+    }
+
+    fn text_selection(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        range: Range<usize>,
+        props: TextProperties,
+    ) {
+        match self {
+            WhichDrawHandle::Flat(handle) => handle.text_selection(rect, text, range, props),
+            WhichDrawHandle::Shaded(handle) => handle.text_selection(rect, text, range, props),
+        }
+    }
+
+    fn text_caret(&mut self, rect: Rect, text: &str, index: usize, props: TextProperties) {
+        match self {
+            WhichDrawHandle::Flat(handle) => handle.text_caret(rect, text, index, props),
+            WhichDrawHandle::Shaded(handle) => handle.text_caret(rect, text, index, props),
+        }
+    }
+
+    fn selection_box(&mut self, rect: Rect) {
+        match self {
+            WhichDrawHandle::Flat(handle) => handle.selection_box(rect),
+            WhichDrawHandle::Shaded(handle) => handle.selection_box(rect),
+        }
+    }
+
+    fn scroll_edge(&mut self, rect: Rect) {
+        match self {
+            WhichDrawHandle::Flat(handle) => handle.scroll_edge(rect),
+            WhichDrawHandle::Shaded(handle) => handle.scroll_edge(rect),
+        }
+    }
+
+    fn image(&mut self, rect: Rect, image: &ImageData) {
+        match self {
+            WhichDrawHandle::Flat(handle) => handle.image(rect, image),
+            WhichDrawHandle::Shaded(handle) => handle.image(rect, image),
+        }
+    }
+
+    fn interaction_frame(&mut self, rect: Rect, highlights: HighlightState) {
+        match self {
+            WhichDrawHandle::Flat(handle) => handle.interaction_frame(rect, highlights),
+            WhichDrawHandle::Shaded(handle) => handle.interaction_frame(rect, highlights),
+        }
+    }
+
+    fn separator(&mut self, rect: Rect) {
+        match self {
+            WhichDrawHandle::Flat(handle) => handle.separator(rect),
+            WhichDrawHandle::Shaded(handle) => handle.separator(rect),
+        }
+    }
+
+    fn popup_box(&mut self, rect: Rect) {
+        match self {
+            WhichDrawHandle::Flat(handle) => handle.popup_box(rect),
+            WhichDrawHandle::Shaded(handle) => handle.popup_box(rect),
+        }
+    }
+
     fn button(&mut self, rect: Rect, highlights: HighlightState) {
         match self {
             WhichDrawHandle::Flat(handle) => handle.button(rect, highlights),
@@ -172,17 +344,31 @@ impl theme::DrawHandle for WhichDrawHandle {
         }
     }
 
-    fn edit_box(&mut self, rect: Rect, highlights: HighlightState) {
+    fn edit_box(&mut self, rect: Rect, error: bool, highlights: HighlightState) {
+        match self {
+            WhichDrawHandle::Flat(handle) => handle.edit_box(rect, error, highlights),
+            WhichDrawHandle::Shaded(handle) => handle.edit_box(rect, error, highlights),
+        }
+    }
+
+    fn validation_frame(&mut self, rect: Rect, state: ValidationState) {
+        match self {
+            WhichDrawHandle::Flat(handle) => handle.validation_frame(rect, state),
+            WhichDrawHandle::Shaded(handle) => handle.validation_frame(rect, state),
+        }
+    }
+
+    fn validation_message(&mut self, rect: Rect, text: &str, state: ValidationState) {
         match self {
-            WhichDrawHandle::Flat(handle) => handle.edit_box(rect, highlights),
-            WhichDrawHandle::Shaded(handle) => handle.edit_box(rect, highlights),
+            WhichDrawHandle::Flat(handle) => handle.validation_message(rect, text, state),
+            WhichDrawHandle::Shaded(handle) => handle.validation_message(rect, text, state),
         }
     }
 
-    fn checkbox(&mut self, rect: Rect, checked: bool, highlights: HighlightState) {
+    fn checkbox(&mut self, rect: Rect, state: Option<bool>, highlights: HighlightState) {
         match self {
-            WhichDrawHandle::Flat(handle) => handle.checkbox(rect, checked, highlights),
-            WhichDrawHandle::Shaded(handle) => handle.checkbox(rect, checked, highlights),
+            WhichDrawHandle::Flat(handle) => handle.checkbox(rect, state, highlights),
+            WhichDrawHandle::Shaded(handle) => handle.checkbox(rect, state, highlights),
         }
     }
 
@@ -0,0 +1,33 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Rich text support
+
+use wgpu_glyph::{Scale, SectionText};
+
+use kas::draw::Colour;
+use kas::text::RichText;
+use kas::theme::TextClass;
+
+use crate::font;
+
+/// Convert rich text to glyph sections
+///
+/// Each span is scaled relative to `font_scale`; spans without a colour use
+/// `col`.
+pub fn section_text(
+    text: &RichText,
+    class: TextClass,
+    font_scale: f32,
+    col: Colour,
+) -> Vec<SectionText<'_>> {
+    text.spans()
+        .flat_map(|(text, style)| {
+            let scale = Scale::uniform(font_scale * style.scale);
+            let col = style.colour.unwrap_or(col);
+            font::section_text(text, class, style.bold, style.italic, scale, col)
+        })
+        .collect()
+}
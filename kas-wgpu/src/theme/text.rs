@@ -0,0 +1,284 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Text drawing shared by themes
+
+use std::f32;
+use std::ops::Range;
+use wgpu_glyph::{HorizontalAlign, Layout, Scale, SectionText, VariedSection, VerticalAlign};
+
+use kas::draw::{Colour, Draw};
+use kas::geom::{Coord, Rect, Size};
+use kas::text::RichText;
+use kas::theme::{ColourScheme, TextClass, TextProperties, UnderlineStyle, ValidationState};
+use kas::Align;
+
+use super::{bidi, rich, Dimensions};
+use crate::draw::{DrawPipe, DrawText, Vec2};
+use crate::font;
+
+/// Text drawing routines
+///
+/// Themes construct this from their draw handle to implement the text methods
+/// of [`kas::theme::DrawHandle`]. Input rects are translated by `offset`.
+pub struct TextHandle<'a> {
+    pub draw: &'a mut DrawPipe,
+    pub dims: &'a Dimensions,
+    pub cols: &'a ColourScheme,
+    pub offset: Coord,
+    pub pass: usize,
+}
+
+impl<'a> TextHandle<'a> {
+    /// Draw text in visual order
+    pub fn text(&mut self, rect: Rect, text: &str, props: TextProperties) {
+        let text = bidi::visual_order(text, self.dims.direction);
+        self.text_selected(rect, &text, 0..0, props);
+    }
+
+    /// Draw text, using the selected-text colour over `range`
+    pub fn text_selected(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        range: Range<usize>,
+        props: TextProperties,
+    ) {
+        let col = self.cols.text_class(props.class);
+        let (start, end) = clamp_range(text, range);
+        let sel_col = self.cols.selected_text();
+        let scale = Scale::uniform(self.dims.font_scale);
+        let parts = [
+            (&text[..start], col),
+            (&text[start..end], sel_col),
+            (&text[end..], col),
+        ];
+        let class = props.class;
+        let parts = parts
+            .iter()
+            .flat_map(|&(text, col)| font::section_text(text, class, false, false, scale, col))
+            .collect();
+        self.text_parts(rect, parts, props);
+    }
+
+    /// Draw rich text
+    pub fn rich_text(&mut self, rect: Rect, text: &RichText, props: TextProperties) {
+        let col = self.cols.text_class(props.class);
+        let parts = rich::section_text(text, props.class, self.dims.font_scale, col);
+        self.text_parts(rect, parts, props);
+    }
+
+    /// Underline `range` of `text`
+    pub fn text_underline(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        range: Range<usize>,
+        style: UnderlineStyle,
+        props: TextProperties,
+    ) {
+        let line_height = self.dims.line_height;
+        let thickness = (line_height / 12).max(1);
+        let col = match style {
+            UnderlineStyle::Solid => self.cols.text,
+            UnderlineStyle::Wavy => self.cols.text_error,
+        };
+        let pos = rect.pos + self.offset;
+
+        for (line, x0, x1, _) in self.segments(text, range, props.class) {
+            let y = pos.1 + ((line + 1) * line_height - 2 * thickness) as i32;
+            match style {
+                UnderlineStyle::Solid => {
+                    let rect = Rect::new(
+                        Coord(pos.0 + x0, y),
+                        Size((x1 - x0).max(0) as u32, thickness),
+                    );
+                    self.draw.rect(self.pass, rect, col);
+                }
+                UnderlineStyle::Wavy => {
+                    // Approximate a wave with alternating short segments
+                    let step = 2 * thickness as i32;
+                    let mut x = x0;
+                    let mut up = false;
+                    while x < x1 {
+                        let w = step.min(x1 - x) as u32;
+                        let dy = if up { 0 } else { thickness as i32 };
+                        let rect = Rect::new(Coord(pos.0 + x, y + dy), Size(w, thickness));
+                        self.draw.rect(self.pass, rect, col);
+                        x += step;
+                        up = !up;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Draw the selection background behind `range` of `text`
+    pub fn text_selection(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        range: Range<usize>,
+        props: TextProperties,
+    ) {
+        let segments = self.segments(text, range, props.class);
+        if segments.is_empty() {
+            return;
+        }
+        // Selected line breaks are shown as a space
+        let space = self.measure(" ", props.class) - self.measure("", props.class);
+        let line_height = self.dims.line_height;
+        let pos = rect.pos + self.offset;
+        for (line, x0, mut x1, line_break) in segments {
+            if line_break {
+                x1 += space;
+            }
+            let y = pos.1 + (line * line_height) as i32;
+            let size = Size((x1 - x0).max(0) as u32, line_height);
+            let rect = Rect::new(Coord(pos.0 + x0, y), size);
+            self.draw.rect(self.pass, rect, self.cols.selection);
+        }
+    }
+
+    /// Draw a text caret before the byte `index` of `text`
+    pub fn text_caret(&mut self, rect: Rect, text: &str, index: usize, props: TextProperties) {
+        // TODO: support wrapped text and alignment other than Begin (see
+        // `TextHandle::segments`).
+        let index = index.min(text.len());
+        let line_start = text[..index].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let line = text[..line_start].matches('\n').count() as u32;
+        let x = self.measure(&text[line_start..index], props.class) - self.measure("", props.class);
+
+        let line_height = self.dims.line_height;
+        let width = self.dims.caret_width;
+        let pos = rect.pos + self.offset + Coord(x, (line * line_height) as i32);
+        let rect = Rect::new(pos, Size(width, line_height));
+        self.draw.rect(self.pass, rect, self.cols.caret());
+    }
+
+    /// Draw a validation message, in the colour of `state`
+    pub fn validation_message(&mut self, rect: Rect, text: &str, state: ValidationState) {
+        let col = self
+            .cols
+            .validation_state(state)
+            .unwrap_or(self.cols.label_text);
+        let pos = rect.pos + self.offset;
+        let scale = Scale::uniform(self.dims.font_scale);
+        let parts = font::section_text(text, TextClass::Label, false, false, scale, col);
+        self.draw.draw_text(VariedSection {
+            screen_position: Vec2::from(pos).into(),
+            bounds: Vec2::from(rect.size).into(),
+            layout: Layout::default_wrap(),
+            text: parts.collect(),
+            ..VariedSection::default()
+        });
+    }
+
+    // Draw text sections within `rect`
+    fn text_parts(&mut self, rect: Rect, parts: Vec<SectionText<'_>>, props: TextProperties) {
+        let bounds = Coord::from(rect.size);
+
+        // TODO: support justified alignment
+        let (h_align, h_offset) = match props.horiz {
+            Align::Begin | Align::Stretch => (HorizontalAlign::Left, 0),
+            Align::Centre => (HorizontalAlign::Center, bounds.0 / 2),
+            Align::End => (HorizontalAlign::Right, bounds.0),
+        };
+        let (v_align, v_offset) = match props.vert {
+            Align::Begin | Align::Stretch => (VerticalAlign::Top, 0),
+            Align::Centre => (VerticalAlign::Center, bounds.1 / 2),
+            Align::End => (VerticalAlign::Bottom, bounds.1),
+        };
+
+        let text_pos = rect.pos + self.offset + Coord(h_offset, v_offset);
+
+        let layout = match props.class {
+            TextClass::Label | TextClass::EditMulti | TextClass::Monospace => {
+                Layout::default_wrap()
+            }
+            TextClass::Button | TextClass::Edit | TextClass::Placeholder => {
+                Layout::default_single_line()
+            }
+        };
+        let layout = layout.h_align(h_align).v_align(v_align);
+        let mut layout_bounds: (f32, f32) = Vec2::from(bounds).into();
+        if props.class == TextClass::Monospace {
+            // Lines are not wrapped
+            layout_bounds.0 = f32::INFINITY;
+        }
+
+        self.draw.draw_text(VariedSection {
+            screen_position: Vec2::from(text_pos).into(),
+            bounds: layout_bounds,
+            layout,
+            text: parts,
+            ..VariedSection::default()
+        });
+    }
+
+    // Measure the width of `s` plus a trailing marker
+    //
+    // Trailing white-space is not measured, hence we append a marker. Subtract
+    // `measure("", class)` to get the width of `s` alone.
+    fn measure(&mut self, s: &str, class: TextClass) -> i32 {
+        let s = format!("{}|", s);
+        let scale = Scale::uniform(self.dims.font_scale);
+        let parts = font::section_text(&s, class, false, false, scale, Colour::default());
+        self.draw
+            .glyph_bounds(VariedSection {
+                text: parts.collect(),
+                layout: Layout::default_single_line(),
+                ..VariedSection::default()
+            })
+            .map(|(min, max)| (max - min).0 as i32)
+            .unwrap_or(0)
+    }
+
+    // Split `range` of `text` into per-line segments
+    //
+    // Yields the line number, start and end x-offsets, and whether the segment
+    // is followed by a line break within `range`, for each segment.
+    //
+    // TODO: support wrapped text and alignment other than Begin. For now,
+    // we measure each line independently from the left edge.
+    fn segments(
+        &mut self,
+        text: &str,
+        range: Range<usize>,
+        class: TextClass,
+    ) -> Vec<(u32, i32, i32, bool)> {
+        let (start, end) = clamp_range(text, range);
+        let mut segments = vec![];
+        if start == end {
+            return segments;
+        }
+
+        let marker = self.measure("", class);
+        let mut line_start = text[..start].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let mut line = text[..line_start].matches('\n').count() as u32;
+        let mut seg_start = start;
+        while seg_start < end {
+            let line_end = text[seg_start..]
+                .find('\n')
+                .map(|i| seg_start + i)
+                .unwrap_or(text.len());
+            let seg_end = end.min(line_end);
+            let x0 = self.measure(&text[line_start..seg_start], class) - marker;
+            let x1 = self.measure(&text[line_start..seg_end], class) - marker;
+            segments.push((line, x0, x1, seg_end < end));
+
+            line_start = line_end + 1;
+            line += 1;
+            seg_start = line_start;
+        }
+        segments
+    }
+}
+
+// Clamp `range` to the length of `text`
+fn clamp_range(text: &str, range: Range<usize>) -> (usize, usize) {
+    let end = range.end.min(text.len());
+    (range.start.min(end), end)
+}
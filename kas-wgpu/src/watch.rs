@@ -0,0 +1,57 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Theme configuration reloading
+
+use log::{debug, warn};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use kas::theme::ThemeConfig;
+use winit::event_loop::EventLoopProxy;
+
+use crate::ProxyAction;
+
+/// Interval at which the file is checked for modifications
+const INTERVAL: Duration = Duration::from_millis(500);
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Watch the theme configuration file at `path`, sending updates to `proxy`
+///
+/// The file's modification time is polled from a background thread. The
+/// thread stops once the event loop has terminated.
+pub(crate) fn watch_theme_config(path: PathBuf, proxy: EventLoopProxy<ProxyAction>) {
+    let result = thread::Builder::new()
+        .name("kas-theme-config".to_string())
+        .spawn(move || {
+            let mut last = modified(&path);
+            loop {
+                thread::sleep(INTERVAL);
+                let time = modified(&path);
+                if time == last {
+                    continue;
+                }
+                last = time;
+                match ThemeConfig::load(&path) {
+                    Ok(config) => {
+                        debug!("Reloaded theme config from {}", path.display());
+                        let action = ProxyAction::SetThemeConfig(config);
+                        if proxy.send_event(action).is_err() {
+                            return;
+                        }
+                    }
+                    Err(e) => warn!("Unable to load theme config: {}", e),
+                }
+            }
+        });
+    if let Err(e) = result {
+        warn!("Unable to watch theme config: {}", e);
+    }
+}
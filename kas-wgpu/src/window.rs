@@ -6,16 +6,28 @@
 //! `Window` and `WindowList` types
 
 use log::{debug, info, trace};
+use std::any::Any;
+use std::future::Future;
+#[cfg(feature = "record")]
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::rc::Rc;
 use std::time::Instant;
 
-use kas::event::{Callback, CursorIcon, ManagerState, UpdateHandle};
+use kas::data::WindowPlacement;
+use kas::event::{Callback, CursorIcon, DragData, ManagerState, Priority, Shortcut, UpdateHandle};
 use kas::geom::{Coord, Rect, Size};
-use kas::theme::{self, ThemeAction, ThemeApi};
-use kas::{TkAction, WindowId};
-use winit::dpi::PhysicalSize;
+use kas::layout::Inspector;
+#[cfg(feature = "record")]
+use kas::remote::{ClientEvent, EventRecorder};
+use kas::theme::{self, SizeHandle, ThemeAction, ThemeApi, Window as _};
+use kas::{TkAction, WidgetId, WindowId};
+use winit::dpi::{PhysicalPosition, PhysicalSize};
 use winit::error::OsError;
-use winit::event::WindowEvent;
+use winit::event::{ElementState, KeyboardInput, VirtualKeyCode, WindowEvent};
 use winit::event_loop::EventLoopWindowTarget;
+use winit::monitor::MonitorHandle;
+use winit::window::WindowBuilder;
 
 use crate::draw::DrawPipe;
 use crate::shared::{PendingAction, SharedState};
@@ -32,8 +44,18 @@ pub(crate) struct Window<TW> {
     swap_chain: wgpu::SwapChain,
     draw_pipe: DrawPipe,
     theme_window: TW,
+    /// Input is blocked by a modal window
+    blocked: bool,
+    /// The widget inspector, if shown
+    inspector: Option<Inspector>,
+    /// Input recording and the path to save it to
+    #[cfg(feature = "record")]
+    recorder: Option<(PathBuf, EventRecorder)>,
 }
 
+/// Factor by which zoom keys change the UI scale
+const ZOOM_STEP: f32 = 1.1;
+
 // Public functions, for use by the toolkit
 impl<TW: theme::Window<DrawPipe> + 'static> Window<TW> {
     /// Construct a window
@@ -42,8 +64,23 @@ impl<TW: theme::Window<DrawPipe> + 'static> Window<TW> {
         elwt: &EventLoopWindowTarget<ProxyAction>,
         widget: Box<dyn kas::Window>,
     ) -> Result<Self, OsError> {
-        let window = winit::window::Window::new(elwt)?;
-        window.set_title(widget.title());
+        // Apply saved placement before the window is shown, avoiding flicker
+        let placement = (widget.name())
+            .and_then(|name| shared.placements.get(name))
+            .cloned();
+        let mut builder = WindowBuilder::new().with_title(widget.title());
+        if let Some(p) = placement.as_ref() {
+            builder = builder
+                .with_inner_size(PhysicalSize::new(p.size.0, p.size.1))
+                .with_visible(false);
+        }
+        let window = builder.build(elwt)?;
+        if let Some(p) = placement {
+            if let Some(pos) = restore_position(&window, &p) {
+                window.set_outer_position(PhysicalPosition::new(pos.0, pos.1));
+            }
+            window.set_visible(true);
+        }
 
         let dpi_factor = window.scale_factor();
         let size: Size = window.inner_size().into();
@@ -56,14 +93,25 @@ impl<TW: theme::Window<DrawPipe> + 'static> Window<TW> {
             format: wgpu::TextureFormat::Bgra8UnormSrgb,
             width: size.0,
             height: size.1,
-            present_mode: wgpu::PresentMode::Vsync,
+            present_mode: shared.present_mode,
         };
         let swap_chain = shared.device.create_swap_chain(&surface, &sc_desc);
 
         let mut draw_pipe = DrawPipe::new(shared, sc_desc.format, size);
-        let theme_window = shared.theme.new_window(&mut draw_pipe, dpi_factor as f32);
+        let scale_factor = dpi_factor as f32 * shared.ui_scale;
+        let theme_window = shared.theme.new_window(&mut draw_pipe, scale_factor);
+
+        let mut mgr = ManagerState::new(dpi_factor);
+        mgr.set_touch_mode_auto(shared.touch_mode_auto);
+        mgr.set_keymap(shared.keymap.clone());
+        mgr.set_click_threshold(shared.click_time, shared.click_distance);
 
-        let mgr = ManagerState::new(dpi_factor);
+        #[cfg(feature = "record")]
+        let recorder = shared.record_path.take().map(|path| {
+            let mut recorder = EventRecorder::new();
+            recorder.record(ClientEvent::Resized(size));
+            (path, recorder)
+        });
 
         Ok(Window {
             widget,
@@ -74,6 +122,10 @@ impl<TW: theme::Window<DrawPipe> + 'static> Window<TW> {
             swap_chain,
             draw_pipe,
             theme_window,
+            blocked: false,
+            inspector: None,
+            #[cfg(feature = "record")]
+            recorder,
         })
     }
 
@@ -86,7 +138,11 @@ impl<TW: theme::Window<DrawPipe> + 'static> Window<TW> {
         shared: &mut SharedState<T>,
     ) -> TkAction {
         debug!("Window::init");
-        let mut tkw = TkWindow::new(&self.window, shared);
+        let mut tkw = TkWindow::new(
+            &self.window,
+            shared,
+            Some((&mut self.theme_window, &mut self.draw_pipe)),
+        );
         let mut mgr = self.mgr.manager(&mut tkw);
         mgr.send_action(TkAction::Reconfigure);
 
@@ -114,8 +170,13 @@ impl<TW: theme::Window<DrawPipe> + 'static> Window<TW> {
         let (min, max) = self.widget.resize(&mut size_handle, size);
         self.window.set_min_inner_size(min);
         self.window.set_max_inner_size(max);
-        let mut tkw = TkWindow::new(&self.window, shared);
+        let mut tkw = TkWindow::new(
+            &self.window,
+            shared,
+            Some((&mut self.theme_window, &mut self.draw_pipe)),
+        );
         self.mgr.configure(&mut tkw, &mut *self.widget);
+        self.update_inspector();
         self.window.request_redraw();
 
         self.mgr.next_resume()
@@ -124,20 +185,67 @@ impl<TW: theme::Window<DrawPipe> + 'static> Window<TW> {
     pub fn theme_resize<T: kas::theme::Theme<DrawPipe, Window = TW>>(
         &mut self,
         shared: &SharedState<T>,
+    ) {
+        let scale_factor = self.window.scale_factor();
+        self.apply_scale_factor(shared, scale_factor);
+    }
+
+    /// Update theme dimensions for `scale_factor` and resize widgets
+    fn apply_scale_factor<T: kas::theme::Theme<DrawPipe, Window = TW>>(
+        &mut self,
+        shared: &SharedState<T>,
+        scale_factor: f64,
     ) {
         debug!("Applying theme resize");
-        let scale_factor = self.window.scale_factor() as f32;
-        shared
-            .theme
-            .update_window(&mut self.theme_window, scale_factor);
+        let scale_factor = scale_factor as f32 * shared.ui_scale;
         let size = Size(self.sc_desc.width, self.sc_desc.height);
-        let mut size_handle = unsafe { self.theme_window.size_handle(&mut self.draw_pipe) };
-        let (min, max) = self.widget.resize(&mut size_handle, size);
+        let (min, max) = resize_for_scale_factor(
+            &shared.theme,
+            &mut self.theme_window,
+            &mut self.draw_pipe,
+            &mut *self.widget,
+            scale_factor,
+            size,
+        );
         self.window.set_min_inner_size(min);
         self.window.set_max_inner_size(max);
+        self.update_inspector();
+        self.mgr.redraw_all();
+        self.window.request_redraw();
+    }
+
+    /// Set whether input is blocked by a modal window
+    ///
+    /// Blocked windows are drawn dimmed.
+    pub fn set_blocked(&mut self, blocked: bool) {
+        if blocked != self.blocked {
+            self.blocked = blocked;
+            self.redraw_all();
+        }
+    }
+
+    /// Request a full redraw
+    pub fn redraw_all(&mut self) {
+        self.mgr.redraw_all();
         self.window.request_redraw();
     }
 
+    /// Request a redraw, if the redraw region intersects the window
+    pub fn redraw_dirty(&self) {
+        if self.dirty_region().is_some() {
+            self.window.request_redraw();
+        }
+    }
+
+    /// The part of the redraw region within the window, if any
+    fn dirty_region(&self) -> Option<Rect> {
+        let size = Size(self.sc_desc.width, self.sc_desc.height);
+        let rect = Rect::new(Coord::ZERO, size);
+        self.mgr
+            .redraw_region(&*self.widget)
+            .and_then(|region| region.intersection(rect))
+    }
+
     /// Handle an event
     ///
     /// Return true to remove the window
@@ -146,6 +254,18 @@ impl<TW: theme::Window<DrawPipe> + 'static> Window<TW> {
         shared: &mut SharedState<T>,
         event: WindowEvent,
     ) -> (TkAction, Option<Instant>) {
+        #[cfg(feature = "record")]
+        if let Some((_, recorder)) = self.recorder.as_mut() {
+            recorder.record_window_event(&event);
+        }
+
+        if let WindowEvent::KeyboardInput { input, .. } = &event {
+            if let Some(scale) = self.zoom_scale(shared, input) {
+                shared.set_ui_scale(scale);
+                return (TkAction::None, self.mgr.next_resume());
+            }
+        }
+
         // Note: resize must be handled here to update self.swap_chain.
         let action = match event {
             WindowEvent::Resized(size) => self.do_resize(shared, size),
@@ -154,14 +274,28 @@ impl<TW: theme::Window<DrawPipe> + 'static> Window<TW> {
                 new_inner_size,
             } => {
                 // Note: API allows us to set new window size here.
-                shared
-                    .theme
-                    .update_window(&mut self.theme_window, scale_factor as f32);
                 self.mgr.set_dpi_factor(scale_factor);
-                self.do_resize(shared, *new_inner_size)
+                let size = (*new_inner_size).into();
+                if size != Size(self.sc_desc.width, self.sc_desc.height) {
+                    self.resize_surface(shared, size);
+                }
+                // Theme dimensions depend on the scale factor, thus widgets
+                // and window size limits must be updated even when the
+                // physical size is unchanged (as may happen with fractional
+                // scaling, e.g. on Wayland). We update the theme before
+                // resizing widgets, thus widgets are only resized once.
+                self.apply_scale_factor(shared, scale_factor);
+                TkAction::Redraw
+            }
+            WindowEvent::KeyboardInput { input, .. } if self.is_inspector_key(shared, &input) => {
+                self.toggle_inspector()
             }
             event @ _ => {
-                let mut tkw = TkWindow::new(&self.window, shared);
+                let mut tkw = TkWindow::new(
+                    &self.window,
+                    shared,
+                    Some((&mut self.theme_window, &mut self.draw_pipe)),
+                );
                 self.mgr
                     .manager(&mut tkw)
                     .handle_winit(&mut *self.widget, event)
@@ -171,6 +305,27 @@ impl<TW: theme::Window<DrawPipe> + 'static> Window<TW> {
         (action, self.mgr.next_resume())
     }
 
+    /// Deliver coalesced input events
+    pub fn flush_pending<T: theme::Theme<DrawPipe, Window = TW>>(
+        &mut self,
+        shared: &mut SharedState<T>,
+    ) -> (TkAction, Option<Instant>) {
+        let mut tkw = TkWindow::new(
+            &self.window,
+            shared,
+            Some((&mut self.theme_window, &mut self.draw_pipe)),
+        );
+        let mut mgr = self.mgr.manager(&mut tkw);
+        mgr.flush_pending(&mut *self.widget);
+        let action = mgr.unwrap_action();
+        (action, self.mgr.next_resume())
+    }
+
+    /// Queue a message for delivery (see [`kas::event::Manager::send_to`])
+    pub fn send_to(&mut self, id: WidgetId, msg: Rc<dyn Any>) {
+        self.mgr.send_to(id, msg);
+    }
+
     pub fn handle_moved(&mut self) {
         self.mgr.region_moved(&mut *self.widget);
     }
@@ -179,7 +334,22 @@ impl<TW: theme::Window<DrawPipe> + 'static> Window<TW> {
         mut self,
         shared: &mut SharedState<T>,
     ) -> TkAction {
-        let mut tkw = TkWindow::new(&self.window, shared);
+        if let Some(name) = self.widget.name() {
+            shared.set_placement(name, self.placement());
+        }
+
+        #[cfg(feature = "record")]
+        if let Some((path, recorder)) = self.recorder.take() {
+            if let Err(e) = recorder.finish().save(&path) {
+                log::warn!("Unable to save event recording: {}", e);
+            }
+        }
+
+        let mut tkw = TkWindow::new(
+            &self.window,
+            shared,
+            Some((&mut self.theme_window, &mut self.draw_pipe)),
+        );
         let mut mgr = self.mgr.manager(&mut tkw);
 
         for (i, condition) in self.widget.callbacks() {
@@ -201,10 +371,36 @@ impl<TW: theme::Window<DrawPipe> + 'static> Window<TW> {
         &mut self,
         shared: &mut SharedState<T>,
     ) -> (TkAction, Option<Instant>) {
-        let mut tkw = TkWindow::new(&self.window, shared);
+        let frame_time = shared.frame_time;
+        let mut tkw = TkWindow::new(
+            &self.window,
+            shared,
+            Some((&mut self.theme_window, &mut self.draw_pipe)),
+        );
         let mut mgr = self.mgr.manager(&mut tkw);
         mgr.update_timer(&mut *self.widget);
-        (mgr.unwrap_action(), self.mgr.next_resume())
+        let action = mgr.unwrap_action();
+
+        let mut resume = self.mgr.next_resume();
+        if let Some(frame_time) = frame_time {
+            // Limit the frame rate by delaying the next timer update
+            let earliest = Instant::now() + frame_time;
+            resume = resume.map(|time| time.max(earliest));
+        }
+        (action, resume)
+    }
+
+    /// Recreate the swap chain using the current present mode
+    pub fn update_present_mode<T>(&mut self, shared: &mut SharedState<T>) {
+        // PresentMode does not implement PartialEq
+        if self.sc_desc.present_mode as u32 == shared.present_mode as u32 {
+            return;
+        }
+        self.sc_desc.present_mode = shared.present_mode;
+        self.swap_chain = shared
+            .device
+            .create_swap_chain(&self.surface, &self.sc_desc);
+        self.window.request_redraw();
     }
 
     pub fn update_handle<T: kas::theme::Theme<DrawPipe>>(
@@ -213,15 +409,68 @@ impl<TW: theme::Window<DrawPipe> + 'static> Window<TW> {
         handle: UpdateHandle,
         payload: u64,
     ) -> TkAction {
-        let mut tkw = TkWindow::new(&self.window, shared);
+        let mut tkw = TkWindow::new(
+            &self.window,
+            shared,
+            Some((&mut self.theme_window, &mut self.draw_pipe)),
+        );
         let mut mgr = self.mgr.manager(&mut tkw);
         mgr.update_handle(&mut *self.widget, handle, payload);
         mgr.unwrap_action()
     }
+
+    /// Deliver a pushed message to the window's inspectors
+    pub fn inspect_msg<T: kas::theme::Theme<DrawPipe>>(
+        &mut self,
+        shared: &mut SharedState<T>,
+        msg: &dyn Any,
+    ) -> TkAction {
+        let mut tkw = TkWindow::new(
+            &self.window,
+            shared,
+            Some((&mut self.theme_window, &mut self.draw_pipe)),
+        );
+        let mut mgr = self.mgr.manager(&mut tkw);
+        self.widget.inspect_msg(&mut mgr, msg);
+        mgr.unwrap_action()
+    }
+
+    /// The window's client area, in screen coordinates
+    pub fn screen_rect(&self) -> Option<Rect> {
+        let pos = self.window.inner_position().ok()?;
+        let size = self.window.inner_size();
+        Some(Rect::new(pos.into(), size.into()))
+    }
+
+    /// Deliver data dropped from another window
+    pub fn handle_drop<T: kas::theme::Theme<DrawPipe>>(
+        &mut self,
+        shared: &mut SharedState<T>,
+        data: DragData,
+        coord: Coord,
+    ) -> TkAction {
+        let mut tkw = TkWindow::new(
+            &self.window,
+            shared,
+            Some((&mut self.theme_window, &mut self.draw_pipe)),
+        );
+        let mut mgr = self.mgr.manager(&mut tkw);
+        mgr.handle_drop(&mut *self.widget, data, coord);
+        mgr.unwrap_action()
+    }
 }
 
 // Internal functions
 impl<TW: theme::Window<DrawPipe> + 'static> Window<TW> {
+    /// Get the current placement
+    fn placement(&self) -> WindowPlacement {
+        WindowPlacement {
+            size: self.window.inner_size().into(),
+            position: self.window.outer_position().ok().map(Coord::from),
+            monitor: self.window.current_monitor().name(),
+        }
+    }
+
     fn do_resize<T: theme::Theme<DrawPipe, Window = TW>>(
         &mut self,
         shared: &mut SharedState<T>,
@@ -235,7 +484,17 @@ impl<TW: theme::Window<DrawPipe> + 'static> Window<TW> {
         debug!("Resizing window to size={:?}", size);
         let mut size_handle = unsafe { self.theme_window.size_handle(&mut self.draw_pipe) };
         self.widget.resize(&mut size_handle, size);
+        self.resize_surface(shared, size);
 
+        if let Some(inspector) = self.inspector.as_mut() {
+            inspector.update_rects(self.widget.as_widget());
+        }
+        self.mgr.redraw_all();
+        TkAction::Redraw
+    }
+
+    /// Resize the draw pipe and swap chain
+    fn resize_surface<T>(&mut self, shared: &mut SharedState<T>, size: Size) {
         let buf = self.draw_pipe.resize(&shared.device, size);
         shared.queue.submit(&[buf]);
 
@@ -244,15 +503,90 @@ impl<TW: theme::Window<DrawPipe> + 'static> Window<TW> {
         self.swap_chain = shared
             .device
             .create_swap_chain(&self.surface, &self.sc_desc);
+    }
+
+    fn is_inspector_key<T>(&self, shared: &SharedState<T>, input: &KeyboardInput) -> bool {
+        let modifiers = self.mgr.modifiers();
+        match (input.state, input.virtual_keycode) {
+            (ElementState::Pressed, Some(key)) => {
+                shared.inspector_key == Some(Shortcut { modifiers, key })
+            }
+            _ => false,
+        }
+    }
 
+    /// Get the new UI scale if `input` is a zoom key press
+    fn zoom_scale<T>(&self, shared: &SharedState<T>, input: &KeyboardInput) -> Option<f32> {
+        let modifiers = self.mgr.modifiers();
+        if !shared.zoom_keys
+            || input.state != ElementState::Pressed
+            || !modifiers.ctrl
+            || modifiers.alt
+            || modifiers.logo
+        {
+            return None;
+        }
+        match input.virtual_keycode? {
+            VirtualKeyCode::Equals | VirtualKeyCode::Add => Some(shared.ui_scale * ZOOM_STEP),
+            VirtualKeyCode::Minus | VirtualKeyCode::Subtract => Some(shared.ui_scale / ZOOM_STEP),
+            VirtualKeyCode::Key0 | VirtualKeyCode::Numpad0 => Some(1.0),
+            _ => None,
+        }
+    }
+
+    /// Show or hide the widget inspector
+    ///
+    /// When shown, the widget tree is logged.
+    fn toggle_inspector(&mut self) -> TkAction {
+        if self.inspector.take().is_none() {
+            let inspector = self.inspect();
+            info!(
+                "Widget tree of window \"{}\":\n{}",
+                self.widget.title(),
+                inspector
+            );
+            self.inspector = Some(inspector);
+        } else {
+            debug!("Hiding widget inspector");
+        }
+        self.mgr.redraw_all();
         TkAction::Redraw
     }
 
+    /// Rebuild the widget inspector, if shown
+    ///
+    /// Call after the widget tree or its size rules change; on a plain resize,
+    /// [`Inspector::update_rects`] suffices.
+    fn update_inspector(&mut self) {
+        if self.inspector.is_some() {
+            self.inspector = Some(self.inspect());
+        }
+    }
+
+    /// Inspect the widget tree, then restore its layout
+    fn inspect(&mut self) -> Inspector {
+        let size = Size(self.sc_desc.width, self.sc_desc.height);
+        let mut size_handle = unsafe { self.theme_window.size_handle(&mut self.draw_pipe) };
+        let inspector = Inspector::new(self.widget.as_widget_mut(), &mut size_handle);
+        self.widget.resize(&mut size_handle, size);
+        inspector
+    }
+
     pub(crate) fn do_draw<T: theme::Theme<DrawPipe, Window = TW>>(
         &mut self,
         shared: &mut SharedState<T>,
     ) {
-        trace!("Drawing window");
+        // Redraws not requested via the event manager (e.g. by the OS) are
+        // always drawn. Otherwise, we skip the frame if nothing visible within
+        // the window changed. Where drawn, the full frame is drawn and
+        // presented: swap chain textures do not retain previous contents.
+        let region = self.dirty_region();
+        if self.mgr.redraw_requested() && region.is_none() {
+            trace!("Skipping redraw: region is empty or offscreen");
+            self.mgr.clear_redraw();
+            return;
+        }
+        trace!("Drawing window (region = {:?})", region);
         let size = Size(self.sc_desc.width, self.sc_desc.height);
         let rect = Rect {
             pos: Coord::ZERO,
@@ -264,18 +598,47 @@ impl<TW: theme::Window<DrawPipe> + 'static> Window<TW> {
                 .theme
                 .draw_handle(&mut self.draw_pipe, &mut self.theme_window, rect)
         };
-        let mut tkw = TkWindow::new(&self.window, shared);
-        self.widget
-            .draw(&mut draw_handle, &self.mgr.manager(&mut tkw));
+        let mut tkw = TkWindow::<_, TW>::new(&self.window, shared, None);
+        let mgr = self.mgr.manager(&mut tkw);
+        if self.blocked {
+            let widget = &self.widget;
+            let f = &mut |handle: &mut dyn theme::DrawHandle| widget.draw(handle, &mgr);
+            theme::DrawHandle::with_dimming(&mut draw_handle, f);
+        } else {
+            self.widget.draw(&mut draw_handle, &mgr);
+        }
+        if let Some(inspector) = self.inspector.as_ref() {
+            inspector.draw(&mut draw_handle, &mgr);
+        }
+        drop(mgr);
         let clear_color = to_wgpu_color(shared.theme.clear_colour());
         let buf = self
             .draw_pipe
             .render(&mut shared.device, &frame.view, clear_color);
         shared.queue.submit(&[buf]);
+        self.mgr.clear_redraw();
+        shared.stats.count_frame();
     }
 }
 
-fn to_wgpu_color(c: kas::draw::Colour) -> wgpu::Color {
+/// The saved position, if on an available monitor
+fn restore_position(window: &winit::window::Window, placement: &WindowPlacement) -> Option<Coord> {
+    let pos = placement.position?;
+    let mut monitors = window.available_monitors();
+    let on_monitor = |monitor: &MonitorHandle| {
+        if placement.monitor.is_some() && monitor.name() != placement.monitor {
+            return false;
+        }
+        let (mpos, msize) = (monitor.position(), monitor.size());
+        pos.0 >= mpos.x
+            && pos.1 >= mpos.y
+            && pos.0 < mpos.x + msize.width as i32
+            && pos.1 < mpos.y + msize.height as i32
+    };
+    monitors.find(on_monitor).map(|_| pos)
+}
+
+pub(crate) fn to_wgpu_color(c: kas::draw::Colour) -> wgpu::Color {
     wgpu::Color {
         r: c.r as f64,
         g: c.g as f64,
@@ -284,18 +647,51 @@ fn to_wgpu_color(c: kas::draw::Colour) -> wgpu::Color {
     }
 }
 
-struct TkWindow<'a, T> {
+/// Update theme dimensions for `scale_factor` and resize `widget` to `size`
+///
+/// Theme dimensions depend on the scale factor, thus widgets must be resized
+/// even where the physical size is unchanged (as may happen with fractional
+/// scaling, e.g. on Wayland). The theme is updated first, thus widgets are
+/// only resized once. Returns the window's minimum and maximum size.
+fn resize_for_scale_factor<T: theme::Theme<DrawPipe>>(
+    theme: &T,
+    theme_window: &mut T::Window,
+    draw_pipe: &mut DrawPipe,
+    widget: &mut dyn kas::Window,
+    scale_factor: f32,
+    size: Size,
+) -> (Option<Size>, Option<Size>) {
+    theme.update_window(theme_window, scale_factor);
+    let mut size_handle = unsafe { theme_window.size_handle(draw_pipe) };
+    widget.resize(&mut size_handle, size)
+}
+
+struct TkWindow<'a, T, TW> {
     window: &'a winit::window::Window,
     shared: &'a mut SharedState<T>,
+    /// Source of size handles; unavailable while drawing
+    size: Option<(&'a mut TW, &'a mut DrawPipe)>,
 }
 
-impl<'a, T> TkWindow<'a, T> {
-    fn new(window: &'a winit::window::Window, shared: &'a mut SharedState<T>) -> Self {
-        TkWindow { window, shared }
+impl<'a, T, TW> TkWindow<'a, T, TW> {
+    fn new(
+        window: &'a winit::window::Window,
+        shared: &'a mut SharedState<T>,
+        size: Option<(&'a mut TW, &'a mut DrawPipe)>,
+    ) -> Self {
+        TkWindow {
+            window,
+            shared,
+            size,
+        }
     }
 }
 
-impl<'a, T: kas::theme::Theme<DrawPipe>> kas::TkWindow for TkWindow<'a, T> {
+impl<'a, T, TW> kas::TkWindow for TkWindow<'a, T, TW>
+where
+    T: kas::theme::Theme<DrawPipe>,
+    TW: kas::theme::Window<DrawPipe>,
+{
     fn add_window(&mut self, widget: Box<dyn kas::Window>) -> WindowId {
         // By far the simplest way to implement this is to let our call
         // anscestor, event::Loop::handle, do the work.
@@ -310,6 +706,15 @@ impl<'a, T: kas::theme::Theme<DrawPipe>> kas::TkWindow for TkWindow<'a, T> {
         id
     }
 
+    fn add_modal_window(&mut self, widget: Box<dyn kas::Window>) -> WindowId {
+        let id = self.shared.next_window_id();
+        let owner = self.window.id();
+        self.shared
+            .pending
+            .push(PendingAction::AddModalWindow(id, widget, owner));
+        id
+    }
+
     fn close_window(&mut self, id: WindowId) {
         self.shared.pending.push(PendingAction::CloseWindow(id));
     }
@@ -320,6 +725,16 @@ impl<'a, T: kas::theme::Theme<DrawPipe>> kas::TkWindow for TkWindow<'a, T> {
             .push(PendingAction::Update(handle, payload));
     }
 
+    fn push_msg(&mut self, msg: Rc<dyn Any>) {
+        self.shared.pending.push(PendingAction::Msg(msg));
+    }
+
+    fn spawn(&mut self, id: WidgetId, task: Pin<Box<dyn Future<Output = Rc<dyn Any>>>>) {
+        let window = self.window.id();
+        let task = self.shared.executor.spawn(window, id, task);
+        self.shared.pending.push(PendingAction::Poll(task));
+    }
+
     #[inline]
     fn get_clipboard(&mut self) -> Option<String> {
         self.shared.get_clipboard()
@@ -331,15 +746,93 @@ impl<'a, T: kas::theme::Theme<DrawPipe>> kas::TkWindow for TkWindow<'a, T> {
     }
 
     fn adjust_theme(&mut self, f: &mut dyn FnMut(&mut dyn ThemeApi) -> ThemeAction) {
-        match f(&mut self.shared.theme) {
-            ThemeAction::None => (),
-            ThemeAction::RedrawAll => self.shared.pending.push(PendingAction::RedrawAll),
-            ThemeAction::ThemeResize => self.shared.pending.push(PendingAction::ThemeResize),
-        }
+        self.shared.adjust_theme(|theme| f(theme));
     }
 
     #[inline]
     fn set_cursor_icon(&mut self, icon: CursorIcon) {
         self.window.set_cursor_icon(icon);
     }
+
+    #[inline]
+    fn set_ime_position(&mut self, coord: Coord) {
+        self.window
+            .set_ime_position(PhysicalPosition::new(coord.0, coord.1));
+    }
+
+    #[inline]
+    fn announce(&mut self, text: &str, priority: Priority) {
+        self.shared.announce(text, priority);
+    }
+
+    /// Get the area of each monitor
+    ///
+    /// Note: this returns full monitor rects, not work areas: winit does not
+    /// report panels such as task bars, thus popups may overlap these.
+    fn screen_areas(&self) -> Vec<Rect> {
+        let origin: Coord = match self.window.inner_position() {
+            Ok(pos) => pos.into(),
+            Err(_) => return vec![],
+        };
+        self.window
+            .available_monitors()
+            .map(|m| Rect::new(Coord::from(m.position()) - origin, m.size().into()))
+            .collect()
+    }
+
+    fn size_handle(&mut self, f: &mut dyn FnMut(&mut dyn SizeHandle)) {
+        if let Some((theme_window, draw_pipe)) = self.size.as_mut() {
+            let mut size_handle = unsafe { theme_window.size_handle(draw_pipe) };
+            f(&mut size_handle);
+        }
+    }
+
+    fn drop_outside(&mut self, data: DragData, coord: Coord) {
+        let id = self.window.id();
+        self.shared
+            .pending
+            .push(PendingAction::Drop(id, data, coord));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::theme::FlatTheme;
+    use crate::Options;
+    use kas::theme::Theme;
+    use kas::widget::Label;
+    use kas::WidgetCore;
+
+    // wgpu 0.4 aborts where no graphics adapter is available, thus this test
+    // must be run explicitly: cargo test -- --ignored
+    #[test]
+    #[ignore]
+    fn scale_factor_resizes_widgets() {
+        let size = Size(400, 300);
+        let mut shared = SharedState::new(FlatTheme::new(), Options::from_env()).unwrap();
+        let format = wgpu::TextureFormat::Bgra8UnormSrgb;
+        let mut draw_pipe = DrawPipe::new(&mut shared, format, size);
+        let mut theme_window = shared.theme.new_window(&mut draw_pipe, 1.0);
+        let mut widget = kas::widget::Window::new("Scale", Label::new("Scale factor"));
+
+        let mut resize = |scale_factor| {
+            let (min, _) = resize_for_scale_factor(
+                &shared.theme,
+                &mut theme_window,
+                &mut draw_pipe,
+                &mut widget,
+                scale_factor,
+                size,
+            );
+            min.unwrap()
+        };
+        let min1 = resize(1.0);
+        let min2 = resize(2.0);
+
+        // The new scale factor is used when widgets are resized
+        assert!(min2.0 > min1.0 && min2.1 > min1.1);
+        assert_eq!(resize(1.0), min1);
+        assert_eq!(widget.rect().size, size);
+    }
 }
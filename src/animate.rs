@@ -0,0 +1,249 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Animation support
+//!
+//! Widgets animate via timer updates: an animated widget requests an update
+//! each frame via [`Manager::request_frame`], then in
+//! [`Widget::update_timer`] requests a redraw and returns [`FRAME`] until the
+//! animation is finished. An [`Animation`] tracks progress and computes the
+//! current value:
+//!
+//! ```
+//! # use std::time::Duration;
+//! # use kas::animate::{Animation, Easing};
+//! # use kas::geom::Coord;
+//! let slide = Animation::new(Coord(0, 0), Coord(100, 0), Duration::from_millis(200))
+//!     .with_easing(Easing::EaseOut);
+//! let pos = slide.value();
+//! ```
+//!
+//! [`Manager::request_frame`]: crate::event::Manager::request_frame
+//! [`Widget::update_timer`]: crate::Widget::update_timer
+
+use std::time::{Duration, Instant};
+
+use crate::draw::Colour;
+use crate::geom::{Coord, Rect, Size};
+
+/// Duration requesting an update on the next frame
+///
+/// This may be returned from [`Widget::update_timer`](crate::Widget::update_timer).
+/// It is the frame interval at 60 frames per second; toolkits may delay
+/// updates further where a lower frame rate limit is configured.
+pub const FRAME: Duration = Duration::from_millis(16);
+
+/// An easing function
+///
+/// This maps linear progress `t` in the range `[0, 1]` to eased progress.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Easing {
+    /// Constant speed
+    Linear,
+    /// Accelerate from rest
+    EaseIn,
+    /// Decelerate to rest
+    EaseOut,
+    /// Accelerate then decelerate
+    #[default]
+    EaseInOut,
+}
+
+impl Easing {
+    /// Apply to progress `t`, which is clamped to `[0, 1]`
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => {
+                let u = 1.0 - t;
+                1.0 - u * u * u
+            }
+            Easing::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+/// Linear interpolation
+pub trait Interpolate: Copy {
+    /// Interpolate between `self` (`t = 0`) and `to` (`t = 1`)
+    fn lerp(self, to: Self, t: f32) -> Self;
+}
+
+impl Interpolate for f32 {
+    #[inline]
+    fn lerp(self, to: Self, t: f32) -> Self {
+        self + (to - self) * t
+    }
+}
+
+impl Interpolate for i32 {
+    #[inline]
+    fn lerp(self, to: Self, t: f32) -> Self {
+        (self as f32).lerp(to as f32, t).round() as i32
+    }
+}
+
+impl Interpolate for u32 {
+    #[inline]
+    fn lerp(self, to: Self, t: f32) -> Self {
+        (self as f32).lerp(to as f32, t).round().max(0.0) as u32
+    }
+}
+
+impl Interpolate for Coord {
+    fn lerp(self, to: Self, t: f32) -> Self {
+        Coord(self.0.lerp(to.0, t), self.1.lerp(to.1, t))
+    }
+}
+
+impl Interpolate for Size {
+    fn lerp(self, to: Self, t: f32) -> Self {
+        Size(self.0.lerp(to.0, t), self.1.lerp(to.1, t))
+    }
+}
+
+impl Interpolate for Rect {
+    fn lerp(self, to: Self, t: f32) -> Self {
+        Rect::new(self.pos.lerp(to.pos, t), self.size.lerp(to.size, t))
+    }
+}
+
+impl Interpolate for Colour {
+    /// Interpolate each component (including alpha)
+    fn lerp(self, to: Self, t: f32) -> Self {
+        Colour {
+            r: self.r.lerp(to.r, t),
+            g: self.g.lerp(to.g, t),
+            b: self.b.lerp(to.b, t),
+            a: self.a.lerp(to.a, t),
+        }
+    }
+}
+
+/// An animated value
+///
+/// The animation starts on construction and runs from `from` to `to` over
+/// the given duration. The value is computed on demand from the current time.
+#[derive(Clone, Debug)]
+pub struct Animation<T: Interpolate> {
+    start: Instant,
+    duration: Duration,
+    from: T,
+    to: T,
+    easing: Easing,
+}
+
+impl<T: Interpolate> Animation<T> {
+    /// Construct, starting now
+    ///
+    /// The default easing is [`Easing::EaseInOut`].
+    pub fn new(from: T, to: T, duration: Duration) -> Self {
+        Animation {
+            start: Instant::now(),
+            duration,
+            from,
+            to,
+            easing: Easing::default(),
+        }
+    }
+
+    /// Set the easing function (chain style)
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Time elapsed since the start
+    #[inline]
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// True once the duration has elapsed
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        self.elapsed() >= self.duration
+    }
+
+    /// Eased progress, in the range `[0, 1]`
+    pub fn progress(&self) -> f32 {
+        if self.duration == Duration::from_secs(0) {
+            return 1.0;
+        }
+        let t = self.elapsed().as_secs_f32() / self.duration.as_secs_f32();
+        self.easing.apply(t)
+    }
+
+    /// The current value
+    pub fn value(&self) -> T {
+        self.from.lerp(self.to, self.progress())
+    }
+
+    /// The final value
+    #[inline]
+    pub fn target(&self) -> T {
+        self.to
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EASINGS: [Easing; 4] = [
+        Easing::Linear,
+        Easing::EaseIn,
+        Easing::EaseOut,
+        Easing::EaseInOut,
+    ];
+
+    #[test]
+    fn easing_endpoints() {
+        for easing in &EASINGS {
+            assert_eq!(easing.apply(0.0), 0.0, "{:?}", easing);
+            assert_eq!(easing.apply(1.0), 1.0, "{:?}", easing);
+            // Input is clamped
+            assert_eq!(easing.apply(-1.0), 0.0, "{:?}", easing);
+            assert_eq!(easing.apply(2.0), 1.0, "{:?}", easing);
+        }
+    }
+
+    #[test]
+    fn easing_monotonic() {
+        for easing in &EASINGS {
+            let mut last = easing.apply(0.0);
+            for i in 1..=1000 {
+                let x = easing.apply(i as f32 / 1000.0);
+                assert!(x >= last, "{:?} at {}", easing, i);
+                last = x;
+            }
+        }
+    }
+
+    #[test]
+    fn easing_shape() {
+        assert!(Easing::EaseIn.apply(0.5) < 0.5);
+        assert!(Easing::EaseOut.apply(0.5) > 0.5);
+        assert!((Easing::EaseInOut.apply(0.5) - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn interpolate() {
+        assert_eq!(2.0f32.lerp(4.0, 0.25), 2.5);
+        assert_eq!(Coord(0, 10).lerp(Coord(10, 0), 0.5), Coord(5, 5));
+        assert_eq!(Size(0, 10).lerp(Size(10, 0), 1.0), Size(10, 0));
+        let c = Colour::grey(0.0).lerp(Colour::grey(1.0), 0.5);
+        assert_eq!(c, Colour::grey(0.5));
+    }
+
+    #[test]
+    fn zero_duration() {
+        let anim = Animation::new(0.0f32, 1.0, Duration::from_secs(0));
+        assert!(anim.is_finished());
+        assert_eq!(anim.value(), 1.0);
+    }
+}
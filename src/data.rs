@@ -4,13 +4,39 @@
 //     https://www.apache.org/licenses/LICENSE-2.0
 
 //! Data types
+//!
+//! This module contains core data types (also re-exported from the crate root)
+//! and data models.
+//!
+//! A *model* provides access to data stored outside of widgets, allowing this
+//! data to be shared between multiple widgets (*views*). See [`SingleModel`],
+//! [`ListModel`] and the [`SharedRc`] implementation.
+//!
+//! Views over multiple items may support selection via a [`SelectionModel`].
+//!
+//! [`RecentFiles`] provides a list of recently-used files.
+//!
+//! [`Date`] and [`Time`] represent calendar dates and times of day; calendar
+//! names are localised via [`DateLocale`].
+
+mod date;
+mod model;
+mod placement;
+mod recent;
+mod selection;
 
 use std::convert::TryFrom;
 use std::fmt;
 use std::num::NonZeroU32;
 use std::u32;
 
-use crate::geom::{Rect, Size};
+use crate::geom::{Coord, Rect, Size};
+
+pub use date::{Date, DateLocale, EnglishLocale, Time, Weekday};
+pub use model::{ListModel, SharedRc, SingleModel};
+pub use placement::{WindowPlacement, WindowPlacements};
+pub use recent::{RecentFile, RecentFiles};
+pub use selection::{SelectionChanged, SelectionMode, SelectionModel};
 
 /// Widget identifier
 ///
@@ -76,10 +102,19 @@ impl fmt::Display for WidgetId {
 pub struct CoreData {
     pub rect: Rect,
     pub id: WidgetId,
+    /// Context help text (see [`WidgetCore::with_help`])
+    ///
+    /// [`WidgetCore::with_help`]: crate::WidgetCore::with_help
+    pub help: Option<String>,
+    /// Lookup key (see [`WidgetCore::with_key`])
+    ///
+    /// [`WidgetCore::with_key`]: crate::WidgetCore::with_key
+    pub key: Option<String>,
 }
 
 /// Alignment of contents
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Align {
     /// Align to top or left (for left-to-right text)
     Begin,
@@ -100,8 +135,64 @@ impl Default for Align {
     }
 }
 
+/// Horizontal layout direction
+///
+/// In right-to-left mode (used e.g. for Arabic and Hebrew locales), rows of
+/// widgets are mirrored and text is aligned to the right by default.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LayoutDirection {
+    /// Left-to-right
+    LeftToRight,
+    /// Right-to-left
+    RightToLeft,
+}
+
+/// Default direction: LeftToRight
+impl Default for LayoutDirection {
+    fn default() -> Self {
+        LayoutDirection::LeftToRight
+    }
+}
+
+impl LayoutDirection {
+    /// True for [`LayoutDirection::RightToLeft`]
+    #[inline]
+    pub fn is_rtl(self) -> bool {
+        self == LayoutDirection::RightToLeft
+    }
+
+    /// Map horizontal alignment
+    ///
+    /// In right-to-left mode, `Begin` and `End` are swapped.
+    #[inline]
+    pub fn halign(self, align: Align) -> Align {
+        match (self, align) {
+            (LayoutDirection::RightToLeft, Align::Begin) => Align::End,
+            (LayoutDirection::RightToLeft, Align::End) => Align::Begin,
+            (_, align) => align,
+        }
+    }
+
+    /// Map a child's rect within the parent's rect
+    ///
+    /// In right-to-left mode, `rect` is mirrored horizontally within `parent`.
+    /// Layouts may position children in left-to-right order then apply this.
+    #[inline]
+    pub fn mirror(self, rect: Rect, parent: Rect) -> Rect {
+        match self {
+            LayoutDirection::LeftToRight => rect,
+            LayoutDirection::RightToLeft => {
+                let offset = rect.pos.0 - parent.pos.0;
+                let x = parent.pos.0 + parent.size.0 as i32 - offset - rect.size.0 as i32;
+                Rect::new(Coord(x, rect.pos.1), rect.size)
+            }
+        }
+    }
+}
+
 /// Partial alignment information provided by the parent
-#[derive(Debug, Default)]
+#[derive(Clone, Copy, Debug, Default)]
 pub struct AlignHints {
     pub horiz: Option<Align>,
     pub vert: Option<Align>,
@@ -165,7 +256,7 @@ impl CompleteAlignment {
 ///
 /// Using a generic `<D: Directional>` over [`Direction`] allows compile-time
 /// substitution via the [`Horizontal`] and [`Vertical`] instantiations.
-pub trait Directional: Copy + Sized + std::fmt::Debug {
+pub trait Directional: Copy + Sized + std::fmt::Debug + 'static {
     fn as_direction(self) -> Direction;
 
     #[inline]
@@ -203,6 +294,7 @@ impl Directional for Vertical {
 ///
 /// This is a variable instantiation of [`Directional`].
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Direction {
     Horizontal = 0,
     Vertical = 1,
@@ -0,0 +1,333 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Calendar dates, times of day and date localisation
+
+use std::fmt::{self, Debug};
+
+use crate::macros::VoidMsg;
+
+/// A day of the week
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl Weekday {
+    const ALL: [Weekday; 7] = [
+        Weekday::Monday,
+        Weekday::Tuesday,
+        Weekday::Wednesday,
+        Weekday::Thursday,
+        Weekday::Friday,
+        Weekday::Saturday,
+        Weekday::Sunday,
+    ];
+
+    /// Construct from the number of days since Monday (modulo 7)
+    #[inline]
+    pub fn from_index(index: usize) -> Self {
+        Weekday::ALL[index % 7]
+    }
+
+    /// Number of days since Monday (`0..7`)
+    #[inline]
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// The following day
+    #[inline]
+    pub fn succ(self) -> Self {
+        Weekday::from_index(self.index() + 1)
+    }
+}
+
+/// A date in the proleptic Gregorian calendar
+///
+/// Dates are ordered chronologically. Months and days are numbered from 1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, VoidMsg)]
+pub struct Date {
+    year: i32,
+    month: u8,
+    day: u8,
+}
+
+impl Date {
+    /// Construct, returning `None` if the date is invalid
+    pub fn new(year: i32, month: u8, day: u8) -> Option<Self> {
+        let len = Date::days_in_month(year, month)?;
+        if day >= 1 && day <= len {
+            Some(Date { year, month, day })
+        } else {
+            None
+        }
+    }
+
+    /// True if `year` is a leap year
+    pub fn is_leap_year(year: i32) -> bool {
+        (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+    }
+
+    /// Number of days in the given `month` (`1..=12`) of `year`
+    ///
+    /// Returns `None` if `month` is out of range.
+    pub fn days_in_month(year: i32, month: u8) -> Option<u8> {
+        Some(match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if Date::is_leap_year(year) => 29,
+            2 => 28,
+            _ => return None,
+        })
+    }
+
+    /// Number of days in this date's month
+    #[inline]
+    pub fn month_len(self) -> u8 {
+        // The month of a Date is always valid
+        Date::days_in_month(self.year, self.month).unwrap_or(31)
+    }
+
+    /// Get the year
+    #[inline]
+    pub fn year(self) -> i32 {
+        self.year
+    }
+
+    /// Get the month (`1..=12`)
+    #[inline]
+    pub fn month(self) -> u8 {
+        self.month
+    }
+
+    /// Get the day of the month (`1..=31`)
+    #[inline]
+    pub fn day(self) -> u8 {
+        self.day
+    }
+
+    /// Get the day of the week
+    pub fn weekday(self) -> Weekday {
+        // Sakamoto's method, giving the number of days since Sunday
+        const T: [i32; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+        let y = if self.month < 3 {
+            self.year - 1
+        } else {
+            self.year
+        };
+        let d = y + y.div_euclid(4) - y.div_euclid(100)
+            + y.div_euclid(400)
+            + T[self.month as usize - 1]
+            + self.day as i32;
+        Weekday::from_index(d.rem_euclid(7) as usize + 6)
+    }
+
+    /// Add `n` months (which may be negative)
+    ///
+    /// The day is clamped to the length of the resulting month.
+    pub fn add_months(self, n: i32) -> Self {
+        let m = self.year * 12 + (self.month as i32 - 1) + n;
+        let year = m.div_euclid(12);
+        let month = m.rem_euclid(12) as u8 + 1;
+        let first = Date {
+            year,
+            month,
+            day: 1,
+        };
+        let day = self.day.min(first.month_len());
+        Date { day, ..first }
+    }
+}
+
+/// Formats as `YYYY-MM-DD` (ISO 8601)
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// A time of day, with minute resolution
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, VoidMsg)]
+pub struct Time {
+    hour: u8,
+    minute: u8,
+}
+
+impl Time {
+    /// Construct, returning `None` if the time is invalid
+    pub fn new(hour: u8, minute: u8) -> Option<Self> {
+        if hour < 24 && minute < 60 {
+            Some(Time { hour, minute })
+        } else {
+            None
+        }
+    }
+
+    /// Get the hour (`0..24`)
+    #[inline]
+    pub fn hour(self) -> u8 {
+        self.hour
+    }
+
+    /// Get the minute (`0..60`)
+    #[inline]
+    pub fn minute(self) -> u8 {
+        self.minute
+    }
+}
+
+/// Formats as `HH:MM`
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:02}:{:02}", self.hour, self.minute)
+    }
+}
+
+/// Localisation of calendar names and conventions
+///
+/// This is used by [`DatePicker`](crate::widget::DatePicker). Applications
+/// supporting other languages should implement this trait, typically by
+/// looking names up in their translation catalogue. [`EnglishLocale`] is used
+/// by default.
+pub trait DateLocale: Debug {
+    /// The first day of the week, as displayed by calendars
+    fn first_day_of_week(&self) -> Weekday;
+
+    /// Name of the given `month` (`1..=12`)
+    fn month_name(&self, month: u8) -> String;
+
+    /// Abbreviated name of the given day, used for calendar column headings
+    fn weekday_name(&self, day: Weekday) -> String;
+
+    /// Heading for the given month of `year`
+    ///
+    /// The default implementation yields e.g. "March 2020".
+    fn month_heading(&self, year: i32, month: u8) -> String {
+        format!("{} {}", self.month_name(month), year)
+    }
+}
+
+/// English names, with configurable first day of the week
+///
+/// By default, weeks start on Monday (ISO 8601).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EnglishLocale {
+    /// The first day of the week
+    pub first_day: Weekday,
+}
+
+impl Default for EnglishLocale {
+    fn default() -> Self {
+        EnglishLocale {
+            first_day: Weekday::Monday,
+        }
+    }
+}
+
+impl DateLocale for EnglishLocale {
+    fn first_day_of_week(&self) -> Weekday {
+        self.first_day
+    }
+
+    fn month_name(&self, month: u8) -> String {
+        const NAMES: [&str; 12] = [
+            "January",
+            "February",
+            "March",
+            "April",
+            "May",
+            "June",
+            "July",
+            "August",
+            "September",
+            "October",
+            "November",
+            "December",
+        ];
+        NAMES[(month as usize + 11) % 12].to_string()
+    }
+
+    fn weekday_name(&self, day: Weekday) -> String {
+        const NAMES: [&str; 7] = ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"];
+        NAMES[day.index()].to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn date(year: i32, month: u8, day: u8) -> Date {
+        Date::new(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn leap_years() {
+        assert!(Date::is_leap_year(2000));
+        assert!(Date::is_leap_year(2004));
+        assert!(Date::is_leap_year(2020));
+        assert!(Date::is_leap_year(1600));
+        assert!(!Date::is_leap_year(1900));
+        assert!(!Date::is_leap_year(2100));
+        assert!(!Date::is_leap_year(2019));
+        assert!(Date::is_leap_year(0));
+        assert!(Date::is_leap_year(-4));
+    }
+
+    #[test]
+    fn days_in_month() {
+        assert_eq!(Date::days_in_month(2000, 2), Some(29));
+        assert_eq!(Date::days_in_month(1900, 2), Some(28));
+        assert_eq!(Date::days_in_month(2019, 2), Some(28));
+        assert_eq!(Date::days_in_month(2019, 1), Some(31));
+        assert_eq!(Date::days_in_month(2019, 4), Some(30));
+        assert_eq!(Date::days_in_month(2019, 12), Some(31));
+        assert_eq!(Date::days_in_month(2019, 0), None);
+        assert_eq!(Date::days_in_month(2019, 13), None);
+    }
+
+    #[test]
+    fn new() {
+        assert!(Date::new(2000, 2, 29).is_some());
+        assert!(Date::new(1900, 2, 29).is_none());
+        assert!(Date::new(2019, 4, 31).is_none());
+        assert!(Date::new(2019, 1, 0).is_none());
+        assert!(Date::new(2019, 0, 1).is_none());
+        assert!(Date::new(2019, 13, 1).is_none());
+        assert_eq!(date(2000, 2, 1).month_len(), 29);
+    }
+
+    #[test]
+    fn weekday() {
+        assert_eq!(date(1900, 1, 1).weekday(), Weekday::Monday);
+        assert_eq!(date(2000, 1, 1).weekday(), Weekday::Saturday);
+        assert_eq!(date(2000, 2, 29).weekday(), Weekday::Tuesday);
+        assert_eq!(date(2000, 3, 1).weekday(), Weekday::Wednesday);
+        assert_eq!(date(2020, 3, 15).weekday(), Weekday::Sunday);
+    }
+
+    #[test]
+    fn add_months() {
+        assert_eq!(date(2000, 1, 31).add_months(1), date(2000, 2, 29));
+        assert_eq!(date(1900, 1, 31).add_months(1), date(1900, 2, 28));
+        assert_eq!(date(2019, 12, 15).add_months(1), date(2020, 1, 15));
+        assert_eq!(date(2020, 1, 15).add_months(-1), date(2019, 12, 15));
+        assert_eq!(date(2020, 3, 31).add_months(-13), date(2019, 2, 28));
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(date(2000, 2, 29).to_string(), "2000-02-29");
+        assert_eq!(Time::new(9, 5).unwrap().to_string(), "09:05");
+        assert!(Time::new(24, 0).is_none());
+    }
+}
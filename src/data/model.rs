@@ -0,0 +1,171 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Data models
+
+use std::cell::{Ref, RefCell};
+use std::fmt;
+use std::rc::Rc;
+
+use crate::event::{Manager, UpdateHandle};
+
+/// A model over a single value
+///
+/// Change notification uses an [`UpdateHandle`]: views subscribe to the
+/// model's handle (see [`Manager::update_on_handle`]) and are updated whenever
+/// the model is changed via [`Manager::trigger_update`].
+pub trait SingleModel: fmt::Debug {
+    /// Type of value
+    type Item;
+
+    /// Get a copy of the current value
+    fn get(&self) -> Self::Item;
+
+    /// Get the update handle, if any
+    ///
+    /// Views should subscribe to this handle to be notified of changes.
+    fn update_handle(&self) -> Option<UpdateHandle>;
+}
+
+/// A model over a list of values
+///
+/// Change notification works as for [`SingleModel`].
+pub trait ListModel: fmt::Debug {
+    /// Type of list items
+    type Item;
+
+    /// Number of items in the list
+    fn len(&self) -> usize;
+
+    /// True if the list is empty
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get a copy of the item at `index`, if within bounds
+    fn get(&self, index: usize) -> Option<Self::Item>;
+
+    /// Get the update handle, if any
+    ///
+    /// Views should subscribe to this handle to be notified of changes.
+    fn update_handle(&self) -> Option<UpdateHandle>;
+}
+
+/// Shared data with change notification
+///
+/// This is a reference-counted wrapper around some data of type `T`; cloning
+/// this object creates a new reference to the same data. Each instance has its
+/// own [`UpdateHandle`], triggered by [`SharedRc::update`].
+///
+/// Where `T: Clone`, this type implements [`SingleModel`]. Where
+/// `T = Vec<U>, U: Clone`, this type implements [`ListModel`].
+pub struct SharedRc<T> {
+    rc: Rc<(RefCell<T>, UpdateHandle)>,
+}
+
+impl<T> Clone for SharedRc<T> {
+    fn clone(&self) -> Self {
+        SharedRc {
+            rc: self.rc.clone(),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for SharedRc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "SharedRc {{ data: {:?}, handle: {:?} }}",
+            (self.rc).0,
+            (self.rc).1
+        )
+    }
+}
+
+impl<T> SharedRc<T> {
+    /// Construct with the given data
+    pub fn new(data: T) -> Self {
+        SharedRc {
+            rc: Rc::new((RefCell::new(data), UpdateHandle::new())),
+        }
+    }
+
+    /// Get the update handle
+    #[inline]
+    pub fn handle(&self) -> UpdateHandle {
+        (self.rc).1
+    }
+
+    /// Borrow the data
+    ///
+    /// Panics if the data is currently being updated.
+    pub fn borrow(&self) -> Ref<'_, T> {
+        (self.rc).0.borrow()
+    }
+
+    /// Update the data and notify all subscribed views
+    pub fn update<F: FnOnce(&mut T)>(&self, mgr: &mut Manager, f: F) {
+        f(&mut *(self.rc).0.borrow_mut());
+        mgr.trigger_update(self.handle(), 0);
+    }
+
+    /// Replace the data and notify all subscribed views
+    pub fn set(&self, mgr: &mut Manager, data: T) {
+        self.update(mgr, |value| *value = data);
+    }
+}
+
+impl<T: Clone + fmt::Debug> SingleModel for SharedRc<T> {
+    type Item = T;
+
+    fn get(&self) -> T {
+        self.borrow().clone()
+    }
+
+    fn update_handle(&self) -> Option<UpdateHandle> {
+        Some(self.handle())
+    }
+}
+
+impl<T: Clone + fmt::Debug> ListModel for SharedRc<Vec<T>> {
+    type Item = T;
+
+    fn len(&self) -> usize {
+        self.borrow().len()
+    }
+
+    fn get(&self, index: usize) -> Option<T> {
+        self.borrow().get(index).cloned()
+    }
+
+    fn update_handle(&self) -> Option<UpdateHandle> {
+        Some(self.handle())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn shared_rc() {
+        let a = SharedRc::new(1);
+        let b = a.clone();
+        assert_eq!(a.handle(), b.handle());
+        assert_ne!(a.handle(), SharedRc::new(1).handle());
+        assert_eq!(SingleModel::get(&b), 1);
+        assert_eq!(SingleModel::update_handle(&a), Some(a.handle()));
+    }
+
+    #[test]
+    fn shared_rc_list() {
+        let list = SharedRc::new(vec!["a", "b"]);
+        assert_eq!(ListModel::len(&list), 2);
+        assert!(!ListModel::is_empty(&list));
+        assert_eq!(ListModel::get(&list, 1), Some("b"));
+        assert_eq!(ListModel::get(&list, 2), None);
+        assert!(ListModel::is_empty(&SharedRc::new(Vec::<u8>::new())));
+    }
+}
@@ -0,0 +1,136 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Persistent window placement
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use crate::geom::{Coord, Size};
+
+/// The size and position of a window
+///
+/// Sizes and positions are in physical pixels.
+///
+/// The maximized state is not recorded since winit does not (yet) report it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WindowPlacement {
+    /// Inner size of the window
+    pub size: Size,
+    /// Outer position of the window's top-left corner, if known
+    pub position: Option<Coord>,
+    /// Name of the monitor showing the window, if known
+    ///
+    /// If this monitor is not available when the window is restored, the
+    /// position is not restored.
+    pub monitor: Option<String>,
+}
+
+/// Window placements, keyed by window name
+///
+/// Toolkits use this to save the placement of named windows (see
+/// [`Window::name`](crate::Window::name)) when closed and to restore it when
+/// the window is next opened.
+///
+/// With the `serde` feature, this may be serialised in any format supported by
+/// serde. Alternatively, use [`WindowPlacements::load`] and
+/// [`WindowPlacements::save`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WindowPlacements {
+    windows: HashMap<String, WindowPlacement>,
+}
+
+impl WindowPlacements {
+    /// Construct, with no entries
+    pub fn new() -> Self {
+        WindowPlacements::default()
+    }
+
+    /// Get the placement of the window with the given `name`
+    pub fn get(&self, name: &str) -> Option<&WindowPlacement> {
+        self.windows.get(name)
+    }
+
+    /// Set the placement of the window with the given `name`
+    pub fn set<S: Into<String>>(&mut self, name: S, placement: WindowPlacement) {
+        self.windows.insert(name.into(), placement);
+    }
+
+    /// Remove the placement of the window with the given `name`
+    pub fn remove(&mut self, name: &str) -> Option<WindowPlacement> {
+        self.windows.remove(name)
+    }
+
+    /// Load from a file
+    ///
+    /// See [`WindowPlacements::save`] for the format. Malformed lines are
+    /// ignored.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let reader = BufReader::new(fs::File::open(path)?);
+        let mut placements = WindowPlacements::new();
+        for line in reader.lines() {
+            let line = line?;
+            if let Some((name, placement)) = parse_line(&line) {
+                placements.set(name, placement);
+            }
+        }
+        Ok(placements)
+    }
+
+    /// Save to a file
+    ///
+    /// Windows are written one per line as tab-separated fields: name, width,
+    /// height, x and y position (`-` if unknown) and monitor name (empty if
+    /// unknown). Entries whose names contain tabs
+    /// or line breaks are not saved.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = BufWriter::new(fs::File::create(path)?);
+        let bad = |s: &str| s.contains(&['\t', '\n', '\r'][..]);
+        for (name, p) in &self.windows {
+            if bad(name) {
+                continue;
+            }
+            let (x, y) = match p.position {
+                Some(pos) => (pos.0.to_string(), pos.1.to_string()),
+                None => ("-".to_string(), "-".to_string()),
+            };
+            let monitor = match p.monitor.as_ref() {
+                Some(monitor) if !bad(monitor) => monitor.as_str(),
+                _ => "",
+            };
+            writeln!(
+                writer,
+                "{}\t{}\t{}\t{}\t{}\t{}",
+                name, p.size.0, p.size.1, x, y, monitor
+            )?;
+        }
+        writer.flush()
+    }
+}
+
+fn parse_line(line: &str) -> Option<(&str, WindowPlacement)> {
+    let mut fields = line.split('\t');
+    let name = fields.next().filter(|name| !name.is_empty())?;
+    let width = fields.next()?.parse().ok()?;
+    let height = fields.next()?.parse().ok()?;
+    let position = match (fields.next()?, fields.next()?) {
+        ("-", "-") => None,
+        (x, y) => Some(Coord(x.parse().ok()?, y.parse().ok()?)),
+    };
+    let monitor = fields
+        .next()
+        .filter(|monitor| !monitor.is_empty())
+        .map(|monitor| monitor.to_string());
+    let placement = WindowPlacement {
+        size: Size(width, height),
+        position,
+        monitor,
+    };
+    Some((name, placement))
+}
@@ -0,0 +1,195 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Recently-used files
+
+use std::fs;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// An entry of [`RecentFiles`]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RecentFile {
+    /// Path to the file
+    pub path: PathBuf,
+    /// Whether the entry is pinned
+    pub pinned: bool,
+}
+
+/// A list of recently-used files
+///
+/// Pinned entries are listed first, followed by other entries in order of most
+/// recent use. The number of unpinned entries is limited; when exceeded, the
+/// least-recently used entries are dropped. Pinned entries are never dropped.
+///
+/// Typically an application shares a single list between all windows using a
+/// [`SharedRc`](super::SharedRc); see also
+/// [`RecentFilesList`](crate::widget::RecentFilesList).
+///
+/// The list may be persisted with [`RecentFiles::save`] and
+/// [`RecentFiles::load`]. Paths are stored one per line, hence paths
+/// containing line breaks (or which are not valid Unicode) are not saved.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RecentFiles {
+    entries: Vec<RecentFile>,
+    limit: usize,
+}
+
+impl RecentFiles {
+    /// Construct an empty list, holding at most `limit` unpinned entries
+    pub fn new(limit: usize) -> Self {
+        RecentFiles {
+            entries: vec![],
+            limit,
+        }
+    }
+
+    /// Load from a file
+    ///
+    /// See [`RecentFiles::save`] for the format.
+    pub fn load<P: AsRef<Path>>(path: P, limit: usize) -> io::Result<Self> {
+        let reader = BufReader::new(fs::File::open(path)?);
+        let mut list = RecentFiles::new(limit);
+        for line in reader.lines() {
+            let line = line?;
+            let (pinned, path) = if let Some(path) = line.strip_prefix("* ") {
+                (true, path)
+            } else if let Some(path) = line.strip_prefix("  ") {
+                (false, path)
+            } else {
+                continue;
+            };
+            if !path.is_empty() && list.position(Path::new(path)).is_none() {
+                list.entries.push(RecentFile {
+                    path: path.into(),
+                    pinned,
+                });
+            }
+        }
+        list.entries.sort_by_key(|entry| !entry.pinned);
+        list.truncate();
+        Ok(list)
+    }
+
+    /// Save to a file
+    ///
+    /// Entries are written one per line, prefixed with `"* "` if pinned or
+    /// `"  "` otherwise.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = BufWriter::new(fs::File::create(path)?);
+        for entry in &self.entries {
+            let path = match entry.path.to_str() {
+                Some(path) if !path.contains('\n') => path,
+                _ => continue,
+            };
+            let prefix = if entry.pinned { "* " } else { "  " };
+            writeln!(writer, "{}{}", prefix, path)?;
+        }
+        writer.flush()
+    }
+
+    /// True if the list is empty
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Number of entries (including pinned entries)
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Get the entry at `index`, if within bounds
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&RecentFile> {
+        self.entries.get(index)
+    }
+
+    /// Iterate over entries, pinned entries first
+    pub fn iter(&self) -> impl Iterator<Item = &RecentFile> {
+        self.entries.iter()
+    }
+
+    /// Get the limit on unpinned entries
+    #[inline]
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Set the limit on unpinned entries
+    ///
+    /// Excess entries are dropped.
+    pub fn set_limit(&mut self, limit: usize) {
+        self.limit = limit;
+        self.truncate();
+    }
+
+    /// Record use of a file
+    ///
+    /// The file is moved to the front of its section (pinned or unpinned), or
+    /// inserted as the most recent unpinned entry if not already present.
+    pub fn add<P: Into<PathBuf>>(&mut self, path: P) {
+        let path = path.into();
+        let entry = match self.position(&path) {
+            Some(index) => self.entries.remove(index),
+            None => RecentFile {
+                path,
+                pinned: false,
+            },
+        };
+        let index = if entry.pinned { 0 } else { self.num_pinned() };
+        self.entries.insert(index, entry);
+        self.truncate();
+    }
+
+    /// Remove a file, returning true if it was present
+    pub fn remove<P: AsRef<Path>>(&mut self, path: P) -> bool {
+        match self.position(path.as_ref()) {
+            Some(index) => {
+                self.entries.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Pin or unpin a file, returning true if it was present
+    ///
+    /// A newly pinned entry becomes the last pinned entry; a newly unpinned
+    /// entry becomes the most recent unpinned entry.
+    pub fn set_pinned<P: AsRef<Path>>(&mut self, path: P, pinned: bool) -> bool {
+        let index = match self.position(path.as_ref()) {
+            Some(index) => index,
+            None => return false,
+        };
+        if self.entries[index].pinned != pinned {
+            let mut entry = self.entries.remove(index);
+            entry.pinned = pinned;
+            let index = self.num_pinned();
+            self.entries.insert(index, entry);
+            self.truncate();
+        }
+        true
+    }
+
+    /// Remove all unpinned entries
+    pub fn clear(&mut self) {
+        self.entries.retain(|entry| entry.pinned);
+    }
+
+    fn position(&self, path: &Path) -> Option<usize> {
+        self.entries.iter().position(|entry| entry.path == path)
+    }
+
+    fn num_pinned(&self) -> usize {
+        self.entries.iter().take_while(|entry| entry.pinned).count()
+    }
+
+    fn truncate(&mut self) {
+        let len = self.num_pinned() + self.limit;
+        self.entries.truncate(len);
+    }
+}
@@ -0,0 +1,362 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Selection model
+
+use std::collections::BTreeSet;
+
+use crate::event::Modifiers;
+use crate::macros::VoidMsg;
+
+/// Selection mode of a [`SelectionModel`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SelectionMode {
+    /// Selection is disabled
+    #[default]
+    None,
+    /// At most one item may be selected
+    Single,
+    /// Any number of items may be selected
+    Multiple,
+}
+
+/// Message returned by views when their selection changes
+///
+/// The new selection may be read from the view.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, VoidMsg)]
+pub struct SelectionChanged;
+
+/// Selection state over a sequence of items
+///
+/// Items are identified by index. This type tracks the set of selected items
+/// and the *anchor* (the item last pressed without `Shift`), and implements
+/// the usual pointer semantics via [`SelectionModel::press`]:
+///
+/// -   a plain press selects only the pressed item
+/// -   `Ctrl`+press toggles the pressed item
+/// -   `Shift`+press selects the range from the anchor to the pressed item
+///     (adding to the existing selection if `Ctrl` is also held)
+///
+/// Methods modifying the selection return `true` when the selection changed.
+#[derive(Clone, Debug, Default)]
+pub struct SelectionModel {
+    mode: SelectionMode,
+    selected: BTreeSet<usize>,
+    anchor: Option<usize>,
+}
+
+impl SelectionModel {
+    /// Construct with the given `mode` and nothing selected
+    pub fn new(mode: SelectionMode) -> Self {
+        SelectionModel {
+            mode,
+            selected: BTreeSet::new(),
+            anchor: None,
+        }
+    }
+
+    /// Get the selection mode
+    #[inline]
+    pub fn mode(&self) -> SelectionMode {
+        self.mode
+    }
+
+    /// Set the selection mode
+    ///
+    /// The selection is reduced as required by the new mode.
+    pub fn set_mode(&mut self, mode: SelectionMode) -> bool {
+        self.mode = mode;
+        match mode {
+            SelectionMode::None => self.clear(),
+            SelectionMode::Single if self.selected.len() > 1 => {
+                let first = self.first();
+                self.selected.retain(|i| Some(*i) == first);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// True if nothing is selected
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.selected.is_empty()
+    }
+
+    /// Number of selected items
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.selected.len()
+    }
+
+    /// Check whether item `index` is selected
+    #[inline]
+    pub fn is_selected(&self, index: usize) -> bool {
+        self.selected.contains(&index)
+    }
+
+    /// Iterate over selected indices, in ascending order
+    pub fn iter<'a>(&'a self) -> impl Iterator<Item = usize> + 'a {
+        self.selected.iter().cloned()
+    }
+
+    /// Get the first selected index, if any
+    pub fn first(&self) -> Option<usize> {
+        self.selected.iter().next().cloned()
+    }
+
+    /// Get the anchor, if any
+    #[inline]
+    pub fn anchor(&self) -> Option<usize> {
+        self.anchor
+    }
+
+    /// Clear the selection
+    pub fn clear(&mut self) -> bool {
+        self.anchor = None;
+        if self.selected.is_empty() {
+            false
+        } else {
+            self.selected.clear();
+            true
+        }
+    }
+
+    /// Select item `index`
+    ///
+    /// In [`SelectionMode::Single`] this replaces any existing selection.
+    pub fn select(&mut self, index: usize) -> bool {
+        match self.mode {
+            SelectionMode::None => return false,
+            SelectionMode::Single => {
+                if self.selected.len() == 1 && self.is_selected(index) {
+                    self.anchor = Some(index);
+                    return false;
+                }
+                self.selected.clear();
+            }
+            SelectionMode::Multiple => (),
+        }
+        self.anchor = Some(index);
+        self.selected.insert(index)
+    }
+
+    /// Deselect item `index`
+    pub fn deselect(&mut self, index: usize) -> bool {
+        self.selected.remove(&index)
+    }
+
+    /// Toggle selection of item `index`
+    pub fn toggle(&mut self, index: usize) -> bool {
+        if self.is_selected(index) {
+            self.anchor = Some(index);
+            self.deselect(index)
+        } else {
+            self.select(index)
+        }
+    }
+
+    /// Select the inclusive range between `a` and `b`
+    ///
+    /// In [`SelectionMode::Single`] only `b` is selected. The anchor is not
+    /// changed.
+    pub fn select_range(&mut self, a: usize, b: usize) -> bool {
+        match self.mode {
+            SelectionMode::None => false,
+            SelectionMode::Single => {
+                let anchor = self.anchor;
+                let changed = self.select(b);
+                self.anchor = anchor;
+                changed
+            }
+            SelectionMode::Multiple => {
+                let len = self.selected.len();
+                self.selected.extend(a.min(b)..=a.max(b));
+                self.selected.len() != len
+            }
+        }
+    }
+
+    /// Select all items, given the number of items `len`
+    ///
+    /// This has no effect unless the mode is [`SelectionMode::Multiple`].
+    pub fn select_all(&mut self, len: usize) -> bool {
+        if self.mode != SelectionMode::Multiple || len == 0 {
+            return false;
+        }
+        self.select_range(0, len - 1)
+    }
+
+    /// Deselect all items with index `len` or greater
+    ///
+    /// Views should call this when the number of items is reduced.
+    pub fn truncate(&mut self, len: usize) -> bool {
+        if self.anchor.map(|i| i >= len).unwrap_or(false) {
+            self.anchor = None;
+        }
+        !self.selected.split_off(&len).is_empty()
+    }
+
+    /// Update the selection for a press on item `index`
+    ///
+    /// See the [type-level documentation](SelectionModel) for semantics.
+    pub fn press(&mut self, index: usize, modifiers: Modifiers) -> bool {
+        match self.mode {
+            SelectionMode::None => false,
+            SelectionMode::Multiple if modifiers.shift => {
+                let anchor = self.anchor.unwrap_or(index);
+                let mut changed = false;
+                if !modifiers.ctrl {
+                    let len = self.selected.len();
+                    let (a, b) = (anchor.min(index), anchor.max(index));
+                    self.selected.retain(|i| a <= *i && *i <= b);
+                    changed = self.selected.len() != len;
+                }
+                self.anchor = Some(anchor);
+                self.select_range(anchor, index) || changed
+            }
+            _ if modifiers.ctrl => self.toggle(index),
+            _ => {
+                let mut changed = false;
+                if self.mode == SelectionMode::Multiple {
+                    let len = self.selected.len();
+                    self.selected.retain(|i| *i == index);
+                    changed = self.selected.len() != len;
+                }
+                self.select(index) || changed
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn selected(model: &SelectionModel) -> Vec<usize> {
+        model.iter().collect()
+    }
+
+    fn modifiers(shift: bool, ctrl: bool) -> Modifiers {
+        Modifiers {
+            shift,
+            ctrl,
+            ..Modifiers::default()
+        }
+    }
+
+    const PLAIN: (bool, bool) = (false, false);
+    const SHIFT: (bool, bool) = (true, false);
+    const CTRL: (bool, bool) = (false, true);
+    const CTRL_SHIFT: (bool, bool) = (true, true);
+
+    fn press(model: &mut SelectionModel, index: usize, (shift, ctrl): (bool, bool)) -> bool {
+        model.press(index, modifiers(shift, ctrl))
+    }
+
+    #[test]
+    fn none() {
+        let mut model = SelectionModel::new(SelectionMode::None);
+        assert!(!press(&mut model, 1, PLAIN));
+        assert!(!press(&mut model, 2, CTRL_SHIFT));
+        assert!(!model.select(3));
+        assert!(!model.select_all(5));
+        assert!(model.is_empty());
+    }
+
+    #[test]
+    fn single() {
+        let mut model = SelectionModel::new(SelectionMode::Single);
+        assert!(press(&mut model, 2, PLAIN));
+        assert_eq!(selected(&model), vec![2]);
+        assert!(!press(&mut model, 2, PLAIN));
+        assert!(press(&mut model, 4, SHIFT));
+        assert_eq!(selected(&model), vec![4]);
+        assert!(press(&mut model, 4, CTRL));
+        assert!(model.is_empty());
+        assert!(press(&mut model, 1, CTRL));
+        assert_eq!(selected(&model), vec![1]);
+        assert!(!model.select_all(5));
+        assert_eq!(model.len(), 1);
+    }
+
+    #[test]
+    fn multiple() {
+        let mut model = SelectionModel::new(SelectionMode::Multiple);
+        assert!(press(&mut model, 1, PLAIN));
+        assert!(press(&mut model, 3, CTRL));
+        assert!(press(&mut model, 5, CTRL));
+        assert_eq!(selected(&model), vec![1, 3, 5]);
+        assert_eq!(model.anchor(), Some(5));
+        assert!(press(&mut model, 3, CTRL));
+        assert_eq!(selected(&model), vec![1, 5]);
+        assert_eq!(model.anchor(), Some(3));
+
+        // A plain press selects only the pressed item
+        assert!(press(&mut model, 5, PLAIN));
+        assert_eq!(selected(&model), vec![5]);
+        assert!(!press(&mut model, 5, PLAIN));
+
+        assert!(model.select_all(4));
+        assert_eq!(selected(&model), vec![0, 1, 2, 3, 5]);
+        assert!(model.clear());
+        assert!(!model.clear());
+        assert_eq!(model.anchor(), None);
+    }
+
+    #[test]
+    fn range() {
+        let mut model = SelectionModel::new(SelectionMode::Multiple);
+        assert!(press(&mut model, 4, PLAIN));
+        assert!(press(&mut model, 7, SHIFT));
+        assert_eq!(selected(&model), vec![4, 5, 6, 7]);
+        // The anchor is kept: the range shrinks and may reverse
+        assert!(press(&mut model, 2, SHIFT));
+        assert_eq!(selected(&model), vec![2, 3, 4]);
+        assert_eq!(model.anchor(), Some(4));
+
+        // Ctrl+Shift adds a range to the existing selection
+        assert!(press(&mut model, 9, CTRL));
+        assert!(press(&mut model, 11, CTRL_SHIFT));
+        assert_eq!(selected(&model), vec![2, 3, 4, 9, 10, 11]);
+        assert!(!press(&mut model, 10, CTRL_SHIFT));
+
+        // Without an anchor, Shift+press selects only the pressed item
+        let mut model = SelectionModel::new(SelectionMode::Multiple);
+        assert!(press(&mut model, 3, SHIFT));
+        assert_eq!(selected(&model), vec![3]);
+    }
+
+    #[test]
+    fn set_mode() {
+        let mut model = SelectionModel::new(SelectionMode::Multiple);
+        model.select_range(2, 4);
+        assert!(model.set_mode(SelectionMode::Single));
+        assert_eq!(selected(&model), vec![2]);
+        assert!(!model.set_mode(SelectionMode::Multiple));
+        assert!(model.set_mode(SelectionMode::None));
+        assert!(model.is_empty());
+    }
+
+    #[test]
+    fn shrinking_model() {
+        let mut model = SelectionModel::new(SelectionMode::Multiple);
+        assert!(press(&mut model, 1, PLAIN));
+        assert!(press(&mut model, 6, CTRL_SHIFT));
+        assert!(model.truncate(4));
+        assert_eq!(selected(&model), vec![1, 2, 3]);
+        assert_eq!(model.anchor(), Some(1));
+        assert!(!model.truncate(4));
+
+        // The anchor is cleared when its item is removed
+        assert!(press(&mut model, 3, CTRL));
+        assert_eq!(model.anchor(), Some(3));
+        assert!(!model.truncate(3));
+        assert_eq!(selected(&model), vec![1, 2]);
+        assert_eq!(model.anchor(), None);
+        assert!(model.truncate(0));
+        assert!(model.is_empty());
+    }
+}
@@ -6,7 +6,8 @@
 //! Colour type and theming
 
 /// Standard colour description
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Colour {
     pub r: f32,
     pub g: f32,
@@ -24,6 +25,76 @@ impl Colour {
     pub const fn grey(s: f32) -> Self {
         Colour::new(s, s, s)
     }
+
+    /// Relative luminance
+    ///
+    /// Colour components are assumed to be linear (not sRGB-encoded). The
+    /// result is in the range `0.0` (black) to `1.0` (white), as defined by
+    /// [WCAG 2.0](https://www.w3.org/TR/WCAG20/#relativeluminancedef).
+    /// The alpha component is ignored.
+    pub fn relative_luminance(self) -> f32 {
+        0.2126 * self.r + 0.7152 * self.g + 0.0722 * self.b
+    }
+
+    /// Contrast ratio between two colours
+    ///
+    /// The result is in the range `1.0` (no contrast) to `21.0` (black on
+    /// white), as defined by
+    /// [WCAG 2.0](https://www.w3.org/TR/WCAG20/#contrast-ratiodef).
+    /// The order of parameters does not matter.
+    ///
+    /// WCAG recommends a ratio of at least 4.5 for normal text and 3.0 for
+    /// large text.
+    pub fn contrast_ratio(self, other: Colour) -> f32 {
+        let l1 = self.relative_luminance();
+        let l2 = other.relative_luminance();
+        (l1.max(l2) + 0.05) / (l1.min(l2) + 0.05)
+    }
+
+    /// Adjust `self` for use as a foreground colour over `background`
+    ///
+    /// If the [contrast ratio](Colour::contrast_ratio) of `self` over
+    /// `background` is at least `min_ratio`, `self` is returned unchanged.
+    /// Otherwise, `self` is blended towards white (if lighter than
+    /// `background`) or black (if darker) just enough to meet `min_ratio`. If
+    /// this is not possible, the other extreme is used if it yields higher
+    /// contrast.
+    pub fn with_min_contrast(self, background: Colour, min_ratio: f32) -> Colour {
+        if self.contrast_ratio(background) >= min_ratio {
+            return self;
+        }
+
+        let bg = background.relative_luminance();
+        let l0 = self.relative_luminance();
+        // Prefer to keep the foreground lighter (or darker) than the
+        // background, if this can meet min_ratio
+        let (max_lighter, max_darker) = (1.05 / (bg + 0.05), (bg + 0.05) / 0.05);
+        let lighter = if l0 >= bg {
+            max_lighter >= min_ratio || max_lighter >= max_darker
+        } else {
+            max_darker < min_ratio && max_lighter > max_darker
+        };
+        let (target, l_target, l_req) = if lighter {
+            (1.0, 1.0, min_ratio * (bg + 0.05) - 0.05)
+        } else {
+            (0.0, 0.0, (bg + 0.05) / min_ratio - 0.05)
+        };
+
+        // Luminance is linear in the colour components, thus we can solve
+        // directly for the blend factor.
+        let t = if l_target == l0 {
+            1.0
+        } else {
+            ((l_req - l0) / (l_target - l0)).clamp(0.0, 1.0)
+        };
+        let blend = |x: f32| x + (target - x) * t;
+        Colour {
+            r: blend(self.r),
+            g: blend(self.g),
+            b: blend(self.b),
+            a: self.a,
+        }
+    }
 }
 
 impl From<Colour> for [f32; 4] {
@@ -31,3 +102,70 @@ impl From<Colour> for [f32; 4] {
         [c.r, c.g, c.b, c.a]
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const BLACK: Colour = Colour::grey(0.0);
+    const WHITE: Colour = Colour::grey(1.0);
+
+    fn approx_eq(a: f32, b: f32) -> bool {
+        (a - b).abs() < 1e-4
+    }
+
+    #[test]
+    fn luminance() {
+        assert!(approx_eq(BLACK.relative_luminance(), 0.0));
+        assert!(approx_eq(WHITE.relative_luminance(), 1.0));
+        assert!(approx_eq(Colour::grey(0.5).relative_luminance(), 0.5));
+    }
+
+    #[test]
+    fn contrast_ratio() {
+        assert!(approx_eq(BLACK.contrast_ratio(WHITE), 21.0));
+        assert!(approx_eq(WHITE.contrast_ratio(BLACK), 21.0));
+        assert!(approx_eq(WHITE.contrast_ratio(WHITE), 1.0));
+        let grey = Colour::grey(0.2);
+        assert!(approx_eq(grey.contrast_ratio(grey), 1.0));
+    }
+
+    #[test]
+    fn min_contrast_unchanged() {
+        assert_eq!(BLACK.with_min_contrast(WHITE, 4.5), BLACK);
+        let c = Colour::new(0.0, 0.0, 0.6);
+        assert_eq!(c.with_min_contrast(WHITE, 4.5), c);
+    }
+
+    #[test]
+    fn min_contrast_adjusted() {
+        let pairs = [
+            (Colour::grey(0.8), Colour::grey(0.9)),
+            (Colour::grey(0.3), Colour::grey(0.2)),
+            (Colour::new(1.0, 0.5, 0.0), WHITE),
+            (Colour::grey(0.5), Colour::grey(0.5)),
+        ];
+        for &(fg, bg) in &pairs {
+            // The ratio achievable with black or white text
+            let max_ratio = BLACK.contrast_ratio(bg).max(WHITE.contrast_ratio(bg));
+            for &ratio in &[3.0, 4.5, 7.0] {
+                let c = fg.with_min_contrast(bg, ratio);
+                let expected = ratio.min(max_ratio) - 1e-3;
+                assert!(c.contrast_ratio(bg) >= expected, "{:?} on {:?}", fg, bg);
+                assert_eq!(c.a, fg.a);
+            }
+        }
+    }
+
+    #[test]
+    fn min_contrast_keeps_side() {
+        // Light text on a dark background stays lighter than the background
+        let bg = Colour::grey(0.1);
+        let c = Colour::grey(0.2).with_min_contrast(bg, 4.5);
+        assert!(c.relative_luminance() > bg.relative_luminance());
+        // ... unless the required contrast is only possible with dark text
+        let bg = Colour::grey(0.6);
+        let c = Colour::grey(0.7).with_min_contrast(bg, 4.5);
+        assert!(c.relative_luminance() < bg.relative_luminance());
+    }
+}
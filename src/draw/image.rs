@@ -0,0 +1,110 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Raster image data
+
+use std::fmt;
+use std::num::NonZeroU64;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use crate::geom::Size;
+
+/// Identifier of some [`ImageData`]
+///
+/// Each image has a unique identifier, which toolkits may use as a key when
+/// caching uploaded textures. Clones of an [`ImageData`] share the identifier.
+///
+/// Identifiers are 64-bit and never reused; since images may be created
+/// frequently (e.g. when re-rasterising vector graphics on resize), a 32-bit
+/// counter could realistically be exhausted.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct ImageId(NonZeroU64);
+
+impl ImageId {
+    fn new() -> ImageId {
+        static COUNT: AtomicU64 = AtomicU64::new(0);
+
+        let mut c = COUNT.load(Ordering::Relaxed);
+        loop {
+            // Overflow would take centuries even at a billion images per second
+            let h = c
+                .checked_add(1)
+                .expect("ImageId::new: identifiers exhausted");
+            match COUNT.compare_exchange_weak(c, h, Ordering::Relaxed, Ordering::Relaxed) {
+                // h > 0 since it is the result of a checked increment
+                Ok(_) => break ImageId(NonZeroU64::new(h).unwrap()),
+                Err(actual) => c = actual,
+            }
+        }
+    }
+}
+
+/// A raster image
+///
+/// Pixel data is stored in 8-bit sRGBA format, row by row starting from the
+/// top-left corner. Image data is immutable and cheap to clone.
+#[derive(Clone)]
+pub struct ImageData {
+    id: ImageId,
+    size: Size,
+    rgba: Arc<[u8]>,
+}
+
+impl fmt::Debug for ImageData {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "ImageData {{ id: {:?}, size: {:?}, ... }}",
+            self.id, self.size
+        )
+    }
+}
+
+impl ImageData {
+    /// Construct from a raw RGBA buffer
+    ///
+    /// Panics unless `rgba.len()` equals `4 * size.0 * size.1`.
+    pub fn from_rgba(size: Size, rgba: Vec<u8>) -> Self {
+        assert_eq!(
+            rgba.len(),
+            4 * size.0 as usize * size.1 as usize,
+            "ImageData::from_rgba: buffer length does not match size"
+        );
+        ImageData {
+            id: ImageId::new(),
+            size,
+            rgba: rgba.into(),
+        }
+    }
+
+    /// Load from a file
+    ///
+    /// The image format is inferred from the file extension.
+    #[cfg(feature = "image")]
+    pub fn from_path<P: AsRef<std::path::Path>>(path: P) -> Result<Self, image::ImageError> {
+        let image = image::open(path)?.to_rgba();
+        let (w, h) = image.dimensions();
+        Ok(ImageData::from_rgba(Size(w, h), image.into_raw()))
+    }
+
+    /// Get the identifier
+    #[inline]
+    pub fn id(&self) -> ImageId {
+        self.id
+    }
+
+    /// Get the size in pixels
+    #[inline]
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    /// Get the pixel data
+    #[inline]
+    pub fn rgba(&self) -> &[u8] {
+        &self.rgba
+    }
+}
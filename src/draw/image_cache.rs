@@ -0,0 +1,251 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Asynchronous image loading and caching
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::mpsc::{channel, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use log::warn;
+
+use super::ImageData;
+use crate::event::UpdateHandle;
+
+/// Function loading an image, given its key
+pub type ImageLoader = dyn Fn(&str) -> Result<ImageData, String> + Send + Sync;
+
+/// Function notifying the UI thread of an update
+pub type UpdateNotifier = dyn Fn(UpdateHandle) + Send + Sync;
+
+/// Status of an image requested from an [`ImageCache`]
+#[derive(Clone, Debug)]
+pub enum ImageStatus {
+    /// The image is being loaded
+    Loading,
+    /// The image is available
+    Ready(ImageData),
+    /// Loading failed with the given error message
+    Failed(String),
+}
+
+enum Entry {
+    Loading,
+    Ready(ImageData, u64),
+    Failed(String),
+}
+
+struct State {
+    entries: HashMap<String, Entry>,
+    budget: usize,
+    used: usize,
+    tick: u64,
+}
+
+impl State {
+    /// Evict least-recently used images until within budget
+    ///
+    /// The image under `keep` (if any) is never evicted.
+    fn evict(&mut self, keep: Option<&str>) {
+        while self.used > self.budget {
+            let lru = self
+                .entries
+                .iter()
+                .filter_map(|(key, entry)| match entry {
+                    Entry::Ready(_, tick) if Some(key.as_str()) != keep => Some((*tick, key)),
+                    _ => None,
+                })
+                .min()
+                .map(|(_, key)| key.clone());
+            let key = match lru {
+                Some(key) => key,
+                None => break,
+            };
+            if let Some(Entry::Ready(image, _)) = self.entries.remove(&key) {
+                self.used -= image.rgba().len();
+            }
+        }
+    }
+}
+
+struct Shared {
+    state: Mutex<State>,
+    handle: UpdateHandle,
+}
+
+/// An asynchronous image loader with an LRU cache
+///
+/// Images are identified by a key (e.g. a path or URL) and loaded on a worker
+/// thread by a user-supplied [`ImageLoader`] (or from files; see
+/// [`ImageCache::from_files`]). Loaded images are cached until the total size
+/// of cached image data exceeds a budget (in bytes), at which point the
+/// least-recently requested images are dropped.
+///
+/// When an image has been loaded, the `notify` function passed on construction
+/// is called with the cache's [`UpdateHandle`]; this should trigger the handle
+/// on the UI thread (e.g. via `kas_wgpu::ToolkitProxy::trigger_update`).
+/// Widgets subscribed to the handle may then call [`ImageCache::request`]
+/// again. See also [`Image::from_cache`](crate::widget::Image::from_cache).
+///
+/// Decoding happens on the worker thread; upload to the GPU happens when the
+/// image is first drawn.
+///
+/// This type is a reference to shared state; clones refer to the same cache.
+/// The worker thread exits once all clones have been dropped.
+#[derive(Clone)]
+pub struct ImageCache {
+    shared: Arc<Shared>,
+    sender: Sender<String>,
+}
+
+impl fmt::Debug for ImageCache {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let state = self.shared.state.lock().unwrap();
+        write!(
+            f,
+            "ImageCache {{ handle: {:?}, len: {}, used: {}, budget: {} }}",
+            self.shared.handle,
+            state.entries.len(),
+            state.used,
+            state.budget
+        )
+    }
+}
+
+impl ImageCache {
+    /// Construct with the given `budget` (in bytes), `loader` and `notify`
+    /// functions
+    pub fn new<L, N>(budget: usize, loader: L, notify: N) -> Self
+    where
+        L: Fn(&str) -> Result<ImageData, String> + Send + Sync + 'static,
+        N: Fn(UpdateHandle) + Send + Sync + 'static,
+    {
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State {
+                entries: HashMap::new(),
+                budget,
+                used: 0,
+                tick: 0,
+            }),
+            handle: UpdateHandle::new(),
+        });
+
+        let (sender, receiver) = channel::<String>();
+        let worker = shared.clone();
+        let loader: Box<ImageLoader> = Box::new(loader);
+        let notify: Box<UpdateNotifier> = Box::new(notify);
+        let spawn = thread::Builder::new()
+            .name("kas-image-loader".to_string())
+            .spawn(move || {
+                for key in receiver {
+                    let result = loader(&key);
+                    {
+                        let mut state = worker.state.lock().unwrap();
+                        let entry = match result {
+                            Ok(image) => {
+                                state.used += image.rgba().len();
+                                state.tick += 1;
+                                Entry::Ready(image, state.tick)
+                            }
+                            Err(msg) => {
+                                warn!("ImageCache: failed to load {:?}: {}", key, msg);
+                                Entry::Failed(msg)
+                            }
+                        };
+                        state.entries.insert(key.clone(), entry);
+                        state.evict(Some(&key));
+                    }
+                    notify(worker.handle);
+                }
+            });
+        if let Err(e) = spawn {
+            warn!("ImageCache: failed to spawn worker thread: {}", e);
+        }
+
+        ImageCache { shared, sender }
+    }
+
+    /// Construct a cache loading images from files
+    ///
+    /// Keys are interpreted as file paths; see [`ImageData::from_path`].
+    #[cfg(feature = "image")]
+    pub fn from_files<N>(budget: usize, notify: N) -> Self
+    where
+        N: Fn(UpdateHandle) + Send + Sync + 'static,
+    {
+        let loader = |key: &str| ImageData::from_path(key).map_err(|e| e.to_string());
+        ImageCache::new(budget, loader, notify)
+    }
+
+    /// Get the update handle
+    ///
+    /// This handle is triggered (via the `notify` function) whenever an image
+    /// has been loaded or failed to load.
+    #[inline]
+    pub fn update_handle(&self) -> UpdateHandle {
+        self.shared.handle
+    }
+
+    /// Request an image
+    ///
+    /// If the image is not cached, loading is started and
+    /// [`ImageStatus::Loading`] is returned.
+    pub fn request(&self, key: &str) -> ImageStatus {
+        let mut state = self.shared.state.lock().unwrap();
+        state.tick += 1;
+        let tick = state.tick;
+        match state.entries.get_mut(key) {
+            Some(Entry::Loading) => ImageStatus::Loading,
+            Some(Entry::Ready(image, last_use)) => {
+                *last_use = tick;
+                ImageStatus::Ready(image.clone())
+            }
+            Some(Entry::Failed(msg)) => ImageStatus::Failed(msg.clone()),
+            None => {
+                if self.sender.send(key.to_string()).is_err() {
+                    let msg = "worker thread not running".to_string();
+                    state
+                        .entries
+                        .insert(key.to_string(), Entry::Failed(msg.clone()));
+                    return ImageStatus::Failed(msg);
+                }
+                state.entries.insert(key.to_string(), Entry::Loading);
+                ImageStatus::Loading
+            }
+        }
+    }
+
+    /// Get the budget (in bytes)
+    pub fn budget(&self) -> usize {
+        self.shared.state.lock().unwrap().budget
+    }
+
+    /// Set the budget (in bytes)
+    ///
+    /// Excess images are dropped immediately.
+    pub fn set_budget(&self, budget: usize) {
+        let mut state = self.shared.state.lock().unwrap();
+        state.budget = budget;
+        state.evict(None);
+    }
+
+    /// Get the total size of cached image data (in bytes)
+    pub fn used(&self) -> usize {
+        self.shared.state.lock().unwrap().used
+    }
+
+    /// Drop cached images and failure records
+    ///
+    /// Images currently being loaded are not affected.
+    pub fn clear(&self) {
+        let mut state = self.shared.state.lock().unwrap();
+        state
+            .entries
+            .retain(|_, entry| matches!(entry, Entry::Loading));
+        state.used = 0;
+    }
+}
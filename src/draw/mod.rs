@@ -11,12 +11,18 @@
 //! All draw operations are batched and do not happen immediately.
 
 mod colour;
+mod image;
+mod image_cache;
+mod transform;
 
 use std::any::Any;
 
 use crate::geom::Rect;
 
+pub use self::image::{ImageData, ImageId};
 pub use colour::Colour;
+pub use image_cache::{ImageCache, ImageLoader, ImageStatus, UpdateNotifier};
+pub use transform::Transform;
 
 /// Abstraction over drawing commands
 ///
@@ -53,3 +59,12 @@ pub trait Draw {
     /// Failure may result in graphical glitches.
     fn frame(&mut self, region: Self::Region, outer: Rect, inner: Rect, col: Colour);
 }
+
+/// Abstraction over drawing raster images
+pub trait DrawImage: Draw {
+    /// Add an image to the draw buffer
+    ///
+    /// The image is scaled to fill `rect`. Implementations are expected to
+    /// upload image data on first use and to cache it while in use.
+    fn image(&mut self, region: Self::Region, image: &ImageData, rect: Rect);
+}
@@ -0,0 +1,177 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Coordinate transforms
+
+use crate::geom::{Coord, Rect, Size};
+
+/// An affine transform between two 2D coordinate spaces
+///
+/// A point `p` is mapped to `matrix × p + translate`, where `matrix` is a
+/// (row-major) 2×2 matrix. This allows translation, scaling, rotation and
+/// shearing. The matrix must be invertible.
+///
+/// Transforms are used by [`DrawHandle::transform_region`] to draw content
+/// (e.g. a zoomed view), in which case the same transform should be used to
+/// map event coordinates (via the [inverse](Transform::inverse); see
+/// [`Event::transform`]).
+///
+/// [`DrawHandle::transform_region`]: crate::theme::DrawHandle::transform_region
+/// [`Event::transform`]: crate::event::Event::transform
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Transform {
+    /// Linear part: a row-major 2×2 matrix
+    pub matrix: [[f32; 2]; 2],
+    /// Translation (applied after the linear part)
+    pub translate: (f32, f32),
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Transform::IDENTITY
+    }
+}
+
+impl Transform {
+    /// The identity transform
+    pub const IDENTITY: Transform = Transform {
+        matrix: [[1.0, 0.0], [0.0, 1.0]],
+        translate: (0.0, 0.0),
+    };
+
+    /// Construct a translation
+    #[inline]
+    pub fn translation(x: f32, y: f32) -> Self {
+        Transform {
+            translate: (x, y),
+            ..Transform::IDENTITY
+        }
+    }
+
+    /// Construct a scaling about the origin
+    #[inline]
+    pub fn scaling(scale: f32) -> Self {
+        Transform {
+            matrix: [[scale, 0.0], [0.0, scale]],
+            translate: (0.0, 0.0),
+        }
+    }
+
+    /// Construct a rotation about the origin
+    ///
+    /// The `angle` is in radians; since the y-axis points down, positive
+    /// angles rotate clockwise on screen.
+    pub fn rotation(angle: f32) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Transform {
+            matrix: [[cos, -sin], [sin, cos]],
+            translate: (0.0, 0.0),
+        }
+    }
+
+    /// Apply `self` about the point `centre` instead of the origin
+    ///
+    /// For example, `Transform::rotation(a).about(c)` rotates about `c`.
+    pub fn about(self, centre: (f32, f32)) -> Self {
+        Transform::translation(-centre.0, -centre.1)
+            .then(self)
+            .then(Transform::translation(centre.0, centre.1))
+    }
+
+    /// Compose: apply `self`, then `next`
+    pub fn then(self, next: Transform) -> Self {
+        let (a, b) = (next.matrix, self.matrix);
+        Transform {
+            matrix: [
+                [
+                    a[0][0] * b[0][0] + a[0][1] * b[1][0],
+                    a[0][0] * b[0][1] + a[0][1] * b[1][1],
+                ],
+                [
+                    a[1][0] * b[0][0] + a[1][1] * b[1][0],
+                    a[1][0] * b[0][1] + a[1][1] * b[1][1],
+                ],
+            ],
+            translate: next.map_point(self.translate),
+        }
+    }
+
+    /// Get the inverse transform
+    pub fn inverse(self) -> Self {
+        let m = self.matrix;
+        let det = m[0][0] * m[1][1] - m[0][1] * m[1][0];
+        let s = 1.0 / det;
+        let inv = Transform {
+            matrix: [[m[1][1] * s, -m[0][1] * s], [-m[1][0] * s, m[0][0] * s]],
+            translate: (0.0, 0.0),
+        };
+        let t = inv.map_point(self.translate);
+        Transform {
+            translate: (-t.0, -t.1),
+            ..inv
+        }
+    }
+
+    /// The scale factor
+    ///
+    /// This is the square root of the change in area; for a rotation combined
+    /// with uniform scaling it is the scaling factor.
+    pub fn scale_factor(self) -> f32 {
+        let m = self.matrix;
+        (m[0][0] * m[1][1] - m[0][1] * m[1][0]).abs().sqrt()
+    }
+
+    /// True if the transform maps axis-aligned rectangles to axis-aligned
+    /// rectangles (i.e. does not rotate or shear)
+    pub fn is_axis_aligned(self) -> bool {
+        self.matrix[0][1] == 0.0 && self.matrix[1][0] == 0.0
+    }
+
+    /// Map a point
+    #[inline]
+    pub fn map_point(self, p: (f32, f32)) -> (f32, f32) {
+        let m = self.matrix;
+        (
+            m[0][0] * p.0 + m[0][1] * p.1 + self.translate.0,
+            m[1][0] * p.0 + m[1][1] * p.1 + self.translate.1,
+        )
+    }
+
+    /// Map a coordinate, rounding to the nearest pixel
+    pub fn map_coord(self, c: Coord) -> Coord {
+        let p = self.map_point((c.0 as f32, c.1 as f32));
+        Coord(p.0.round() as i32, p.1.round() as i32)
+    }
+
+    /// Map a relative offset (e.g. a motion delta)
+    ///
+    /// This applies only the linear part, not translation.
+    pub fn map_delta(self, d: Coord) -> Coord {
+        let m = self.matrix;
+        let (x, y) = (d.0 as f32, d.1 as f32);
+        Coord(
+            (m[0][0] * x + m[0][1] * y).round() as i32,
+            (m[1][0] * x + m[1][1] * y).round() as i32,
+        )
+    }
+
+    /// Map a rectangle, rounding corners to the nearest pixel
+    ///
+    /// If the transform is not [axis-aligned](Transform::is_axis_aligned),
+    /// this yields the bounding box of the mapped rectangle.
+    pub fn map_rect(self, r: Rect) -> Rect {
+        let end = r.pos + r.size;
+        let corners = [r.pos, end, Coord(r.pos.0, end.1), Coord(end.0, r.pos.1)];
+        let mut min = self.map_coord(corners[0]);
+        let mut max = min;
+        for c in &corners[1..] {
+            let c = self.map_coord(*c);
+            min = min.min(c);
+            max = max.max(c);
+        }
+        Rect::new(min, Size((max.0 - min.0) as u32, (max.1 - min.1) as u32))
+    }
+}
@@ -0,0 +1,190 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Event handling: drag-and-drop data
+
+use std::path::{Path, PathBuf};
+
+/// MIME type of UTF-8 encoded plain text
+pub const MIME_TEXT: &str = "text/plain;charset=utf-8";
+
+/// MIME type of a list of URIs (RFC 2483), used for files
+pub const MIME_URI_LIST: &str = "text/uri-list";
+
+/// Data transferred by drag-and-drop
+///
+/// Data is stored in one or more formats, each identified by a MIME type
+/// (e.g. [`MIME_TEXT`] or [`MIME_URI_LIST`]). A drag source should provide as
+/// many formats as it sensibly can; a drop target uses the first format it
+/// understands.
+///
+/// Data is dropped onto widgets via [`Event::Drop`](super::Event::Drop). A
+/// drag within the application is started with [`Manager::start_drag`].
+/// Files dragged from other applications are received as a
+/// [`MIME_URI_LIST`]; dragging data out to other applications is not currently
+/// supported by the windowing backend.
+///
+/// [`Manager::start_drag`]: super::Manager::start_drag
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DragData {
+    formats: Vec<(String, Vec<u8>)>,
+}
+
+impl DragData {
+    /// Construct, with no data
+    pub fn new() -> Self {
+        DragData::default()
+    }
+
+    /// Construct from plain text
+    pub fn from_text<S: Into<String>>(text: S) -> Self {
+        DragData::new().with_format(MIME_TEXT, text.into().into_bytes())
+    }
+
+    /// Construct from a list of file paths
+    pub fn from_paths<I, P>(paths: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let mut data = DragData::new();
+        for path in paths {
+            data.push_path(path.as_ref());
+        }
+        data
+    }
+
+    /// Add data in the given `mime` format (chain style)
+    ///
+    /// This replaces any existing data of the same format.
+    pub fn with_format<M: Into<String>>(mut self, mime: M, data: Vec<u8>) -> Self {
+        self.set_format(mime, data);
+        self
+    }
+
+    /// Set data in the given `mime` format
+    ///
+    /// This replaces any existing data of the same format.
+    pub fn set_format<M: Into<String>>(&mut self, mime: M, data: Vec<u8>) {
+        let mime = mime.into();
+        match self.formats.iter_mut().find(|f| f.0 == mime) {
+            Some(format) => format.1 = data,
+            None => self.formats.push((mime, data)),
+        }
+    }
+
+    /// Append a file path to the [`MIME_URI_LIST`] data
+    pub fn push_path(&mut self, path: &Path) {
+        let uri = format!("{}\r\n", path_to_uri(path));
+        match self.formats.iter_mut().find(|f| f.0 == MIME_URI_LIST) {
+            Some(format) => format.1.extend_from_slice(uri.as_bytes()),
+            None => self.formats.push((MIME_URI_LIST.to_string(), uri.into())),
+        }
+    }
+
+    /// True if no data is present
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.formats.is_empty()
+    }
+
+    /// Iterate over available formats, in order of preference
+    pub fn formats(&self) -> impl Iterator<Item = &str> {
+        self.formats.iter().map(|f| f.0.as_str())
+    }
+
+    /// True if data is available in the given `mime` format
+    pub fn has_format(&self, mime: &str) -> bool {
+        self.formats.iter().any(|f| f.0 == mime)
+    }
+
+    /// Get data in the given `mime` format, if available
+    pub fn get(&self, mime: &str) -> Option<&[u8]> {
+        self.formats
+            .iter()
+            .find(|f| f.0 == mime)
+            .map(|f| f.1.as_slice())
+    }
+
+    /// Get plain text, if available
+    ///
+    /// This reads [`MIME_TEXT`] or `text/plain`, if valid UTF-8.
+    pub fn text(&self) -> Option<&str> {
+        let data = self.get(MIME_TEXT).or_else(|| self.get("text/plain"))?;
+        std::str::from_utf8(data).ok()
+    }
+
+    /// Get file paths
+    ///
+    /// This reads `file:` URIs from the [`MIME_URI_LIST`] data; other URIs
+    /// are ignored.
+    pub fn paths(&self) -> Vec<PathBuf> {
+        let data = match self.get(MIME_URI_LIST) {
+            Some(data) => String::from_utf8_lossy(data),
+            None => return vec![],
+        };
+        data.lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.starts_with('#'))
+            .filter_map(uri_to_path)
+            .collect()
+    }
+}
+
+/// Percent-encode a path as a `file:` URI
+fn path_to_uri(path: &Path) -> String {
+    let path = path.to_string_lossy();
+    let path = if cfg!(windows) {
+        path.replace('\\', "/")
+    } else {
+        path.into_owned()
+    };
+    let mut uri = String::from("file://");
+    if !path.starts_with('/') {
+        uri.push('/');
+    }
+    for b in path.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' => uri.push(b as char),
+            b'-' | b'.' | b'_' | b'~' | b'/' | b':' => uri.push(b as char),
+            b => uri.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    uri
+}
+
+/// Decode a `file:` URI
+fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    if !uri.starts_with("file:") {
+        return None;
+    }
+    let mut rest = &uri[5..];
+    if rest.starts_with("//") {
+        // Skip the authority; only local files are supported
+        let end = rest[2..].find('/')? + 2;
+        match &rest[2..end] {
+            "" | "localhost" => (),
+            _ => return None,
+        }
+        rest = &rest[end..];
+    }
+    let mut bytes = Vec::with_capacity(rest.len());
+    let mut iter = rest.bytes();
+    while let Some(b) = iter.next() {
+        if b == b'%' {
+            let hex = [iter.next()?, iter.next()?];
+            let hex = std::str::from_utf8(&hex).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+        } else {
+            bytes.push(b);
+        }
+    }
+    let path = String::from_utf8(bytes).ok()?;
+    if cfg!(windows) && path.starts_with('/') && path[1..].find(':') == Some(1) {
+        // "/C:/..." -> "C:/..."
+        return Some(PathBuf::from(&path[1..]));
+    }
+    Some(PathBuf::from(path))
+}
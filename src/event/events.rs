@@ -5,8 +5,12 @@
 
 //! Event handling: events
 
-use super::MouseButton;
+use std::any::Any;
+use std::rc::Rc;
 
+use super::{DragData, MouseButton, UpdateHandle};
+
+use crate::draw::Transform;
 use crate::geom::Coord;
 use crate::WidgetId;
 
@@ -17,8 +21,26 @@ pub enum Action {
     Activate,
     /// Widget receives a character of text input
     ReceivedCharacter(char),
+    /// Widget receives pre-edit (composition) text from an input method
+    ///
+    /// This replaces any previous pre-edit text; empty text ends composition
+    /// without input. Pre-edit text should be displayed at the cursor (e.g.
+    /// underlined) but is not yet input.
+    ImePreedit(String),
+    /// Widget receives text committed by an input method
+    ///
+    /// This ends composition, replacing any pre-edit text.
+    ImeCommit(String),
     /// A mouse or touchpad scroll event
     Scroll(ScrollDelta),
+    /// Widget receives a navigation key press
+    ///
+    /// This is sent to the widget with character focus, if any, otherwise to
+    /// the widget with keyboard navigation focus.
+    /// Current modifier state is available via [`Manager::modifiers`].
+    ///
+    /// [`Manager::modifiers`]: super::Manager::modifiers
+    ControlKey(ControlKey),
 }
 
 /// Low-level events addressed to a widget by [`WidgetId`] or coordinate.
@@ -26,9 +48,16 @@ pub enum Action {
 pub enum Event {
     Action(Action),
     /// A mouse button was pressed or touch event started
+    ///
+    /// `clicks` is the number of consecutive presses: 1 for a single click, 2
+    /// for a double-click, 3 for a triple-click, etc. Presses are consecutive
+    /// when from the same source (the same mouse button, or any touch) and
+    /// within the time and distance thresholds of the previous press (by
+    /// default, 500ms and 4 pixels; the toolkit may configure these).
     PressStart {
         source: PressSource,
         coord: Coord,
+        clicks: u32,
     },
     /// Movement of mouse or a touch press
     ///
@@ -38,17 +67,143 @@ pub enum Event {
         coord: Coord,
         delta: Coord,
     },
+    /// Motion of touches grabbed for panning
+    ///
+    /// Received only given a [pan grab](super::Manager::request_pan_grab),
+    /// instead of [`Event::PressMove`]. With a single touch, `coord` is its
+    /// position and `delta` its motion. With two touches, `coord` is the
+    /// centre of the touches and `delta` the motion of this centre, `scale` is
+    /// the ratio of new to old distance between the touches (a pinch) and
+    /// `rotation` is the change in angle between them (in radians, clockwise).
+    Pan {
+        coord: Coord,
+        delta: Coord,
+        scale: f32,
+        rotation: f32,
+    },
     /// End of a click/touch press
     ///
     /// Received only given a [press grab](super::Manager::request_press_grab).
     ///
     /// When `end_id == None`, this is a "cancelled press": the end of the press
-    /// is outside the application window.
+    /// is outside the application window, or the press was cancelled (e.g.
+    /// after an [`Event::LongPress`]).
     PressEnd {
         source: PressSource,
         end_id: Option<WidgetId>,
         coord: Coord,
     },
+    /// A touch press was held in place for some time
+    ///
+    /// This is delivered to the widget which received the
+    /// [`Event::PressStart`] (without requiring a press grab), for example to
+    /// open a context menu. If handled, the press is then cancelled (see
+    /// [`Event::PressEnd`]). If unhandled, the widget's help text (if any) is
+    /// shown, which also cancels the press.
+    LongPress {
+        source: PressSource,
+        coord: Coord,
+    },
+    /// Data was dropped at `coord`
+    ///
+    /// This is delivered to the widget under the pointer at the end of a drag
+    /// (see [`DragData`]). If unhandled, parents may handle the drop.
+    ///
+    /// Files dropped from other applications (e.g. a file manager) are also
+    /// delivered as this event, with paths available via
+    /// [`DragData::paths`]. Where the pointer position is unknown, these are
+    /// delivered to the widget with keyboard focus, or else the window.
+    Drop {
+        data: DragData,
+        coord: Coord,
+    },
+    /// A scheduled timer event
+    ///
+    /// Sent to widgets which requested this via
+    /// [`Manager::event_on_timer`](super::Manager::event_on_timer).
+    Timer,
+    /// A message sent to this widget
+    ///
+    /// Sent via [`Manager::send_to`](super::Manager::send_to) (or from another
+    /// thread via the toolkit's proxy). The recipient may inspect the message
+    /// with [`Any::downcast_ref`].
+    Message(Rc<dyn Any>),
+    /// An update handle was triggered
+    ///
+    /// Sent to widgets subscribed to `handle` via
+    /// [`Manager::update_on_handle`](super::Manager::update_on_handle).
+    /// The default handler, [`Manager::handle_generic`](super::Manager::handle_generic),
+    /// calls [`Widget::update_handle`](crate::Widget::update_handle).
+    HandleUpdate {
+        handle: UpdateHandle,
+        payload: u64,
+    },
+}
+
+impl Event {
+    /// Map pointer coordinates through a `transform`
+    ///
+    /// Coordinates are mapped by `transform` and motion deltas by its linear
+    /// part; [`Event::Action`] is returned unchanged. Widgets drawing children
+    /// via [`DrawHandle::transform_region`] should use this with the
+    /// [inverse](Transform::inverse) transform to map events to the children's
+    /// coordinate space.
+    ///
+    /// [`DrawHandle::transform_region`]: crate::theme::DrawHandle::transform_region
+    pub fn transform(self, transform: Transform) -> Self {
+        match self {
+            Event::PressStart {
+                source,
+                coord,
+                clicks,
+            } => Event::PressStart {
+                source,
+                coord: transform.map_coord(coord),
+                clicks,
+            },
+            Event::PressMove {
+                source,
+                coord,
+                delta,
+            } => Event::PressMove {
+                source,
+                coord: transform.map_coord(coord),
+                delta: transform.map_delta(delta),
+            },
+            Event::PressEnd {
+                source,
+                end_id,
+                coord,
+            } => Event::PressEnd {
+                source,
+                end_id,
+                coord: transform.map_coord(coord),
+            },
+            Event::Pan {
+                coord,
+                delta,
+                scale,
+                rotation,
+            } => Event::Pan {
+                coord: transform.map_coord(coord),
+                delta: transform.map_delta(delta),
+                scale,
+                rotation,
+            },
+            Event::LongPress { source, coord } => Event::LongPress {
+                source,
+                coord: transform.map_coord(coord),
+            },
+            Event::Drop { data, coord } => Event::Drop {
+                data,
+                coord: transform.map_coord(coord),
+            },
+            e @ Event::Action(_)
+            | e @ Event::Timer
+            | e @ Event::Message(_)
+            | e @ Event::HandleUpdate { .. } => e,
+        }
+    }
 }
 
 /// Source of `EventChild::Press`
@@ -79,3 +234,37 @@ pub enum ScrollDelta {
     /// Scroll a given number of pixels
     PixelDelta(Coord),
 }
+
+/// Type used by [`Action::ControlKey`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ControlKey {
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+}
+
+/// State of keyboard modifiers
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Modifiers {
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+    pub logo: bool,
+}
+
+/// Priority of an announcement
+///
+/// See [`Manager::announce`](super::Manager::announce).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+    /// Announce after any current speech has finished
+    Polite,
+    /// Interrupt any current speech to announce immediately
+    Assertive,
+}
@@ -5,6 +5,7 @@
 
 //! Event handling - handler
 
+use std::any::Any;
 use std::time::Duration;
 
 use crate::event::{Action, Event, Manager, Response, UpdateHandle};
@@ -66,7 +67,7 @@ pub trait Handler: Widget {
     }
 }
 
-impl<M> Handler for Box<dyn Handler<Msg = M>> {
+impl<M: 'static> Handler for Box<dyn Handler<Msg = M>> {
     type Msg = M;
 
     fn activation_via_press(&self) -> bool {
@@ -82,11 +83,15 @@ impl<M> Handler for Box<dyn Handler<Msg = M>> {
     }
 }
 
-impl<M> Widget for Box<dyn Handler<Msg = M>> {
+impl<M: 'static> Widget for Box<dyn Handler<Msg = M>> {
     fn configure(&mut self, mgr: &mut Manager) {
         self.as_mut().configure(mgr);
     }
 
+    fn detach(&mut self, mgr: &mut Manager) {
+        self.as_mut().detach(mgr);
+    }
+
     fn update_timer(&mut self, mgr: &mut Manager) -> Option<Duration> {
         self.as_mut().update_timer(mgr)
     }
@@ -100,7 +105,7 @@ impl<M> Widget for Box<dyn Handler<Msg = M>> {
     }
 }
 
-impl<M> Layout for Box<dyn Handler<Msg = M>> {
+impl<M: 'static> Layout for Box<dyn Handler<Msg = M>> {
     fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
         self.as_mut().size_rules(size_handle, axis)
     }
@@ -118,7 +123,7 @@ impl<M> Layout for Box<dyn Handler<Msg = M>> {
     }
 }
 
-impl<M> WidgetCore for Box<dyn Handler<Msg = M>> {
+impl<M: 'static> WidgetCore for Box<dyn Handler<Msg = M>> {
     fn core_data(&self) -> &CoreData {
         self.as_ref().core_data()
     }
@@ -136,6 +141,12 @@ impl<M> WidgetCore for Box<dyn Handler<Msg = M>> {
     fn as_widget_mut(&mut self) -> &mut dyn Widget {
         self.as_mut().as_widget_mut()
     }
+    fn as_any(&self) -> &dyn Any {
+        self.as_ref().as_any()
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self.as_mut().as_any_mut()
+    }
 
     fn len(&self) -> usize {
         self.as_ref().len()
@@ -186,7 +197,11 @@ impl<'a> Manager<'a> {
         let activable = widget.activation_via_press();
         match event {
             Event::Action(action) => widget.handle_action(mgr, action),
-            Event::PressStart { source, coord } if activable && source.is_primary() => {
+            Event::HandleUpdate { handle, payload } => {
+                widget.update_handle(mgr, handle, payload);
+                Response::None
+            }
+            Event::PressStart { source, coord, .. } if activable && source.is_primary() => {
                 mgr.request_press_grab(source, widget.as_widget(), coord, None);
                 Response::None
             }
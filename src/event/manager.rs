@@ -5,18 +5,35 @@
 
 //! Event manager
 
-use log::trace;
+use log::{trace, warn};
 use smallvec::SmallVec;
+use std::any::Any;
 use std::collections::HashMap;
+use std::fmt::{self, Debug};
+use std::future::Future;
+use std::rc::Rc;
 use std::time::{Duration, Instant};
 
 use super::*;
-use crate::geom::Coord;
-use crate::theme::{ThemeAction, ThemeApi};
+use crate::geom::{Coord, Rect};
+use crate::theme::{SizeHandle, ThemeAction, ThemeApi};
 use crate::{TkAction, TkWindow, Widget, WidgetId, WindowId};
 
+/// Half-period of the text caret's blink cycle
+const CARET_BLINK: Duration = Duration::from_millis(500);
+/// The caret stops blinking (remaining visible) after this period idle
+const CARET_BLINK_TIMEOUT: Duration = Duration::from_secs(10);
+/// Default maximum interval between presses of a double-click
+const CLICK_TIME: Duration = Duration::from_millis(500);
+/// Default maximum distance between presses of a double-click (logical pixels)
+const CLICK_DISTANCE: u32 = 4;
+/// Duration a touch must be held in place to produce a long press
+#[cfg(feature = "winit")]
+const LONG_PRESS: Duration = Duration::from_millis(500);
+
 /// Highlighting state of a widget
 #[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HighlightState {
     /// "Hover" is true if the mouse is over this element or if an active touch
     /// event is over the element.
@@ -44,12 +61,42 @@ impl HighlightState {
     }
 }
 
+/// Target of a registered shortcut
+#[derive(Clone)]
+enum ShortcutTarget {
+    /// Send [`Action::Activate`] to a widget
+    Widget(WidgetId),
+    /// Push a message (see [`Manager::push_msg`])
+    Msg(#[cfg_attr(not(feature = "winit"), allow(dead_code))] Rc<dyn Any>),
+}
+
+impl Debug for ShortcutTarget {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ShortcutTarget::Widget(id) => write!(f, "Widget({})", id),
+            ShortcutTarget::Msg(_) => write!(f, "Msg(..)"),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 struct TouchEvent {
     touch_id: u64,
     start_id: WidgetId,
     cur_id: Option<WidgetId>,
     coord: Coord,
+    // Motion is delivered as Event::Pan instead of Event::PressMove
+    #[cfg_attr(not(feature = "winit"), allow(dead_code))]
+    pan: bool,
+}
+
+/// A coalesced [`Event::PressMove`]
+#[derive(Clone, Debug)]
+struct PendingMove {
+    id: WidgetId,
+    source: PressSource,
+    coord: Coord,
+    delta: Coord,
 }
 
 /// Window event manager
@@ -75,9 +122,43 @@ pub struct ManagerState {
     mouse_grab: Option<(WidgetId, MouseButton)>,
     touch_grab: SmallVec<[TouchEvent; 10]>,
     accel_keys: HashMap<VirtualKeyCode, WidgetId>,
+    keymap: Rc<Keymap>,
+    // Registered shortcuts, with action name
+    shortcuts: HashMap<Shortcut, (String, ShortcutTarget)>,
+    modifiers: Modifiers,
+    // Last press: source, time, coord and count of consecutive clicks
+    #[cfg_attr(not(feature = "winit"), allow(dead_code))]
+    last_click: Option<(PressSource, Instant, Coord, u32)>,
+    click_time: Duration,
+    click_distance: u32,
+    // Pending long press: touch id, widget, start coord and time due
+    long_press: Option<(u64, WidgetId, Coord, Instant)>,
+    // Widgets requiring redraw, unless redraw_all is set
+    redraw_ids: SmallVec<[WidgetId; 8]>,
+    redraw_all: bool,
+    // Coalesced events awaiting delivery (see Manager::flush_pending)
+    pending_moves: SmallVec<[PendingMove; 4]>,
+    pending_scroll: Option<(WidgetId, ScrollDelta)>,
+    pending_drop: Option<DragData>,
+    // Messages awaiting delivery (see Manager::send_to)
+    sent_msgs: Vec<(WidgetId, Rc<dyn Any>)>,
+    // In-process drag, ending with the given press
+    drag: Option<(PressSource, DragData)>,
+    // Files dragged from another application over the window
+    os_drag: Option<DragData>,
+    // Context help mode: the next press shows help instead
+    help_mode: bool,
+    // Switch theme touch mode according to the last input device
+    touch_mode_auto: bool,
+    // Text caret of the char-focus widget, start of blink cycle, and whether
+    // the caret is currently shown
+    caret: Option<(WidgetId, Rect)>,
+    caret_start: Instant,
+    caret_shown: bool,
 
     time_start: Instant,
     time_updates: Vec<(Instant, WidgetId)>,
+    timer_events: Vec<(Instant, WidgetId)>,
     // TODO(opt): consider other containers, e.g. C++ multimap
     // or sorted Vec with binary search yielding a range
     handle_updates: HashMap<UpdateHandle, Vec<WidgetId>>,
@@ -102,9 +183,30 @@ impl ManagerState {
             mouse_grab: None,
             touch_grab: Default::default(),
             accel_keys: HashMap::new(),
+            keymap: Default::default(),
+            shortcuts: HashMap::new(),
+            modifiers: Modifiers::default(),
+            last_click: None,
+            click_time: CLICK_TIME,
+            click_distance: CLICK_DISTANCE,
+            long_press: None,
+            redraw_ids: Default::default(),
+            redraw_all: true,
+            pending_moves: Default::default(),
+            pending_scroll: None,
+            pending_drop: None,
+            sent_msgs: vec![],
+            drag: None,
+            os_drag: None,
+            help_mode: false,
+            touch_mode_auto: false,
+            caret: None,
+            caret_start: Instant::now(),
+            caret_shown: true,
 
             time_start: Instant::now(),
             time_updates: vec![],
+            timer_events: vec![],
             handle_updates: HashMap::new(),
         }
     }
@@ -124,8 +226,15 @@ impl ManagerState {
 
         // We re-set these instead of remapping:
         self.accel_keys.clear();
+        self.shortcuts.clear();
         self.time_updates.clear();
+        self.timer_events.clear();
         self.handle_updates.clear();
+        self.pending_moves.clear();
+        self.pending_scroll = None;
+        self.pending_drop = None;
+        self.long_press = None;
+        self.redraw_all = true;
 
         let coord = self.last_mouse_coord;
         let mut mgr = self.manager(tkw);
@@ -177,6 +286,10 @@ impl ManagerState {
         do_map!(self.key_events, |elt: (u32, WidgetId)| map
             .get(&elt.1)
             .map(|id| (elt.0, *id)));
+
+        do_map!(self.sent_msgs, |elt: (WidgetId, Rc<dyn Any>)| map
+            .get(&elt.0)
+            .map(|id| (*id, elt.1)));
     }
 
     pub fn region_moved<W: Widget + ?Sized>(&mut self, widget: &mut W) {
@@ -190,6 +303,59 @@ impl ManagerState {
         }
     }
 
+    /// Mark the whole window as requiring redraw
+    ///
+    /// The toolkit should call this when redrawing for reasons not known to
+    /// the event manager (e.g. resizing).
+    #[inline]
+    pub fn redraw_all(&mut self) {
+        self.redraw_all = true;
+    }
+
+    /// Get the region requiring redraw
+    ///
+    /// This is the union of the visible rects of all widgets passed to
+    /// [`Manager::redraw`] since the last call to [`ManagerState::clear_redraw`],
+    /// or the rect of the whole `widget` tree if a full redraw is required.
+    ///
+    /// Rects are in window coordinates: where widgets are drawn with an offset
+    /// or transform (e.g. within a [`ScrollRegion`](crate::widget::ScrollRegion)),
+    /// their rects are mapped via [`crate::Layout::child_region`] and clipped to the
+    /// region in which they are drawn. Returns `None` if no redraw is required
+    /// or if no widget requiring redraw is visible.
+    pub fn redraw_region<W: Widget + ?Sized>(&self, widget: &W) -> Option<Rect> {
+        if self.redraw_all {
+            return Some(widget.rect());
+        }
+        self.redraw_ids
+            .iter()
+            .filter(|id| widget.find(**id).is_some())
+            .filter_map(|id| visible_rect(widget.as_widget(), *id))
+            .fold(None, |acc: Option<Rect>, rect| match acc {
+                Some(acc) => Some(acc.union(rect)),
+                None => Some(rect),
+            })
+    }
+
+    /// True if any redraw was requested since the last call to
+    /// [`ManagerState::clear_redraw`]
+    ///
+    /// Unlike [`ManagerState::redraw_region`], this includes requests to
+    /// redraw widgets which are not visible.
+    #[inline]
+    pub fn redraw_requested(&self) -> bool {
+        self.redraw_all || !self.redraw_ids.is_empty()
+    }
+
+    /// Clear the redraw region
+    ///
+    /// The toolkit should call this after drawing.
+    #[inline]
+    pub fn clear_redraw(&mut self) {
+        self.redraw_ids.clear();
+        self.redraw_all = false;
+    }
+
     /// Set the DPI factor. Must be updated for correct event translation by
     /// [`Manager::handle_winit`].
     #[inline]
@@ -197,9 +363,103 @@ impl ManagerState {
         self.dpi_factor = dpi_factor;
     }
 
+    /// Enable or disable automatic switching of touch mode
+    ///
+    /// When enabled, [`ThemeApi::set_touch_mode`] is called whenever the input
+    /// device switches between touch and mouse.
+    #[inline]
+    pub fn set_touch_mode_auto(&mut self, auto: bool) {
+        self.touch_mode_auto = auto;
+    }
+
+    /// Set the keymap
+    ///
+    /// Bindings of the keymap override the defaults passed to
+    /// [`Manager::register_shortcut`]. The toolkit should call this before
+    /// configuring the window.
+    #[inline]
+    pub fn set_keymap(&mut self, keymap: Rc<Keymap>) {
+        self.keymap = keymap;
+    }
+
+    /// Set thresholds for double-click (and triple-click) detection
+    ///
+    /// Presses are counted as consecutive clicks when within `time` of the
+    /// previous press and within `distance` (logical pixels) of its position.
+    #[inline]
+    pub fn set_click_threshold(&mut self, time: Duration, distance: u32) {
+        self.click_time = time;
+        self.click_distance = distance;
+    }
+
+    /// Get the current state of keyboard modifiers
+    #[inline]
+    pub fn modifiers(&self) -> Modifiers {
+        self.modifiers
+    }
+
     /// Get the next resume time
     pub fn next_resume(&self) -> Option<Instant> {
-        self.time_updates.first().map(|time| time.0)
+        let next = self.time_updates.first().map(|time| time.0);
+        let event = self.timer_events.first().map(|time| time.0);
+        let long_press = self.long_press.map(|press| press.3);
+        let msg = if self.sent_msgs.is_empty() {
+            None
+        } else {
+            Some(Instant::now())
+        };
+        [next, event, self.next_caret_blink(), long_press, msg]
+            .iter()
+            .flatten()
+            .min()
+            .cloned()
+    }
+
+    /// Queue a message for delivery to widget `id`
+    ///
+    /// This is used by the toolkit to deliver messages sent from other
+    /// threads; see [`Manager::send_to`].
+    pub fn send_to(&mut self, id: WidgetId, msg: Rc<dyn Any>) {
+        self.sent_msgs.push((id, msg));
+    }
+
+    /// Get the caret rect of the char-focus widget, if registered
+    ///
+    /// See [`Manager::set_caret`].
+    pub fn caret_rect(&self) -> Option<Rect> {
+        match self.caret {
+            Some((id, rect)) if self.char_focus == Some(id) => Some(rect),
+            _ => None,
+        }
+    }
+
+    // Time at which the caret should next be shown or hidden, if blinking
+    fn next_caret_blink(&self) -> Option<Instant> {
+        self.char_focus?;
+        let elapsed = self.caret_start.elapsed();
+        if elapsed >= CARET_BLINK_TIMEOUT {
+            // Ensure the caret is left visible
+            return if self.caret_shown {
+                None
+            } else {
+                Some(Instant::now())
+            };
+        }
+        let n = (elapsed.as_nanos() / CARET_BLINK.as_nanos()) as u32 + 1;
+        Some(self.caret_start + CARET_BLINK * n)
+    }
+
+    // Whether the caret should currently be shown
+    fn caret_phase(&self) -> bool {
+        let elapsed = self.caret_start.elapsed();
+        elapsed >= CARET_BLINK_TIMEOUT
+            || elapsed.as_nanos() % (2 * CARET_BLINK.as_nanos()) < CARET_BLINK.as_nanos()
+    }
+
+    // Restart the blink cycle, showing the caret
+    fn reset_caret(&mut self) {
+        self.caret_start = Instant::now();
+        self.caret_shown = true;
     }
 
     /// Construct a [`Manager`] referring to this state
@@ -209,6 +469,9 @@ impl ManagerState {
             action: TkAction::None,
             mgr: self,
             tkw,
+            batch_depth: 0,
+            batch_action: TkAction::None,
+            batch_updates: vec![],
         }
     }
 }
@@ -218,6 +481,11 @@ pub struct Manager<'a> {
     action: TkAction,
     mgr: &'a mut ManagerState,
     tkw: &'a mut dyn TkWindow,
+    // Nesting depth of with_batch, and the action and updates deferred until
+    // its end
+    batch_depth: u32,
+    batch_action: TkAction,
+    batch_updates: Vec<(UpdateHandle, u64)>,
 }
 
 /// Public API (around toolkit functionality)
@@ -233,34 +501,69 @@ impl<'a> Manager<'a> {
     /// used to schedule an update on the next frame. Frames should in any case
     /// be limited by vsync, avoiding excessive frame rates.
     ///
-    /// This should be called from [`Widget::configure`] or from an event
-    /// handler. Note that scheduled updates are cleared if reconfigured.
+    /// Each widget has at most one scheduled update: if an update is already
+    /// scheduled, the earlier of the two times is kept (see also
+    /// [`Manager::cancel_timer`]). Updates repeat while
+    /// [`Widget::update_timer`] returns a duration.
+    ///
+    /// This should be called from [`Widget::configure`], from an event handler
+    /// or from [`Widget::update_timer`]. Note that scheduled updates are
+    /// cleared if reconfigured.
     pub fn update_on_timer(&mut self, duration: Duration, w_id: WidgetId) {
-        let time = Instant::now() + duration;
-        'outer: loop {
-            for row in &mut self.mgr.time_updates {
-                if row.1 == w_id {
-                    if row.0 <= time {
-                        return;
-                    } else {
-                        row.0 = time;
-                        break 'outer;
-                    }
-                }
-            }
+        schedule(&mut self.mgr.time_updates, Instant::now() + duration, w_id);
+    }
 
-            self.mgr.time_updates.push((time, w_id));
-            break;
-        }
+    /// Schedule a timer event
+    ///
+    /// Roughly at time `now + duration`, [`Event::Timer`] is sent to the
+    /// widget's [`Handler`]. Unlike [`Widget::update_timer`], the handler may
+    /// respond with a message; for example, a button may use this to repeat
+    /// its action while held. Events do not repeat automatically.
+    ///
+    /// Each widget has at most one scheduled event: if an event is already
+    /// scheduled, the earlier of the two times is kept (see also
+    /// [`Manager::cancel_timer_event`]). Scheduled events are cleared if
+    /// reconfigured.
+    pub fn event_on_timer(&mut self, duration: Duration, w_id: WidgetId) {
+        schedule(&mut self.mgr.timer_events, Instant::now() + duration, w_id);
+    }
+
+    /// Cancel a scheduled timer event
+    ///
+    /// Returns true if an event was scheduled for the widget.
+    pub fn cancel_timer_event(&mut self, w_id: WidgetId) -> bool {
+        let len = self.mgr.timer_events.len();
+        self.mgr.timer_events.retain(|row| row.1 != w_id);
+        self.mgr.timer_events.len() != len
+    }
 
-        self.mgr.time_updates.sort_by_key(|row| row.0);
+    /// Request an update on the next frame
+    ///
+    /// This schedules a timer update (see [`Manager::update_on_timer`]) for
+    /// the next frame. To continue animating, [`Widget::update_timer`] should
+    /// return [`FRAME`](crate::animate::FRAME). See also [`crate::animate`].
+    #[inline]
+    pub fn request_frame(&mut self, w_id: WidgetId) {
+        self.update_on_timer(crate::animate::FRAME, w_id);
+    }
+
+    /// Cancel a scheduled update
+    ///
+    /// Returns true if an update was scheduled for the widget. To postpone an
+    /// update (e.g. to restart a hover delay), cancel it then call
+    /// [`Manager::update_on_timer`].
+    pub fn cancel_timer(&mut self, w_id: WidgetId) -> bool {
+        let len = self.mgr.time_updates.len();
+        self.mgr.time_updates.retain(|row| row.1 != w_id);
+        self.mgr.time_updates.len() != len
     }
 
     /// Subscribe to an update handle
     ///
-    /// All widgets subscribed to an update handle will have their
-    /// [`Widget::update_handle`] method called when [`Manager::trigger_update`]
-    /// is called with the corresponding handle.
+    /// All widgets subscribed to an update handle receive
+    /// [`Event::HandleUpdate`] when [`Manager::trigger_update`] is called with
+    /// the corresponding handle (from any window, or from another thread via
+    /// the toolkit's proxy). Subscribers are then redrawn.
     ///
     /// This should be called from [`Widget::configure`].
     pub fn update_on_handle(&mut self, handle: UpdateHandle, w_id: WidgetId) {
@@ -272,11 +575,22 @@ impl<'a> Manager<'a> {
     }
 
     /// Notify that a widget must be redrawn
-    #[inline]
-    pub fn redraw(&mut self, _id: WidgetId) {
-        // Theoretically, notifying by WidgetId allows selective redrawing
-        // (damage events). This is not yet implemented.
-        self.send_action(TkAction::Redraw);
+    ///
+    /// The widget's id is added to the window's redraw region (see
+    /// [`ManagerState::redraw_region`]). Toolkits may use this to skip
+    /// redrawing when nothing visible changed; where a redraw happens, the
+    /// whole window is still redrawn.
+    pub fn redraw(&mut self, id: WidgetId) {
+        trace_detail!("Redraw of widget {} requested", id);
+        if self.batch_depth > 0 {
+            // Duplicates are removed at the end of the batch
+            if !self.mgr.redraw_all {
+                self.mgr.redraw_ids.push(id);
+            }
+        } else if !self.mgr.redraw_all && !self.mgr.redraw_ids.contains(&id) {
+            self.mgr.redraw_ids.push(id);
+        }
+        self.add_action(TkAction::Redraw);
     }
 
     /// Notify that a [`TkAction`] action should happen
@@ -288,7 +602,22 @@ impl<'a> Manager<'a> {
     /// affect the UI after a reconfigure action.
     #[inline]
     pub fn send_action(&mut self, action: TkAction) {
-        self.action = self.action.max(action);
+        if action > TkAction::None {
+            trace_detail!("Action {:?} requested", action);
+        }
+        if action >= TkAction::Redraw {
+            self.mgr.redraw_all = true;
+        }
+        self.add_action(action);
+    }
+
+    // Accumulate an action, deferring it until the end of any batch
+    fn add_action(&mut self, action: TkAction) {
+        if self.batch_depth > 0 {
+            self.batch_action = self.batch_action.max(action);
+        } else {
+            self.action = self.action.max(action);
+        }
     }
 
     /// Add a window
@@ -303,6 +632,16 @@ impl<'a> Manager<'a> {
         self.tkw.add_window(widget)
     }
 
+    /// Add a modal window
+    ///
+    /// As [`Manager::add_window`], except that input to the current window is
+    /// blocked until the new window is closed. See also
+    /// [`Window::show_modal`](kas::Window::show_modal).
+    #[inline]
+    pub fn add_modal_window(&mut self, widget: Box<dyn kas::Window>) -> WindowId {
+        self.tkw.add_modal_window(widget)
+    }
+
     /// Close a window
     #[inline]
     pub fn close_window(&mut self, id: WindowId) {
@@ -313,9 +652,112 @@ impl<'a> Manager<'a> {
     ///
     /// All widgets subscribed to the given [`UpdateHandle`], across all
     /// windows, will receive an update.
-    #[inline]
+    ///
+    /// Within [`Manager::with_batch`], the update is deferred until the end
+    /// of the batch and coalesced with other updates on the same handle.
     pub fn trigger_update(&mut self, handle: UpdateHandle, payload: u64) {
-        self.tkw.trigger_update(handle, payload);
+        if self.batch_depth == 0 {
+            self.tkw.trigger_update(handle, payload);
+        } else if let Some(item) = self.batch_updates.iter_mut().find(|u| u.0 == handle) {
+            item.1 = payload;
+        } else {
+            self.batch_updates.push((handle, payload));
+        }
+    }
+
+    /// Push a message to the inspectors of all windows
+    ///
+    /// Unlike messages returned via [`Response::Msg`], which must be handled
+    /// by (or converted for) each parent in turn, pushed messages bypass the
+    /// widget tree: after event handling, [`crate::Window::inspect_msg`] of
+    /// each window receives the message (see also
+    /// [`crate::widget::Window::add_inspector`]).
+    ///
+    /// This may be used for logging, analytics or application-wide actions
+    /// such as "save" which may be requested from anywhere.
+    pub fn push_msg<M: Debug + 'static>(&mut self, msg: M) {
+        trace!("Manager::push_msg: {:?}", msg);
+        self.tkw.push_msg(Rc::new(msg));
+    }
+
+    /// Send a message to a specific widget
+    ///
+    /// The message is delivered to the widget with the given `id` (in this
+    /// window) as [`Event::Message`] after the current event has been handled,
+    /// regardless of where the sender is in the widget tree. The recipient's
+    /// [`Handler::handle`] may recover the message via [`Any::downcast_ref`]
+    /// and may respond with a message as usual, which is passed to its
+    /// parents. Messages to widgets which no longer exist are dropped.
+    ///
+    /// The widget's id may be found via [`crate::WidgetCore::find_key`].
+    pub fn send_to<M: Debug + 'static>(&mut self, id: WidgetId, msg: M) {
+        trace!("Manager::send_to({}): {:?}", id, msg);
+        self.mgr.send_to(id, Rc::new(msg));
+    }
+
+    /// Spawn a future, delivering its output to a widget
+    ///
+    /// The future is run by the toolkit's executor and, on completion, its
+    /// output is delivered to the widget with the given `id` as
+    /// [`Event::Message`] (as with [`Manager::send_to`]). For example:
+    /// ```ignore
+    /// mgr.spawn(async { fetch(url).await }, self.id());
+    /// ```
+    ///
+    /// The future is polled on the UI thread, thus should not block; blocking
+    /// or CPU-intensive work should be run on another thread (with the future
+    /// awaiting its result). The future is dropped if the window is closed.
+    pub fn spawn<F>(&mut self, future: F, id: WidgetId)
+    where
+        F: Future + 'static,
+        F::Output: Debug + 'static,
+    {
+        trace!("Manager::spawn: task for widget {}", id);
+        let task = async move { Rc::new(future.await) as Rc<dyn Any> };
+        self.tkw.spawn(id, Box::pin(task));
+    }
+
+    /// Run `f`, coalescing the redraw, re-layout and update requests it makes
+    ///
+    /// Within `f`:
+    ///
+    /// -   toolkit actions (see [`Manager::redraw`] and
+    ///     [`Manager::send_action`]) are accumulated into a single action
+    ///     (the most significant requested), applied when the batch ends
+    /// -   widget ids passed to [`Manager::redraw`] are collected without
+    ///     checking for duplicates, then sorted and de-duplicated once when
+    ///     the batch ends
+    /// -   calls to [`Manager::trigger_update`] are deferred until the batch
+    ///     ends, then sent once per handle with the last payload given
+    ///
+    /// Since the toolkit applies the action resulting from each update
+    /// separately, coalescing updates is what avoids repeated work: e.g.
+    /// adding 1000 items to a [`crate::data::SharedRc`] list model within a
+    /// batch updates each view once, resulting in a single re-layout and
+    /// redraw instead of 1000.
+    ///
+    /// Batches may be nested; deferred work is completed when the outermost
+    /// batch ends.
+    pub fn with_batch<F: FnOnce(&mut Manager<'a>) -> T, T>(&mut self, f: F) -> T {
+        self.batch_depth += 1;
+        let result = f(self);
+        self.batch_depth -= 1;
+
+        if self.batch_depth == 0 {
+            let action = std::mem::replace(&mut self.batch_action, TkAction::None);
+            self.action = self.action.max(action);
+            if self.mgr.redraw_all {
+                self.mgr.redraw_ids.clear();
+            } else {
+                self.mgr.redraw_ids.sort_unstable();
+                self.mgr.redraw_ids.dedup();
+            }
+            let updates = std::mem::take(&mut self.batch_updates);
+            for (handle, payload) in updates {
+                self.tkw.trigger_update(handle, payload);
+            }
+        }
+        result
     }
 
     /// Attempt to get clipboard contents
@@ -333,15 +775,110 @@ impl<'a> Manager<'a> {
         self.tkw.set_clipboard(content)
     }
 
+    /// Announce some text to assistive technology
+    ///
+    /// This may be used to notify the user of results of asynchronous
+    /// operations, e.g. "Download complete". An announcement with
+    /// [`Priority::Assertive`] interrupts any current speech.
+    ///
+    /// Where the toolkit has no means of announcement this does nothing.
+    #[inline]
+    pub fn announce(&mut self, text: &str, priority: Priority) {
+        self.tkw.announce(text, priority)
+    }
+
     /// Adjust the theme
     #[inline]
     pub fn adjust_theme<F: FnMut(&mut dyn ThemeApi) -> ThemeAction>(&mut self, mut f: F) {
         self.tkw.adjust_theme(&mut f);
     }
+
+    /// Get the screen area available to a popup anchored at `anchor`
+    ///
+    /// This is the area of the screen containing the centre of `anchor`, or
+    /// failing that the screen nearest to it, relative to the window. Returns
+    /// `None` where the toolkit does not report screen geometry. Depending on
+    /// the toolkit, this may be the full screen area, including panels such
+    /// as task bars (this is the case with `kas-wgpu`).
+    ///
+    /// See [`PopupPlacement`](crate::layout::PopupPlacement).
+    pub fn screen_area(&self, anchor: Rect) -> Option<Rect> {
+        let c = anchor.pos + Coord(anchor.size.0 as i32 / 2, anchor.size.1 as i32 / 2);
+        let dist = |r: &Rect| {
+            let (x0, y0) = (r.pos.0, r.pos.1);
+            let (x1, y1) = (x0 + r.size.0 as i32 - 1, y0 + r.size.1 as i32 - 1);
+            let dx = (x0 - c.0).max(c.0 - x1).max(0);
+            let dy = (y0 - c.1).max(c.1 - y1).max(0);
+            dx.max(dy)
+        };
+        let areas = self.tkw.screen_areas();
+        areas.into_iter().min_by_key(dist)
+    }
+
+    /// Access a [`SizeHandle`]
+    ///
+    /// This may be used to measure text during event handling. Returns `None`
+    /// where the toolkit cannot provide a size handle.
+    pub fn size_handle<F: FnOnce(&mut dyn SizeHandle) -> T, T>(&mut self, f: F) -> Option<T> {
+        let mut f = Some(f);
+        let mut result = None;
+        self.tkw.size_handle(&mut |size_handle| {
+            if let Some(f) = f.take() {
+                result = Some(f(size_handle));
+            }
+        });
+        result
+    }
 }
 
 /// Public API (around event manager state)
 impl<'a> Manager<'a> {
+    /// Get the current state of keyboard modifiers
+    #[inline]
+    pub fn modifiers(&self) -> Modifiers {
+        self.mgr.modifiers
+    }
+
+    /// True if accelerator keys should be indicated
+    ///
+    /// Widgets with mnemonics (e.g. [`crate::widget::AccelLabel`]) underline
+    /// the mnemonic character while this is true (while Alt is held).
+    #[inline]
+    pub fn show_accel_labels(&self) -> bool {
+        self.mgr.modifiers.alt
+    }
+
+    /// Enter context help mode
+    ///
+    /// In this mode, the next click (or touch) on the window is not delivered
+    /// as an event; instead the help text of the widget under the pointer (or
+    /// its nearest ancestor with help text) is shown in a new window. See
+    /// [`WidgetCore::with_help`](crate::WidgetCore::with_help).
+    ///
+    /// This mode may also be toggled with Shift+F1 and cancelled with Escape.
+    pub fn start_help_mode(&mut self) {
+        self.mgr.help_mode = true;
+        self.tkw.set_cursor_icon(CursorIcon::Help);
+    }
+
+    /// True if in context help mode
+    #[inline]
+    pub fn is_help_mode(&self) -> bool {
+        self.mgr.help_mode
+    }
+
+    /// Get the data of the drag-and-drop operation in progress, if any
+    ///
+    /// This includes drags started via [`Manager::start_drag`] and files
+    /// dragged over the window from other applications. Drop targets may use
+    /// this together with [`HighlightState::hover`] to highlight themselves.
+    pub fn drag_data(&self) -> Option<&DragData> {
+        match self.mgr.drag {
+            Some((_, ref data)) => Some(data),
+            None => self.mgr.os_drag.as_ref(),
+        }
+    }
+
     /// Get the complete highlight state
     pub fn highlight_state(&self, w_id: WidgetId) -> HighlightState {
         HighlightState {
@@ -358,6 +895,61 @@ impl<'a> Manager<'a> {
         self.mgr.char_focus == Some(w_id)
     }
 
+    /// Check whether the text caret of the given widget should be drawn
+    ///
+    /// This is true when the widget has character focus, except during the
+    /// "off" phase of the caret's blink cycle. The event manager schedules
+    /// redraws of the char-focus widget as the caret blinks; widgets should
+    /// draw their caret via [`DrawHandle::text_caret`] when this is true.
+    ///
+    /// [`DrawHandle::text_caret`]: crate::theme::DrawHandle::text_caret
+    #[inline]
+    pub fn caret_visible(&self, w_id: WidgetId) -> bool {
+        self.mgr.char_focus == Some(w_id) && self.mgr.caret_shown
+    }
+
+    /// Set the cursor icon shown while `w_id` is hovered
+    ///
+    /// Widgets normally specify a cursor icon via [`Widget::cursor_icon`],
+    /// which is queried when the widget becomes hovered. This method may be
+    /// used to change the icon while hovered (e.g. after a change of state).
+    /// The icon is reset when the hovered widget changes.
+    ///
+    /// Does nothing unless `w_id` is hovered.
+    pub fn set_cursor_icon(&mut self, w_id: WidgetId, icon: CursorIcon) {
+        if self.mgr.hover != Some(w_id) || icon == self.mgr.hover_icon {
+            return;
+        }
+        self.mgr.hover_icon = icon;
+        if self.mgr.mouse_grab.is_none() && !self.mgr.help_mode {
+            self.tkw.set_cursor_icon(icon);
+        }
+    }
+
+    /// Register the text caret position of the char-focus widget
+    ///
+    /// Text-editing widgets should call this after moving the caret (or
+    /// editing text) while they have character focus. The `rect` is the
+    /// caret's location in window coordinates, used to position input method
+    /// (IME) candidate windows. This also restarts the blink cycle, keeping
+    /// the caret visible while the user types.
+    ///
+    /// Does nothing unless `w_id` has character focus.
+    pub fn set_caret(&mut self, w_id: WidgetId, rect: Rect) {
+        if self.mgr.char_focus != Some(w_id) {
+            return;
+        }
+        if !self.mgr.caret_shown {
+            self.redraw(w_id);
+        }
+        self.mgr.reset_caret();
+        if self.mgr.caret != Some((w_id, rect)) {
+            self.mgr.caret = Some((w_id, rect));
+            let pos = rect.pos + Coord(0, rect.size.1 as i32);
+            self.tkw.set_ime_position(pos);
+        }
+    }
+
     /// Get whether this widget has keyboard focus
     #[inline]
     pub fn key_focus(&self, w_id: WidgetId) -> bool {
@@ -395,6 +987,8 @@ impl<'a> Manager<'a> {
     ///
     /// If this key is pressed when the window has focus and no widget has a
     /// key-grab, the given widget will receive an [`Action::Activate`] event.
+    /// The key is also accepted with the Alt modifier, even when another
+    /// widget has character focus.
     ///
     /// This should be set from [`Widget::configure`].
     #[inline]
@@ -402,6 +996,66 @@ impl<'a> Manager<'a> {
         self.mgr.accel_keys.insert(key, id);
     }
 
+    /// Register a keyboard shortcut activating a widget
+    ///
+    /// The shortcut for the named `action` is that bound by the keymap (see
+    /// [`Keymap`]), if any, otherwise `default`. When pressed, the widget
+    /// `id` receives an [`Action::Activate`] event. While a widget has
+    /// character focus, only shortcuts using `Ctrl`, `Alt` or `Logo` are
+    /// matched. Shortcuts take precedence over accelerator keys (see
+    /// [`Manager::add_accel_key`]).
+    ///
+    /// Fails if the shortcut is already registered to another action.
+    /// Registering the same action again replaces the previous registration.
+    ///
+    /// This should be called from [`Widget::configure`].
+    pub fn register_shortcut(
+        &mut self,
+        action: &str,
+        default: Shortcut,
+        id: WidgetId,
+    ) -> Result<(), ShortcutConflict> {
+        self.add_shortcut(action, default, ShortcutTarget::Widget(id))
+    }
+
+    /// Register a keyboard shortcut pushing a message
+    ///
+    /// As [`Manager::register_shortcut`], except that when pressed `msg` is
+    /// pushed to the inspectors of all windows (see [`Manager::push_msg`]).
+    /// This is intended for application-wide actions such as "save".
+    pub fn register_shortcut_msg<M: Debug + 'static>(
+        &mut self,
+        action: &str,
+        default: Shortcut,
+        msg: M,
+    ) -> Result<(), ShortcutConflict> {
+        self.add_shortcut(action, default, ShortcutTarget::Msg(Rc::new(msg)))
+    }
+
+    /// Get the shortcut registered for the named `action`, if any
+    ///
+    /// This may be used to display the shortcut, e.g. in a menu.
+    pub fn shortcut(&self, action: &str) -> Option<Shortcut> {
+        let mut iter = self.mgr.shortcuts.iter();
+        iter.find(|(_, (name, _))| name == action)
+            .map(|(shortcut, _)| *shortcut)
+    }
+
+    /// Get the keymap
+    #[inline]
+    pub fn keymap(&self) -> &Keymap {
+        &self.mgr.keymap
+    }
+
+    /// Replace the keymap
+    ///
+    /// This affects only the current window. The window is reconfigured,
+    /// thus shortcuts are registered anew.
+    pub fn set_keymap(&mut self, keymap: Keymap) {
+        self.mgr.keymap = Rc::new(keymap);
+        self.send_action(TkAction::Reconfigure);
+    }
+
     /// Request character-input focus
     ///
     /// If successful, [`Action::ReceivedCharacter`] events are sent to this
@@ -409,10 +1063,15 @@ impl<'a> Manager<'a> {
     ///
     /// Currently, this method always succeeds.
     pub fn request_char_focus(&mut self, id: WidgetId) {
-        if self.mgr.key_focus.is_some() {
+        if let Some(old_id) = self.mgr.key_focus {
+            self.redraw(old_id);
             self.mgr.key_focus = Some(id);
         }
+        if let Some(old_id) = self.mgr.char_focus {
+            self.redraw(old_id);
+        }
         self.mgr.char_focus = Some(id);
+        self.mgr.reset_caret();
         self.redraw(id);
     }
 
@@ -436,6 +1095,35 @@ impl<'a> Manager<'a> {
         widget: &dyn Widget,
         coord: Coord,
         cursor: Option<CursorIcon>,
+    ) -> bool {
+        self.grab_press(source, widget, coord, cursor, false)
+    }
+
+    /// Request a pan grab on the given `source`
+    ///
+    /// This is as [`Manager::request_press_grab`], except that motion of
+    /// grabbed touches is delivered as [`Event::Pan`] instead of
+    /// [`Event::PressMove`]. Where the widget grabs two touches (e.g. on each
+    /// [`Event::PressStart`] it receives), their motion is combined into
+    /// pinch (scale) and rotate gestures. Mouse presses are grabbed as by
+    /// [`Manager::request_press_grab`].
+    pub fn request_pan_grab(
+        &mut self,
+        source: PressSource,
+        widget: &dyn Widget,
+        coord: Coord,
+        cursor: Option<CursorIcon>,
+    ) -> bool {
+        self.grab_press(source, widget, coord, cursor, true)
+    }
+
+    fn grab_press(
+        &mut self,
+        source: PressSource,
+        widget: &dyn Widget,
+        coord: Coord,
+        cursor: Option<CursorIcon>,
+        pan: bool,
     ) -> bool {
         let w_id = widget.id();
         match source {
@@ -458,6 +1146,7 @@ impl<'a> Manager<'a> {
                     start_id: w_id,
                     cur_id: Some(w_id),
                     coord,
+                    pan,
                 });
             }
         }
@@ -472,15 +1161,158 @@ impl<'a> Manager<'a> {
         self.redraw(w_id);
         true
     }
+
+    /// Start a drag-and-drop operation
+    ///
+    /// The drag is tied to the press `source`, which must be grabbed (see
+    /// [`Manager::request_press_grab`]); typically this is called on an
+    /// [`Event::PressMove`] once the press has moved some distance. When the
+    /// press ends, an [`Event::Drop`] is delivered to the widget under the
+    /// pointer (after the [`Event::PressEnd`] delivered to the grabbing
+    /// widget). If no widget handles the drop, the data is discarded.
+    ///
+    /// If the press ends outside of the window, the data may be dropped onto
+    /// another window of the application (see [`TkWindow::drop_outside`]).
+    /// Where the press ends outside of all windows, the grabbing widget may
+    /// detect this from the `end_id` and `coord` of [`Event::PressEnd`], e.g.
+    /// to open the dragged content in a new window.
+    ///
+    /// Note that only the [`DragData`] is transferred between windows: KAS
+    /// does not yet provide tab or dock widgets which may be torn off into a
+    /// new window and re-docked.
+    ///
+    /// Returns false (and does nothing) if `source` is not grabbed.
+    pub fn start_drag(&mut self, source: PressSource, data: DragData) -> bool {
+        let grabbed = match source {
+            PressSource::Mouse(button) => self.mgr.mouse_grab.map(|grab| grab.1) == Some(button),
+            PressSource::Touch(touch_id) => self.get_touch(touch_id).is_some(),
+        };
+        if grabbed {
+            if let PressSource::Mouse(_) = source {
+                self.tkw.set_cursor_icon(CursorIcon::Grabbing);
+            }
+            self.mgr.drag = Some((source, data));
+        }
+        grabbed
+    }
+
+    /// Detach a widget removed from the widget tree
+    ///
+    /// This calls [`Widget::detach`] on `widget` and all its descendants and
+    /// removes all registrations (update handles, timers, accelerator keys,
+    /// focus and grabs) held by these widgets.
+    ///
+    /// Widgets removing children at run-time should call this on each removed
+    /// child before sending [`TkAction::Reconfigure`].
+    pub fn detach<W: Widget + ?Sized>(&mut self, widget: &mut W) {
+        widget.walk_mut(&mut |w| {
+            w.detach(self);
+            self.forget(w.id());
+        });
+    }
+
+    /// Release focus and grabs held by a widget hidden from the widget tree
+    ///
+    /// This removes keyboard and character focus, hover state and press grabs
+    /// held by `widget` and its descendants. Unlike [`Manager::detach`], other
+    /// registrations (update handles, timers, accelerator keys) are kept.
+    ///
+    /// Widgets hiding children at run-time (e.g. [`crate::widget::Stack`])
+    /// should call this on each hidden child.
+    pub fn hide<W: Widget + ?Sized>(&mut self, widget: &W) {
+        widget.walk(&mut |w| self.release(w.id()));
+    }
 }
 
 /// Internal methods
 impl<'a> Manager<'a> {
+    /// Release focus, hover state, grabs and pending input of widget `id`
+    fn release(&mut self, id: WidgetId) {
+        let mgr = &mut self.mgr;
+        if mgr.char_focus == Some(id) {
+            mgr.char_focus = None;
+        }
+        if mgr.key_focus == Some(id) {
+            mgr.key_focus = None;
+        }
+        if mgr.hover == Some(id) {
+            mgr.hover = None;
+        }
+        if mgr.mouse_grab.map(|grab| grab.0) == Some(id) {
+            mgr.mouse_grab = None;
+            self.tkw.set_cursor_icon(mgr.hover_icon);
+        }
+        mgr.touch_grab.retain(|touch| touch.start_id != id);
+        if mgr.long_press.map(|press| press.1) == Some(id) {
+            mgr.long_press = None;
+        }
+        let drag_source = mgr.drag.as_ref().map(|drag| drag.0);
+        let cancel_drag = match drag_source {
+            Some(PressSource::Mouse(_)) => mgr.mouse_grab.is_none(),
+            Some(PressSource::Touch(touch_id)) => !mgr
+                .touch_grab
+                .iter()
+                .any(|touch| touch.touch_id == touch_id),
+            None => false,
+        };
+        if cancel_drag {
+            mgr.drag = None;
+        }
+        for touch in &mut mgr.touch_grab {
+            if touch.cur_id == Some(id) {
+                touch.cur_id = None;
+            }
+        }
+        mgr.key_events.retain(|event| event.1 != id);
+        mgr.pending_moves.retain(|ev| ev.id != id);
+        if mgr.pending_scroll.map(|scroll| scroll.0) == Some(id) {
+            mgr.pending_scroll = None;
+        }
+    }
+
+    /// Remove all state referring to widget `id`
+    fn forget(&mut self, id: WidgetId) {
+        self.release(id);
+        let mgr = &mut self.mgr;
+        mgr.accel_keys.retain(|_, w_id| *w_id != id);
+        mgr.shortcuts.retain(|_, (_, target)| match target {
+            ShortcutTarget::Widget(w_id) => *w_id != id,
+            ShortcutTarget::Msg(_) => true,
+        });
+        mgr.time_updates.retain(|update| update.1 != id);
+        mgr.timer_events.retain(|update| update.1 != id);
+        for ids in mgr.handle_updates.values_mut() {
+            ids.retain(|w_id| *w_id != id);
+        }
+        mgr.redraw_ids.retain(|w_id| *w_id != id);
+        mgr.sent_msgs.retain(|msg| msg.0 != id);
+    }
+
+    /// Send an event to widget `id`, logging the result (see the `trace` feature)
+    fn send_event<W>(&mut self, widget: &mut W, id: WidgetId, event: Event) -> Response<W::Msg>
+    where
+        W: Handler + ?Sized,
+    {
+        trace_detail!("Sending {:?} to widget {}", event, id);
+        let response = widget.handle(self, id, event);
+        if let Response::Unhandled(ref event) = response {
+            trace_detail!("Widget {} did not handle {:?}", id, event);
+        }
+        response
+    }
+
     #[cfg(feature = "winit")]
     fn set_hover<W: Widget + ?Sized>(&mut self, widget: &mut W, w_id: Option<WidgetId>) {
         if self.mgr.hover != w_id {
+            trace_detail!("Hover: {:?} -> {:?}", self.mgr.hover, w_id);
+            // Only the old and new hovered widgets need redrawing
+            if let Some(id) = self.mgr.hover {
+                self.redraw(id);
+            }
+            if let Some(id) = w_id {
+                self.redraw(id);
+            }
             self.mgr.hover = w_id;
-            self.send_action(TkAction::Redraw);
 
             if let Some(id) = w_id {
                 let icon = widget
@@ -489,7 +1321,7 @@ impl<'a> Manager<'a> {
                     .unwrap_or(CursorIcon::Default);
                 if icon != self.mgr.hover_icon {
                     self.mgr.hover_icon = icon;
-                    if self.mgr.mouse_grab.is_none() {
+                    if self.mgr.mouse_grab.is_none() && !self.mgr.help_mode {
                         self.tkw.set_cursor_icon(icon);
                     }
                 }
@@ -497,6 +1329,121 @@ impl<'a> Manager<'a> {
         }
     }
 
+    #[cfg(feature = "winit")]
+    fn toggle_help_mode(&mut self) {
+        if self.mgr.help_mode {
+            self.end_help_mode();
+        } else {
+            self.start_help_mode();
+        }
+    }
+
+    #[cfg(feature = "winit")]
+    fn end_help_mode(&mut self) {
+        if self.mgr.help_mode {
+            self.mgr.help_mode = false;
+            let icon = self.mgr.hover_icon;
+            self.tkw.set_cursor_icon(icon);
+        }
+    }
+
+    // Switch theme touch mode according to the device of a press, if auto
+    //
+    // The theme is shared between windows, hence it tracks the current mode.
+    #[cfg(feature = "winit")]
+    fn set_touch_input(&mut self, touch: bool) {
+        if self.mgr.touch_mode_auto {
+            self.adjust_theme(|theme| theme.set_touch_mode(touch));
+        }
+    }
+
+    // Show help for the widget under a press and end help mode
+    //
+    // Returns true if the widget (or an ancestor) has help.
+    #[cfg(feature = "winit")]
+    fn show_help(&mut self, widget: &dyn Widget, id: WidgetId) -> bool {
+        self.end_help_mode();
+        if let Some(text) = find_help(widget, id) {
+            let text = text.to_string();
+            self.add_window(Box::new(crate::widget::MessageBox::new("Help", text)));
+            true
+        } else {
+            false
+        }
+    }
+
+    // Deliver a due long press
+    //
+    // If unhandled, this shows help for the widget, if any. If either, the
+    // press is cancelled so that its release does not activate the widget.
+    #[cfg(feature = "winit")]
+    fn long_press<W>(&mut self, widget: &mut W, now: Instant)
+    where
+        W: Widget + Handler<Msg = VoidMsg> + ?Sized,
+    {
+        let (touch_id, id, coord) = match self.mgr.long_press {
+            Some((touch_id, id, coord, time)) if time <= now => (touch_id, id, coord),
+            _ => return,
+        };
+        self.mgr.long_press = None;
+
+        let source = PressSource::Touch(touch_id);
+        let handled = match self.send_event(widget, id, Event::LongPress { source, coord }) {
+            Response::Unhandled(_) => self.show_help(widget.as_widget(), id),
+            _ => true,
+        };
+        if handled {
+            if let Some(grab) = self.remove_touch(touch_id) {
+                if let Some(cur_id) = grab.cur_id {
+                    self.redraw(cur_id);
+                }
+                self.take_drag(source);
+                let event = Event::PressEnd {
+                    source,
+                    end_id: None,
+                    coord: grab.coord,
+                };
+                let _ = self.send_event(widget, grab.start_id, event);
+            }
+        }
+    }
+
+    fn add_shortcut(
+        &mut self,
+        action: &str,
+        default: Shortcut,
+        target: ShortcutTarget,
+    ) -> Result<(), ShortcutConflict> {
+        let shortcut = self.mgr.keymap.get(action).unwrap_or(default);
+        if let Some((name, _)) = self.mgr.shortcuts.get(&shortcut) {
+            if name != action {
+                warn!(
+                    "Manager: shortcut {} for {:?} conflicts with {:?}",
+                    shortcut, action, name
+                );
+                let action = name.clone();
+                return Err(ShortcutConflict { shortcut, action });
+            }
+        }
+        let mgr = &mut *self.mgr;
+        mgr.shortcuts.retain(|_, (name, _)| name != action);
+        let entry = (action.to_string(), target);
+        mgr.shortcuts.insert(shortcut, entry);
+        Ok(())
+    }
+
+    /// Get the target of the shortcut for `key`, if any
+    #[cfg(feature = "winit")]
+    fn shortcut_target(&self, key: VirtualKeyCode) -> Option<ShortcutTarget> {
+        let modifiers = self.mgr.modifiers;
+        if self.mgr.char_focus.is_some() && !(modifiers.ctrl || modifiers.alt || modifiers.logo) {
+            return None;
+        }
+        let shortcut = Shortcut { modifiers, key };
+        let entry = self.mgr.shortcuts.get(&shortcut)?;
+        Some(entry.1.clone())
+    }
+
     #[cfg(feature = "winit")]
     fn add_key_event(&mut self, scancode: u32, id: WidgetId) {
         for item in &self.mgr.key_events {
@@ -530,6 +1477,99 @@ impl<'a> Manager<'a> {
         self.mgr.mouse_grab
     }
 
+    /// Construct an [`Event::Pan`] for motion `delta` of `touch_id` to `coord`
+    ///
+    /// Where widget `id` has a second touch grabbed for panning, this includes
+    /// scale and rotation about the other touch.
+    #[cfg(feature = "winit")]
+    fn pan_event(&self, id: WidgetId, touch_id: u64, coord: Coord, delta: Coord) -> Event {
+        use std::f32::consts::PI;
+        let other = (self.mgr.touch_grab.iter())
+            .find(|grab| grab.start_id == id && grab.pan && grab.touch_id != touch_id)
+            .map(|grab| grab.coord);
+        let other = match other {
+            Some(other) => other,
+            None => {
+                return Event::Pan {
+                    coord,
+                    delta,
+                    scale: 1.0,
+                    rotation: 0.0,
+                };
+            }
+        };
+
+        let old = coord - delta;
+        let (v0, v1) = (old - other, coord - other);
+        let len = |v: Coord| ((v.0 as f32).powi(2) + (v.1 as f32).powi(2)).sqrt();
+        let angle = |v: Coord| (v.1 as f32).atan2(v.0 as f32);
+        let scale = if len(v0) > 0.0 {
+            len(v1) / len(v0)
+        } else {
+            1.0
+        };
+        let mut rotation = angle(v1) - angle(v0);
+        if rotation > PI {
+            rotation -= 2.0 * PI;
+        } else if rotation < -PI {
+            rotation += 2.0 * PI;
+        }
+        let centre = |a: Coord, b: Coord| Coord((a.0 + b.0) / 2, (a.1 + b.1) / 2);
+        let coord = centre(coord, other);
+        Event::Pan {
+            coord,
+            delta: coord - centre(old, other),
+            scale,
+            rotation,
+        }
+    }
+
+    /// Cancel a pending long press of `touch_id`
+    ///
+    /// If `coord` is given, this only cancels when moved beyond the click
+    /// distance threshold.
+    #[cfg(feature = "winit")]
+    fn cancel_long_press(&mut self, touch_id: u64, coord: Option<Coord>) {
+        if let Some((id, _, start, _)) = self.mgr.long_press {
+            let max_dist = (self.mgr.click_distance as f64 * self.mgr.dpi_factor).round() as i32;
+            let moved = coord
+                .map(|coord| {
+                    let d = coord - start;
+                    d.0.abs() > max_dist || d.1.abs() > max_dist
+                })
+                .unwrap_or(true);
+            if id == touch_id && moved {
+                self.mgr.long_press = None;
+            }
+        }
+    }
+
+    /// Count consecutive clicks, including this press
+    #[cfg(feature = "winit")]
+    fn count_click(&mut self, source: PressSource, coord: Coord) -> u32 {
+        let now = Instant::now();
+        let max_dist = (self.mgr.click_distance as f64 * self.mgr.dpi_factor).round() as i32;
+        let clicks = match self.mgr.last_click {
+            Some((last_source, time, last_coord, clicks)) => {
+                let same_source = match (source, last_source) {
+                    (PressSource::Mouse(a), PressSource::Mouse(b)) => a == b,
+                    (PressSource::Touch(_), PressSource::Touch(_)) => true,
+                    _ => false,
+                };
+                let d = coord - last_coord;
+                let near = d.0.abs() <= max_dist && d.1.abs() <= max_dist;
+                if same_source && near && now - time <= self.mgr.click_time {
+                    clicks + 1
+                } else {
+                    1
+                }
+            }
+            None => 1,
+        };
+        self.mgr.last_click = Some((source, now, coord, clicks));
+        clicks
+    }
+
     #[cfg(feature = "winit")]
     fn end_mouse_grab(&mut self, button: MouseButton) {
         if let Some(grab) = self.mgr.mouse_grab {
@@ -566,22 +1606,71 @@ impl<'a> Manager<'a> {
         None
     }
 
+    /// Deliver an [`Event::Drop`] to the widget at `coord`
+    ///
+    /// If `coord` is outside the window, the data is passed to the toolkit.
+    #[cfg(feature = "winit")]
+    fn drop_at<W>(&mut self, widget: &mut W, data: DragData, coord: Coord)
+    where
+        W: Widget + Handler<Msg = VoidMsg> + ?Sized,
+    {
+        if !widget.rect().contains(coord) {
+            self.tkw.drop_outside(data, coord);
+        } else {
+            self.handle_drop(widget, data, coord);
+        }
+    }
+
+    /// Take the drag tied to press `source`, if any
+    #[cfg(feature = "winit")]
+    fn take_drag(&mut self, source: PressSource) -> Option<DragData> {
+        match self.mgr.drag {
+            Some((drag_source, _)) if drag_source == source => self.mgr.drag.take().map(|d| d.1),
+            _ => None,
+        }
+    }
+
+    /// Queue a [`Event::PressMove`], merging with any pending move
+    #[cfg(feature = "winit")]
+    fn queue_move(&mut self, id: WidgetId, source: PressSource, coord: Coord, delta: Coord) {
+        for pending in &mut self.mgr.pending_moves {
+            if pending.id == id && pending.source == source {
+                pending.coord = coord;
+                pending.delta = pending.delta + delta;
+                return;
+            }
+        }
+        let pending = PendingMove {
+            id,
+            source,
+            coord,
+            delta,
+        };
+        self.mgr.pending_moves.push(pending);
+    }
+
     #[cfg(feature = "winit")]
     fn next_key_focus(&mut self, widget: &mut dyn Widget) {
-        let mut id = self.mgr.key_focus.unwrap_or(WidgetId::FIRST);
+        let mut id = match self.mgr.key_focus {
+            Some(id) => id.next(),
+            None => WidgetId::FIRST,
+        };
         let end = widget.id();
         loop {
-            id = id.next();
             if id >= end {
                 return self.unset_key_focus();
             }
 
             // TODO(opt): incorporate walk/find logic
             if widget.find(id).map(|w| w.allow_focus()).unwrap_or(false) {
-                self.send_action(TkAction::Redraw);
+                if let Some(old_id) = self.mgr.key_focus {
+                    self.redraw(old_id);
+                }
+                self.redraw(id);
                 self.mgr.key_focus = Some(id);
                 return;
             }
+            id = id.next();
         }
     }
 
@@ -603,49 +1692,177 @@ impl<'a> Manager<'a> {
     }
 
     /// Update widgets due to timer
-    pub fn update_timer<W: Widget + ?Sized>(&mut self, widget: &mut W) {
+    pub fn update_timer<W>(&mut self, widget: &mut W)
+    where
+        W: Widget + Handler<Msg = VoidMsg> + ?Sized,
+    {
+        if let Some(id) = self.mgr.char_focus {
+            let shown = self.mgr.caret_phase();
+            if shown != self.mgr.caret_shown {
+                self.mgr.caret_shown = shown;
+                self.redraw(id);
+            }
+        }
+
         let now = Instant::now();
 
-        // assumption: time_updates are sorted
-        let mut i = 0;
-        while i < self.mgr.time_updates.len() {
-            if self.mgr.time_updates[i].0 > now {
-                break;
-            }
+        #[cfg(feature = "winit")]
+        self.long_press(widget, now);
 
-            let w_id = self.mgr.time_updates[i].1;
+        // assumption: time_updates are sorted
+        // Due updates are removed before delivery, so that widgets may
+        // schedule or cancel updates from Widget::update_timer.
+        let updates = &mut self.mgr.time_updates;
+        let due = updates.iter().take_while(|row| row.0 <= now).count();
+        let due: Vec<_> = updates.drain(..due).collect();
+        for (_, w_id) in due {
             trace!("Updating widget {} via timer", w_id);
             let dur = widget.find_mut(w_id).and_then(|w| w.update_timer(self));
             if let Some(dur) = dur {
                 assert!(dur > Duration::new(0, 0));
-                self.mgr.time_updates[i].0 = now + dur;
-                i += 1;
-            } else {
-                self.mgr.time_updates.remove(i);
+                self.update_on_timer(dur, w_id);
             }
         }
 
-        self.mgr.time_updates.sort_by_key(|row| row.0);
+        let events = &mut self.mgr.timer_events;
+        let due = events.iter().take_while(|row| row.0 <= now).count();
+        let due: Vec<_> = events.drain(..due).collect();
+        for (_, w_id) in due {
+            trace!("Sending timer event to widget {}", w_id);
+            let _ = self.send_event(widget, w_id, Event::Timer);
+        }
+    }
+
+    /// Deliver input method pre-edit text
+    ///
+    /// This sends an [`Action::ImePreedit`] to the widget with character
+    /// focus, if any.
+    ///
+    /// Note: `kas-wgpu` does not call this method since `winit` 0.21 does not
+    /// report input method events; composed text is received only as
+    /// committed characters ([`Action::ReceivedCharacter`]).
+    pub fn ime_preedit<W>(&mut self, widget: &mut W, text: String)
+    where
+        W: Widget + Handler<Msg = VoidMsg> + ?Sized,
+    {
+        self.send_char_focus(widget, Action::ImePreedit(text));
+    }
+
+    /// Deliver text committed by an input method
+    ///
+    /// This sends an [`Action::ImeCommit`] to the widget with character
+    /// focus, if any.
+    ///
+    /// Note: `kas-wgpu` does not call this method (see [`Manager::ime_preedit`]).
+    pub fn ime_commit<W>(&mut self, widget: &mut W, text: String)
+    where
+        W: Widget + Handler<Msg = VoidMsg> + ?Sized,
+    {
+        self.send_char_focus(widget, Action::ImeCommit(text));
+    }
+
+    fn send_char_focus<W>(&mut self, widget: &mut W, action: Action)
+    where
+        W: Widget + Handler<Msg = VoidMsg> + ?Sized,
+    {
+        if let Some(id) = self.mgr.char_focus {
+            self.redraw(id);
+            let _ = self.send_event(widget, id, Event::Action(action));
+        }
+    }
+
+    /// Deliver data dropped from another window
+    ///
+    /// Delivers an [`Event::Drop`] to the widget at `coord` (see
+    /// [`TkWindow::drop_outside`]).
+    pub fn handle_drop<W>(&mut self, widget: &mut W, data: DragData, coord: Coord)
+    where
+        W: Widget + Handler<Msg = VoidMsg> + ?Sized,
+    {
+        if let Some(id) = widget.find_id(coord) {
+            self.redraw(id);
+            let _ = self.send_event(widget, id, Event::Drop { data, coord });
+        }
     }
 
     /// Update widgets due to handle
-    pub fn update_handle<W: Widget + ?Sized>(
-        &mut self,
-        widget: &mut W,
-        handle: UpdateHandle,
-        payload: u64,
-    ) {
+    pub fn update_handle<W>(&mut self, widget: &mut W, handle: UpdateHandle, payload: u64)
+    where
+        W: Widget + Handler<Msg = VoidMsg> + ?Sized,
+    {
         // NOTE: to avoid borrow conflict, we must clone values!
         if let Some(mut values) = self.mgr.handle_updates.get(&handle).cloned() {
             for w_id in values.drain(..) {
                 trace!("Updating widget {} via {:?}", w_id, handle);
-                if let Some(w) = widget.find_mut(w_id) {
-                    w.update_handle(self, handle, payload);
+                if widget.find(w_id).is_none() {
+                    continue;
                 }
+                let _ = self.send_event(widget, w_id, Event::HandleUpdate { handle, payload });
+                self.redraw(w_id);
             }
         }
     }
 
+    /// Deliver coalesced input events
+    ///
+    /// High-frequency events ([`Event::PressMove`] and [`Action::Scroll`]) are
+    /// not delivered immediately by [`Manager::handle_winit`]; instead
+    /// successive events to the same target are merged, keeping the latest
+    /// coordinate and accumulating deltas. Other input flushes pending events
+    /// first, thus event order is preserved.
+    ///
+    /// The toolkit should call this once per frame, after handling all
+    /// available input and before drawing.
+    pub fn flush_pending<W>(&mut self, widget: &mut W)
+    where
+        W: Widget + Handler<Msg = VoidMsg> + ?Sized,
+    {
+        for pending in std::mem::take(&mut self.mgr.pending_moves) {
+            let ev = Event::PressMove {
+                source: pending.source,
+                coord: pending.coord,
+                delta: pending.delta,
+            };
+            let _ = self.send_event(widget, pending.id, ev);
+        }
+        if let Some((id, delta)) = self.mgr.pending_scroll.take() {
+            let _ = self.send_event(widget, id, Event::Action(Action::Scroll(delta)));
+        }
+        for (id, msg) in std::mem::take(&mut self.mgr.sent_msgs) {
+            trace!("Delivering message to widget {}", id);
+            let _ = self.send_event(widget, id, Event::Message(msg));
+        }
+        if let Some(data) = self.mgr.pending_drop.take() {
+            // Files dropped by the OS always target this window, but the
+            // cursor position is not reported during an OS drag on some
+            // platforms. Where unknown, we target the focussed widget.
+            let coord = self.mgr.last_mouse_coord;
+            let id = widget
+                .find_id(coord)
+                .or(self.mgr.char_focus)
+                .or(self.mgr.key_focus)
+                .unwrap_or(widget.id());
+            self.redraw(id);
+            let _ = self.send_event(widget, id, Event::Drop { data, coord });
+        }
+    }
+
+    // Update the state of keyboard modifiers, as reported with input events
+    #[cfg(feature = "winit")]
+    fn update_modifiers(&mut self, state: winit::event::ModifiersState) {
+        let modifiers = Modifiers {
+            shift: state.shift(),
+            ctrl: state.ctrl(),
+            alt: state.alt(),
+            logo: state.logo(),
+        };
+        if modifiers.alt != self.mgr.modifiers.alt {
+            // Show or hide accelerator key underlines
+            self.send_action(TkAction::Redraw);
+        }
+        self.mgr.modifiers = modifiers;
+    }
+
     /// Handle a winit `WindowEvent`.
     ///
     /// Note that some event types are not *does not* handled, since for these
@@ -659,6 +1876,14 @@ impl<'a> Manager<'a> {
         use winit::event::{ElementState, MouseScrollDelta, TouchPhase, WindowEvent::*};
         trace!("Event: {:?}", event);
 
+        // Events which may be coalesced are queued; others must not overtake
+        // queued events.
+        match event {
+            CursorMoved { .. } | MouseWheel { .. } | DroppedFile(_) => (),
+            Touch(ref touch) if touch.phase == TouchPhase::Moved => (),
+            _ => self.flush_pending(widget),
+        }
+
         let response = match event {
             // Resized(size) [handled by toolkit]
             // Moved(position)
@@ -667,22 +1892,66 @@ impl<'a> Manager<'a> {
                 Response::None
             }
             // Destroyed
-            // DroppedFile(PathBuf),
-            // HoveredFile(PathBuf),
-            // HoveredFileCancelled,
+            DroppedFile(path) => {
+                // Files are dropped one at a time; we deliver them together
+                self.mgr.os_drag = None;
+                self.mgr.pending_drop.get_or_insert_with(DragData::new).push_path(&path);
+                Response::None
+            }
+            HoveredFile(path) => {
+                self.mgr.os_drag.get_or_insert_with(DragData::new).push_path(&path);
+                if let Some(id) = self.mgr.hover {
+                    self.redraw(id);
+                }
+                Response::None
+            }
+            HoveredFileCancelled => {
+                self.mgr.os_drag = None;
+                if let Some(id) = self.mgr.hover {
+                    self.redraw(id);
+                }
+                Response::None
+            }
             ReceivedCharacter(c) if c != '\u{1b}' /* escape */ => {
                 if let Some(id) = self.mgr.char_focus {
                     let ev = Event::Action(Action::ReceivedCharacter(c));
-                    widget.handle(&mut self, id, ev)
+                    self.send_event(widget, id, ev)
                 } else {
+                    trace_detail!("Dropping character {:?}: no widget has char focus", c);
                     Response::None
                 }
             }
             // Focused(bool),
+            // We do not receive DeviceEvent::ModifiersChanged per-window
+            #[allow(deprecated)]
             KeyboardInput { input, is_synthetic, .. } => {
+                self.update_modifiers(input.modifiers);
                 let char_focus = self.mgr.char_focus.is_some();
-                match (input.scancode, input.state, input.virtual_keycode) {
-                    (_, ElementState::Pressed, Some(vkey)) if char_focus && !is_synthetic => match vkey {
+                let shortcut = match (input.state, input.virtual_keycode) {
+                    (ElementState::Pressed, Some(vkey)) if !is_synthetic => self.shortcut_target(vkey),
+                    _ => None,
+                };
+                match (input.scancode, input.state, input.virtual_keycode, shortcut) {
+                    (scancode, _, _, Some(target)) => match target {
+                        ShortcutTarget::Widget(id) => {
+                            self.add_key_event(scancode, id);
+                            let ev = Event::Action(Action::Activate);
+                            self.send_event(widget, id, ev)
+                        }
+                        ShortcutTarget::Msg(msg) => {
+                            self.tkw.push_msg(msg);
+                            Response::None
+                        }
+                    },
+                    (scancode, ElementState::Pressed, Some(vkey), None) if char_focus && !is_synthetic => match vkey {
+                        VirtualKeyCode::F1 if self.mgr.modifiers.shift => {
+                            self.toggle_help_mode();
+                            Response::None
+                        }
+                        VirtualKeyCode::Escape if self.mgr.help_mode => {
+                            self.end_help_mode();
+                            Response::None
+                        }
                         VirtualKeyCode::Escape => {
                             if let Some(id) = self.mgr.char_focus {
                                 self.redraw(id);
@@ -690,9 +1959,21 @@ impl<'a> Manager<'a> {
                             self.mgr.char_focus = None;
                             Response::None
                         }
-                        _ => Response::None,
+                        vkey if self.mgr.modifiers.alt && self.mgr.accel_keys.contains_key(&vkey) => {
+                            let id = self.mgr.accel_keys[&vkey];
+                            self.add_key_event(scancode, id);
+                            let ev = Event::Action(Action::Activate);
+                            self.send_event(widget, id, ev)
+                        }
+                        vkey => match (control_key(vkey), self.mgr.char_focus) {
+                            (Some(key), Some(id)) => {
+                                let ev = Event::Action(Action::ControlKey(key));
+                                self.send_event(widget, id, ev)
+                            }
+                            _ => Response::None,
+                        },
                     },
-                    (scancode, ElementState::Pressed, Some(vkey)) if !char_focus && !is_synthetic => match vkey {
+                    (scancode, ElementState::Pressed, Some(vkey), None) if !char_focus && !is_synthetic => match vkey {
                         VirtualKeyCode::Tab => {
                             self.next_key_focus(widget.as_widget_mut());
                             Response::None
@@ -703,9 +1984,17 @@ impl<'a> Manager<'a> {
                                 self.add_key_event(scancode, id);
 
                                 let ev = Event::Action(Action::Activate);
-                                widget.handle(&mut self, id, ev)
+                                self.send_event(widget, id, ev)
                             } else { Response::None }
                         }
+                        VirtualKeyCode::F1 if self.mgr.modifiers.shift => {
+                            self.toggle_help_mode();
+                            Response::None
+                        }
+                        VirtualKeyCode::Escape if self.mgr.help_mode => {
+                            self.end_help_mode();
+                            Response::None
+                        }
                         VirtualKeyCode::Escape => {
                             self.unset_key_focus();
                             Response::None
@@ -716,11 +2005,17 @@ impl<'a> Manager<'a> {
                                 self.add_key_event(scancode, id);
 
                                 let ev = Event::Action(Action::Activate);
-                                widget.handle(&mut self, id, ev)
-                            } else { Response::None }
+                                self.send_event(widget, id, ev)
+                            } else if let (Some(key), Some(id)) = (control_key(vkey), self.mgr.key_focus) {
+                                let ev = Event::Action(Action::ControlKey(key));
+                                self.send_event(widget, id, ev)
+                            } else {
+                                trace_detail!("Dropping key {:?}: no accelerator key binding", vkey);
+                                Response::None
+                            }
                         }
                     },
-                    (scancode, ElementState::Released, _) => {
+                    (scancode, ElementState::Released, _, None) => {
                         self.remove_key_event(scancode);
                         Response::None
                     }
@@ -736,18 +2031,15 @@ impl<'a> Manager<'a> {
                 // Update hovered widget
                 self.set_hover(widget, widget.find_id(coord));
 
-                let r = if let Some((grab_id, button)) = self.mouse_grab() {
+                if let Some((grab_id, button)) = self.mouse_grab() {
                     let source = PressSource::Mouse(button);
                     let delta = coord - self.mgr.last_mouse_coord;
-                    let ev = Event::PressMove { source, coord, delta };
-                    widget.handle(&mut self, grab_id, ev)
-                } else {
-                    // We don't forward move events without a grab
-                    Response::None
-                };
+                    self.queue_move(grab_id, source, coord, delta);
+                }
+                // We don't forward move events without a grab
 
                 self.mgr.last_mouse_coord = coord;
-                r
+                Response::None
             }
             // CursorEntered { .. },
             CursorLeft { .. } => {
@@ -757,49 +2049,70 @@ impl<'a> Manager<'a> {
                 Response::None
             }
             MouseWheel { delta, .. } => {
-                let action = Action::Scroll(match delta {
+                let delta = match delta {
                     MouseScrollDelta::LineDelta(x, y) => ScrollDelta::LineDelta(x, y),
                     MouseScrollDelta::PixelDelta(pos) =>
                         ScrollDelta::PixelDelta(Coord::from_logical(pos, self.mgr.dpi_factor)),
-                });
+                };
                 if let Some(id) = self.mgr.hover {
-                    widget.handle(&mut self, id, Event::Action(action))
-                } else {
-                    Response::None
+                    let merged = match self.mgr.pending_scroll {
+                        Some((pending_id, pending)) if pending_id == id => merge_scroll(pending, delta),
+                        _ => None,
+                    };
+                    if merged.is_none() {
+                        self.flush_pending(widget);
+                    }
+                    self.mgr.pending_scroll = Some((id, merged.unwrap_or(delta)));
                 }
+                Response::None
             }
+            #[allow(deprecated)]
             MouseInput {
                 state,
                 button,
+                modifiers,
                 ..
             } => {
+                self.update_modifiers(modifiers);
                 let coord = self.mgr.last_mouse_coord;
                 let source = PressSource::Mouse(button);
+                let mut clicks = 0;
+                if state == ElementState::Pressed {
+                    self.set_touch_input(false);
+                    clicks = self.count_click(source, coord);
+                }
 
                 if let Some((grab_id, _)) = self.mouse_grab() {
                     // Mouse grab active: send events there
                     let ev = match state {
-                        ElementState::Pressed => Event::PressStart { source, coord },
+                        ElementState::Pressed => Event::PressStart { source, coord, clicks },
                         ElementState::Released => Event::PressEnd {
                             source,
                             end_id: self.mgr.hover,
                             coord,
                         },
                     };
-                    let r = widget.handle(&mut self, grab_id, ev);
+                    let r = self.send_event(widget, grab_id, ev);
                     if state == ElementState::Released {
                         self.end_mouse_grab(button);
+                        if let Some(data) = self.take_drag(source) {
+                            self.drop_at(widget, data, coord);
+                        }
                     }
                     r
                 } else if let Some(id) = self.mgr.hover {
                     // No mouse grab but have a hover target
-                    if state == ElementState::Pressed {
-                        let ev = Event::PressStart { source, coord };
-                        widget.handle(&mut self, id, ev)
+                    if state == ElementState::Pressed && self.mgr.help_mode {
+                        self.show_help(widget.as_widget(), id);
+                        Response::None
+                    } else if state == ElementState::Pressed {
+                        let ev = Event::PressStart { source, coord, clicks };
+                        self.send_event(widget, id, ev)
                     } else {
                         Response::None
                     }
                 } else {
+                    trace_detail!("Dropping mouse input at {:?}: no widget under cursor", coord);
                     Response::None
                 }
             }
@@ -811,25 +2124,29 @@ impl<'a> Manager<'a> {
                 let coord = touch.location.into();
                 match touch.phase {
                     TouchPhase::Started => {
+                        self.set_touch_input(true);
+                        let clicks = self.count_click(source, coord);
                         if let Some(id) = widget.find_id(coord) {
-                            let ev = Event::PressStart { source, coord };
-                            widget.handle(&mut self, id, ev)
+                            if self.mgr.help_mode {
+                                self.show_help(widget.as_widget(), id);
+                                Response::None
+                            } else {
+                                let time = Instant::now() + LONG_PRESS;
+                                self.mgr.long_press = Some((touch.id, id, coord, time));
+                                let ev = Event::PressStart { source, coord, clicks };
+                                self.send_event(widget, id, ev)
+                            }
                         } else {
                             Response::None
                         }
                     }
                     TouchPhase::Moved => {
-                        // NOTE: calling widget.handle twice appears
-                        // to be unavoidable (as with CursorMoved)
                         let cur_id = widget.find_id(coord);
+                        self.cancel_long_press(touch.id, Some(coord));
 
                         let r = self.get_touch(touch.id).map(|grab| {
                             let id = grab.start_id;
-                            let action = Event::PressMove {
-                                source,
-                                coord,
-                                delta: coord - grab.coord,
-                            };
+                            let delta = coord - grab.coord;
                             // Only when 'depressed' status changes:
                             let redraw = grab.cur_id != cur_id &&
                                 (grab.cur_id == Some(grab.start_id) || cur_id == Some(grab.start_id));
@@ -837,19 +2154,27 @@ impl<'a> Manager<'a> {
                             grab.cur_id = cur_id;
                             grab.coord = coord;
 
-                            (id, action, redraw)
+                            (id, delta, redraw, grab.pan)
                         });
 
-                        if let Some((id, action, redraw)) = r {
-                            if redraw {
-                                self.send_action(TkAction::Redraw);
+                        match r {
+                            Some((id, delta, redraw, pan)) => {
+                                if redraw {
+                                    self.redraw(id);
+                                }
+                                if pan {
+                                    let event = self.pan_event(id, touch.id, coord, delta);
+                                    self.send_event(widget, id, event)
+                                } else {
+                                    self.queue_move(id, source, coord, delta);
+                                    Response::None
+                                }
                             }
-                            widget.handle(&mut self, id, action)
-                        } else {
-                            Response::None
+                            None => Response::None,
                         }
                     }
                     TouchPhase::Ended => {
+                        self.cancel_long_press(touch.id, None);
                         if let Some(grab) = self.remove_touch(touch.id) {
                             let action = Event::PressEnd {
                                 source,
@@ -859,12 +2184,17 @@ impl<'a> Manager<'a> {
                             if let Some(cur_id) = grab.cur_id {
                                 self.redraw(cur_id);
                             }
-                            widget.handle(&mut self, grab.start_id, action)
+                            let r = self.send_event(widget, grab.start_id, action);
+                            if let Some(data) = self.take_drag(source) {
+                                self.drop_at(widget, data, coord);
+                            }
+                            r
                         } else {
                             Response::None
                         }
                     }
                     TouchPhase::Cancelled => {
+                        self.cancel_long_press(touch.id, None);
                         if let Some(grab) = self.remove_touch(touch.id) {
                             let action = Event::PressEnd {
                                 source,
@@ -874,7 +2204,9 @@ impl<'a> Manager<'a> {
                             if let Some(cur_id) = grab.cur_id {
                                 self.redraw(cur_id);
                             }
-                            widget.handle(&mut self, grab.start_id, action)
+                            // A cancelled press does not drop anything
+                            self.take_drag(source);
+                            self.send_event(widget, grab.start_id, action)
                         } else {
                             Response::None
                         }
@@ -887,8 +2219,9 @@ impl<'a> Manager<'a> {
 
         match response {
             Response::None => (),
-            Response::Unhandled(_) => {
+            Response::Unhandled(_event) => {
                 // we can safely ignore unhandled events here
+                trace_detail!("Event {:?} was not handled", _event);
             }
             Response::Msg(_) => unreachable!(),
         };
@@ -896,3 +2229,88 @@ impl<'a> Manager<'a> {
         self.unwrap_action()
     }
 }
+
+/// Find help text of widget `id` or its nearest ancestor with help
+#[cfg(feature = "winit")]
+fn find_help(widget: &dyn Widget, id: WidgetId) -> Option<&str> {
+    if id > widget.id() {
+        return None;
+    } else if id < widget.id() {
+        for i in 0..widget.len() {
+            if let Some(w) = widget.get(i) {
+                if id > w.id() {
+                    continue;
+                }
+                if let Some(text) = find_help(w, id) {
+                    return Some(text);
+                }
+            }
+            break;
+        }
+    }
+    widget.help()
+}
+
+/// Find the rect of widget `id` in the coordinates of `widget`, clipped to the
+/// region in which it is drawn
+fn visible_rect(widget: &dyn Widget, id: WidgetId) -> Option<Rect> {
+    if id == widget.id() {
+        return Some(widget.rect());
+    }
+    let child = (0..widget.len())
+        .filter_map(|i| widget.get(i))
+        .find(|w| id <= w.id())?;
+    let rect = visible_rect(child, id)?;
+    match widget.child_region(child.id()) {
+        Some((clip, transform)) => transform.map_rect(rect).intersection(clip),
+        None => Some(rect),
+    }
+}
+
+/// Merge two scroll deltas, if of the same kind
+#[cfg(feature = "winit")]
+fn merge_scroll(a: ScrollDelta, b: ScrollDelta) -> Option<ScrollDelta> {
+    use ScrollDelta::{LineDelta, PixelDelta};
+    match (a, b) {
+        (LineDelta(x0, y0), LineDelta(x1, y1)) => Some(LineDelta(x0 + x1, y0 + y1)),
+        (PixelDelta(d0), PixelDelta(d1)) => Some(PixelDelta(d0 + d1)),
+        _ => None,
+    }
+}
+
+/// Translate navigation keys
+#[cfg(feature = "winit")]
+fn control_key(vkey: VirtualKeyCode) -> Option<ControlKey> {
+    Some(match vkey {
+        VirtualKeyCode::Left => ControlKey::Left,
+        VirtualKeyCode::Right => ControlKey::Right,
+        VirtualKeyCode::Up => ControlKey::Up,
+        VirtualKeyCode::Down => ControlKey::Down,
+        VirtualKeyCode::Home => ControlKey::Home,
+        VirtualKeyCode::End => ControlKey::End,
+        VirtualKeyCode::PageUp => ControlKey::PageUp,
+        VirtualKeyCode::PageDown => ControlKey::PageDown,
+        _ => return None,
+    })
+}
+
+/// Add or update a scheduled `time` for `w_id`, keeping the earlier time
+fn schedule(queue: &mut Vec<(Instant, WidgetId)>, time: Instant, w_id: WidgetId) {
+    'outer: loop {
+        for row in queue.iter_mut() {
+            if row.1 == w_id {
+                if row.0 <= time {
+                    return;
+                } else {
+                    row.0 = time;
+                    break 'outer;
+                }
+            }
+        }
+
+        queue.push((time, w_id));
+        break;
+    }
+
+    queue.sort_by_key(|row| row.0);
+}
@@ -52,15 +52,23 @@
 //! Highlighting information can be obtained directly in the `draw` method, and
 //! press events provide information on their start and end widget.
 //!
+//! ## Drag and drop
+//!
+//! A widget holding a press grab may start a drag via [`Manager::start_drag`].
+//! When the press ends, an [`Event::Drop`] is delivered to the widget under the
+//! pointer. Files dragged from other applications are delivered likewise.
+//!
 //! [`WidgetId`]: crate::WidgetId
 
 mod callback;
+mod drag;
 #[cfg(not(feature = "winit"))]
 mod enums;
 mod events;
 mod handler;
 mod manager;
 mod response;
+mod shortcut;
 mod update;
 
 use std::fmt::Debug;
@@ -72,12 +80,14 @@ pub use winit::event::{MouseButton, VirtualKeyCode};
 pub use winit::window::CursorIcon;
 
 pub use callback::Callback;
+pub use drag::{DragData, MIME_TEXT, MIME_URI_LIST};
 #[cfg(not(feature = "winit"))]
 pub use enums::{CursorIcon, MouseButton, VirtualKeyCode};
 pub use events::*;
 pub use handler::Handler;
 pub use manager::{HighlightState, Manager, ManagerState};
 pub use response::Response;
+pub use shortcut::{Keymap, Shortcut, ShortcutConflict};
 pub use update::UpdateHandle;
 
 /// A void message
@@ -0,0 +1,334 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Keyboard shortcuts
+
+use log::warn;
+use std::collections::HashMap;
+use std::fmt;
+
+use super::{Modifiers, VirtualKeyCode};
+
+/// A keyboard shortcut: a key combined with modifiers
+///
+/// Shortcuts may be written (see [`Shortcut::parse`]) and displayed as text,
+/// for example `Ctrl+S` or `Ctrl+Shift+Z`.
+///
+/// With the `serde` feature, shortcuts are serialised in this text form.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Shortcut {
+    pub modifiers: Modifiers,
+    pub key: VirtualKeyCode,
+}
+
+impl Shortcut {
+    /// Construct, without modifiers
+    pub fn new(key: VirtualKeyCode) -> Self {
+        let modifiers = Modifiers::default();
+        Shortcut { modifiers, key }
+    }
+
+    /// Construct with the `Ctrl` modifier
+    pub fn ctrl(key: VirtualKeyCode) -> Self {
+        Shortcut::new(key).with_ctrl()
+    }
+
+    /// Add the `Ctrl` modifier (chain style)
+    pub fn with_ctrl(mut self) -> Self {
+        self.modifiers.ctrl = true;
+        self
+    }
+
+    /// Add the `Shift` modifier (chain style)
+    pub fn with_shift(mut self) -> Self {
+        self.modifiers.shift = true;
+        self
+    }
+
+    /// Add the `Alt` modifier (chain style)
+    pub fn with_alt(mut self) -> Self {
+        self.modifiers.alt = true;
+        self
+    }
+
+    /// Add the `Logo` modifier (chain style)
+    pub fn with_logo(mut self) -> Self {
+        self.modifiers.logo = true;
+        self
+    }
+
+    /// Parse a shortcut
+    ///
+    /// The format is zero or more modifiers (`Ctrl`, `Shift`, `Alt` or `Logo`)
+    /// followed by a key name, separated by `+`. Parsing is case-insensitive.
+    /// Key names are those of [`VirtualKeyCode`], except that digit keys may
+    /// also be written without the `Key` prefix (e.g. `Ctrl+1`).
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut parts = s.split('+').map(|part| part.trim());
+        let key = key_for_name(parts.next_back()?)?;
+        let mut shortcut = Shortcut::new(key);
+        for part in parts {
+            let m = &mut shortcut.modifiers;
+            match part.to_ascii_lowercase().as_str() {
+                "ctrl" => m.ctrl = true,
+                "shift" => m.shift = true,
+                "alt" => m.alt = true,
+                "logo" => m.logo = true,
+                _ => return None,
+            }
+        }
+        Some(shortcut)
+    }
+}
+
+impl From<VirtualKeyCode> for Shortcut {
+    fn from(key: VirtualKeyCode) -> Self {
+        Shortcut::new(key)
+    }
+}
+
+impl fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let m = self.modifiers;
+        for (set, name) in &[
+            (m.ctrl, "Ctrl+"),
+            (m.shift, "Shift+"),
+            (m.alt, "Alt+"),
+            (m.logo, "Logo+"),
+        ] {
+            if *set {
+                f.write_str(name)?;
+            }
+        }
+        write!(f, "{:?}", self.key)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Shortcut {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Shortcut {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::{Error, Unexpected};
+        let s = String::deserialize(deserializer)?;
+        Shortcut::parse(&s).ok_or_else(|| Error::invalid_value(Unexpected::Str(&s), &"a shortcut"))
+    }
+}
+
+fn key_for_name(name: &str) -> Option<VirtualKeyCode> {
+    use VirtualKeyCode::*;
+    // All keys, such that any shortcut may be written and parsed
+    #[rustfmt::skip]
+    const KEYS: &[VirtualKeyCode] = &[
+        Key1, Key2, Key3, Key4, Key5, Key6, Key7, Key8, Key9, Key0, A, B, C, D, E, F, G, H, I, J, K,
+        L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, Escape, F1, F2, F3, F4, F5, F6, F7, F8, F9,
+        F10, F11, F12, F13, F14, F15, F16, F17, F18, F19, F20, F21, F22, F23, F24, Snapshot, Scroll,
+        Pause, Insert, Home, Delete, End, PageDown, PageUp, Left, Up, Right, Down, Back, Return,
+        Space, Compose, Caret, Numlock, Numpad0, Numpad1, Numpad2, Numpad3, Numpad4, Numpad5,
+        Numpad6, Numpad7, Numpad8, Numpad9, AbntC1, AbntC2, Add, Apostrophe, Apps, At, Ax,
+        Backslash, Calculator, Capital, Colon, Comma, Convert, Decimal, Divide, Equals, Grave, Kana,
+        Kanji, LAlt, LBracket, LControl, LShift, LWin, Mail, MediaSelect, MediaStop, Minus,
+        Multiply, Mute, MyComputer, NavigateForward, NavigateBackward, NextTrack, NoConvert,
+        NumpadComma, NumpadEnter, NumpadEquals, OEM102, Period, PlayPause, Power, PrevTrack, RAlt,
+        RBracket, RControl, RShift, RWin, Semicolon, Slash, Sleep, Stop, Subtract, Sysrq, Tab,
+        Underline, Unlabeled, VolumeDown, VolumeUp, Wake, WebBack, WebFavorites, WebForward,
+        WebHome, WebRefresh, WebSearch, WebStop, Yen, Copy, Paste, Cut,
+    ];
+    let name = if name.len() == 1 && name.as_bytes()[0].is_ascii_digit() {
+        format!("Key{}", name)
+    } else {
+        name.to_string()
+    };
+    KEYS.iter()
+        .cloned()
+        .find(|key| format!("{:?}", key).eq_ignore_ascii_case(&name))
+}
+
+/// A user-configurable keymap
+///
+/// A keymap binds named application actions to shortcuts, overriding the
+/// defaults given to [`Manager::register_shortcut`]. Toolkits may load a
+/// keymap from user configuration.
+///
+/// The text format (see [`Keymap::parse`]) has one binding per line, e.g.:
+/// ```text
+/// # Comment
+/// save = Ctrl+S
+/// redo = Ctrl+Y
+/// ```
+///
+/// [`Manager::register_shortcut`]: super::Manager::register_shortcut
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Keymap {
+    bindings: HashMap<String, Shortcut>,
+}
+
+impl Keymap {
+    /// Construct an empty keymap
+    pub fn new() -> Self {
+        Keymap::default()
+    }
+
+    /// Parse a keymap from text
+    ///
+    /// Empty lines and lines starting with `#` are ignored. Other lines have
+    /// the form `action = shortcut` (see [`Shortcut::parse`]). Invalid lines
+    /// are skipped with a warning.
+    pub fn parse(text: &str) -> Self {
+        let mut keymap = Keymap::new();
+        for (n, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut parts = line.splitn(2, '=');
+            let action = parts.next().unwrap().trim();
+            match parts.next().and_then(Shortcut::parse) {
+                Some(shortcut) if !action.is_empty() => keymap.bind(action, shortcut),
+                _ => warn!("Keymap: invalid binding on line {}: {}", n + 1, line),
+            }
+        }
+        keymap
+    }
+
+    /// Bind `action` to `shortcut`
+    ///
+    /// This replaces any existing binding for `action`.
+    pub fn bind<S: Into<String>>(&mut self, action: S, shortcut: Shortcut) {
+        self.bindings.insert(action.into(), shortcut);
+    }
+
+    /// Remove the binding for `action`, if any
+    pub fn unbind(&mut self, action: &str) -> Option<Shortcut> {
+        self.bindings.remove(action)
+    }
+
+    /// Get the shortcut bound to `action`, if any
+    pub fn get(&self, action: &str) -> Option<Shortcut> {
+        self.bindings.get(action).cloned()
+    }
+
+    /// Iterate over all bindings
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Shortcut)> {
+        self.bindings.iter().map(|(k, v)| (k.as_str(), *v))
+    }
+}
+
+impl fmt::Display for Keymap {
+    /// Write in the format read by [`Keymap::parse`]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut bindings: Vec<_> = self.iter().collect();
+        bindings.sort_by_key(|binding| binding.0);
+        for (action, shortcut) in bindings {
+            writeln!(f, "{} = {}", action, shortcut)?;
+        }
+        Ok(())
+    }
+}
+
+/// Error returned when a shortcut is already registered to another action
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShortcutConflict {
+    /// The shortcut
+    pub shortcut: Shortcut,
+    /// The action to which the shortcut is registered
+    pub action: String,
+}
+
+impl fmt::Display for ShortcutConflict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "shortcut {} is already registered to action {:?}",
+            self.shortcut, self.action
+        )
+    }
+}
+
+impl std::error::Error for ShortcutConflict {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_shortcut() {
+        use VirtualKeyCode::*;
+        assert_eq!(Shortcut::parse("S"), Some(Shortcut::new(S)));
+        assert_eq!(Shortcut::parse("ctrl+s"), Some(Shortcut::ctrl(S)));
+        assert_eq!(
+            Shortcut::parse(" Ctrl + Shift + Z "),
+            Some(Shortcut::ctrl(Z).with_shift())
+        );
+        assert_eq!(
+            Shortcut::parse("Alt+1"),
+            Some(Shortcut::new(Key1).with_alt())
+        );
+        assert_eq!(
+            Shortcut::parse("Logo+PageDown"),
+            Some(Shortcut::new(PageDown).with_logo())
+        );
+    }
+
+    #[test]
+    fn parse_shortcut_errors() {
+        assert_eq!(Shortcut::parse(""), None);
+        assert_eq!(Shortcut::parse("Ctrl+"), None);
+        assert_eq!(Shortcut::parse("Ctrl"), None);
+        assert_eq!(Shortcut::parse("Hyper+S"), None);
+        assert_eq!(Shortcut::parse("Ctrl+NoSuchKey"), None);
+        assert_eq!(Shortcut::parse("S+Ctrl"), None);
+    }
+
+    #[test]
+    fn shortcut_round_trip() {
+        use VirtualKeyCode::*;
+        for key in &[A, Key0, F12, Numpad5, NavigateBackward, LBracket, Cut] {
+            let shortcuts = [
+                Shortcut::new(*key),
+                Shortcut::ctrl(*key).with_alt(),
+                Shortcut::new(*key).with_shift().with_logo(),
+            ];
+            for shortcut in &shortcuts {
+                let text = shortcut.to_string();
+                assert_eq!(Shortcut::parse(&text), Some(*shortcut), "{}", text);
+            }
+        }
+    }
+
+    #[test]
+    fn keymap_parse() {
+        let keymap = Keymap::parse(
+            "# Comment\n\nsave = Ctrl+S\n  redo=Ctrl+Y  \nbad = Ctrl+Foo\n= Ctrl+Q\nnoequals\n",
+        );
+        assert_eq!(keymap.iter().count(), 2);
+        assert_eq!(keymap.get("save"), Some(Shortcut::ctrl(VirtualKeyCode::S)));
+        assert_eq!(keymap.get("redo"), Some(Shortcut::ctrl(VirtualKeyCode::Y)));
+        assert_eq!(keymap.get("bad"), None);
+    }
+
+    #[test]
+    fn keymap_round_trip() {
+        let mut keymap = Keymap::new();
+        keymap.bind("save", Shortcut::ctrl(VirtualKeyCode::S));
+        keymap.bind("undo", Shortcut::ctrl(VirtualKeyCode::Z));
+        keymap.bind("redo", Shortcut::ctrl(VirtualKeyCode::Z).with_shift());
+        let text = keymap.to_string();
+        assert_eq!(text, "redo = Ctrl+Shift+Z\nsave = Ctrl+S\nundo = Ctrl+Z\n");
+        assert_eq!(Keymap::parse(&text), keymap);
+
+        assert_eq!(
+            keymap.unbind("undo"),
+            Some(Shortcut::ctrl(VirtualKeyCode::Z))
+        );
+        assert_eq!(keymap.unbind("undo"), None);
+    }
+}
@@ -29,7 +29,10 @@ impl UpdateHandle {
             let nz = NonZeroU32::new(h).unwrap_or_else(|| {
                 panic!("UpdateHandle::new: all available handles have been issued")
             });
-            if COUNT.compare_and_swap(c, h, Ordering::Relaxed) == c {
+            if COUNT
+                .compare_exchange_weak(c, h, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok()
+            {
                 break UpdateHandle(nz);
             }
         }
@@ -10,6 +10,7 @@ use winit::dpi::{LogicalPosition, PhysicalPosition, PhysicalSize, Pixel};
 
 /// An `(x, y)` coordinate.
 #[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Coord(pub i32, pub i32);
 
 impl Coord {
@@ -112,6 +113,7 @@ impl std::ops::AddAssign<Size> for Coord {
 
 /// A `(w, h)` size.
 #[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Size(pub u32, pub u32);
 
 impl Size {
@@ -204,7 +206,8 @@ impl std::ops::SubAssign for Size {
 }
 
 /// A rectangular region.
-#[derive(Clone, Copy, Default, Debug)]
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Rect {
     pub pos: Coord,
     pub size: Size,
@@ -235,6 +238,32 @@ impl Rect {
         let size = Size(w, h);
         Rect { pos, size }
     }
+
+    /// Get the smallest rect containing both `self` and `other`
+    pub fn union(&self, other: Rect) -> Rect {
+        let x0 = self.pos.0.min(other.pos.0);
+        let y0 = self.pos.1.min(other.pos.1);
+        let x1 = (self.pos.0 + self.size.0 as i32).max(other.pos.0 + other.size.0 as i32);
+        let y1 = (self.pos.1 + self.size.1 as i32).max(other.pos.1 + other.size.1 as i32);
+        let pos = Coord(x0, y0);
+        let size = Size((x1 - x0) as u32, (y1 - y0) as u32);
+        Rect { pos, size }
+    }
+
+    /// Get the intersection of `self` and `other`, if not empty
+    pub fn intersection(&self, other: Rect) -> Option<Rect> {
+        let x0 = self.pos.0.max(other.pos.0);
+        let y0 = self.pos.1.max(other.pos.1);
+        let x1 = (self.pos.0 + self.size.0 as i32).min(other.pos.0 + other.size.0 as i32);
+        let y1 = (self.pos.1 + self.size.1 as i32).min(other.pos.1 + other.size.1 as i32);
+        if x1 > x0 && y1 > y0 {
+            let pos = Coord(x0, y0);
+            let size = Size((x1 - x0) as u32, (y1 - y0) as u32);
+            Some(Rect { pos, size })
+        } else {
+            None
+        }
+    }
 }
 
 impl std::ops::Add<Coord> for Rect {
@@ -237,9 +237,13 @@ impl<RT: RowTemp, CT: RowTemp, S: GridStorage> GridSetter<RT, CT, S> {
         rect.pos += margins.first;
         rect.size -= margins.first + margins.last;
         let inter = margins.inter;
+        let width = rect.size.0;
+        let width = width.saturating_sub(inter.0 * (cols as u32).saturating_sub(1));
+        let height = rect.size.1;
+        let height = height.saturating_sub(inter.1 * (rows as u32).saturating_sub(1));
 
-        SizeRules::solve_seq(widths.as_mut(), storage.width_ref(), rect.size.0);
-        SizeRules::solve_seq(heights.as_mut(), storage.height_ref(), rect.size.1);
+        SizeRules::solve_seq(widths.as_mut(), storage.width_ref(), width);
+        SizeRules::solve_seq(heights.as_mut(), storage.height_ref(), height);
 
         let mut col_pos = RT::default();
         let mut row_pos = CT::default();
@@ -0,0 +1,187 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Widget tree inspector
+
+use std::fmt;
+
+use super::{AxisInfo, SizeRules};
+use crate::draw::Colour;
+use crate::event::Manager;
+use crate::geom::Rect;
+use crate::theme::{DrawHandle, SizeHandle, TextClass, TextProperties};
+use crate::{Align, Direction, Widget, WidgetId};
+
+/// Frame colours, cycled by depth in the widget tree
+const COLOURS: [Colour; 4] = [
+    Colour::new(1.0, 0.2, 0.2),
+    Colour::new(0.2, 0.8, 0.2),
+    Colour::new(0.2, 0.4, 1.0),
+    Colour::new(0.9, 0.6, 0.0),
+];
+
+/// Layout information about a single widget
+#[derive(Clone, Debug)]
+pub struct WidgetInfo {
+    pub id: WidgetId,
+    pub name: &'static str,
+    /// Depth in the widget tree; the root has depth 0
+    pub depth: usize,
+    pub rect: Rect,
+    /// Size rules for the horizontal axis
+    pub horiz: SizeRules,
+    /// Size rules for the vertical axis, given the widget's current width
+    pub vert: SizeRules,
+}
+
+impl fmt::Display for WidgetInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {}\tpos={:?}\tsize={:?}\thoriz={}\tvert={}",
+            self.id,
+            self.name,
+            self.rect.pos,
+            self.rect.size,
+            Rules(self.horiz),
+            Rules(self.vert),
+        )
+    }
+}
+
+struct Rules(SizeRules);
+impl fmt::Display for Rules {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let r = self.0;
+        write!(
+            f,
+            "{}..{} ({:?})",
+            r.min_size(),
+            r.ideal_size(),
+            r.stretch()
+        )
+    }
+}
+
+/// A snapshot of the layout of a widget tree, for debugging
+///
+/// Toolkits may draw this over a window (see [`Inspector::draw`]) and print
+/// it (via [`fmt::Display`]) to help debug layout problems.
+#[derive(Clone, Debug, Default)]
+pub struct Inspector {
+    widgets: Vec<WidgetInfo>,
+}
+
+impl Inspector {
+    /// Inspect a widget tree
+    ///
+    /// Size rules of each widget are queried anew. Since this may affect
+    /// layout state stored by widgets, the caller must re-solve the layout of
+    /// the tree afterwards (e.g. via [`Window::resize`](crate::Window::resize)).
+    pub fn new(widget: &mut dyn Widget, size_handle: &mut dyn SizeHandle) -> Self {
+        let mut inspector = Inspector::default();
+        inspector.visit(widget, size_handle, 0);
+        inspector
+    }
+
+    fn visit(&mut self, widget: &mut dyn Widget, size_handle: &mut dyn SizeHandle, depth: usize) {
+        let rect = widget.rect();
+        let horiz = widget.size_rules(size_handle, AxisInfo::new(Direction::Horizontal, None));
+        let axis = AxisInfo::new(Direction::Vertical, Some(rect.size.0));
+        let vert = widget.size_rules(size_handle, axis);
+        self.widgets.push(WidgetInfo {
+            id: widget.id(),
+            name: widget.widget_name(),
+            depth,
+            rect,
+            horiz,
+            vert,
+        });
+        for i in 0..widget.len() {
+            if let Some(child) = widget.get_mut(i) {
+                self.visit(child, size_handle, depth + 1);
+            }
+        }
+    }
+
+    /// Refresh widget positions and sizes
+    ///
+    /// Unlike [`Inspector::new`], this does not query size rules and so need
+    /// not be followed by a re-layout. The widget tree must be unchanged since
+    /// the inspector was constructed.
+    pub fn update_rects(&mut self, widget: &dyn Widget) {
+        let mut index = 0;
+        self.visit_rects(widget, &mut index);
+    }
+
+    fn visit_rects(&mut self, widget: &dyn Widget, index: &mut usize) {
+        if let Some(info) = self.widgets.get_mut(*index) {
+            debug_assert_eq!(info.id, widget.id());
+            info.rect = widget.rect();
+        }
+        *index += 1;
+        for i in 0..widget.len() {
+            if let Some(child) = widget.get(i) {
+                self.visit_rects(child, index);
+            }
+        }
+    }
+
+    /// Access widget information, in depth-first order
+    pub fn widgets(&self) -> &[WidgetInfo] {
+        &self.widgets
+    }
+
+    /// Get information on the widget with the given `id`
+    pub fn get(&self, id: WidgetId) -> Option<&WidgetInfo> {
+        self.widgets.iter().find(|info| info.id == id)
+    }
+
+    /// Draw an overlay showing widget boundaries
+    ///
+    /// The id, position and size rules of the hovered widget are shown at the
+    /// bottom of the window. Widgets within scroll regions are outlined at
+    /// their unscrolled positions.
+    pub fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &Manager) {
+        let mut hovered = None;
+        for info in &self.widgets {
+            let col = COLOURS[info.depth % COLOURS.len()];
+            if mgr.is_hovered(info.id) {
+                hovered = Some(info);
+                draw_handle.frame(info.rect, info.rect.shrink(2), col);
+            } else {
+                draw_handle.frame(info.rect, info.rect.shrink(1), col);
+            }
+        }
+
+        if let Some(info) = hovered {
+            let rect = draw_handle.target_rect().shrink(4);
+            let text = format!(
+                "{} {}\npos: {:?}  size: {:?}\nhoriz: {}\nvert: {}",
+                info.id,
+                info.name,
+                info.rect.pos,
+                info.rect.size,
+                Rules(info.horiz),
+                Rules(info.vert),
+            );
+            let props = TextProperties {
+                class: TextClass::Label,
+                horiz: Align::Begin,
+                vert: Align::End,
+            };
+            draw_handle.text(rect, &text, props);
+        }
+    }
+}
+
+impl fmt::Display for Inspector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for info in &self.widgets {
+            writeln!(f, "{}{}", "- ".repeat(info.depth), info)?;
+        }
+        Ok(())
+    }
+}
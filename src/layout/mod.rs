@@ -8,6 +8,8 @@
 //! This is only of interest if building a custom widget with children.
 
 mod grid_solver;
+mod inspect;
+mod popup;
 mod row_solver;
 mod single_solver;
 mod size_rules;
@@ -18,6 +20,8 @@ use crate::geom::Size;
 use crate::{Direction, Directional};
 
 pub use grid_solver::{GridChildInfo, GridSetter, GridSolver};
+pub use inspect::{Inspector, WidgetInfo};
+pub use popup::{PopupPlacement, Side};
 pub use row_solver::{RowPositionSolver, RowSetter, RowSolver};
 pub use single_solver::{SingleSetter, SingleSolver};
 pub use size_rules::{Margins, SizeRules, StretchPolicy};
@@ -38,7 +42,7 @@ pub struct AxisInfo {
 }
 
 impl AxisInfo {
-    fn new(dir: Direction, fixed: Option<u32>) -> Self {
+    pub(crate) fn new(dir: Direction, fixed: Option<u32>) -> Self {
         AxisInfo {
             vertical: dir.is_vertical(),
             has_fixed: fixed.is_some(),
@@ -68,6 +72,26 @@ impl AxisInfo {
         }
     }
 
+    /// Size of other axis, if fixed
+    #[inline]
+    pub fn other(&self) -> Option<u32> {
+        if self.has_fixed {
+            Some(self.other_axis)
+        } else {
+            None
+        }
+    }
+
+    /// Subtract `x` from size of other axis (if fixed)
+    ///
+    /// Parents should use this to exclude their own margins and decorations
+    /// before passing `AxisInfo` to children, such that children with
+    /// height-for-width sizing (e.g. wrapped text) see their actual width.
+    #[inline]
+    pub fn sub_other(&mut self, x: u32) {
+        self.other_axis = self.other_axis.saturating_sub(x);
+    }
+
     /// Extract horizontal or vertical component of a [`Size`]
     #[inline]
     pub fn extract_size(&self, size: Size) -> u32 {
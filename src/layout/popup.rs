@@ -0,0 +1,262 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Popup placement
+
+use crate::geom::{Coord, Rect, Size};
+use crate::Align;
+
+/// Side of an anchor on which a popup is placed
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Side {
+    /// Below the anchor (e.g. menus, combo boxes, tooltips)
+    Below,
+    /// Above the anchor
+    Above,
+    /// Right of the anchor (e.g. sub-menus)
+    Right,
+    /// Left of the anchor
+    Left,
+}
+
+impl Side {
+    /// The opposite side
+    #[inline]
+    pub fn flipped(self) -> Side {
+        match self {
+            Side::Below => Side::Above,
+            Side::Above => Side::Below,
+            Side::Right => Side::Left,
+            Side::Left => Side::Right,
+        }
+    }
+
+    /// True for [`Side::Below`] and [`Side::Above`]
+    #[inline]
+    pub fn is_vertical(self) -> bool {
+        match self {
+            Side::Below | Side::Above => true,
+            Side::Right | Side::Left => false,
+        }
+    }
+}
+
+/// Placement of a popup relative to an anchor
+///
+/// This positions popups such as menus, combo box lists, tooltips and
+/// autocompletion lists. The popup is placed adjacent to the anchor `rect` on
+/// the preferred [`Side`], or on the opposite side where that has more space.
+/// Along the other axis the popup is aligned to the anchor then shifted to
+/// remain within bounds. The popup is shrunk where it would not fit.
+///
+/// Bounds are typically the available screen area (see
+/// [`Manager::screen_area`](crate::event::Manager::screen_area)), intersected
+/// with the window's rect where popups are drawn within the window.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PopupPlacement {
+    /// Preferred side
+    pub side: Side,
+    /// Alignment along the anchor
+    ///
+    /// With [`Align::Stretch`] the popup is aligned to the start of the anchor
+    /// and is at least as long as the anchor (as for combo boxes).
+    pub align: Align,
+    /// Gap between the anchor and the popup
+    pub gap: u32,
+}
+
+impl Default for PopupPlacement {
+    fn default() -> Self {
+        PopupPlacement::new(Side::Below)
+    }
+}
+
+impl PopupPlacement {
+    /// Construct, with [`Align::Begin`] and no gap
+    pub fn new(side: Side) -> Self {
+        PopupPlacement {
+            side,
+            align: Align::Begin,
+            gap: 0,
+        }
+    }
+
+    /// Set alignment (inline)
+    pub fn with_align(mut self, align: Align) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Set gap (inline)
+    pub fn with_gap(mut self, gap: u32) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Place a popup of ideal `size`
+    ///
+    /// Returns the popup's rect and the side chosen.
+    pub fn place(&self, anchor: Rect, size: Size, bounds: Rect) -> (Rect, Side) {
+        let vertical = self.side.is_vertical();
+        // Main axis: anchor a, bounds b; other axis: anchor c, bounds d
+        let a = Range::along(anchor, vertical);
+        let b = Range::along(bounds, vertical);
+        let c = Range::along(anchor, !vertical);
+        let d = Range::along(bounds, !vertical);
+        let (len, len2) = if !vertical {
+            (size.0 as i32, size.1 as i32)
+        } else {
+            (size.1 as i32, size.0 as i32)
+        };
+
+        // Main axis: choose the side, flipping if the other has more space
+        let after = match self.side {
+            Side::Below | Side::Right => true,
+            Side::Above | Side::Left => false,
+        };
+        let gap = self.gap as i32;
+        let space_after = (b.1 - a.1 - gap).max(0);
+        let space_before = (a.0 - b.0 - gap).max(0);
+        let (space, other) = if after {
+            (space_after, space_before)
+        } else {
+            (space_before, space_after)
+        };
+        let flip = len > space && other > space;
+        let after = after != flip;
+        let len = len.min(space.max(other));
+        let pos = if after { a.1 + gap } else { a.0 - gap - len };
+
+        // Other axis: align then shift to remain within bounds
+        let mut len2 = len2;
+        if self.align == Align::Stretch {
+            len2 = len2.max(c.1 - c.0);
+        }
+        len2 = len2.min(d.1 - d.0).max(0);
+        let pos2 = match self.align {
+            Align::Begin | Align::Stretch => c.0,
+            Align::Centre => c.0 + (c.1 - c.0 - len2) / 2,
+            Align::End => c.1 - len2,
+        };
+        let pos2 = pos2.min(d.1 - len2).max(d.0);
+
+        let (pos, size) = if !vertical {
+            (Coord(pos, pos2), Size(len as u32, len2 as u32))
+        } else {
+            (Coord(pos2, pos), Size(len2 as u32, len as u32))
+        };
+        let side = if flip { self.side.flipped() } else { self.side };
+        (Rect { pos, size }, side)
+    }
+}
+
+// Start and end along one axis
+struct Range(i32, i32);
+
+impl Range {
+    fn along(rect: Rect, vertical: bool) -> Self {
+        if !vertical {
+            Range(rect.pos.0, rect.pos.0 + rect.size.0 as i32)
+        } else {
+            Range(rect.pos.1, rect.pos.1 + rect.size.1 as i32)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const BOUNDS: Rect = Rect {
+        pos: Coord(0, 0),
+        size: Size(800, 600),
+    };
+
+    fn rect(x: i32, y: i32, w: u32, h: u32) -> Rect {
+        Rect::new(Coord(x, y), Size(w, h))
+    }
+
+    #[test]
+    fn below() {
+        let anchor = rect(100, 100, 80, 20);
+        let p = PopupPlacement::new(Side::Below).with_gap(2);
+        let (r, side) = p.place(anchor, Size(120, 200), BOUNDS);
+        assert_eq!(side, Side::Below);
+        assert_eq!(r, rect(100, 122, 120, 200));
+    }
+
+    #[test]
+    fn flip() {
+        // Not enough space below, more space above: flip
+        let anchor = rect(100, 500, 80, 20);
+        let p = PopupPlacement::new(Side::Below);
+        let (r, side) = p.place(anchor, Size(120, 200), BOUNDS);
+        assert_eq!(side, Side::Above);
+        assert_eq!(r, rect(100, 300, 120, 200));
+
+        // Not enough space right, more space left: flip
+        let anchor = rect(700, 100, 80, 20);
+        let p = PopupPlacement::new(Side::Right).with_gap(4);
+        let (r, side) = p.place(anchor, Size(120, 200), BOUNDS);
+        assert_eq!(side, Side::Left);
+        assert_eq!(r, rect(576, 100, 120, 200));
+
+        // Not enough space above, but no more below: do not flip
+        let anchor = rect(100, 290, 80, 20);
+        let p = PopupPlacement::new(Side::Above);
+        let (r, side) = p.place(anchor, Size(120, 400), BOUNDS);
+        assert_eq!(side, Side::Above);
+        assert_eq!(r, rect(100, 0, 120, 290));
+    }
+
+    #[test]
+    fn shrink_when_too_large() {
+        // Neither side has enough space: use the larger side and shrink
+        let anchor = rect(100, 200, 80, 20);
+        let p = PopupPlacement::new(Side::Below);
+        let (r, side) = p.place(anchor, Size(1000, 500), BOUNDS);
+        assert_eq!(side, Side::Below);
+        assert_eq!(r, rect(0, 220, 800, 380));
+    }
+
+    #[test]
+    fn clamp_other_axis() {
+        // Aligned to anchor start, then shifted to remain within bounds
+        let anchor = rect(750, 100, 40, 20);
+        let p = PopupPlacement::new(Side::Below);
+        let (r, _) = p.place(anchor, Size(120, 100), BOUNDS);
+        assert_eq!(r, rect(680, 120, 120, 100));
+
+        let anchor = rect(-20, 100, 40, 20);
+        let p = PopupPlacement::new(Side::Below).with_align(Align::End);
+        let (r, _) = p.place(anchor, Size(120, 100), BOUNDS);
+        assert_eq!(r, rect(0, 120, 120, 100));
+
+        let anchor = rect(100, 100, 40, 20);
+        let p = PopupPlacement::new(Side::Below).with_align(Align::Centre);
+        let (r, _) = p.place(anchor, Size(60, 100), BOUNDS);
+        assert_eq!(r, rect(90, 120, 60, 100));
+    }
+
+    #[test]
+    fn stretch() {
+        let anchor = rect(100, 100, 200, 20);
+        let p = PopupPlacement::new(Side::Below).with_align(Align::Stretch);
+        let (r, _) = p.place(anchor, Size(120, 100), BOUNDS);
+        assert_eq!(r, rect(100, 120, 200, 100));
+    }
+
+    #[test]
+    fn bounds_offset() {
+        // Bounds need not start at the origin (e.g. a second monitor)
+        let bounds = rect(-1024, 0, 1024, 768);
+        let anchor = rect(-100, 700, 80, 20);
+        let p = PopupPlacement::new(Side::Below);
+        let (r, side) = p.place(anchor, Size(200, 100), bounds);
+        assert_eq!(side, Side::Above);
+        assert_eq!(r, rect(-200, 600, 200, 100));
+    }
+}
@@ -122,12 +122,16 @@ impl<D: Directional, T: RowTemp, S: RowStorage> RowSetter<D, T, S> {
         let mut crect = rect;
 
         let (width, inter) = if dim.0.is_horizontal() {
-            crect.size.0 = 0; // hack to get correct first offset
+            // hack to get correct first offset
+            crect.pos.0 -= margins.inter.0 as i32;
+            crect.size.0 = 0;
             (rect.size.0, margins.inter.0)
         } else {
+            crect.pos.1 -= margins.inter.1 as i32;
             crect.size.1 = 0;
             (rect.size.1, margins.inter.1)
         };
+        let width = width.saturating_sub(inter * (dim.1 as u32).saturating_sub(1));
 
         SizeRules::solve_seq(widths.as_mut(), storage.as_ref(), width);
 
@@ -174,6 +178,14 @@ impl<D: Directional> RowPositionSolver<D> {
         RowPositionSolver { direction }
     }
 
+    // True if children are positioned in reverse order (right-to-left mode)
+    fn is_reversed<W: Widget>(self, widgets: &[W]) -> bool {
+        let len = widgets.len();
+        self.direction.is_horizontal()
+            && len > 1
+            && widgets[0].rect().pos.0 > widgets[len - 1].rect().pos.0
+    }
+
     fn binary_search<W: Widget>(self, widgets: &[W], coord: Coord) -> Result<usize, usize> {
         if self.direction.is_horizontal() {
             widgets.binary_search_by_key(&coord.0, |w| w.rect().pos.0)
@@ -187,6 +199,10 @@ impl<D: Directional> RowPositionSolver<D> {
     /// Returns `None` when the coordinates lie within the margin area or
     /// outside of the parent widget.
     pub fn find_child<'a, W: Widget>(self, widgets: &'a [W], coord: Coord) -> Option<&'a W> {
+        if self.is_reversed(widgets) {
+            return widgets.iter().find(|w| w.rect().contains(coord));
+        }
+
         let index = match self.binary_search(widgets, coord) {
             Ok(i) => i,
             Err(i) => {
@@ -201,6 +217,17 @@ impl<D: Directional> RowPositionSolver<D> {
 
     /// Call `f` on each child intersecting the given `rect`
     pub fn for_children<W: Widget, F: FnMut(&W)>(self, widgets: &[W], rect: Rect, mut f: F) {
+        if self.is_reversed(widgets) {
+            let end = rect.pos.0 + rect.size.0 as i32;
+            for child in widgets {
+                let r = child.rect();
+                if r.pos.0 < end && r.pos.0 + r.size.0 as i32 > rect.pos.0 {
+                    f(child);
+                }
+            }
+            return;
+        }
+
         let start = match self.binary_search(widgets, rect.pos) {
             Ok(i) => i,
             Err(i) if i > 0 => {
@@ -44,17 +44,41 @@ impl Margins {
         }
     }
 
+    /// Scale by the given DPI factor
+    ///
+    /// This converts margins given in logical pixels to physical pixels.
+    pub fn scaled(self, factor: f32) -> Self {
+        let scale = |size: Size| {
+            Size(
+                (size.0 as f32 * factor).round() as u32,
+                (size.1 as f32 * factor).round() as u32,
+            )
+        };
+        Margins {
+            first: scale(self.first),
+            last: scale(self.last),
+            inter: scale(self.inter),
+        }
+    }
+
     /// Generate `SizeRules` from self
     ///
     /// Assumes zero-sized content (usually added separately).
     ///
     /// Requires the number of child columns and rows.
     pub fn size_rules(&self, axis_info: AxisInfo, columns: u32, rows: u32) -> SizeRules {
-        SizeRules::fixed(if axis_info.is_horizontal() {
+        SizeRules::fixed(self.sum(axis_info.is_vertical(), columns, rows))
+    }
+
+    /// Total size of margins along the horizontal or vertical axis
+    ///
+    /// Requires the number of child columns and rows.
+    pub fn sum(&self, vertical: bool, columns: u32, rows: u32) -> u32 {
+        if !vertical {
             self.first.0 + self.last.0 + self.inter.0 * columns.saturating_sub(1)
         } else {
             self.first.1 + self.last.1 + self.inter.1 * rows.saturating_sub(1)
-        })
+        }
     }
 }
 
@@ -147,6 +171,24 @@ impl SizeRules {
         self.b
     }
 
+    /// Get the stretch policy
+    #[inline]
+    pub fn stretch(self) -> StretchPolicy {
+        self.stretch
+    }
+
+    /// Set the stretch policy (chain style)
+    ///
+    /// When a row or column has more than its ideal size, extra space is
+    /// given only to the children with the highest stretch policy. Parents
+    /// may use this to override the policy of a child, for example so that a
+    /// main editing area grows before buttons.
+    #[inline]
+    pub fn with_stretch(mut self, stretch: StretchPolicy) -> Self {
+        self.stretch = stretch;
+        self
+    }
+
     /// Like `self = self.max(x - y)` but handling negative values correctly
     // TODO: switch to i32?
     pub fn set_at_least_op_sub(&mut self, x: Self, y: Self) {
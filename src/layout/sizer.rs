@@ -90,6 +90,16 @@ pub fn solve<L: Widget>(
         WidgetHeirarchy(widget, 0),
     );
 
+    #[cfg(feature = "trace")]
+    {
+        // Inspection queries size rules anew, thus we must solve again after
+        let inspector = super::Inspector::new(widget.as_widget_mut(), size_handle);
+        trace!("Size rules of widgets:\n{}", inspector);
+        widget.size_rules(size_handle, AxisInfo::new(Horizontal, None));
+        widget.size_rules(size_handle, AxisInfo::new(Vertical, Some(size.0)));
+        widget.set_rect(size_handle, Rect { pos, size }, AlignHints::NONE);
+    }
+
     (
         Size(w.min_size(), h.min_size()),
         Size(w.ideal_size(), h.ideal_size()),
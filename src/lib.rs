@@ -23,17 +23,39 @@
 extern crate kas_macros;
 extern crate self as kas; // required for reliable self-reference in kas_macros
 
+// Log at trace level if the "trace" feature is enabled
+//
+// Arguments are type-checked but not evaluated when the feature is disabled.
+#[cfg(feature = "trace")]
+macro_rules! trace_detail {
+    ($($arg:tt)*) => { log::trace!($($arg)*) };
+}
+#[cfg(not(feature = "trace"))]
+macro_rules! trace_detail {
+    ($($arg:tt)*) => {
+        if false {
+            log::trace!($($arg)*)
+        }
+    };
+}
+
 // internal modules:
-mod data;
 mod toolkit;
 mod traits;
 
 // public implementations:
+pub mod animate;
 pub mod class;
+pub mod data;
 pub mod draw;
 pub mod event;
 pub mod geom;
 pub mod layout;
+#[cfg(feature = "remote")]
+pub mod remote;
+#[cfg(feature = "winit")]
+pub mod testing;
+pub mod text;
 pub mod theme;
 pub mod widget;
 
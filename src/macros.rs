@@ -77,6 +77,8 @@
 //! -   (optional): `area=FIELD` where `FIELD` is a child widget; if specified,
 //!     the area of self is considered to refer to child `FIELD`. This causes
 //!     the [`kas::Layout::find_id`] function to directly return the child's Id.
+//! -   (optional): `margins` or `margins=SIZE`: a margin around child widgets
+//! -   (optional): `spacing` or `spacing=SIZE`: spacing between child widgets
 //!
 //! Child widgets are arranged as specified by the first parameter:
 //!
@@ -90,6 +92,12 @@
 //!
 //! If the `frame` parameter is given, a frame is drawn around child widgets.
 //!
+//! By default, no margin or spacing is added, since most widgets include their
+//! own margins. The `margins` and `spacing` parameters add these; without a
+//! value the size is taken from the theme ([`SizeHandle::outer_margin`]),
+//! otherwise `SIZE` is an expression giving the size in logical pixels, e.g.
+//! `#[layout(vertical, spacing = 4)]`.
+//!
 //! Derivation of [`Layout`] for non-single layouts requires a data storage
 //! field as follows; for the `single` layout this field is optional:
 //! ```none
@@ -144,16 +152,27 @@
 //!
 //! -   `col = ...` — grid column, from left (defaults to 0)
 //! -   `row = ...` — grid row, from top (defaults to 0)
-//! -   `cspan = ...` — number of columns to span (defaults to 1)
-//! -   `rspan = ...` — number of rows to span (defaults to 1)
+//! -   `cspan = ...` — number of columns to span (defaults to 1); may also be
+//!     written `colspan`
+//! -   `rspan = ...` — number of rows to span (defaults to 1); may also be
+//!     written `rowspan`
 //!
 //! These two affect alignment in the case that a widget finds itself within a
 //! cell larger than its ideal size. Application of alignment is determined by
 //! the child widget's implementation of [`Layout::set_rect`], which may simply
 //! ignore these alignment hints.
 //!
-//! -   `halign = ...` — one of `begin`, `centre`, `end`, `stretch`
-//! -   `valign = ...` — one of `begin`, `centre`, `end`, `stretch`
+//! -   `halign = ...` — one of `begin`, `centre`, `end`, `stretch`; in
+//!     right-to-left mode, `begin` and `end` are mirrored
+//! -   `valign = ...` — one of `begin`, `centre`, `end`, `stretch` (`top` and
+//!     `bottom` may be used for `begin` and `end`)
+//!
+//! The stretch policy of a child (see [`StretchPolicy`]) may be overridden.
+//! When a row or column has more than its ideal size, extra space is given only
+//! to the children with the highest policy; for example a main editing area
+//! may be given `stretch = maximise` so that it grows before buttons.
+//!
+//! -   `stretch = ...` — one of `fixed`, `filler`, `low_utility`, `maximise`
 //!
 //! Finally, a parent widget may handle event-responses from a child widget
 //! (see [`Handler`]). The parent widget should implement a utility method
@@ -356,6 +375,8 @@
 //! [`Layout`]: crate::Layout
 //! [`Layout::set_rect`]: crate::Layout::set_rect
 //! [`LayoutData`]: crate::LayoutData
+//! [`StretchPolicy`]: crate::layout::StretchPolicy
+//! [`SizeHandle::outer_margin`]: crate::theme::SizeHandle::outer_margin
 //! [`Handler`]: crate::event::Handler
 //! [`Handler::Msg`]: crate::event::Handler::Msg
 
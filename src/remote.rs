@@ -0,0 +1,462 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Remote UI (experimental)
+//!
+//! A [`RemoteSession`] runs a widget tree headless: input arrives as
+//! [`ClientEvent`]s and each [`Frame`] is a list of [`DrawCommand`]s. All
+//! protocol types are serialisable via `serde`; the choice of transport (e.g.
+//! a TCP socket) and format is left to the application. A thin display client
+//! may draw a frame with any theme via [`Frame::replay`] and forward input.
+//!
+//! ```ignore
+//! // Server
+//! let mut session = RemoteSession::new(widget, Size(800, 600));
+//! loop {
+//!     let event: ClientEvent = bincode::deserialize_from(&mut stream)?;
+//!     session.handle(event);
+//!     bincode::serialize_into(&mut stream, &session.frame())?;
+//! }
+//! ```
+//!
+//! Limitations: as with [`TestHarness`], text is measured by the server with
+//! fixed metrics, thus layout may not match the client's fonts. Images are
+//! not transferred. Timers run only when [`RemoteSession::update_timers`] is
+//! called.
+//!
+//! Input delivered to a window may be recorded with timing via an
+//! [`EventRecorder`], saved to a file and later replayed against the same UI
+//! via [`EventRecording::replay`], e.g. for regression tests.
+
+mod record;
+
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+use winit::dpi::PhysicalPosition;
+use winit::event::{
+    ElementState, KeyboardInput, ModifiersState, MouseScrollDelta, TouchPhase, WindowEvent,
+};
+
+use crate::draw::{Colour, ImageData, Transform};
+use crate::event::{Handler, HighlightState, Modifiers, MouseButton, VirtualKeyCode};
+use crate::geom::{Coord, Rect, Size};
+use crate::testing::{device_id, TestHarness};
+use crate::theme::{DrawHandle, TextProperties, UnderlineStyle, ValidationState};
+use crate::Direction;
+
+pub use record::{EventRecorder, EventRecording};
+
+/// Input from a display client
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ClientEvent {
+    /// The display area was resized
+    Resized(Size),
+    /// The mouse cursor moved
+    CursorMoved(Coord),
+    /// A mouse button was pressed or released
+    MouseInput { button: MouseButton, pressed: bool },
+    /// Mouse wheel scrolling, in lines
+    MouseWheel(f32, f32),
+    /// Modifier keys changed
+    ///
+    /// The new state applies to subsequent input events.
+    ModifiersChanged(Modifiers),
+    /// A key was pressed or released
+    Key { key: VirtualKeyCode, pressed: bool },
+    /// A character was received
+    Char(char),
+    /// Input method pre-edit text
+    ImePreedit(String),
+    /// Text committed by an input method
+    ImeCommit(String),
+}
+
+/// A draw command
+///
+/// Commands correspond to the methods of [`DrawHandle`]. Region methods
+/// (e.g. [`DrawHandle::clip_region`]) start a nested region, which is ended
+/// by a matching [`DrawCommand::Pop`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum DrawCommand {
+    ClipRegion(Rect, Coord),
+    TransformRegion(Rect, Transform),
+    Opacity(f32),
+    Dimming,
+    /// End the last region
+    Pop,
+    Rect(Rect, Colour),
+    Frame(Rect, Rect, Colour),
+    OuterFrame(Rect),
+    Text(Rect, String, TextProperties),
+    TextSelected(Rect, String, Range<usize>, TextProperties),
+    TextUnderline(Rect, String, Range<usize>, UnderlineStyle, TextProperties),
+    TextSelection(Rect, String, Range<usize>, TextProperties),
+    TextCaret(Rect, String, usize, TextProperties),
+    SelectionBox(Rect),
+    ScrollEdge(Rect),
+    /// An image (the image data is not transferred)
+    Image(Rect),
+    InteractionFrame(Rect, HighlightState),
+    Separator(Rect),
+    PopupBox(Rect),
+    Button(Rect, HighlightState),
+    EditBox(Rect, bool, HighlightState),
+    ValidationFrame(Rect, ValidationState),
+    ValidationMessage(Rect, String, ValidationState),
+    CheckBox(Rect, Option<bool>, HighlightState),
+    RadioBox(Rect, bool, HighlightState),
+    ScrollBar(Rect, Rect, Direction, HighlightState),
+}
+
+/// A frame: the size of the drawn area and draw commands
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Frame {
+    pub size: Size,
+    pub commands: Vec<DrawCommand>,
+}
+
+impl Frame {
+    /// Draw all commands via `draw_handle`
+    ///
+    /// Images are not drawn.
+    pub fn replay(&self, draw_handle: &mut dyn DrawHandle) {
+        let rest = replay(draw_handle, &self.commands);
+        debug_assert!(rest.is_empty(), "Frame::replay: unbalanced Pop");
+    }
+}
+
+// Replay commands until an unmatched Pop; return the commands after it
+fn replay<'a>(handle: &mut dyn DrawHandle, mut cmds: &'a [DrawCommand]) -> &'a [DrawCommand] {
+    use DrawCommand::*;
+    while let Some((cmd, rest)) = cmds.split_first() {
+        cmds = rest;
+        let mut after = cmds;
+        match cmd {
+            ClipRegion(rect, offset) => {
+                handle.clip_region(*rect, *offset, &mut |h| after = replay(h, cmds));
+            }
+            TransformRegion(rect, t) => {
+                handle.transform_region(*rect, *t, &mut |h| after = replay(h, cmds));
+            }
+            Opacity(opacity) => handle.with_opacity(*opacity, &mut |h| after = replay(h, cmds)),
+            Dimming => handle.with_dimming(&mut |h| after = replay(h, cmds)),
+            Pop => return cmds,
+            Rect(rect, col) => handle.rect(*rect, *col),
+            Frame(outer, inner, col) => handle.frame(*outer, *inner, *col),
+            OuterFrame(rect) => handle.outer_frame(*rect),
+            Text(rect, text, props) => handle.text(*rect, text, *props),
+            TextSelected(rect, text, range, props) => {
+                handle.text_selected(*rect, text, range.clone(), *props)
+            }
+            TextUnderline(rect, text, range, style, props) => {
+                handle.text_underline(*rect, text, range.clone(), *style, *props)
+            }
+            TextSelection(rect, text, range, props) => {
+                handle.text_selection(*rect, text, range.clone(), *props)
+            }
+            TextCaret(rect, text, index, props) => handle.text_caret(*rect, text, *index, *props),
+            SelectionBox(rect) => handle.selection_box(*rect),
+            ScrollEdge(rect) => handle.scroll_edge(*rect),
+            Image(_) => (),
+            InteractionFrame(rect, hl) => handle.interaction_frame(*rect, *hl),
+            Separator(rect) => handle.separator(*rect),
+            PopupBox(rect) => handle.popup_box(*rect),
+            Button(rect, hl) => handle.button(*rect, *hl),
+            EditBox(rect, error, hl) => handle.edit_box(*rect, *error, *hl),
+            ValidationFrame(rect, state) => handle.validation_frame(*rect, *state),
+            ValidationMessage(rect, text, state) => handle.validation_message(*rect, text, *state),
+            CheckBox(rect, state, hl) => handle.checkbox(*rect, *state, *hl),
+            RadioBox(rect, checked, hl) => handle.radiobox(*rect, *checked, *hl),
+            ScrollBar(rect, h_rect, dir, hl) => handle.scrollbar(*rect, *h_rect, *dir, *hl),
+        }
+        cmds = after;
+    }
+    cmds
+}
+
+/// A [`DrawHandle`] recording draw commands
+struct Recorder {
+    target: Rect,
+    commands: Vec<DrawCommand>,
+}
+
+impl Recorder {
+    fn region(&mut self, cmd: DrawCommand, target: Rect, f: &mut dyn FnMut(&mut dyn DrawHandle)) {
+        self.commands.push(cmd);
+        let old_target = std::mem::replace(&mut self.target, target);
+        f(self);
+        self.target = old_target;
+        self.commands.push(DrawCommand::Pop);
+    }
+}
+
+impl DrawHandle for Recorder {
+    fn clip_region(&mut self, rect: Rect, offset: Coord, f: &mut dyn FnMut(&mut dyn DrawHandle)) {
+        self.region(DrawCommand::ClipRegion(rect, offset), rect + offset, f);
+    }
+
+    fn transform_region(
+        &mut self,
+        rect: Rect,
+        transform: Transform,
+        f: &mut dyn FnMut(&mut dyn DrawHandle),
+    ) {
+        let target = self.target;
+        self.region(DrawCommand::TransformRegion(rect, transform), target, f);
+    }
+
+    fn with_opacity(&mut self, opacity: f32, f: &mut dyn FnMut(&mut dyn DrawHandle)) {
+        let target = self.target;
+        self.region(DrawCommand::Opacity(opacity), target, f);
+    }
+
+    fn with_dimming(&mut self, f: &mut dyn FnMut(&mut dyn DrawHandle)) {
+        let target = self.target;
+        self.region(DrawCommand::Dimming, target, f);
+    }
+
+    fn target_rect(&self) -> Rect {
+        self.target
+    }
+
+    fn rect(&mut self, rect: Rect, col: Colour) {
+        self.commands.push(DrawCommand::Rect(rect, col));
+    }
+
+    fn frame(&mut self, outer: Rect, inner: Rect, col: Colour) {
+        self.commands.push(DrawCommand::Frame(outer, inner, col));
+    }
+
+    fn outer_frame(&mut self, rect: Rect) {
+        self.commands.push(DrawCommand::OuterFrame(rect));
+    }
+
+    fn text(&mut self, rect: Rect, text: &str, props: TextProperties) {
+        let text = text.to_string();
+        self.commands.push(DrawCommand::Text(rect, text, props));
+    }
+
+    fn text_selected(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        range: Range<usize>,
+        props: TextProperties,
+    ) {
+        let text = text.to_string();
+        let cmd = DrawCommand::TextSelected(rect, text, range, props);
+        self.commands.push(cmd);
+    }
+
+    fn text_underline(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        range: Range<usize>,
+        style: UnderlineStyle,
+        props: TextProperties,
+    ) {
+        let text = text.to_string();
+        let cmd = DrawCommand::TextUnderline(rect, text, range, style, props);
+        self.commands.push(cmd);
+    }
+
+    fn text_selection(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        range: Range<usize>,
+        props: TextProperties,
+    ) {
+        let text = text.to_string();
+        let cmd = DrawCommand::TextSelection(rect, text, range, props);
+        self.commands.push(cmd);
+    }
+
+    fn text_caret(&mut self, rect: Rect, text: &str, index: usize, props: TextProperties) {
+        let text = text.to_string();
+        let cmd = DrawCommand::TextCaret(rect, text, index, props);
+        self.commands.push(cmd);
+    }
+
+    fn selection_box(&mut self, rect: Rect) {
+        self.commands.push(DrawCommand::SelectionBox(rect));
+    }
+
+    fn scroll_edge(&mut self, rect: Rect) {
+        self.commands.push(DrawCommand::ScrollEdge(rect));
+    }
+
+    fn image(&mut self, rect: Rect, _: &ImageData) {
+        self.commands.push(DrawCommand::Image(rect));
+    }
+
+    fn interaction_frame(&mut self, rect: Rect, highlights: HighlightState) {
+        let cmd = DrawCommand::InteractionFrame(rect, highlights);
+        self.commands.push(cmd);
+    }
+
+    fn separator(&mut self, rect: Rect) {
+        self.commands.push(DrawCommand::Separator(rect));
+    }
+
+    fn popup_box(&mut self, rect: Rect) {
+        self.commands.push(DrawCommand::PopupBox(rect));
+    }
+
+    fn button(&mut self, rect: Rect, highlights: HighlightState) {
+        self.commands.push(DrawCommand::Button(rect, highlights));
+    }
+
+    fn edit_box(&mut self, rect: Rect, error: bool, highlights: HighlightState) {
+        let cmd = DrawCommand::EditBox(rect, error, highlights);
+        self.commands.push(cmd);
+    }
+
+    fn validation_frame(&mut self, rect: Rect, state: ValidationState) {
+        let cmd = DrawCommand::ValidationFrame(rect, state);
+        self.commands.push(cmd);
+    }
+
+    fn validation_message(&mut self, rect: Rect, text: &str, state: ValidationState) {
+        let text = text.to_string();
+        let cmd = DrawCommand::ValidationMessage(rect, text, state);
+        self.commands.push(cmd);
+    }
+
+    fn checkbox(&mut self, rect: Rect, state: Option<bool>, highlights: HighlightState) {
+        let cmd = DrawCommand::CheckBox(rect, state, highlights);
+        self.commands.push(cmd);
+    }
+
+    fn radiobox(&mut self, rect: Rect, checked: bool, highlights: HighlightState) {
+        let cmd = DrawCommand::RadioBox(rect, checked, highlights);
+        self.commands.push(cmd);
+    }
+
+    fn scrollbar(&mut self, rect: Rect, h_rect: Rect, dir: Direction, highlights: HighlightState) {
+        let cmd = DrawCommand::ScrollBar(rect, h_rect, dir, highlights);
+        self.commands.push(cmd);
+    }
+}
+
+/// A headless widget tree driven by a remote display client
+///
+/// See the [module documentation](self).
+pub struct RemoteSession<W: Handler + 'static> {
+    harness: TestHarness<W>,
+    size: Size,
+    /// Modifier state, reported with subsequent input events
+    modifiers: ModifiersState,
+}
+
+impl<W: Handler + 'static> RemoteSession<W> {
+    /// Construct with the given initial display size
+    pub fn new(widget: W, size: Size) -> Self {
+        RemoteSession {
+            harness: TestHarness::new_with_size(widget, size),
+            size,
+            modifiers: ModifiersState::empty(),
+        }
+    }
+
+    /// Access the underlying harness (e.g. to access the widget tree)
+    pub fn harness(&mut self) -> &mut TestHarness<W> {
+        &mut self.harness
+    }
+
+    /// True if the widget tree requested to close
+    pub fn is_closed(&self) -> bool {
+        self.harness.is_closed()
+    }
+
+    /// Handle an input event from the client
+    #[allow(deprecated)]
+    pub fn handle(&mut self, event: ClientEvent) {
+        let device_id = device_id();
+        let modifiers = self.modifiers;
+        let event = match event {
+            ClientEvent::Resized(size) => {
+                self.size = size;
+                self.harness.resize(size);
+                return;
+            }
+            ClientEvent::CursorMoved(coord) => WindowEvent::CursorMoved {
+                device_id,
+                position: PhysicalPosition::from(coord),
+                modifiers,
+            },
+            ClientEvent::MouseInput { button, pressed } => WindowEvent::MouseInput {
+                device_id,
+                state: element_state(pressed),
+                button,
+                modifiers,
+            },
+            ClientEvent::MouseWheel(x, y) => WindowEvent::MouseWheel {
+                device_id,
+                delta: MouseScrollDelta::LineDelta(x, y),
+                phase: TouchPhase::Moved,
+                modifiers,
+            },
+            ClientEvent::ModifiersChanged(m) => {
+                // winit reports modifiers with each input event
+                let state = &mut self.modifiers;
+                state.set(ModifiersState::SHIFT, m.shift);
+                state.set(ModifiersState::CTRL, m.ctrl);
+                state.set(ModifiersState::ALT, m.alt);
+                state.set(ModifiersState::LOGO, m.logo);
+                return;
+            }
+            ClientEvent::Key { key, pressed } => WindowEvent::KeyboardInput {
+                device_id,
+                input: KeyboardInput {
+                    scancode: key as u32,
+                    state: element_state(pressed),
+                    virtual_keycode: Some(key),
+                    modifiers,
+                },
+                is_synthetic: false,
+            },
+            ClientEvent::Char(c) => WindowEvent::ReceivedCharacter(c),
+            ClientEvent::ImePreedit(text) => {
+                self.harness.ime_preedit(&text);
+                return;
+            }
+            ClientEvent::ImeCommit(text) => {
+                self.harness.ime_commit(&text);
+                return;
+            }
+        };
+        self.harness.send(event);
+    }
+
+    /// Run widget timer updates which are due
+    pub fn update_timers(&mut self) {
+        self.harness.update_timers();
+    }
+
+    /// Draw the widget tree, returning the frame
+    pub fn frame(&mut self) -> Frame {
+        let target = Rect::new(Coord::ZERO, self.size);
+        let mut recorder = Recorder {
+            target,
+            commands: vec![],
+        };
+        self.harness.draw(&mut recorder);
+        Frame {
+            size: self.size,
+            commands: recorder.commands,
+        }
+    }
+}
+
+fn element_state(pressed: bool) -> ElementState {
+    if pressed {
+        ElementState::Pressed
+    } else {
+        ElementState::Released
+    }
+}
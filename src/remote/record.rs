@@ -0,0 +1,289 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Event recording and replay
+
+use std::fs;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::de::{value, IntoDeserializer};
+use serde::{Deserialize, Serialize};
+use winit::event::{ElementState, MouseScrollDelta, WindowEvent};
+
+use super::{ClientEvent, RemoteSession};
+use crate::event::{Handler, Modifiers, MouseButton, VirtualKeyCode};
+use crate::geom::{Coord, Size};
+
+/// A sequence of input events with timing
+///
+/// Each event is stored with its time since the start of recording. Events
+/// may be recorded from a live window via [`EventRecorder`] and replayed
+/// against the same UI via [`EventRecording::replay`], e.g. for regression
+/// testing or to reproduce a bug.
+///
+/// This may be serialised in any format supported by serde. Alternatively,
+/// use [`EventRecording::load`] and [`EventRecording::save`].
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct EventRecording {
+    events: Vec<(Duration, ClientEvent)>,
+}
+
+impl EventRecording {
+    /// Construct, with no events
+    pub fn new() -> Self {
+        EventRecording::default()
+    }
+
+    /// Append an event
+    ///
+    /// Events should be pushed in order of time.
+    pub fn push(&mut self, time: Duration, event: ClientEvent) {
+        self.events.push((time, event));
+    }
+
+    /// True if no events are recorded
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Access events, with their time since the start of recording
+    pub fn events(&self) -> &[(Duration, ClientEvent)] {
+        &self.events
+    }
+
+    /// Replay all events as fast as possible
+    ///
+    /// Timers which are due are run after each event. Since recorded delays
+    /// are not honoured, timer-driven behaviour (e.g. double-click detection
+    /// or animations) may differ from the recording; see
+    /// [`EventRecording::replay_timed`].
+    pub fn replay<W: Handler + 'static>(&self, session: &mut RemoteSession<W>) {
+        for (_, event) in &self.events {
+            session.handle(event.clone());
+            session.update_timers();
+        }
+    }
+
+    /// Replay all events with their recorded timing
+    ///
+    /// This blocks the current thread for the duration of the recording.
+    pub fn replay_timed<W: Handler + 'static>(&self, session: &mut RemoteSession<W>) {
+        let start = Instant::now();
+        for (time, event) in &self.events {
+            let now = Instant::now();
+            if start + *time > now {
+                thread::sleep(start + *time - now);
+            }
+            session.update_timers();
+            session.handle(event.clone());
+        }
+        session.update_timers();
+    }
+
+    /// Load from a file
+    ///
+    /// Malformed lines are ignored.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let reader = BufReader::new(fs::File::open(path)?);
+        let mut recording = EventRecording::new();
+        for line in reader.lines() {
+            if let Some((time, event)) = parse_line(&line?) {
+                recording.push(time, event);
+            }
+        }
+        Ok(recording)
+    }
+
+    /// Save to a file
+    ///
+    /// Events are written one per line as tab-separated fields: time since
+    /// the start of recording in microseconds, event kind and the event's
+    /// values. Input method text containing tabs or line breaks is not saved.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = BufWriter::new(fs::File::create(path)?);
+        let bad = |s: &str| s.contains(|c| c == '\t' || c == '\n' || c == '\r');
+        for (time, event) in &self.events {
+            let fields = match event {
+                ClientEvent::Resized(size) => format!("resized\t{}\t{}", size.0, size.1),
+                ClientEvent::CursorMoved(coord) => format!("cursor\t{}\t{}", coord.0, coord.1),
+                ClientEvent::MouseInput { button, pressed } => {
+                    let button = match button {
+                        MouseButton::Other(n) => n.to_string(),
+                        button => format!("{:?}", button),
+                    };
+                    format!("mouse\t{}\t{}", button, *pressed as u8)
+                }
+                ClientEvent::MouseWheel(x, y) => format!("wheel\t{}\t{}", x, y),
+                ClientEvent::ModifiersChanged(m) => {
+                    let (s, c, a, l) = (m.shift as u8, m.ctrl as u8, m.alt as u8, m.logo as u8);
+                    format!("modifiers\t{}\t{}\t{}\t{}", s, c, a, l)
+                }
+                ClientEvent::Key { key, pressed } => format!("key\t{:?}\t{}", key, *pressed as u8),
+                ClientEvent::Char(c) => format!("char\t{}", *c as u32),
+                ClientEvent::ImePreedit(text) if !bad(text) => format!("preedit\t{}", text),
+                ClientEvent::ImeCommit(text) if !bad(text) => format!("commit\t{}", text),
+                ClientEvent::ImePreedit(_) | ClientEvent::ImeCommit(_) => continue,
+            };
+            writeln!(writer, "{}\t{}", time.as_micros(), fields)?;
+        }
+        writer.flush()
+    }
+}
+
+/// Records input events delivered to a window
+///
+/// Event times are measured from construction of the recorder.
+#[derive(Clone, Debug)]
+pub struct EventRecorder {
+    start: Instant,
+    recording: EventRecording,
+    modifiers: Modifiers,
+}
+
+impl Default for EventRecorder {
+    fn default() -> Self {
+        EventRecorder::new()
+    }
+}
+
+impl EventRecorder {
+    /// Construct, starting the clock
+    pub fn new() -> Self {
+        EventRecorder {
+            start: Instant::now(),
+            recording: EventRecording::new(),
+            modifiers: Modifiers::default(),
+        }
+    }
+
+    /// Record an event
+    pub fn record(&mut self, event: ClientEvent) {
+        let time = self.start.elapsed();
+        self.recording.push(time, event);
+    }
+
+    /// Record a window event
+    ///
+    /// Events without a [`ClientEvent`] equivalent are ignored. Since winit
+    /// reports modifier state with key and mouse-button events, a
+    /// [`ClientEvent::ModifiersChanged`] event is recorded before such events
+    /// when the modifier state changes.
+    pub fn record_window_event(&mut self, event: &WindowEvent) {
+        let state = match event {
+            #[allow(deprecated)]
+            WindowEvent::KeyboardInput { input, .. } => Some(input.modifiers),
+            #[allow(deprecated)]
+            WindowEvent::MouseInput { modifiers, .. } => Some(*modifiers),
+            _ => None,
+        };
+        if let Some(state) = state {
+            let modifiers = Modifiers {
+                shift: state.shift(),
+                ctrl: state.ctrl(),
+                alt: state.alt(),
+                logo: state.logo(),
+            };
+            if modifiers != self.modifiers {
+                self.modifiers = modifiers;
+                self.record(ClientEvent::ModifiersChanged(modifiers));
+            }
+        }
+        if let Some(event) = ClientEvent::from_window_event(event) {
+            self.record(event);
+        }
+    }
+
+    /// Access the recording
+    pub fn recording(&self) -> &EventRecording {
+        &self.recording
+    }
+
+    /// Stop recording, returning the result
+    pub fn finish(self) -> EventRecording {
+        self.recording
+    }
+}
+
+impl ClientEvent {
+    /// Convert from a window event, if possible
+    ///
+    /// Only input events are converted. Synthetic key events and scrolling by
+    /// pixel deltas are not supported. Modifier state is not converted; see
+    /// [`EventRecorder::record_window_event`].
+    pub fn from_window_event(event: &WindowEvent) -> Option<ClientEvent> {
+        let pressed = |state: ElementState| state == ElementState::Pressed;
+        Some(match event {
+            WindowEvent::Resized(size) => ClientEvent::Resized(Size::from(*size)),
+            WindowEvent::CursorMoved { position, .. } => {
+                ClientEvent::CursorMoved(Coord::from(*position))
+            }
+            WindowEvent::MouseInput { state, button, .. } => ClientEvent::MouseInput {
+                button: *button,
+                pressed: pressed(*state),
+            },
+            WindowEvent::MouseWheel {
+                delta: MouseScrollDelta::LineDelta(x, y),
+                ..
+            } => ClientEvent::MouseWheel(*x, *y),
+            WindowEvent::KeyboardInput {
+                input,
+                is_synthetic: false,
+                ..
+            } => ClientEvent::Key {
+                key: input.virtual_keycode?,
+                pressed: pressed(input.state),
+            },
+            WindowEvent::ReceivedCharacter(c) => ClientEvent::Char(*c),
+            _ => return None,
+        })
+    }
+}
+
+fn parse_line(line: &str) -> Option<(Duration, ClientEvent)> {
+    let mut fields = line.split('\t');
+    let time = Duration::from_micros(fields.next()?.parse().ok()?);
+    let mut next = || fields.next();
+    let flag = |field: Option<&str>| match field? {
+        "0" => Some(false),
+        "1" => Some(true),
+        _ => None,
+    };
+    let event = match next()? {
+        "resized" => ClientEvent::Resized(Size(next()?.parse().ok()?, next()?.parse().ok()?)),
+        "cursor" => ClientEvent::CursorMoved(Coord(next()?.parse().ok()?, next()?.parse().ok()?)),
+        "mouse" => {
+            let button = match next()? {
+                "Left" => MouseButton::Left,
+                "Right" => MouseButton::Right,
+                "Middle" => MouseButton::Middle,
+                n => MouseButton::Other(n.parse().ok()?),
+            };
+            let pressed = flag(next())?;
+            ClientEvent::MouseInput { button, pressed }
+        }
+        "wheel" => ClientEvent::MouseWheel(next()?.parse().ok()?, next()?.parse().ok()?),
+        "modifiers" => ClientEvent::ModifiersChanged(Modifiers {
+            shift: flag(next())?,
+            ctrl: flag(next())?,
+            alt: flag(next())?,
+            logo: flag(next())?,
+        }),
+        "key" => {
+            // Key names match the (serde) variant names
+            let de: value::StrDeserializer<value::Error> = next()?.into_deserializer();
+            let key = VirtualKeyCode::deserialize(de).ok()?;
+            let pressed = flag(next())?;
+            ClientEvent::Key { key, pressed }
+        }
+        "char" => ClientEvent::Char(std::char::from_u32(next()?.parse().ok()?)?),
+        "preedit" => ClientEvent::ImePreedit(next()?.to_string()),
+        "commit" => ClientEvent::ImeCommit(next()?.to_string()),
+        _ => return None,
+    };
+    Some((time, event))
+}
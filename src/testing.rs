@@ -0,0 +1,1457 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Integration testing
+//!
+//! [`TestHarness`] drives a widget tree with synthetic input. Input is
+//! translated to winit events and passed through the real event [`Manager`],
+//! thus focus handling, press grabs, accelerator keys and so on behave as in
+//! an application. Messages emitted by the widget tree are collected for
+//! inspection. Drawing may be recorded as a list of [`DrawCommand`]s (see
+//! [`TestHarness::record_draw`]).
+//!
+//! ```
+//! # use kas::testing::TestHarness;
+//! # use kas::event::VirtualKeyCode;
+//! # use kas::widget::EditBox;
+//! # use kas::WidgetCore;
+//! let mut harness = TestHarness::new(EditBox::new("").on_activate(|text| text.to_string()));
+//! let id = harness.widget().id();
+//! harness.click(id).type_text("hello").key(VirtualKeyCode::Return);
+//! assert_eq!(harness.take_messages(), vec!["hello".to_string()]);
+//! ```
+//!
+//! No theme is used: text is measured with fixed metrics, where each
+//! character is [`CHAR_WIDTH`] pixels wide and each line is [`LINE_HEIGHT`]
+//! pixels high.
+
+use std::any::Any;
+use std::fmt::{self, Debug};
+use std::future::Future;
+use std::mem::take;
+use std::num::NonZeroU32;
+use std::ops::Range;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+use winit::dpi::PhysicalPosition;
+use winit::event::{
+    DeviceId, ElementState, KeyboardInput, ModifiersState, MouseButton, MouseScrollDelta,
+    TouchPhase, WindowEvent,
+};
+
+use crate::draw::{Colour, ImageData, Transform};
+use crate::event::{
+    CursorIcon, DragData, Event, Handler, HighlightState, Manager, ManagerState, Modifiers,
+    Priority, Response, UpdateHandle, VirtualKeyCode, VoidMsg,
+};
+use crate::geom::{Coord, Rect, Size};
+use crate::layout::{self, AxisInfo, SizeRules};
+use crate::macros::Widget;
+use crate::text::RichText;
+use crate::theme::{DrawHandle, SizeHandle, TextClass, TextProperties, ThemeAction, ThemeApi};
+use crate::theme::{UnderlineStyle, ValidationState};
+use crate::{
+    CoreData, Direction, Layout, LayoutData, LayoutDirection, TkAction, TkWindow, Widget,
+    WidgetCore, WidgetId, WindowId,
+};
+
+/// Width of each character, as measured by the test harness
+pub const CHAR_WIDTH: u32 = 8;
+
+/// Height of each line of text, as measured by the test harness
+pub const LINE_HEIGHT: u32 = 16;
+
+/// A driver for integration tests
+///
+/// The widget tree is configured and sized on construction. Each input method
+/// returns `&mut Self`, allowing calls to be chained.
+pub struct TestHarness<W: Handler + 'static> {
+    widget: Capture<W>,
+    mgr: ManagerState,
+    tkw: TestTkWindow,
+    size: Size,
+    closed: bool,
+    // Number of times the widget tree has been configured
+    configures: usize,
+}
+
+impl<W: Handler + 'static> TestHarness<W> {
+    /// Construct with a window size of 800×600
+    pub fn new(widget: W) -> Self {
+        TestHarness::new_with_size(widget, Size(800, 600))
+    }
+
+    /// Construct with the given window size
+    pub fn new_with_size(widget: W, size: Size) -> Self {
+        let mut harness = TestHarness {
+            widget: Capture {
+                core: Default::default(),
+                layout_data: Default::default(),
+                child: widget,
+                msgs: vec![],
+            },
+            mgr: ManagerState::new(1.0),
+            tkw: TestTkWindow::default(),
+            size,
+            closed: false,
+            configures: 0,
+        };
+        harness.configure();
+        harness
+    }
+
+    /// Access the widget tree
+    pub fn widget(&self) -> &W {
+        &self.widget.child
+    }
+
+    /// Access the widget tree mutably
+    ///
+    /// The closure is passed an event [`Manager`], allowing widget state to be
+    /// adjusted as by an event handler.
+    pub fn with_widget<F: FnOnce(&mut W, &mut Manager)>(&mut self, f: F) -> &mut Self {
+        let mut mgr = self.mgr.manager(&mut self.tkw);
+        f(&mut self.widget.child, &mut mgr);
+        let action = mgr.unwrap_action();
+        self.finish(action)
+    }
+
+    /// Take all messages emitted since the last call
+    pub fn take_messages(&mut self) -> Vec<W::Msg> {
+        take(&mut self.widget.msgs)
+    }
+
+    /// True if the given widget has character focus
+    pub fn char_focus(&mut self, id: WidgetId) -> bool {
+        self.mgr.manager(&mut self.tkw).char_focus(id)
+    }
+
+    /// True if the window requested to close
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    /// Get the clipboard contents
+    pub fn clipboard(&self) -> Option<&str> {
+        self.tkw.clipboard.as_deref()
+    }
+
+    /// Set the clipboard contents
+    pub fn set_clipboard(&mut self, content: String) -> &mut Self {
+        self.tkw.clipboard = Some(content);
+        self
+    }
+
+    /// Take all messages pushed (see [`Manager::push_msg`]) since the last call
+    pub fn take_msgs(&mut self) -> Vec<Rc<dyn Any>> {
+        take(&mut self.tkw.msgs)
+    }
+
+    /// Take all announcements made since the last call
+    pub fn take_announcements(&mut self) -> Vec<(String, Priority)> {
+        take(&mut self.tkw.announcements)
+    }
+
+    /// Poll all spawned tasks (see [`Manager::spawn`])
+    ///
+    /// Each incomplete task is polled once; outputs of completed tasks are
+    /// delivered to their target widgets.
+    pub fn run_tasks(&mut self) -> &mut Self {
+        // Safe: the functions of NOOP_VTABLE do nothing
+        let waker = unsafe { Waker::from_raw(noop_raw_waker()) };
+        let mut cx = Context::from_waker(&waker);
+        for (id, mut task) in take(&mut self.tkw.tasks) {
+            match task.as_mut().poll(&mut cx) {
+                Poll::Pending => self.tkw.tasks.push((id, task)),
+                Poll::Ready(output) => self.mgr.send_to(id, output),
+            }
+        }
+        let mut mgr = self.mgr.manager(&mut self.tkw);
+        mgr.flush_pending(&mut self.widget);
+        let action = mgr.unwrap_action();
+        self.finish(action)
+    }
+
+    /// Take all data dropped outside the window since the last call
+    pub fn take_drops_outside(&mut self) -> Vec<(DragData, Coord)> {
+        take(&mut self.tkw.drops_outside)
+    }
+
+    /// Move the mouse cursor to `coord`
+    #[allow(deprecated)]
+    pub fn move_to(&mut self, coord: Coord) -> &mut Self {
+        self.send(WindowEvent::CursorMoved {
+            device_id: device_id(),
+            position: PhysicalPosition::from(coord),
+            modifiers: ModifiersState::empty(),
+        })
+    }
+
+    /// Press or release the left mouse button
+    #[allow(deprecated)]
+    pub fn mouse_button(&mut self, state: ElementState) -> &mut Self {
+        self.send(WindowEvent::MouseInput {
+            device_id: device_id(),
+            state,
+            button: MouseButton::Left,
+            modifiers: ModifiersState::empty(),
+        })
+    }
+
+    /// Scroll the mouse wheel by the given number of lines
+    ///
+    /// Positive `y` scrolls up (i.e. towards the start of the content).
+    #[allow(deprecated)]
+    pub fn scroll_wheel(&mut self, x: f32, y: f32) -> &mut Self {
+        self.send(WindowEvent::MouseWheel {
+            device_id: device_id(),
+            delta: MouseScrollDelta::LineDelta(x, y),
+            phase: TouchPhase::Moved,
+            modifiers: ModifiersState::empty(),
+        })
+    }
+
+    /// Click at `coord` with the left mouse button
+    pub fn click_at(&mut self, coord: Coord) -> &mut Self {
+        self.move_to(coord)
+            .mouse_button(ElementState::Pressed)
+            .mouse_button(ElementState::Released)
+    }
+
+    /// Click on the centre of the widget with the given `id`
+    ///
+    /// Panics if no such widget exists.
+    pub fn click(&mut self, id: WidgetId) -> &mut Self {
+        let rect = match self.widget.find(id) {
+            Some(w) => w.rect(),
+            None => panic!("TestHarness::click: no widget with id {}", id),
+        };
+        let coord = rect.pos + Coord(rect.size.0 as i32 / 2, rect.size.1 as i32 / 2);
+        self.click_at(coord)
+    }
+
+    /// Type the given text
+    ///
+    /// Each character is received by the widget with character focus, if any.
+    pub fn type_text(&mut self, text: &str) -> &mut Self {
+        for c in text.chars() {
+            self.send(WindowEvent::ReceivedCharacter(c));
+        }
+        self
+    }
+
+    /// Send input method pre-edit text
+    ///
+    /// This is received by the widget with character focus, if any.
+    pub fn ime_preedit(&mut self, text: &str) -> &mut Self {
+        let mut mgr = self.mgr.manager(&mut self.tkw);
+        mgr.ime_preedit(&mut self.widget, text.to_string());
+        let action = mgr.unwrap_action();
+        self.finish(action)
+    }
+
+    /// Send text committed by an input method
+    ///
+    /// This is received by the widget with character focus, if any.
+    pub fn ime_commit(&mut self, text: &str) -> &mut Self {
+        let mut mgr = self.mgr.manager(&mut self.tkw);
+        mgr.ime_commit(&mut self.widget, text.to_string());
+        let action = mgr.unwrap_action();
+        self.finish(action)
+    }
+
+    /// Press and release `key`
+    pub fn key(&mut self, key: VirtualKeyCode) -> &mut Self {
+        self.key_with(Modifiers::default(), key)
+    }
+
+    /// Press and release `key` while holding `modifiers`
+    ///
+    /// As on most platforms, a character is received for keys such as
+    /// `Return` and `Back` and for letter keys combined with `Ctrl` (e.g.
+    /// `Ctrl+C` yields `'\u{03}'`). Other keys should be typed via
+    /// [`TestHarness::type_text`].
+    pub fn key_with(&mut self, modifiers: Modifiers, key: VirtualKeyCode) -> &mut Self {
+        let mut state = ModifiersState::empty();
+        state.set(ModifiersState::SHIFT, modifiers.shift);
+        state.set(ModifiersState::CTRL, modifiers.ctrl);
+        state.set(ModifiersState::ALT, modifiers.alt);
+        state.set(ModifiersState::LOGO, modifiers.logo);
+
+        // winit reports modifier state with each key event, thus we press
+        // and release the modifier keys around `key`
+        let held = [
+            (modifiers.shift, VirtualKeyCode::LShift),
+            (modifiers.ctrl, VirtualKeyCode::LControl),
+            (modifiers.alt, VirtualKeyCode::LAlt),
+            (modifiers.logo, VirtualKeyCode::LWin),
+        ];
+        let held: Vec<_> = held.iter().filter(|(h, _)| *h).map(|(_, k)| *k).collect();
+        for k in &held {
+            self.keyboard_input(ElementState::Pressed, *k, state);
+        }
+
+        self.keyboard_input(ElementState::Pressed, key, state);
+        if let Some(c) = key_char(key, modifiers) {
+            self.send(WindowEvent::ReceivedCharacter(c));
+        }
+        self.keyboard_input(ElementState::Released, key, state);
+
+        for k in held.iter().rev() {
+            self.keyboard_input(ElementState::Released, *k, ModifiersState::empty());
+        }
+        self
+    }
+
+    /// Resize the window
+    pub fn resize(&mut self, size: Size) -> &mut Self {
+        self.size = size;
+        self.finish(TkAction::Reconfigure)
+    }
+
+    /// Set the layout direction (default: left-to-right)
+    pub fn set_layout_direction(&mut self, direction: LayoutDirection) -> &mut Self {
+        self.tkw.layout_direction = direction;
+        self.finish(TkAction::Reconfigure)
+    }
+
+    /// Draw the widget tree via `draw_handle`
+    pub fn draw(&mut self, draw_handle: &mut dyn DrawHandle) {
+        let mgr = self.mgr.manager(&mut self.tkw);
+        self.widget.draw(draw_handle, &mgr);
+    }
+
+    /// Draw the widget tree, recording draw commands
+    ///
+    /// Commands are listed in the order drawn, with rects in window
+    /// coordinates.
+    pub fn record_draw(&mut self) -> Vec<DrawCommand> {
+        let mut draw_handle = TestDrawHandle {
+            rect: Rect::new(Coord::ZERO, self.size),
+            transform: Transform::default(),
+            commands: vec![],
+        };
+        self.draw(&mut draw_handle);
+        draw_handle.commands
+    }
+
+    /// Run widget timer updates which are due
+    pub fn update_timers(&mut self) -> &mut Self {
+        let mut mgr = self.mgr.manager(&mut self.tkw);
+        mgr.update_timer(&mut self.widget);
+        let action = mgr.unwrap_action();
+        self.finish(action)
+    }
+
+    #[allow(deprecated)]
+    fn keyboard_input(&mut self, state: ElementState, key: VirtualKeyCode, mods: ModifiersState) {
+        let input = KeyboardInput {
+            scancode: key as u32,
+            state,
+            virtual_keycode: Some(key),
+            modifiers: mods,
+        };
+        self.send(WindowEvent::KeyboardInput {
+            device_id: device_id(),
+            input,
+            is_synthetic: false,
+        });
+    }
+
+    pub(crate) fn send(&mut self, event: WindowEvent) -> &mut Self {
+        let action = self
+            .mgr
+            .manager(&mut self.tkw)
+            .handle_winit(&mut self.widget, event);
+        // Deliver coalesced events immediately
+        let mut mgr = self.mgr.manager(&mut self.tkw);
+        mgr.flush_pending(&mut self.widget);
+        let action = action.max(mgr.unwrap_action());
+        self.finish(action)
+    }
+
+    /// Process pending updates and the resulting action
+    ///
+    /// As in the toolkit, the action resulting from each update is applied
+    /// separately.
+    fn finish(&mut self, action: TkAction) -> &mut Self {
+        while !self.tkw.updates.is_empty() {
+            for (handle, payload) in take(&mut self.tkw.updates) {
+                let mut mgr = self.mgr.manager(&mut self.tkw);
+                mgr.update_handle(&mut self.widget, handle, payload);
+                let action = mgr.unwrap_action();
+                self.apply(action);
+            }
+        }
+        self.apply(action);
+        self
+    }
+
+    fn apply(&mut self, action: TkAction) {
+        match action {
+            TkAction::None | TkAction::Redraw => (),
+            TkAction::RegionMoved => self.mgr.region_moved(&mut self.widget),
+            TkAction::Reconfigure => self.configure(),
+            TkAction::Close | TkAction::CloseAll => self.closed = true,
+        }
+    }
+
+    fn configure(&mut self) {
+        let mut size_handle = TestSizeHandle(self.tkw.layout_direction);
+        layout::solve(&mut self.widget, &mut size_handle, self.size);
+        self.mgr.configure(&mut self.tkw, &mut self.widget);
+        self.configures += 1;
+    }
+}
+
+impl<W: Handler + 'static> Debug for TestHarness<W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "TestHarness {{ widget: {:?}, size: {:?}, closed: {:?}, ... }}",
+            self.widget.child, self.size, self.closed
+        )
+    }
+}
+
+pub(crate) fn device_id() -> DeviceId {
+    // Safe: the id is only passed to our own event handler, which ignores it
+    unsafe { DeviceId::dummy() }
+}
+
+/// Character received for `key`, if any
+fn key_char(key: VirtualKeyCode, modifiers: Modifiers) -> Option<char> {
+    use VirtualKeyCode::*;
+    Some(match key {
+        Return | NumpadEnter => '\r',
+        Back => '\u{08}',
+        Tab => '\t',
+        Delete => '\u{7f}',
+        Escape => '\u{1b}',
+        key if modifiers.ctrl && key >= A && key <= Z => (1 + key as u32 - A as u32) as u8 as char,
+        _ => return None,
+    })
+}
+
+/// Wrapper collecting messages emitted by `child`
+#[layout(single)]
+#[derive(Widget)]
+struct Capture<W: Handler + 'static> {
+    #[core]
+    core: CoreData,
+    #[layout_data]
+    layout_data: <Self as LayoutData>::Data,
+    #[widget]
+    child: W,
+    msgs: Vec<W::Msg>,
+}
+
+impl<W: Handler + 'static> Debug for Capture<W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Capture {{ core: {:?}, child: {:?}, ... }}",
+            self.core, self.child
+        )
+    }
+}
+
+impl<W: Handler + 'static> Widget for Capture<W> {}
+
+impl<W: Handler + 'static> Handler for Capture<W> {
+    type Msg = VoidMsg;
+
+    fn handle(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<VoidMsg> {
+        if id <= self.child.id() {
+            match self.child.handle(mgr, id, event).try_into() {
+                Ok(r) => r,
+                Err(msg) => {
+                    self.msgs.push(msg);
+                    Response::None
+                }
+            }
+        } else {
+            debug_assert!(id == self.id(), "Handler::handle: bad WidgetId");
+            Response::Unhandled(event)
+        }
+    }
+}
+
+type Task = Pin<Box<dyn Future<Output = Rc<dyn Any>>>>;
+
+/// Toolkit interface for tests
+#[derive(Default)]
+struct TestTkWindow {
+    next_window: u32,
+    clipboard: Option<String>,
+    updates: Vec<(UpdateHandle, u64)>,
+    msgs: Vec<Rc<dyn Any>>,
+    tasks: Vec<(WidgetId, Task)>,
+    announcements: Vec<(String, Priority)>,
+    drops_outside: Vec<(DragData, Coord)>,
+    layout_direction: LayoutDirection,
+}
+
+impl TkWindow for TestTkWindow {
+    fn add_window(&mut self, _: Box<dyn crate::Window>) -> WindowId {
+        self.next_window += 1;
+        WindowId::new(NonZeroU32::new(self.next_window).unwrap())
+    }
+
+    fn add_modal_window(&mut self, widget: Box<dyn crate::Window>) -> WindowId {
+        self.add_window(widget)
+    }
+
+    fn close_window(&mut self, _: WindowId) {}
+
+    fn trigger_update(&mut self, handle: UpdateHandle, payload: u64) {
+        self.updates.push((handle, payload));
+    }
+
+    fn push_msg(&mut self, msg: Rc<dyn Any>) {
+        self.msgs.push(msg);
+    }
+
+    fn spawn(&mut self, id: WidgetId, task: Task) {
+        self.tasks.push((id, task));
+    }
+
+    fn get_clipboard(&mut self) -> Option<String> {
+        self.clipboard.clone()
+    }
+
+    fn set_clipboard(&mut self, content: String) {
+        self.clipboard = Some(content);
+    }
+
+    fn adjust_theme(&mut self, _: &mut dyn FnMut(&mut dyn ThemeApi) -> ThemeAction) {}
+
+    fn set_cursor_icon(&mut self, _: CursorIcon) {}
+
+    fn set_ime_position(&mut self, _: Coord) {}
+
+    fn announce(&mut self, text: &str, priority: Priority) {
+        self.announcements.push((text.to_string(), priority));
+    }
+
+    fn screen_areas(&self) -> Vec<Rect> {
+        vec![]
+    }
+
+    fn size_handle(&mut self, f: &mut dyn FnMut(&mut dyn SizeHandle)) {
+        f(&mut TestSizeHandle(self.layout_direction));
+    }
+
+    fn drop_outside(&mut self, data: DragData, coord: Coord) {
+        self.drops_outside.push((data, coord));
+    }
+}
+
+/// A draw command, as recorded by [`TestHarness::record_draw`]
+///
+/// Each variant corresponds to a [`DrawHandle`] method. Rects are in window
+/// coordinates; text is recorded without layout.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DrawCommand {
+    /// [`DrawHandle::rect`]
+    Rect(Rect, Colour),
+    /// [`DrawHandle::frame`] (outer rect)
+    Frame(Rect, Colour),
+    /// [`DrawHandle::outer_frame`]
+    OuterFrame(Rect),
+    /// [`DrawHandle::text`] and [`DrawHandle::rich_text`]
+    Text(Rect, String, TextClass),
+    /// [`DrawHandle::text_selected`]
+    TextSelected(Rect, String, Range<usize>),
+    /// [`DrawHandle::text_underline`]
+    TextUnderline(Rect, Range<usize>),
+    /// [`DrawHandle::text_selection`]
+    TextSelection(Rect, Range<usize>),
+    /// [`DrawHandle::text_caret`]
+    TextCaret(Rect, usize),
+    /// [`DrawHandle::selection_box`]
+    SelectionBox(Rect),
+    /// [`DrawHandle::scroll_edge`]
+    ScrollEdge(Rect),
+    /// [`DrawHandle::image`]
+    Image(Rect),
+    /// [`DrawHandle::interaction_frame`]
+    InteractionFrame(Rect, HighlightState),
+    /// [`DrawHandle::separator`]
+    Separator(Rect),
+    /// [`DrawHandle::popup_box`]
+    PopupBox(Rect),
+    /// [`DrawHandle::button`]
+    Button(Rect, HighlightState),
+    /// [`DrawHandle::edit_box`] (with error state)
+    EditBox(Rect, bool, HighlightState),
+    /// [`DrawHandle::validation_frame`]
+    ValidationFrame(Rect, ValidationState),
+    /// [`DrawHandle::validation_message`]
+    ValidationMessage(Rect, String, ValidationState),
+    /// [`DrawHandle::checkbox`]
+    CheckBox(Rect, Option<bool>, HighlightState),
+    /// [`DrawHandle::radiobox`]
+    RadioBox(Rect, bool, HighlightState),
+    /// [`DrawHandle::scrollbar`] (track and handle rects)
+    ScrollBar(Rect, Rect, HighlightState),
+}
+
+/// Draw handle recording [`DrawCommand`]s
+struct TestDrawHandle {
+    // Target rect, in local coordinates
+    rect: Rect,
+    // Transform from local to window coordinates
+    transform: Transform,
+    commands: Vec<DrawCommand>,
+}
+
+impl TestDrawHandle {
+    fn push(&mut self, command: DrawCommand) {
+        self.commands.push(command);
+    }
+
+    fn map(&self, rect: Rect) -> Rect {
+        self.transform.map_rect(rect)
+    }
+
+    fn region(&mut self, rect: Rect, transform: Transform, f: &mut dyn FnMut(&mut dyn DrawHandle)) {
+        let mut handle = TestDrawHandle {
+            rect: transform.inverse().map_rect(rect),
+            transform: transform.then(self.transform),
+            commands: vec![],
+        };
+        f(&mut handle);
+        self.commands.append(&mut handle.commands);
+    }
+}
+
+impl DrawHandle for TestDrawHandle {
+    fn clip_region(&mut self, rect: Rect, offset: Coord, f: &mut dyn FnMut(&mut dyn DrawHandle)) {
+        let transform = Transform::translation(-offset.0 as f32, -offset.1 as f32);
+        self.region(rect, transform, f);
+    }
+
+    fn transform_region(
+        &mut self,
+        rect: Rect,
+        transform: Transform,
+        f: &mut dyn FnMut(&mut dyn DrawHandle),
+    ) {
+        self.region(rect, transform, f);
+    }
+
+    fn with_opacity(&mut self, _: f32, f: &mut dyn FnMut(&mut dyn DrawHandle)) {
+        f(self);
+    }
+
+    fn with_dimming(&mut self, f: &mut dyn FnMut(&mut dyn DrawHandle)) {
+        f(self);
+    }
+
+    fn target_rect(&self) -> Rect {
+        self.rect
+    }
+
+    fn rect(&mut self, rect: Rect, col: Colour) {
+        self.push(DrawCommand::Rect(self.map(rect), col));
+    }
+
+    fn frame(&mut self, outer: Rect, _: Rect, col: Colour) {
+        self.push(DrawCommand::Frame(self.map(outer), col));
+    }
+
+    fn outer_frame(&mut self, rect: Rect) {
+        self.push(DrawCommand::OuterFrame(self.map(rect)));
+    }
+
+    fn text(&mut self, rect: Rect, text: &str, props: TextProperties) {
+        let rect = self.map(rect);
+        self.push(DrawCommand::Text(rect, text.to_string(), props.class));
+    }
+
+    fn rich_text(&mut self, rect: Rect, text: &RichText, props: TextProperties) {
+        self.text(rect, text.as_str(), props);
+    }
+
+    fn text_selected(&mut self, rect: Rect, text: &str, range: Range<usize>, _: TextProperties) {
+        let rect = self.map(rect);
+        self.push(DrawCommand::TextSelected(rect, text.to_string(), range));
+    }
+
+    fn text_underline(
+        &mut self,
+        rect: Rect,
+        _: &str,
+        range: Range<usize>,
+        _: UnderlineStyle,
+        _: TextProperties,
+    ) {
+        self.push(DrawCommand::TextUnderline(self.map(rect), range));
+    }
+
+    fn text_selection(&mut self, rect: Rect, _: &str, range: Range<usize>, _: TextProperties) {
+        self.push(DrawCommand::TextSelection(self.map(rect), range));
+    }
+
+    fn text_caret(&mut self, rect: Rect, _: &str, index: usize, _: TextProperties) {
+        self.push(DrawCommand::TextCaret(self.map(rect), index));
+    }
+
+    fn selection_box(&mut self, rect: Rect) {
+        self.push(DrawCommand::SelectionBox(self.map(rect)));
+    }
+
+    fn scroll_edge(&mut self, rect: Rect) {
+        self.push(DrawCommand::ScrollEdge(self.map(rect)));
+    }
+
+    fn image(&mut self, rect: Rect, _: &ImageData) {
+        self.push(DrawCommand::Image(self.map(rect)));
+    }
+
+    fn interaction_frame(&mut self, rect: Rect, highlights: HighlightState) {
+        self.push(DrawCommand::InteractionFrame(self.map(rect), highlights));
+    }
+
+    fn separator(&mut self, rect: Rect) {
+        self.push(DrawCommand::Separator(self.map(rect)));
+    }
+
+    fn popup_box(&mut self, rect: Rect) {
+        self.push(DrawCommand::PopupBox(self.map(rect)));
+    }
+
+    fn button(&mut self, rect: Rect, highlights: HighlightState) {
+        self.push(DrawCommand::Button(self.map(rect), highlights));
+    }
+
+    fn edit_box(&mut self, rect: Rect, error: bool, highlights: HighlightState) {
+        self.push(DrawCommand::EditBox(self.map(rect), error, highlights));
+    }
+
+    fn validation_frame(&mut self, rect: Rect, state: ValidationState) {
+        self.push(DrawCommand::ValidationFrame(self.map(rect), state));
+    }
+
+    fn validation_message(&mut self, rect: Rect, text: &str, state: ValidationState) {
+        let (rect, text) = (self.map(rect), text.to_string());
+        self.push(DrawCommand::ValidationMessage(rect, text, state));
+    }
+
+    fn checkbox(&mut self, rect: Rect, state: Option<bool>, highlights: HighlightState) {
+        self.push(DrawCommand::CheckBox(self.map(rect), state, highlights));
+    }
+
+    fn radiobox(&mut self, rect: Rect, checked: bool, highlights: HighlightState) {
+        self.push(DrawCommand::RadioBox(self.map(rect), checked, highlights));
+    }
+
+    fn scrollbar(&mut self, rect: Rect, h_rect: Rect, _: Direction, highlights: HighlightState) {
+        let (rect, h_rect) = (self.map(rect), self.map(h_rect));
+        self.push(DrawCommand::ScrollBar(rect, h_rect, highlights));
+    }
+}
+
+/// Waker for tasks polled by [`TestHarness::run_tasks`]
+fn noop_raw_waker() -> RawWaker {
+    RawWaker::new(std::ptr::null(), &NOOP_VTABLE)
+}
+
+static NOOP_VTABLE: RawWakerVTable = RawWakerVTable::new(noop_clone, noop, noop, noop);
+
+unsafe fn noop_clone(_: *const ()) -> RawWaker {
+    noop_raw_waker()
+}
+
+unsafe fn noop(_: *const ()) {}
+
+/// Size handle using fixed metrics
+struct TestSizeHandle(LayoutDirection);
+
+impl SizeHandle for TestSizeHandle {
+    fn dpi_factor(&self) -> f32 {
+        1.0
+    }
+
+    fn layout_direction(&self) -> LayoutDirection {
+        self.0
+    }
+
+    fn outer_frame(&self) -> (Size, Size) {
+        (Size::uniform(2), Size::uniform(2))
+    }
+
+    fn inner_margin(&self) -> Size {
+        Size::uniform(2)
+    }
+
+    fn outer_margin(&self) -> Size {
+        Size::uniform(2)
+    }
+
+    fn line_height(&self, _: TextClass) -> u32 {
+        LINE_HEIGHT
+    }
+
+    fn char_width(&self, _: TextClass) -> f32 {
+        CHAR_WIDTH as f32
+    }
+
+    fn text_bound(&mut self, text: &str, class: TextClass, axis: AxisInfo) -> SizeRules {
+        SizeRules::fixed(axis.extract_size(self.text_size(text, class, None)))
+    }
+
+    fn text_size(&mut self, text: &str, _: TextClass, _: Option<u32>) -> Size {
+        let width = text.lines().map(|line| line.chars().count()).max();
+        let lines = text.lines().count().max(1);
+        Size(
+            CHAR_WIDTH * width.unwrap_or(0) as u32,
+            LINE_HEIGHT * lines as u32,
+        )
+    }
+
+    fn button_surround(&self) -> (Size, Size) {
+        (Size::uniform(2), Size::uniform(2))
+    }
+
+    fn edit_surround(&self) -> (Size, Size) {
+        (Size::uniform(2), Size::uniform(2))
+    }
+
+    fn checkbox(&self) -> Size {
+        Size::uniform(LINE_HEIGHT)
+    }
+
+    fn radiobox(&self) -> Size {
+        Size::uniform(LINE_HEIGHT)
+    }
+
+    fn icon(&self) -> Size {
+        Size::uniform(LINE_HEIGHT)
+    }
+
+    fn scrollbar(&self) -> (u32, u32, u32) {
+        (8, 16, 32)
+    }
+
+    fn separator(&self) -> u32 {
+        2
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::class::{HasBool, HasText};
+    use crate::data::{SelectionChanged, SelectionMode, SharedRc};
+    use crate::macros::VoidMsg;
+    use crate::widget::{CheckBox, Column, EditBox, Expander, Filler, FlowBox, Form, Label};
+    use crate::widget::{ListView, RichLabel, RowSplitter, ScrollBar, ScrollRegion, SingleView};
+    use crate::widget::{SpinBox, Stack, TextArea, TextButton, Window, ZoomView};
+    use crate::Vertical;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Debug, PartialEq, VoidMsg)]
+    struct Toggled(bool);
+
+    /// Records the width given to each query of height rules
+    #[widget]
+    #[handler]
+    #[derive(Clone, Debug, Default, Widget)]
+    struct WidthProbe {
+        #[core]
+        core: CoreData,
+        widths: Vec<Option<u32>>,
+    }
+
+    impl Layout for WidthProbe {
+        fn size_rules(&mut self, _: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+            if axis.is_horizontal() {
+                return SizeRules::fixed(100);
+            }
+            self.widths.push(axis.other());
+            SizeRules::fixed(10)
+        }
+
+        fn draw(&self, _: &mut dyn DrawHandle, _: &Manager) {}
+    }
+
+    /// Records handle updates, reconfiguring the window on each
+    #[handler]
+    #[derive(Clone, Debug, Widget)]
+    struct UpdateCounter {
+        #[core]
+        core: CoreData,
+        handle: UpdateHandle,
+        payloads: Vec<u64>,
+    }
+
+    impl UpdateCounter {
+        fn new(handle: UpdateHandle) -> Self {
+            UpdateCounter {
+                core: Default::default(),
+                handle,
+                payloads: vec![],
+            }
+        }
+    }
+
+    impl Widget for UpdateCounter {
+        fn configure(&mut self, mgr: &mut Manager) {
+            mgr.update_on_handle(self.handle, self.id());
+        }
+
+        fn update_handle(&mut self, mgr: &mut Manager, _: UpdateHandle, payload: u64) {
+            self.payloads.push(payload);
+            mgr.send_action(TkAction::Reconfigure);
+        }
+    }
+
+    impl Layout for UpdateCounter {
+        fn size_rules(&mut self, _: &mut dyn SizeHandle, _: AxisInfo) -> SizeRules {
+            SizeRules::EMPTY
+        }
+
+        fn draw(&self, _: &mut dyn DrawHandle, _: &Manager) {}
+    }
+
+    #[test]
+    fn edit_box() {
+        let mut harness = TestHarness::new(EditBox::new("").on_activate(|text| text.to_string()));
+        let id = harness.widget().id();
+        assert!(!harness.char_focus(id));
+        harness.click(id);
+        assert!(harness.char_focus(id));
+
+        harness.type_text("helo").key(VirtualKeyCode::Back);
+        harness.type_text("lo");
+        assert_eq!(harness.widget().get_text(), "hello");
+        assert!(harness.take_messages().is_empty());
+
+        harness.key(VirtualKeyCode::Return);
+        assert_eq!(harness.take_messages(), vec!["hello".to_string()]);
+
+        // The text is drawn with an empty selection and the caret at the end
+        let commands = harness.record_draw();
+        let text = commands.iter().any(|cmd| match cmd {
+            DrawCommand::TextSelected(_, text, range) => text == "hello" && *range == (5..5),
+            _ => false,
+        });
+        assert!(text, "{:?}", commands);
+        let caret = commands.iter().any(|cmd| match cmd {
+            DrawCommand::TextCaret(_, index) => *index == 5,
+            _ => false,
+        });
+        assert!(caret, "{:?}", commands);
+    }
+
+    #[test]
+    fn check_box() {
+        let mut harness = TestHarness::new(CheckBox::new("Check").on_toggle(Toggled));
+        let id = harness.widget().id();
+        assert!(!harness.widget().get_bool());
+
+        harness.click(id);
+        assert!(harness.widget().get_bool());
+        assert_eq!(harness.take_messages(), vec![Toggled(true)]);
+        let checked = |commands: Vec<DrawCommand>| {
+            commands.into_iter().any(|cmd| match cmd {
+                DrawCommand::CheckBox(_, state, _) => state == Some(true),
+                _ => false,
+            })
+        };
+        assert!(checked(harness.record_draw()));
+
+        harness.click(id);
+        assert!(!harness.widget().get_bool());
+        assert_eq!(harness.take_messages(), vec![Toggled(false)]);
+        assert!(!checked(harness.record_draw()));
+    }
+
+    #[test]
+    fn scroll_bar_keys() {
+        let bar = ScrollBar::<Vertical>::new()
+            .with_limits(100, 10)
+            .with_line_step(5);
+        let mut harness = TestHarness::new(bar);
+
+        // Keys are ignored without focus
+        harness.key(VirtualKeyCode::Down);
+        assert_eq!(harness.widget().value(), 0);
+
+        // Navigation keys are received with key focus, without activation
+        harness.key(VirtualKeyCode::Tab);
+        harness.key(VirtualKeyCode::Down).key(VirtualKeyCode::Down);
+        assert_eq!(harness.widget().value(), 10);
+        harness.key(VirtualKeyCode::PageDown);
+        assert_eq!(harness.widget().value(), 20);
+        harness.key(VirtualKeyCode::End);
+        assert_eq!(harness.widget().value(), 100);
+        assert_eq!(harness.take_messages(), vec![5, 10, 20, 100]);
+        let id = harness.widget().id();
+        assert!(!harness.char_focus(id));
+    }
+
+    #[test]
+    fn splitter_keys() {
+        let splitter = RowSplitter::new(vec![Filler::new(), Filler::new()]);
+        let mut harness = TestHarness::new(splitter);
+        let width =
+            |h: &TestHarness<RowSplitter<Filler>>, i| h.widget().get(i).unwrap().rect().size.0;
+        assert_eq!((width(&harness, 0), width(&harness, 1)), (396, 396));
+
+        // Children are resized directly, without a reconfigure
+        harness.key(VirtualKeyCode::Tab).key(VirtualKeyCode::Right);
+        assert_eq!((width(&harness, 0), width(&harness, 1)), (404, 388));
+        assert_eq!(harness.widget().get(1).unwrap().rect().pos.0, 412);
+        harness.key(VirtualKeyCode::End);
+        assert_eq!((width(&harness, 0), width(&harness, 1)), (792, 0));
+        harness.key(VirtualKeyCode::Home);
+        assert_eq!((width(&harness, 0), width(&harness, 1)), (0, 792));
+
+        // The split is retained on resize
+        harness.resize(Size(408, 600));
+        assert_eq!((width(&harness, 0), width(&harness, 1)), (0, 400));
+    }
+
+    #[test]
+    fn splitter_rtl() {
+        let splitter = RowSplitter::new(vec![Filler::new(), Filler::new()]);
+        let mut harness = TestHarness::new(splitter);
+        harness.set_layout_direction(LayoutDirection::RightToLeft);
+        let rect = |h: &TestHarness<RowSplitter<Filler>>, i| h.widget().get(i).unwrap().rect();
+        assert_eq!(rect(&harness, 0).pos.0, 404);
+        assert_eq!(rect(&harness, 1).pos.0, 0);
+
+        // The first child is on the right, thus Left grows it
+        harness.key(VirtualKeyCode::Tab).key(VirtualKeyCode::Left);
+        assert_eq!(rect(&harness, 0), Rect::new(Coord(396, 0), Size(404, 600)));
+        assert_eq!(rect(&harness, 1).size.0, 388);
+
+        // Dragging the handle right shrinks the first child
+        let start = rect(&harness, 1).pos + Coord(392, 300);
+        harness.move_to(start).mouse_button(ElementState::Pressed);
+        harness.move_to(start + Coord(100, 0));
+        harness.mouse_button(ElementState::Released);
+        assert_eq!(rect(&harness, 0).size.0, 304);
+        assert_eq!(rect(&harness, 1).size.0, 488);
+    }
+
+    #[test]
+    fn stack_hides_inactive() {
+        let boxes = vec![
+            CheckBox::new("A").on_toggle(Toggled),
+            CheckBox::new("B").on_toggle(Toggled),
+        ];
+        let mut harness = TestHarness::new(Stack::new(boxes));
+        let id = |h: &TestHarness<Stack<_>>, i| h.widget().get(i).unwrap().id();
+        let (a, b) = (id(&harness, 0), id(&harness, 1));
+        assert!(harness.widget().find(a).is_some());
+        assert!(harness.widget().find(b).is_none());
+
+        // Both children overlap; only the active child receives the click
+        harness.click(a);
+        assert_eq!(harness.take_messages(), vec![Toggled(true)]);
+
+        // Key focus goes to the active child
+        let mut focus = None;
+        harness.widget().get(0).unwrap().walk(&mut |w| {
+            if w.allow_focus() && focus.is_none() {
+                focus = Some(w.id());
+            }
+        });
+        let focus = focus.unwrap();
+        harness.key(VirtualKeyCode::Tab);
+        assert!(harness.mgr.manager(&mut harness.tkw).key_focus(focus));
+
+        // Focus does not survive hiding the child
+        harness.with_widget(|stack, mgr| stack.set_active(mgr, 1));
+        assert!(harness.widget().find(a).is_none());
+        assert!(!harness.mgr.manager(&mut harness.tkw).key_focus(focus));
+        harness.key(VirtualKeyCode::Space);
+        assert!(harness.take_messages().is_empty());
+        harness.click(b);
+        assert_eq!(harness.take_messages(), vec![Toggled(true)]);
+
+        // Only the active child and the stack itself are walked
+        let (mut count, mut child_count) = (0, 0);
+        harness.widget().walk(&mut |_| count += 1);
+        harness
+            .widget()
+            .get(1)
+            .unwrap()
+            .walk(&mut |_| child_count += 1);
+        assert_eq!(count, child_count + 1);
+    }
+
+    #[test]
+    fn stack_find_key() {
+        let boxes = vec![
+            CheckBox::new("A").on_toggle(Toggled).with_key("a"),
+            CheckBox::new("B").on_toggle(Toggled).with_key("b"),
+        ];
+        let mut harness = TestHarness::new(Stack::new(boxes));
+        let id = |h: &TestHarness<Stack<_>>, i| h.widget().get(i).unwrap().id();
+        let (a, b) = (id(&harness, 0), id(&harness, 1));
+
+        // Inactive pages are not searched
+        assert_eq!(harness.widget().find_key("a").map(|w| w.id()), Some(a));
+        assert!(harness.widget().find_key("b").is_none());
+        harness.with_widget(|stack, mgr| {
+            assert!(stack.find_key_mut("b").is_none());
+            stack.set_active(mgr, 1);
+            assert_eq!(stack.find_key_mut("b").map(|w| w.id()), Some(b));
+            assert!(stack.find_key_mut("a").is_none());
+        });
+    }
+
+    #[test]
+    fn single_view() {
+        let model = SharedRc::new(1);
+        let mut harness = TestHarness::new(SingleView::new(model.clone()));
+        let text = |h: &TestHarness<SingleView<SharedRc<i32>>>| {
+            let label = h.widget().get(0).unwrap().downcast_ref::<Label>();
+            label.unwrap().get_text().to_string()
+        };
+        assert_eq!(text(&harness), "1");
+
+        harness.with_widget(|_, mgr| model.set(mgr, 2));
+        assert_eq!(text(&harness), "2");
+    }
+
+    #[test]
+    fn list_view() {
+        let model = SharedRc::new(vec!["a", "b", "c"]);
+        let view =
+            ListView::<Vertical, _>::new(model.clone()).with_selection_mode(SelectionMode::Single);
+        let mut harness = TestHarness::new(view);
+        let len = |h: &TestHarness<ListView<Vertical, _>>| h.widget().get(0).unwrap().len();
+        assert_eq!(len(&harness), 3);
+
+        let id = harness.widget().get(0).unwrap().get(2).unwrap().id();
+        harness.click(id);
+        assert_eq!(harness.take_messages(), vec![SelectionChanged]);
+        assert!(harness.widget().selection().is_selected(2));
+
+        // Shrinking the model removes items and truncates the selection
+        harness.with_widget(|_, mgr| model.update(mgr, |v| v.truncate(1)));
+        assert_eq!(len(&harness), 1);
+        assert!(harness.widget().selection().is_empty());
+    }
+
+    #[test]
+    fn batch() {
+        let handle = UpdateHandle::new();
+        let mut harness = TestHarness::new(UpdateCounter::new(handle));
+        let configures = harness.configures;
+
+        // Without a batch, each update is delivered and applied separately
+        harness.with_widget(|_, mgr| {
+            for i in 0..10 {
+                mgr.trigger_update(handle, i);
+            }
+        });
+        assert_eq!(harness.widget().payloads, (0..10).collect::<Vec<_>>());
+        assert_eq!(harness.configures, configures + 10);
+
+        // Within a batch, updates are coalesced: the last is delivered once
+        harness.with_widget(|_, mgr| {
+            mgr.with_batch(|mgr| {
+                for i in 0..100 {
+                    mgr.trigger_update(handle, i);
+                }
+            })
+        });
+        assert_eq!(harness.widget().payloads.len(), 11);
+        assert_eq!(harness.widget().payloads.last(), Some(&99));
+        assert_eq!(harness.configures, configures + 11);
+    }
+
+    #[test]
+    fn redraw_region() {
+        let labels = (0..20).map(Label::new).collect();
+        let region = ScrollRegion::new(Column::new(labels));
+        let mut harness = TestHarness::new_with_size(region, Size(100, 100));
+        let last = harness.widget().inner().get(19).unwrap().id();
+
+        // The last label is scrolled out of view
+        harness.mgr.clear_redraw();
+        harness.with_widget(|_, mgr| mgr.redraw(last));
+        assert!(harness.mgr.redraw_requested());
+        assert_eq!(harness.mgr.redraw_region(&harness.widget), None);
+
+        // Once visible, its rect is reported with the scroll offset applied
+        harness.with_widget(|region, mgr| {
+            let offset = region.max_offset();
+            region.set_offset(mgr, offset);
+        });
+        harness.mgr.clear_redraw();
+        harness.with_widget(|_, mgr| mgr.redraw(last));
+        let rect = harness.widget().inner().get(19).unwrap().rect();
+        let offset = harness.widget().offset();
+        assert!(offset != Coord::ZERO);
+        let region = harness.mgr.redraw_region(&harness.widget);
+        assert_eq!(region, Some(rect - offset));
+    }
+
+    #[test]
+    fn text_area() {
+        let area = TextArea::new("").on_change(|text| text.to_string());
+        let mut harness = TestHarness::new(area);
+        let id = harness.widget().id();
+        harness.click(id);
+        assert!(harness.char_focus(id));
+
+        // Return inserts a line break; each edit is reported
+        harness.type_text("ab").key(VirtualKeyCode::Return);
+        harness.type_text("cd");
+        assert_eq!(harness.widget().get_text(), "ab\ncd");
+        let msgs = harness.take_messages();
+        assert_eq!(msgs.len(), 5);
+        assert_eq!(msgs.last().unwrap(), "ab\ncd");
+
+        // Vertical movement preserves the column
+        harness.key(VirtualKeyCode::Left).key(VirtualKeyCode::Up);
+        assert_eq!(harness.widget().cursor(), 1);
+
+        // Shift extends the selection; navigation is not an edit
+        let shift = Modifiers {
+            shift: true,
+            ..Modifiers::default()
+        };
+        harness.key_with(shift, VirtualKeyCode::Down);
+        assert_eq!(harness.widget().selected_text(), "b\nc");
+        assert!(harness.take_messages().is_empty());
+
+        let ctrl = Modifiers {
+            ctrl: true,
+            ..Modifiers::default()
+        };
+        harness.key_with(ctrl, VirtualKeyCode::X);
+        assert_eq!(harness.widget().get_text(), "ad");
+        assert_eq!(harness.clipboard(), Some("b\nc"));
+        assert_eq!(harness.take_messages(), vec!["ad".to_string()]);
+    }
+
+    #[test]
+    fn spin_box() {
+        let mut harness = TestHarness::new(SpinBox::new(0, 10, 3).with_value(8));
+
+        // Up and Down step the value while the edit box has focus
+        harness.key(VirtualKeyCode::Up);
+        assert_eq!(harness.widget().value(), 8);
+        harness.key(VirtualKeyCode::Tab).key(VirtualKeyCode::Up);
+        assert_eq!(harness.widget().value(), 10);
+        assert_eq!(harness.take_messages(), vec![10]);
+        harness.key(VirtualKeyCode::Up);
+        assert!(harness.take_messages().is_empty());
+        harness.key(VirtualKeyCode::Down);
+        assert_eq!(harness.take_messages(), vec![7]);
+
+        // Text is committed on activation; out-of-range text is discarded
+        let ctrl = Modifiers {
+            ctrl: true,
+            ..Modifiers::default()
+        };
+        harness.key(VirtualKeyCode::Return);
+        harness.key_with(ctrl, VirtualKeyCode::A).type_text("4");
+        harness.key(VirtualKeyCode::Return);
+        assert_eq!(harness.widget().value(), 4);
+        assert_eq!(harness.take_messages(), vec![4]);
+        harness.key_with(ctrl, VirtualKeyCode::A).type_text("12");
+        harness.key(VirtualKeyCode::Return);
+        assert_eq!(harness.widget().value(), 4);
+        assert!(harness.take_messages().is_empty());
+
+        // With wrapping, stepping past one end moves to the other
+        let mut harness = TestHarness::new(SpinBox::new(0, 10, 3).with_wrap(true));
+        harness.key(VirtualKeyCode::Tab).key(VirtualKeyCode::Down);
+        assert_eq!(harness.take_messages(), vec![10]);
+        harness.key(VirtualKeyCode::Up);
+        assert_eq!(harness.take_messages(), vec![0]);
+    }
+
+    #[test]
+    fn form() {
+        let form = Form::new()
+            .with_row("&Name", EditBox::new(""))
+            .with_row("&Age", EditBox::new(""))
+            .with_row("&Email address", EditBox::new(""));
+        let mut harness = TestHarness::new(form);
+        let field = |h: &TestHarness<Form<EditBox<()>>>, n| h.widget().field(n).rect();
+        let label = |h: &TestHarness<Form<EditBox<()>>>, n| h.widget().get(2 * n).unwrap().rect();
+
+        // Labels are in the first column, fields aligned in the second
+        for n in 0..3 {
+            assert_eq!(field(&harness, n).pos.0, field(&harness, 0).pos.0);
+            let rect = label(&harness, n);
+            assert!(rect.pos.0 + rect.size.0 as i32 <= field(&harness, n).pos.0);
+        }
+        // Rows are evenly spaced
+        let spacing = field(&harness, 1).pos.1 - field(&harness, 0).pos.1;
+        assert!(spacing > 0);
+        assert_eq!(field(&harness, 2).pos.1 - field(&harness, 1).pos.1, spacing);
+
+        // Mnemonics and clicks on labels give the field character focus
+        let ids: Vec<_> = (0..3).map(|n| harness.widget().field(n).id()).collect();
+        let alt = Modifiers {
+            alt: true,
+            ..Modifiers::default()
+        };
+        harness.key_with(alt, VirtualKeyCode::A);
+        assert!(harness.char_focus(ids[1]));
+        harness.key_with(alt, VirtualKeyCode::N);
+        assert!(harness.char_focus(ids[0]));
+        let label_id = harness.widget().get(4).unwrap().id();
+        harness.click(label_id);
+        assert!(harness.char_focus(ids[2]));
+    }
+
+    #[test]
+    fn zoom_view() {
+        let boxes = vec![
+            CheckBox::new("A").on_toggle(Toggled),
+            CheckBox::new("B").on_toggle(Toggled),
+        ];
+        let view = ZoomView::new(Column::new(boxes));
+        let mut harness = TestHarness::new_with_size(view, Size(200, 200));
+        let target = Coord(100, 60);
+
+        // Zoom, then pan such that the second box is under `target`
+        harness.with_widget(|view, mgr| {
+            view.set_zoom(mgr, 2.0);
+            let rect = view.inner()[1].rect();
+            let centre = rect.pos + Coord(rect.size.0 as i32 / 2, rect.size.1 as i32 / 2);
+            let p = view
+                .transform()
+                .map_point((centre.0 as f32, centre.1 as f32));
+            let pan = view.pan();
+            let pan = (pan.0 + target.0 as f32 - p.0, pan.1 + target.1 as f32 - p.1);
+            view.set_pan(mgr, pan);
+        });
+        assert_eq!(harness.widget().zoom(), 2.0);
+
+        // Clicks are mapped to the child's coordinate space
+        harness.click_at(target);
+        assert_eq!(harness.take_messages(), vec![Toggled(true)]);
+        assert!(!harness.widget().inner()[0].get_bool());
+        assert!(harness.widget().inner()[1].get_bool());
+
+        // Wheel input not used by the child pans the view
+        let pan = harness.widget().pan();
+        harness.scroll_wheel(0.0, 1.0);
+        let expected = pan.1 + 3.0 * LINE_HEIGHT as f32;
+        assert_eq!(harness.widget().pan(), (pan.0, expected));
+    }
+
+    #[test]
+    fn expander() {
+        let expander = Expander::new("More", CheckBox::new("Check").on_toggle(Toggled));
+        let mut harness = TestHarness::new(expander);
+        let id = harness.widget().id();
+        let child_id = harness.widget().inner().id();
+        let configures = harness.configures;
+
+        // Clicking the header expands, reconfiguring the window
+        harness.click(id);
+        assert!(harness.widget().is_expanded());
+        assert_eq!(harness.configures, configures + 1);
+
+        // The child is placed below the header and receives input
+        let rect = harness.widget().rect();
+        let child_rect = harness.widget().inner().rect();
+        assert!(child_rect.pos.1 > rect.pos.1);
+        let bottom = |r: Rect| r.pos.1 + r.size.1 as i32;
+        assert_eq!(bottom(child_rect), bottom(rect));
+        harness.click(child_id);
+        assert_eq!(harness.take_messages(), vec![Toggled(true)]);
+
+        // Clicking the header collapses; the child then receives no input
+        harness.click_at(rect.pos + Coord(10, 2));
+        assert!(!harness.widget().is_expanded());
+        assert_eq!(harness.configures, configures + 2);
+        harness.click_at(child_rect.pos + Coord(10, 2));
+        assert!(harness.take_messages().is_empty());
+        assert!(harness.widget().is_expanded());
+    }
+
+    #[test]
+    fn flow_box() {
+        let labels = (1..=5).map(|i| Label::new(format!("tag{}", i))).collect();
+        let mut harness = TestHarness::new(FlowBox::new(labels));
+        let pos = |h: &TestHarness<FlowBox<Label>>, n: usize| h.widget()[n].rect().pos;
+        let origin = harness.widget().rect().pos;
+        let (w, h) = (4 * CHAR_WIDTH as i32, LINE_HEIGHT as i32);
+
+        // With enough width, children are placed in one row at ideal width
+        for n in 0..5 {
+            assert_eq!(pos(&harness, n), origin + Coord(n as i32 * w, 0));
+            assert_eq!(harness.widget()[n].rect().size, Size(w as u32, h as u32));
+        }
+
+        // Resizing the window wraps children into rows
+        harness.resize(Size(5 * w as u32 / 2, 600));
+        let expected = [(0, 0), (w, 0), (0, h), (w, h), (0, 2 * h)];
+        for (n, offset) in expected.iter().enumerate() {
+            assert_eq!(pos(&harness, n), origin + Coord(offset.0, offset.1));
+        }
+
+        // Heights are queried for the width allocated to each child
+        let harness = TestHarness::new(FlowBox::new(vec![WidthProbe::default()]));
+        assert_eq!(harness.widget()[0].widths, vec![Some(100)]);
+    }
+
+    #[test]
+    fn rich_label() {
+        let label = RichLabel::from_markup("Hello <b>bold</b> &amp; <i>more</i>").unwrap();
+        let mut harness = TestHarness::new(FlowBox::new(vec![label]));
+        let text = "Hello bold & more";
+        assert_eq!(harness.widget()[0].get_text(), text);
+        let spans: Vec<_> = harness.widget()[0].rich_text().spans().collect();
+        assert!(spans.iter().any(|(s, style)| *s == "bold" && style.bold));
+        assert!(spans.iter().any(|(s, style)| *s == "more" && style.italic));
+
+        // Sized and drawn as the plain text
+        let rect = harness.widget()[0].rect();
+        let size = Size(text.len() as u32 * CHAR_WIDTH, LINE_HEIGHT);
+        assert_eq!(rect.size, size);
+        let commands = harness.record_draw();
+        let expected = DrawCommand::Text(rect, text.to_string(), TextClass::Label);
+        assert!(commands.contains(&expected), "{:?}", commands);
+
+        // Setting the text resizes the label
+        let configures = harness.configures;
+        harness.with_widget(|flow, mgr| {
+            let text = RichText::parse("<big>Hello</big> world!").unwrap();
+            flow[0].set_rich_text(mgr, text);
+        });
+        assert_eq!(harness.configures, configures + 1);
+        assert_eq!(harness.widget()[0].rect().size.0, 12 * CHAR_WIDTH);
+    }
+
+    #[derive(Clone, Debug)]
+    struct Save;
+
+    #[test]
+    fn window_inspectors() {
+        static SAVES: AtomicUsize = AtomicUsize::new(0);
+        let mut window = Window::new("Inspect", TextButton::new("Save", Save));
+        window.add_inspector(&|_, _, msg| {
+            if let Some(Save) = msg.downcast_ref() {
+                SAVES.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+        let mut harness = TestHarness::new(window);
+        let id = harness.widget().get(0).unwrap().id();
+
+        // Returned messages are inspected and not passed further
+        harness.click(id);
+        assert_eq!(SAVES.load(Ordering::SeqCst), 1);
+        assert!(harness.take_messages().is_empty());
+    }
+}
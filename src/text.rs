@@ -0,0 +1,433 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Rich text
+//!
+//! [`RichText`] is a string divided into spans, each with a [`SpanStyle`].
+//! It may be built span-by-span or parsed from markup; see
+//! [`RichText::parse`]. Rich text is drawn via [`DrawHandle::rich_text`].
+//!
+//! [`DrawHandle::rich_text`]: crate::theme::DrawHandle::rich_text
+
+use std::fmt;
+
+use crate::draw::Colour;
+
+/// Scale factor applied by `<big>` and `<small>` markup tags
+const SCALE_STEP: f32 = 1.2;
+
+/// Style of a span of [`RichText`]
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpanStyle {
+    /// Use a bold font
+    pub bold: bool,
+    /// Use an italic font
+    pub italic: bool,
+    /// Text colour, or `None` to use the theme's colour for the text class
+    pub colour: Option<Colour>,
+    /// Font size, relative to the theme's font size
+    pub scale: f32,
+}
+
+impl Default for SpanStyle {
+    fn default() -> Self {
+        SpanStyle {
+            bold: false,
+            italic: false,
+            colour: None,
+            scale: 1.0,
+        }
+    }
+}
+
+impl SpanStyle {
+    /// Set bold (chain style)
+    #[inline]
+    pub fn with_bold(mut self, bold: bool) -> Self {
+        self.bold = bold;
+        self
+    }
+
+    /// Set italic (chain style)
+    #[inline]
+    pub fn with_italic(mut self, italic: bool) -> Self {
+        self.italic = italic;
+        self
+    }
+
+    /// Set the colour (chain style)
+    #[inline]
+    pub fn with_colour(mut self, colour: Colour) -> Self {
+        self.colour = Some(colour);
+        self
+    }
+
+    /// Set the relative font size (chain style)
+    #[inline]
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+}
+
+/// Text with styled spans
+///
+/// The text is stored as a single string, accessible via
+/// [`RichText::as_str`]; each span covers the text from its start up to the
+/// start of the next span.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RichText {
+    text: String,
+    spans: Vec<(usize, SpanStyle)>,
+}
+
+impl RichText {
+    /// Construct an empty instance
+    #[inline]
+    pub fn new() -> Self {
+        RichText::default()
+    }
+
+    /// Append a span of text with the given style
+    pub fn push(&mut self, text: &str, style: SpanStyle) {
+        if text.is_empty() {
+            return;
+        }
+        self.spans.push((self.text.len(), style));
+        self.text.push_str(text);
+    }
+
+    /// Append a span of text with the given style (chain style)
+    #[inline]
+    pub fn with_span(mut self, text: &str, style: SpanStyle) -> Self {
+        self.push(text, style);
+        self
+    }
+
+    /// Get the text, without styling
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// True if the text is empty
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Iterate over spans, in order
+    pub fn spans(&self) -> impl Iterator<Item = (&str, SpanStyle)> {
+        let ends = self.spans.iter().skip(1).map(|s| s.0);
+        let ends = ends.chain(std::iter::once(self.text.len()));
+        self.spans
+            .iter()
+            .zip(ends)
+            .map(move |(&(start, style), end)| (&self.text[start..end], style))
+    }
+
+    /// Parse from markup
+    ///
+    /// A small subset of [Pango markup] is supported. Tags may be nested and
+    /// must be closed in order:
+    ///
+    /// -   `<b>` (bold) and `<i>` (italic)
+    /// -   `<big>` and `<small>`: increase or decrease the font size
+    /// -   `<span>` with attributes `color` (or `colour`), of the form
+    ///     `#rrggbb`, and `size`, a font size relative to the enclosing text
+    ///     (e.g. `size="1.5"`)
+    ///
+    /// The entities `&lt;`, `&gt;`, `&amp;`, `&quot;` and `&apos;` are
+    /// recognised.
+    ///
+    /// [Pango markup]: https://developer.gnome.org/pango/stable/PangoMarkupFormat.html
+    pub fn parse(markup: &str) -> Result<Self, MarkupError> {
+        Parser {
+            markup,
+            pos: 0,
+            result: RichText::new(),
+            stack: vec![],
+        }
+        .parse()
+    }
+}
+
+impl From<&str> for RichText {
+    fn from(text: &str) -> Self {
+        RichText::new().with_span(text, SpanStyle::default())
+    }
+}
+
+impl From<String> for RichText {
+    fn from(text: String) -> Self {
+        RichText {
+            spans: if text.is_empty() {
+                vec![]
+            } else {
+                vec![(0, SpanStyle::default())]
+            },
+            text,
+        }
+    }
+}
+
+/// Error returned by [`RichText::parse`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MarkupError {
+    /// Byte position within the input
+    pub pos: usize,
+    /// Description of the error
+    pub msg: &'static str,
+}
+
+impl fmt::Display for MarkupError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid markup at byte {}: {}", self.pos, self.msg)
+    }
+}
+
+impl std::error::Error for MarkupError {}
+
+struct Parser<'a> {
+    markup: &'a str,
+    pos: usize,
+    result: RichText,
+    // Open tags, with the style of enclosing text
+    stack: Vec<(&'a str, SpanStyle)>,
+}
+
+impl<'a> Parser<'a> {
+    fn err<T>(&self, msg: &'static str) -> Result<T, MarkupError> {
+        Err(MarkupError { pos: self.pos, msg })
+    }
+
+    fn parse(mut self) -> Result<RichText, MarkupError> {
+        let mut style = SpanStyle::default();
+        let mut text = String::new();
+        while let Some(c) = self.markup[self.pos..].chars().next() {
+            match c {
+                '<' => {
+                    self.result.push(&text, style);
+                    text.clear();
+                    style = self.tag(style)?;
+                }
+                '&' => text.push(self.entity()?),
+                c => {
+                    text.push(c);
+                    self.pos += c.len_utf8();
+                }
+            }
+        }
+        if !self.stack.is_empty() {
+            return self.err("unclosed tag");
+        }
+        self.result.push(&text, style);
+        Ok(self.result)
+    }
+
+    // Parse an entity, starting at '&'
+    fn entity(&mut self) -> Result<char, MarkupError> {
+        let rest = &self.markup[self.pos..];
+        let end = match rest.find(';') {
+            Some(end) => end,
+            None => return self.err("unterminated entity"),
+        };
+        let c = match &rest[1..end] {
+            "lt" => '<',
+            "gt" => '>',
+            "amp" => '&',
+            "quot" => '"',
+            "apos" => '\'',
+            _ => return self.err("unknown entity"),
+        };
+        self.pos += end + 1;
+        Ok(c)
+    }
+
+    // Parse a tag, starting at '<', returning the new style
+    fn tag(&mut self, style: SpanStyle) -> Result<SpanStyle, MarkupError> {
+        let markup = self.markup;
+        let rest = &markup[self.pos..];
+        let end = match rest.find('>') {
+            Some(end) => end,
+            None => return self.err("unterminated tag"),
+        };
+        let content = &rest[1..end];
+
+        if let Some(name) = content.strip_prefix('/') {
+            let name = name.trim();
+            match self.stack.pop() {
+                Some((open, outer)) if open == name => {
+                    self.pos += end + 1;
+                    return Ok(outer);
+                }
+                _ => return self.err("mismatched closing tag"),
+            }
+        }
+
+        let mut words = content.splitn(2, char::is_whitespace);
+        let name = words.next().unwrap_or("");
+        let attrs = words.next().unwrap_or("").trim();
+        if name != "span" && !attrs.is_empty() {
+            return self.err("unexpected attributes");
+        }
+        let new_style = match name {
+            "b" => style.with_bold(true),
+            "i" => style.with_italic(true),
+            "big" => style.with_scale(style.scale * SCALE_STEP),
+            "small" => style.with_scale(style.scale / SCALE_STEP),
+            "span" => self.span_attrs(attrs, style)?,
+            _ => return self.err("unknown tag"),
+        };
+        self.stack.push((name, style));
+        self.pos += end + 1;
+        Ok(new_style)
+    }
+
+    // Parse attributes of a span tag
+    fn span_attrs(&self, mut attrs: &str, mut style: SpanStyle) -> Result<SpanStyle, MarkupError> {
+        while !attrs.is_empty() {
+            let eq = match attrs.find('=') {
+                Some(eq) => eq,
+                None => return self.err("expected '=' in attribute"),
+            };
+            let key = attrs[..eq].trim();
+            let rest = attrs[eq + 1..].trim_start();
+            let quote = match rest.chars().next() {
+                Some(q) if q == '"' || q == '\'' => q,
+                _ => return self.err("expected quoted attribute value"),
+            };
+            let len = match rest[1..].find(quote) {
+                Some(len) => len,
+                None => return self.err("unterminated attribute value"),
+            };
+            let value = &rest[1..1 + len];
+            attrs = rest[len + 2..].trim_start();
+
+            match key {
+                "color" | "colour" => match parse_colour(value) {
+                    Some(col) => style.colour = Some(col),
+                    None => return self.err("invalid colour"),
+                },
+                "size" => match value.parse::<f32>() {
+                    Ok(x) if x > 0.0 && x.is_finite() => style.scale *= x,
+                    _ => return self.err("invalid size"),
+                },
+                _ => return self.err("unknown attribute"),
+            }
+        }
+        Ok(style)
+    }
+}
+
+// Parse a colour of the form #rrggbb
+pub(crate) fn parse_colour(s: &str) -> Option<Colour> {
+    if s.len() != 7 || !s.starts_with('#') {
+        return None;
+    }
+    let c = |i: usize| u8::from_str_radix(s.get(i..i + 2)?, 16).ok();
+    let (r, g, b) = (c(1)?, c(3)?, c(5)?);
+    let f = |x: u8| x as f32 * (1.0 / 255.0);
+    Some(Colour::new(f(r), f(g), f(b)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Spans as (text, bold, italic, scale)
+    fn spans(markup: &str) -> Vec<(String, bool, bool, f32)> {
+        let text = RichText::parse(markup).unwrap();
+        let spans = text.spans();
+        spans
+            .map(|(s, style)| (s.to_string(), style.bold, style.italic, style.scale))
+            .collect()
+    }
+
+    fn err_pos(markup: &str) -> usize {
+        RichText::parse(markup).unwrap_err().pos
+    }
+
+    #[test]
+    fn plain() {
+        let text = RichText::parse("Hello world").unwrap();
+        assert_eq!(text.as_str(), "Hello world");
+        assert_eq!(
+            spans("Hello world"),
+            vec![("Hello world".into(), false, false, 1.0)]
+        );
+        assert!(RichText::parse("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn nesting() {
+        let big = SCALE_STEP;
+        assert_eq!(
+            spans("a<b>b<i>c</i><big>d</big></b>e"),
+            vec![
+                ("a".into(), false, false, 1.0),
+                ("b".into(), true, false, 1.0),
+                ("c".into(), true, true, 1.0),
+                ("d".into(), true, false, big),
+                ("e".into(), false, false, 1.0),
+            ]
+        );
+        let scale = spans("<big><big>x</big></big><small>y</small>");
+        assert_eq!(scale[0].3, big * big);
+        assert_eq!(scale[1].3, 1.0 / big);
+    }
+
+    #[test]
+    fn span_attributes() {
+        let text = RichText::parse("<span color='#ff0080' size=\"2\">x</span>").unwrap();
+        let (s, style) = text.spans().next().unwrap();
+        assert_eq!(s, "x");
+        assert_eq!(style.colour, Some(Colour::new(1.0, 0.0, 128.0 / 255.0)));
+        assert_eq!(style.scale, 2.0);
+
+        assert!(RichText::parse("<span colour=\"#000000\">x</span>").is_ok());
+        assert!(RichText::parse("<span color=\"red\">x</span>").is_err());
+        assert!(RichText::parse("<span size=\"-1\">x</span>").is_err());
+        assert!(RichText::parse("<span weight=\"bold\">x</span>").is_err());
+        assert!(RichText::parse("<span color>x</span>").is_err());
+        assert!(RichText::parse("<span color=\"#000000>x</span>").is_err());
+        assert!(RichText::parse("<b color=\"#000000\">x</b>").is_err());
+    }
+
+    #[test]
+    fn unbalanced() {
+        assert_eq!(err_pos("<b>x"), 4);
+        assert_eq!(err_pos("x</b>"), 1);
+        assert_eq!(err_pos("<b><i>x</b></i>"), 7);
+        assert_eq!(err_pos("<b"), 0);
+        assert_eq!(err_pos("<u>x</u>"), 0);
+    }
+
+    #[test]
+    fn escapes() {
+        let text = RichText::parse("&lt;b&gt; &amp; &quot;&apos;").unwrap();
+        assert_eq!(text.as_str(), "<b> & \"'");
+        assert_eq!(text.spans().count(), 1);
+
+        let text = RichText::parse("<b>&amp;</b>&amp;").unwrap();
+        assert_eq!(text.as_str(), "&&");
+        assert_eq!(text.spans().count(), 2);
+
+        assert_eq!(err_pos("a &nbsp; b"), 2);
+        assert_eq!(err_pos("a & b"), 2);
+        assert_eq!(err_pos("a &amp"), 2);
+    }
+
+    #[test]
+    fn colour() {
+        assert_eq!(parse_colour("#ffffff"), Some(Colour::grey(1.0)));
+        assert_eq!(parse_colour("#000000"), Some(Colour::grey(0.0)));
+        assert_eq!(parse_colour("ffffff"), None);
+        assert_eq!(parse_colour("#fff"), None);
+        assert_eq!(parse_colour("#gggggg"), None);
+    }
+}
@@ -18,18 +18,27 @@
 //!
 //! [`Widget`]: crate::Widget
 
+mod colours;
+mod config;
+
 use std::any::Any;
+use std::ops::Range;
 
 use rusttype::Font;
 
-use kas::draw::Colour;
+use kas::draw::{Colour, ImageData, Transform};
 use kas::event::HighlightState;
 use kas::geom::{Coord, Rect, Size};
 use kas::layout::{AxisInfo, SizeRules};
-use kas::{Align, Direction};
+use kas::text::RichText;
+use kas::{Align, Direction, LayoutDirection};
+
+pub use colours::{ColourScheme, MIN_HINT_CONTRAST, MIN_TEXT_CONTRAST};
+pub use config::ThemeConfig;
 
 /// Class of text drawn
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TextClass {
     /// Label text is drawn over the background colour
     Label,
@@ -39,10 +48,17 @@ pub enum TextClass {
     Edit,
     /// Class of text drawn in a multi-line edit box
     EditMulti,
+    /// Class of placeholder (hint) text drawn in an empty edit box
+    Placeholder,
+    /// Monospace text, e.g. code
+    ///
+    /// White-space is preserved and lines are not wrapped.
+    Monospace,
 }
 
 /// Text alignment, class, etc.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TextProperties {
     /// Class of text
     pub class: TextClass,
@@ -53,6 +69,42 @@ pub struct TextProperties {
     // Note: do we want to add HighlightState?
 }
 
+/// Style of a text underline
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UnderlineStyle {
+    /// A straight line, in the text colour
+    Solid,
+    /// A wavy line in an error colour, e.g. marking a misspelled word
+    Wavy,
+}
+
+/// Validation state of an input widget
+///
+/// States are ordered by severity, thus the combined state of several widgets
+/// is the maximum of their states.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ValidationState {
+    /// Not validated
+    #[default]
+    None,
+    /// Input is valid
+    Ok,
+    /// Input is acceptable, but the user should check it
+    Warning,
+    /// Input is invalid
+    Error,
+}
+
+impl ValidationState {
+    /// True unless in the `Error` state
+    #[inline]
+    pub fn is_valid(self) -> bool {
+        self != ValidationState::Error
+    }
+}
+
 /// Toolkit actions needed after theme adjustment, if any
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
 pub enum ThemeAction {
@@ -79,6 +131,28 @@ pub trait ThemeApi {
     // TODO: revise scheme identification and error handling?
     fn set_colours(&mut self, _scheme: &str) -> ThemeAction;
 
+    /// Change the colour scheme to a custom palette
+    ///
+    /// Themes may ignore this (the default implementation does nothing).
+    fn set_colour_scheme(&mut self, _scheme: ColourScheme) -> ThemeAction {
+        ThemeAction::None
+    }
+
+    /// Apply configuration, e.g. as loaded from a file
+    ///
+    /// Parameters not given by `config` are unchanged. The default
+    /// implementation applies only the font size and colour scheme.
+    fn apply_config(&mut self, config: &ThemeConfig) -> ThemeAction {
+        let mut action = ThemeAction::None;
+        if let Some(size) = config.font_size {
+            action = action.max(self.set_font_size(size));
+        }
+        if let Some(scheme) = config.colours.as_ref() {
+            action = action.max(self.set_colour_scheme(scheme.clone()));
+        }
+        action
+    }
+
     /// Change the theme itself
     ///
     /// Themes may do nothing, or may react according to their own
@@ -86,6 +160,29 @@ pub trait ThemeApi {
     fn set_theme(&mut self, _theme: &str) -> ThemeAction {
         ThemeAction::None
     }
+
+    /// Enable or disable touch-mode metrics
+    ///
+    /// In touch mode, themes should use larger controls (e.g. checkboxes and
+    /// scroll bars) and paddings, suitable for touch input. The event manager
+    /// may switch this automatically according to the last input device (see
+    /// [`ManagerState::set_touch_mode_auto`]).
+    ///
+    /// Themes may ignore this (the default implementation does nothing).
+    ///
+    /// [`ManagerState::set_touch_mode_auto`]: crate::event::ManagerState::set_touch_mode_auto
+    fn set_touch_mode(&mut self, _touch: bool) -> ThemeAction {
+        ThemeAction::None
+    }
+
+    /// Set the horizontal layout direction
+    ///
+    /// This is reported to widgets via [`SizeHandle::layout_direction`].
+    ///
+    /// Themes may ignore this (the default implementation does nothing).
+    fn set_layout_direction(&mut self, _direction: LayoutDirection) -> ThemeAction {
+        ThemeAction::None
+    }
 }
 
 /// A *theme* provides widget sizing and drawing implementations.
@@ -198,6 +295,19 @@ pub trait Window<Draw> {
 
 /// Handle passed to objects during draw and sizing operations
 pub trait SizeHandle {
+    /// The DPI factor
+    ///
+    /// On "standard" monitors this is 1. All other dimensions returned by this
+    /// handle are already scaled by this factor; it is provided for widgets
+    /// which must convert their own logical sizes to pixels.
+    fn dpi_factor(&self) -> f32;
+
+    /// The horizontal layout direction
+    ///
+    /// Layouts should mirror rows of child widgets and the default alignment
+    /// of text in right-to-left mode (see [`LayoutDirection`]).
+    fn layout_direction(&self) -> LayoutDirection;
+
     /// Size of a frame around child widget(s)
     ///
     /// Returns `(top_left, bottom_right)` dimensions as two `Size`s.
@@ -214,6 +324,13 @@ pub trait SizeHandle {
     /// The height of a line of text
     fn line_height(&self, class: TextClass) -> u32;
 
+    /// The advance width of a character
+    ///
+    /// This is the distance between the starts of adjacent characters. For
+    /// [`TextClass::Monospace`] this is the width of each (non-wide)
+    /// character; for other classes it is the width of a digit.
+    fn char_width(&self, class: TextClass) -> f32;
+
     /// Get a text label size bound
     ///
     /// Sizing requirements of [`DrawHandle::text`].
@@ -222,6 +339,25 @@ pub trait SizeHandle {
     /// passed directly.
     fn text_bound(&mut self, text: &str, class: TextClass, axis: AxisInfo) -> SizeRules;
 
+    /// Measure the size of some text
+    ///
+    /// Returns the size of `text` exactly as it would be drawn by
+    /// [`DrawHandle::text`] using the font and scale for the given `class`.
+    /// If `wrap_width` is given, text is wrapped to this width (if the `class`
+    /// permits wrapping); otherwise text is only broken on explicit line breaks.
+    ///
+    /// Unlike [`SizeHandle::text_bound`], no minimum or ideal sizes are
+    /// applied; this is intended for widgets doing their own text layout.
+    fn text_size(&mut self, text: &str, class: TextClass, wrap_width: Option<u32>) -> Size;
+
+    /// Get a rich text size bound
+    ///
+    /// Sizing requirements of [`DrawHandle::rich_text`]. The default
+    /// implementation measures the text without styling.
+    fn rich_text_bound(&mut self, text: &RichText, class: TextClass, axis: AxisInfo) -> SizeRules {
+        self.text_bound(text.as_str(), class, axis)
+    }
+
     /// Size of the sides of a button.
     ///
     /// Includes each side (as in `outer_frame`), minus the content area (to be added separately).
@@ -238,6 +374,11 @@ pub trait SizeHandle {
     /// Size of the element drawn by [`DrawHandle::radiobox`].
     fn radiobox(&self) -> Size;
 
+    /// Size of icons, e.g. on buttons
+    ///
+    /// This scales with the font size, such that icons match adjacent text.
+    fn icon(&self) -> Size;
+
     /// Dimensions for a scrollbar
     ///
     /// Returns three components:
@@ -250,6 +391,9 @@ pub trait SizeHandle {
     /// `min_handle_len` (so that some movement is always possible).
     /// It is required that `min_len >= min_handle_len`.
     fn scrollbar(&self) -> (u32, u32, u32);
+
+    /// Thickness of the line drawn by [`DrawHandle::separator`]
+    fn separator(&self) -> u32;
 }
 
 /// Handle passed to objects during draw and sizing operations
@@ -263,12 +407,58 @@ pub trait DrawHandle {
     /// (in the current coordinate space, i.e. not translated by `offset`).
     fn clip_region(&mut self, rect: Rect, offset: Coord, f: &mut dyn FnMut(&mut dyn DrawHandle));
 
+    /// Construct a new draw-handle on a transformed region and call `f` with it
+    ///
+    /// Content drawn by `f` is mapped through `transform` from the new region's
+    /// coordinate space to the current coordinate space, and is clipped to
+    /// `rect` (in the current coordinate space). Nested transforms compose.
+    /// Clipping is axis-aligned (in window coordinates); toolkits may not
+    /// support rotation of text.
+    ///
+    /// Event coordinates should be mapped through the inverse transform (see
+    /// [`Event::transform`](kas::event::Event::transform)); see for example
+    /// [`ZoomView`](crate::widget::ZoomView).
+    fn transform_region(
+        &mut self,
+        rect: Rect,
+        transform: Transform,
+        f: &mut dyn FnMut(&mut dyn DrawHandle),
+    );
+
+    /// Construct a new draw-handle with reduced opacity and call `f` with it
+    ///
+    /// All content drawn by `f` has its opacity multiplied by `opacity`
+    /// (clamped to the range `[0, 1]`); nested calls combine multiplicatively.
+    ///
+    /// Opacity is applied to each drawn element independently; where elements
+    /// overlap, lower elements may show through.
+    fn with_opacity(&mut self, opacity: f32, f: &mut dyn FnMut(&mut dyn DrawHandle));
+
+    /// Construct a new draw-handle drawing dimmed content and call `f` with it
+    ///
+    /// This is used by toolkits to draw a window whose input is blocked by a
+    /// modal window. The appearance is theme-dependent; typically the window
+    /// background and all content are darkened.
+    fn with_dimming(&mut self, f: &mut dyn FnMut(&mut dyn DrawHandle));
+
     /// Target area for drawing
     ///
     /// This is the `Rect` passed to [`Theme::draw_handle`] or
     /// [`DrawHandle::clip_region`], minus any offsets.
     fn target_rect(&self) -> Rect;
 
+    /// Draw a rectangle in the given colour
+    ///
+    /// Unlike other draw methods this is not themed; it is intended for custom
+    /// drawing, e.g. by a [`Canvas`](crate::widget::Canvas).
+    fn rect(&mut self, rect: Rect, col: Colour);
+
+    /// Draw a frame in the given colour
+    ///
+    /// The `outer` rect is expected to contain the `inner` rect. This is not
+    /// themed; see [`DrawHandle::rect`].
+    fn frame(&mut self, outer: Rect, inner: Rect, col: Colour);
+
     /// Draw a frame in the given [`Rect`]
     ///
     /// The frame dimensions should equal those of [`SizeHandle::outer_frame`].
@@ -279,18 +469,125 @@ pub trait DrawHandle {
     /// The dimensions required for this text may be queried with [`SizeHandle::text_bound`].
     fn text(&mut self, rect: Rect, text: &str, props: TextProperties);
 
+    /// Draw some rich text
+    ///
+    /// Each span is drawn in its own style; spans without a colour use the
+    /// colour for `props.class`. The dimensions required for this text may be
+    /// queried with [`SizeHandle::rich_text_bound`].
+    ///
+    /// The default implementation draws the text without styling.
+    fn rich_text(&mut self, rect: Rect, text: &RichText, props: TextProperties) {
+        self.text(rect, text.as_str(), props);
+    }
+
+    /// Draw some text, highlighting the given byte `range`
+    ///
+    /// This is as [`DrawHandle::text`], except that the text within `range` is
+    /// drawn in a highlight colour. The range is clamped to the text length.
+    fn text_selected(&mut self, rect: Rect, text: &str, range: Range<usize>, props: TextProperties);
+
+    /// Draw an underline below part of some text
+    ///
+    /// The `rect`, `text` and `props` should match those used to draw the
+    /// text; `range` is the byte range to underline. Only text decorations are
+    /// drawn, not the text itself.
+    fn text_underline(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        range: Range<usize>,
+        style: UnderlineStyle,
+        props: TextProperties,
+    );
+
+    /// Draw the background of a text selection
+    ///
+    /// The `rect`, `text` and `props` should match those used to draw the
+    /// text; `range` is the selected byte range. Widgets should call this
+    /// before drawing the text itself (usually via
+    /// [`DrawHandle::text_selected`]).
+    fn text_selection(
+        &mut self,
+        rect: Rect,
+        text: &str,
+        range: Range<usize>,
+        props: TextProperties,
+    );
+
+    /// Draw a text caret (edit cursor)
+    ///
+    /// The `rect`, `text` and `props` should match those used to draw the
+    /// text; the caret is drawn before the byte `index`. Widgets should only
+    /// call this when [`Manager::caret_visible`] is true, thus the caret blinks.
+    ///
+    /// [`Manager::caret_visible`]: crate::event::Manager::caret_visible
+    fn text_caret(&mut self, rect: Rect, text: &str, index: usize, props: TextProperties);
+
+    /// Draw the background of a selected item
+    ///
+    /// Views should call this before drawing the item itself.
+    fn selection_box(&mut self, rect: Rect);
+
+    /// Draw an indicator that scrolling has reached an edge
+    ///
+    /// The `rect` is a strip along the edge of the scroll region. Callers may
+    /// fade the indicator using [`DrawHandle::with_opacity`].
+    fn scroll_edge(&mut self, rect: Rect);
+
+    /// Draw an image
+    ///
+    /// The image is scaled to fill `rect`.
+    fn image(&mut self, rect: Rect, image: &ImageData);
+
+    /// Draw a highlight frame indicating interaction state
+    ///
+    /// This allows custom widgets to show hover, depress and keyboard-focus
+    /// states consistently with other widgets. The frame is drawn within
+    /// `rect` with width [`SizeHandle::inner_margin`]; content should be drawn
+    /// within the remaining area. Nothing is drawn when `highlights` is empty.
+    fn interaction_frame(&mut self, rect: Rect, highlights: HighlightState);
+
+    /// Draw a separator line
+    ///
+    /// The line fills `rect`, which should have thickness
+    /// [`SizeHandle::separator`] on one axis.
+    fn separator(&mut self, rect: Rect);
+
+    /// Draw the frame and background of a popup, e.g. a notification
+    ///
+    /// The frame dimensions should equal those of [`SizeHandle::outer_frame`].
+    fn popup_box(&mut self, rect: Rect);
+
     /// Draw button sides, background and margin-area highlight
     fn button(&mut self, rect: Rect, highlights: HighlightState);
 
     /// Draw edit box sides, background and margin-area highlight
-    fn edit_box(&mut self, rect: Rect, highlights: HighlightState);
+    ///
+    /// If `error` is true, the background is drawn in an error state.
+    fn edit_box(&mut self, rect: Rect, error: bool, highlights: HighlightState);
+
+    /// Draw a validation frame around an input widget
+    ///
+    /// The frame is drawn within `rect` with dimensions equal to those of
+    /// [`SizeHandle::outer_frame`], in a colour depending on `state`. Nothing
+    /// is drawn in the [`ValidationState::None`] state.
+    fn validation_frame(&mut self, rect: Rect, state: ValidationState);
+
+    /// Draw a validation message
+    ///
+    /// The text is drawn as [`TextClass::Label`] (wrapping within `rect`), in a
+    /// colour depending on `state`.
+    fn validation_message(&mut self, rect: Rect, text: &str, state: ValidationState);
 
     /// Draw UI element: checkbox
     ///
     /// The checkbox is a small, usually square, box with or without a check
     /// mark. A checkbox widget may include a text label, but that label is not
     /// part of this element.
-    fn checkbox(&mut self, rect: Rect, checked: bool, highlights: HighlightState);
+    ///
+    /// The `state` is `Some(checked)`, or `None` for the indeterminate (mixed)
+    /// state.
+    fn checkbox(&mut self, rect: Rect, state: Option<bool>, highlights: HighlightState);
 
     /// Draw UI element: radiobox
     ///
@@ -0,0 +1,338 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Colour schemes
+
+use log::warn;
+
+use super::{TextClass, ValidationState};
+use crate::draw::Colour;
+use crate::event::HighlightState;
+
+/// Minimum contrast ratio of text over its background
+///
+/// This is the WCAG 2.0 (level AA) recommendation for normal text.
+pub const MIN_TEXT_CONTRAST: f32 = 4.5;
+
+/// Minimum contrast ratio of placeholder text and the text caret
+///
+/// This is the WCAG 2.0 recommendation for large text and user interface
+/// components.
+pub const MIN_HINT_CONTRAST: f32 = 3.0;
+
+/// A theme's colour palette
+///
+/// Themes draw using these colours, thus a scheme may be constructed (or a
+/// standard scheme modified) then passed to a theme, either at startup or at
+/// run-time via [`ThemeApi::set_colour_scheme`](super::ThemeApi::set_colour_scheme).
+///
+/// Text colours are adjusted where necessary to ensure a minimum contrast over
+/// their background (see [`ColourScheme::text_class`]), thus overriding
+/// colours may not make text unreadable.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ColourScheme {
+    /// Window background
+    pub background: Colour,
+    /// Frames and borders
+    pub frame: Colour,
+    /// Background of editable text
+    pub text_area: Colour,
+    /// Background of editable text with invalid contents
+    pub text_area_error: Colour,
+    /// Editable text
+    pub text: Colour,
+    /// Error messages and frames
+    pub text_error: Colour,
+    /// Warning messages and frames
+    pub text_warning: Colour,
+    /// Success messages and frames
+    pub text_valid: Colour,
+    /// Label text
+    pub label_text: Colour,
+    /// Placeholder text of empty edit fields
+    pub placeholder_text: Colour,
+    /// Button text
+    pub button_text: Colour,
+    /// Highlight of the widget with keyboard-navigation focus
+    pub key_nav_focus: Colour,
+    /// Background of selected text, and selection boxes
+    pub selection: Colour,
+    /// Selected text
+    pub selection_text: Colour,
+    /// Text caret (edit cursor)
+    pub caret: Colour,
+    /// Buttons and scrollbars
+    pub button: Colour,
+    /// Buttons under the mouse
+    pub button_highlighted: Colour,
+    /// Depressed buttons
+    pub button_depressed: Colour,
+    /// Check marks
+    pub checkbox: Colour,
+}
+
+impl Default for ColourScheme {
+    fn default() -> Self {
+        ColourScheme::new()
+    }
+}
+
+impl ColourScheme {
+    /// Open the given standard scheme, if found
+    ///
+    /// Standard schemes are `default`, `light` and `dark`.
+    pub fn open(scheme: &str) -> Option<Self> {
+        Some(match scheme {
+            "default" => Self::new(),
+            "light" => Self::light(),
+            "dark" => Self::dark(),
+            other => {
+                warn!("ColourScheme::open: scheme \"{}\" not found", other);
+                return None;
+            }
+        })
+    }
+
+    /// Default theme: grey with blue activable items
+    pub fn new() -> Self {
+        ColourScheme {
+            background: Colour::grey(0.8),
+            frame: Colour::grey(0.7),
+            text_area: Colour::grey(1.0),
+            text_area_error: Colour::new(1.0, 0.7, 0.7),
+            text: Colour::grey(0.0),
+            text_error: Colour::new(0.9, 0.0, 0.0),
+            text_warning: Colour::new(0.8, 0.5, 0.0),
+            text_valid: Colour::new(0.0, 0.6, 0.0),
+            label_text: Colour::grey(0.0),
+            placeholder_text: Colour::grey(0.5),
+            button_text: Colour::grey(1.0),
+            key_nav_focus: Colour::new(1.0, 0.7, 0.5),
+            selection: Colour::new(1.0, 0.7, 0.5),
+            selection_text: Colour::grey(0.0),
+            caret: Colour::grey(0.0),
+            button: Colour::new(0.2, 0.7, 1.0),
+            button_highlighted: Colour::new(0.25, 0.8, 1.0),
+            button_depressed: Colour::new(0.15, 0.525, 0.75),
+            checkbox: Colour::new(0.2, 0.7, 1.0),
+        }
+    }
+
+    /// Light scheme
+    pub fn light() -> Self {
+        ColourScheme {
+            background: Colour::grey(0.9),
+            frame: Colour::new(0.8, 0.8, 0.9),
+            text_area: Colour::grey(1.0),
+            text_area_error: Colour::new(1.0, 0.8, 0.8),
+            text: Colour::grey(0.0),
+            text_error: Colour::new(0.9, 0.0, 0.0),
+            text_warning: Colour::new(0.8, 0.5, 0.0),
+            text_valid: Colour::new(0.0, 0.6, 0.0),
+            label_text: Colour::grey(0.0),
+            placeholder_text: Colour::grey(0.5),
+            button_text: Colour::grey(0.0),
+            key_nav_focus: Colour::new(1.0, 0.7, 0.5),
+            selection: Colour::new(1.0, 0.7, 0.5),
+            selection_text: Colour::grey(0.0),
+            caret: Colour::grey(0.0),
+            button: Colour::new(1.0, 1.0, 0.8),
+            button_highlighted: Colour::new(1.0, 1.0, 0.6),
+            button_depressed: Colour::new(0.8, 0.8, 0.6),
+            checkbox: Colour::grey(0.4),
+        }
+    }
+
+    /// Dark scheme: dark grey with blue activable items
+    pub fn dark() -> Self {
+        ColourScheme {
+            background: Colour::grey(0.18),
+            frame: Colour::grey(0.35),
+            text_area: Colour::grey(0.1),
+            text_area_error: Colour::new(0.4, 0.1, 0.1),
+            text: Colour::grey(0.9),
+            text_error: Colour::new(1.0, 0.35, 0.35),
+            text_warning: Colour::new(1.0, 0.7, 0.2),
+            text_valid: Colour::new(0.3, 0.9, 0.3),
+            label_text: Colour::grey(0.9),
+            placeholder_text: Colour::grey(0.5),
+            button_text: Colour::grey(1.0),
+            key_nav_focus: Colour::new(1.0, 0.6, 0.3),
+            selection: Colour::new(0.8, 0.45, 0.2),
+            selection_text: Colour::grey(1.0),
+            caret: Colour::grey(0.9),
+            button: Colour::new(0.1, 0.4, 0.65),
+            button_highlighted: Colour::new(0.15, 0.5, 0.8),
+            button_depressed: Colour::new(0.05, 0.3, 0.5),
+            checkbox: Colour::new(0.2, 0.6, 0.9),
+        }
+    }
+
+    /// Get colour for text of the given class
+    ///
+    /// The colour is adjusted if necessary to meet a minimum contrast ratio
+    /// ([`MIN_TEXT_CONTRAST`], or [`MIN_HINT_CONTRAST`] for placeholder
+    /// text) over the background the text is drawn on.
+    pub fn text_class(&self, class: TextClass) -> Colour {
+        let (col, bg, ratio) = match class {
+            TextClass::Label | TextClass::Monospace => {
+                (self.label_text, self.background, MIN_TEXT_CONTRAST)
+            }
+            TextClass::Button => (self.button_text, self.button, MIN_TEXT_CONTRAST),
+            TextClass::Edit | TextClass::EditMulti => {
+                (self.text, self.text_area, MIN_TEXT_CONTRAST)
+            }
+            TextClass::Placeholder => (self.placeholder_text, self.text_area, MIN_HINT_CONTRAST),
+        };
+        col.with_min_contrast(bg, ratio)
+    }
+
+    /// Get colour for selected text
+    ///
+    /// The colour is adjusted if necessary to meet [`MIN_TEXT_CONTRAST`] over
+    /// the selection background.
+    pub fn selected_text(&self) -> Colour {
+        (self.selection_text).with_min_contrast(self.selection, MIN_TEXT_CONTRAST)
+    }
+
+    /// Get colour for the text caret
+    ///
+    /// The colour is adjusted if necessary to meet [`MIN_HINT_CONTRAST`] over
+    /// the text area background.
+    pub fn caret(&self) -> Colour {
+        (self.caret).with_min_contrast(self.text_area, MIN_HINT_CONTRAST)
+    }
+
+    /// Get colour for navigation highlight region, if any
+    pub fn nav_region(&self, highlights: HighlightState) -> Option<Colour> {
+        if highlights.key_focus {
+            Some(self.key_nav_focus)
+        } else {
+            None
+        }
+    }
+
+    /// Get colour for an interaction highlight frame, if any
+    pub fn interaction_state(&self, highlights: HighlightState) -> Option<Colour> {
+        if highlights.depress {
+            Some(self.button_depressed)
+        } else if highlights.key_focus {
+            Some(self.key_nav_focus)
+        } else if highlights.hover {
+            Some(self.button_highlighted)
+        } else {
+            None
+        }
+    }
+
+    /// Get colour for a text area background, depending on error state
+    #[inline]
+    pub fn text_area_state(&self, error: bool) -> Colour {
+        if error {
+            self.text_area_error
+        } else {
+            self.text_area
+        }
+    }
+
+    /// Get colour for a validation frame or message, if any
+    pub fn validation_state(&self, state: ValidationState) -> Option<Colour> {
+        match state {
+            ValidationState::None => None,
+            ValidationState::Ok => Some(self.text_valid),
+            ValidationState::Warning => Some(self.text_warning),
+            ValidationState::Error => Some(self.text_error),
+        }
+    }
+
+    /// Get colour for a button, depending on state
+    pub fn button_state(&self, highlights: HighlightState) -> Colour {
+        if highlights.depress {
+            self.button_depressed
+        } else if highlights.hover {
+            self.button_highlighted
+        } else {
+            self.button
+        }
+    }
+
+    /// Get colour for a checkbox mark, depending on state
+    pub fn check_mark_state(&self, highlights: HighlightState, checked: bool) -> Option<Colour> {
+        if highlights.depress {
+            Some(self.button_depressed)
+        } else if checked && highlights.hover {
+            Some(self.button_highlighted)
+        } else if checked {
+            Some(self.checkbox)
+        } else {
+            None
+        }
+    }
+
+    /// Get colour of a scrollbar, depending on state
+    #[inline]
+    pub fn scrollbar_state(&self, highlights: HighlightState) -> Colour {
+        self.button_state(highlights)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn schemes() -> Vec<ColourScheme> {
+        vec![
+            ColourScheme::new(),
+            ColourScheme::light(),
+            ColourScheme::dark(),
+        ]
+    }
+
+    #[test]
+    fn standard_schemes_contrast() {
+        let classes = [
+            (TextClass::Label, MIN_TEXT_CONTRAST),
+            (TextClass::Button, MIN_TEXT_CONTRAST),
+            (TextClass::Edit, MIN_TEXT_CONTRAST),
+            (TextClass::Placeholder, MIN_HINT_CONTRAST),
+            (TextClass::Monospace, MIN_TEXT_CONTRAST),
+        ];
+        for cols in schemes() {
+            for &(class, ratio) in &classes {
+                let bg = match class {
+                    TextClass::Label | TextClass::Monospace => cols.background,
+                    TextClass::Button => cols.button,
+                    _ => cols.text_area,
+                };
+                let contrast = cols.text_class(class).contrast_ratio(bg);
+                assert!(contrast >= ratio - 1e-3, "{:?}: {}", class, contrast);
+            }
+            let contrast = cols.selected_text().contrast_ratio(cols.selection);
+            assert!(contrast >= MIN_TEXT_CONTRAST - 1e-3);
+            assert!(cols.caret().contrast_ratio(cols.text_area) >= MIN_HINT_CONTRAST - 1e-3);
+        }
+    }
+
+    #[test]
+    fn overridden_colours_remain_readable() {
+        let mut cols = ColourScheme::new();
+        cols.label_text = cols.background;
+        cols.text = Colour::grey(0.9);
+        cols.text_area = Colour::grey(1.0);
+
+        let label = cols.text_class(TextClass::Label);
+        assert!(label.contrast_ratio(cols.background) >= MIN_TEXT_CONTRAST - 1e-3);
+        let text = cols.text_class(TextClass::Edit);
+        assert!(text.contrast_ratio(cols.text_area) >= MIN_TEXT_CONTRAST - 1e-3);
+    }
+
+    #[test]
+    fn sufficient_contrast_is_unchanged() {
+        let cols = ColourScheme::new();
+        assert_eq!(cols.text_class(TextClass::Edit), cols.text);
+        assert_eq!(cols.text_class(TextClass::Label), cols.label_text);
+    }
+}
@@ -0,0 +1,322 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Theme configuration
+
+use log::warn;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use super::ColourScheme;
+use crate::draw::Colour;
+use crate::text::parse_colour;
+
+/// Theme parameters, e.g. as loaded from a configuration file
+///
+/// Each parameter is optional; themes keep their own value for any parameter
+/// not given. See [`ThemeApi::apply_config`](super::ThemeApi::apply_config).
+///
+/// The text format (see [`ThemeConfig::parse`]) is a subset of TOML, e.g.:
+/// ```text
+/// # Comment
+/// font_size = 16
+/// margin = 3
+///
+/// [colours]
+/// scheme = "dark"
+/// button = "#2060a0"
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ThemeConfig {
+    /// Font size (see [`ThemeApi::set_font_size`](super::ThemeApi::set_font_size))
+    pub font_size: Option<f32>,
+    /// Inner margin, in logical pixels
+    pub margin: Option<f32>,
+    /// Frame size (and radius of rounded frames), in logical pixels
+    pub frame_size: Option<f32>,
+    /// Size of button frames, in logical pixels
+    pub button_frame: Option<f32>,
+    /// Scrollbar width, in logical pixels
+    pub scrollbar_size: Option<f32>,
+    /// Width of the text caret (edit cursor), in logical pixels
+    pub caret_width: Option<f32>,
+    /// Colour scheme
+    pub colours: Option<ColourScheme>,
+}
+
+impl ThemeConfig {
+    /// Construct, with no parameters set
+    pub fn new() -> Self {
+        ThemeConfig::default()
+    }
+
+    /// Parse from text
+    ///
+    /// Empty lines and lines starting with `#` are ignored. Other lines have
+    /// the form `key = value`, where keys are the names of fields of this
+    /// struct and values are numbers. In the `[colours]` section, keys are the
+    /// names of [`ColourScheme`] fields and values are quoted colours of the
+    /// form `"#rrggbb"`; the special key `scheme` selects a standard scheme
+    /// (see [`ColourScheme::open`]) and should come first. Invalid lines are
+    /// skipped with a warning.
+    pub fn parse(text: &str) -> Self {
+        let mut config = ThemeConfig::new();
+        let mut in_colours = false;
+        for (n, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if line.starts_with('[') && line.ends_with(']') {
+                in_colours = match line[1..line.len() - 1].trim() {
+                    "colours" | "colors" => true,
+                    _ => {
+                        warn!("ThemeConfig: unknown section on line {}: {}", n + 1, line);
+                        false
+                    }
+                };
+                continue;
+            }
+
+            let mut parts = line.splitn(2, '=');
+            let key = parts.next().unwrap().trim();
+            let value = parts.next().map(|v| v.trim().trim_matches('"'));
+            let valid = match value {
+                Some(value) if in_colours => config.set_colour(key, value),
+                Some(value) => config.set_param(key, value),
+                None => false,
+            };
+            if !valid {
+                warn!("ThemeConfig: invalid line {}: {}", n + 1, line);
+            }
+        }
+        config
+    }
+
+    /// Load from a file
+    ///
+    /// See [`ThemeConfig::parse`].
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(ThemeConfig::parse(&fs::read_to_string(path)?))
+    }
+
+    /// Save to a file
+    ///
+    /// The file is written in the format read by [`ThemeConfig::parse`].
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    fn params(&self) -> [(&'static str, Option<f32>); 6] {
+        [
+            ("font_size", self.font_size),
+            ("margin", self.margin),
+            ("frame_size", self.frame_size),
+            ("button_frame", self.button_frame),
+            ("scrollbar_size", self.scrollbar_size),
+            ("caret_width", self.caret_width),
+        ]
+    }
+
+    fn set_param(&mut self, key: &str, value: &str) -> bool {
+        let value = match value.parse::<f32>() {
+            Ok(x) if x >= 0.0 && x.is_finite() => Some(x),
+            _ => return false,
+        };
+        match key {
+            "font_size" => self.font_size = value,
+            "margin" => self.margin = value,
+            "frame_size" => self.frame_size = value,
+            "button_frame" => self.button_frame = value,
+            "scrollbar_size" => self.scrollbar_size = value,
+            "caret_width" => self.caret_width = value,
+            _ => return false,
+        }
+        true
+    }
+
+    fn set_colour(&mut self, key: &str, value: &str) -> bool {
+        if key == "scheme" {
+            return match ColourScheme::open(value) {
+                Some(scheme) => {
+                    self.colours = Some(scheme);
+                    true
+                }
+                None => false,
+            };
+        }
+        let colour = match parse_colour(value) {
+            Some(colour) => colour,
+            None => return false,
+        };
+        let mut scheme = self.colours.clone().unwrap_or_default();
+        match colour_mut(&mut scheme, key) {
+            Some(field) => {
+                *field = colour;
+                self.colours = Some(scheme);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl fmt::Display for ThemeConfig {
+    /// Write in the format read by [`ThemeConfig::parse`]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (key, value) in self.params().iter() {
+            if let Some(value) = value {
+                writeln!(f, "{} = {}", key, value)?;
+            }
+        }
+        if let Some(scheme) = self.colours.as_ref() {
+            writeln!(f, "\n[colours]")?;
+            for (key, colour) in colours(scheme).iter() {
+                writeln!(f, "{} = \"{}\"", key, Hex(*colour))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+struct Hex(Colour);
+impl fmt::Display for Hex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let c = |x: f32| (x.clamp(0.0, 1.0) * 255.0).round() as u8;
+        let col = self.0;
+        write!(f, "#{:02x}{:02x}{:02x}", c(col.r), c(col.g), c(col.b))
+    }
+}
+
+fn colours(s: &ColourScheme) -> [(&'static str, Colour); 19] {
+    [
+        ("background", s.background),
+        ("frame", s.frame),
+        ("text_area", s.text_area),
+        ("text_area_error", s.text_area_error),
+        ("text", s.text),
+        ("text_error", s.text_error),
+        ("text_warning", s.text_warning),
+        ("text_valid", s.text_valid),
+        ("label_text", s.label_text),
+        ("placeholder_text", s.placeholder_text),
+        ("button_text", s.button_text),
+        ("key_nav_focus", s.key_nav_focus),
+        ("selection", s.selection),
+        ("selection_text", s.selection_text),
+        ("caret", s.caret),
+        ("button", s.button),
+        ("button_highlighted", s.button_highlighted),
+        ("button_depressed", s.button_depressed),
+        ("checkbox", s.checkbox),
+    ]
+}
+
+fn colour_mut<'a>(s: &'a mut ColourScheme, key: &str) -> Option<&'a mut Colour> {
+    Some(match key {
+        "background" => &mut s.background,
+        "frame" => &mut s.frame,
+        "text_area" => &mut s.text_area,
+        "text_area_error" => &mut s.text_area_error,
+        "text" => &mut s.text,
+        "text_error" => &mut s.text_error,
+        "text_warning" => &mut s.text_warning,
+        "text_valid" => &mut s.text_valid,
+        "label_text" => &mut s.label_text,
+        "placeholder_text" => &mut s.placeholder_text,
+        "button_text" => &mut s.button_text,
+        "key_nav_focus" => &mut s.key_nav_focus,
+        "selection" => &mut s.selection,
+        "selection_text" => &mut s.selection_text,
+        "caret" => &mut s.caret,
+        "button" => &mut s.button,
+        "button_highlighted" => &mut s.button_highlighted,
+        "button_depressed" => &mut s.button_depressed,
+        "checkbox" => &mut s.checkbox,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let config =
+            ThemeConfig::parse("font_size = 16\nmargin=3.5\n\n[colours]\nbutton = \"#2060a0\"\n");
+        assert_eq!(config.font_size, Some(16.0));
+        assert_eq!(config.margin, Some(3.5));
+        assert_eq!(config.frame_size, None);
+        let scheme = config.colours.unwrap();
+        assert_eq!(scheme.button, parse_colour("#2060a0").unwrap());
+        assert_eq!(scheme.frame, ColourScheme::new().frame);
+    }
+
+    #[test]
+    fn scheme_then_colours() {
+        let config = ThemeConfig::parse("[colours]\nscheme = \"dark\"\ncaret = \"#ff0000\"");
+        let mut expected = ColourScheme::open("dark").unwrap();
+        expected.caret = parse_colour("#ff0000").unwrap();
+        assert_eq!(config.colours, Some(expected));
+    }
+
+    #[test]
+    fn comments() {
+        let text =
+            "# font_size = 20\n  # margin = 1\nframe_size = 2\n\n[colours]\n# text = \"#000000\"";
+        let config = ThemeConfig::parse(text);
+        let expected = ThemeConfig {
+            frame_size: Some(2.0),
+            ..ThemeConfig::default()
+        };
+        assert_eq!(config, expected);
+    }
+
+    #[test]
+    fn invalid_lines() {
+        // Unknown keys, unknown sections and malformed lines are skipped
+        let text = "font_size\nsize = 3\nmargin = x\n= 2\ncaret_width = 1\n\
+            [other]\nmargin = 4\n[colours]\nmargin = 5\nbutton = \"#12345\"\n\
+            scheme = \"unknown\"";
+        let config = ThemeConfig::parse(text);
+        let expected = ThemeConfig {
+            caret_width: Some(1.0),
+            margin: Some(4.0),
+            ..ThemeConfig::default()
+        };
+        assert_eq!(config, expected);
+    }
+
+    #[test]
+    fn out_of_range() {
+        let text = "font_size = -1\nmargin = inf\nframe_size = NaN\nbutton_frame = 0";
+        let config = ThemeConfig::parse(text);
+        let expected = ThemeConfig {
+            button_frame: Some(0.0),
+            ..ThemeConfig::default()
+        };
+        assert_eq!(config, expected);
+    }
+
+    #[test]
+    fn round_trip() {
+        assert_eq!(ThemeConfig::new().to_string(), "");
+        let text = "font_size = 14.5\nscrollbar_size = 8\n[colours]\nscheme = \"light\"\n\
+            button = \"#2060a0\"\ncheckbox = \"#00ff7f\"";
+        let config = ThemeConfig::parse(text);
+        assert!(config.colours.is_some());
+        // Colours of standard schemes are rounded to 8 bits when written
+        let text = config.to_string();
+        let config = ThemeConfig::parse(&text);
+        assert_eq!(ThemeConfig::parse(&config.to_string()), config);
+        assert_eq!(config.to_string(), text);
+        assert_eq!(config.font_size, Some(14.5));
+        assert_eq!(config.scrollbar_size, Some(8.0));
+    }
+}
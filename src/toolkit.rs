@@ -14,10 +14,16 @@
 //!
 //! [winit]: https://github.com/rust-windowing/winit
 
+use std::any::Any;
+use std::future::Future;
 use std::num::NonZeroU32;
+use std::pin::Pin;
+use std::rc::Rc;
 
-use crate::event::{CursorIcon, UpdateHandle};
-use crate::theme::{ThemeAction, ThemeApi};
+use crate::event::{CursorIcon, DragData, Priority, UpdateHandle};
+use crate::geom::{Coord, Rect};
+use crate::theme::{SizeHandle, ThemeAction, ThemeApi};
+use crate::WidgetId;
 
 /// Identifier for a window added to a toolkit
 ///
@@ -85,6 +91,12 @@ pub trait TkWindow {
     /// processing, albeit without error handling.
     fn add_window(&mut self, widget: Box<dyn kas::Window>) -> WindowId;
 
+    /// Add a modal window
+    ///
+    /// As [`TkWindow::add_window`], except that input to the current window is
+    /// blocked until the new window is closed.
+    fn add_modal_window(&mut self, widget: Box<dyn kas::Window>) -> WindowId;
+
     /// Close a window
     fn close_window(&mut self, id: WindowId);
 
@@ -94,6 +106,20 @@ pub trait TkWindow {
     /// windows, will receive an update.
     fn trigger_update(&mut self, handle: UpdateHandle, payload: u64);
 
+    /// Push a message to the inspectors of all windows
+    ///
+    /// The message should be delivered to [`kas::Window::inspect_msg`] of each
+    /// window after event handling completes.
+    fn push_msg(&mut self, msg: Rc<dyn Any>);
+
+    /// Spawn a task
+    ///
+    /// The toolkit should poll `task` to completion on the UI thread, then
+    /// deliver its output to widget `id` of this window via
+    /// [`crate::event::ManagerState::send_to`]. Tasks may be dropped if the
+    /// window is closed.
+    fn spawn(&mut self, id: WidgetId, task: Pin<Box<dyn Future<Output = Rc<dyn Any>>>>);
+
     /// Attempt to get clipboard contents
     ///
     /// In case of failure, paste actions will simply fail. The implementation
@@ -108,6 +134,45 @@ pub trait TkWindow {
 
     /// Set the mouse cursor
     fn set_cursor_icon(&mut self, icon: CursorIcon);
+
+    /// Set the position of input method (IME) candidate windows
+    ///
+    /// The `coord` is relative to the window (see [`Manager::set_caret`]).
+    ///
+    /// [`Manager::set_caret`]: crate::event::Manager::set_caret
+    fn set_ime_position(&mut self, coord: Coord);
+
+    /// Announce some text to assistive technology
+    ///
+    /// The implementation should forward `text` to the platform's screen
+    /// reader or speech synthesis, if available; otherwise it may do nothing.
+    fn announce(&mut self, text: &str, priority: Priority);
+
+    /// Get the area of each screen (monitor)
+    ///
+    /// Areas are relative to this window. Where possible, areas should exclude
+    /// panels such as task bars (i.e. be the work area). Where screen geometry
+    /// is unavailable this returns an empty list.
+    fn screen_areas(&self) -> Vec<Rect>;
+
+    /// Access a [`SizeHandle`]
+    ///
+    /// This allows text measurement during event handling. Where no size
+    /// handle is available (e.g. while drawing), `f` is not called.
+    fn size_handle(&mut self, f: &mut dyn FnMut(&mut dyn SizeHandle));
+
+    /// Drop data outside of this window
+    ///
+    /// This is called when a drag started within this window (see
+    /// [`Manager::start_drag`]) is released outside of the window; `coord` is
+    /// relative to this window. The toolkit should deliver an
+    /// [`Event::Drop`] to the other window of the application under the
+    /// cursor at this position (if known; windows may overlap), and otherwise
+    /// discard the data.
+    ///
+    /// [`Manager::start_drag`]: crate::event::Manager::start_drag
+    /// [`Event::Drop`]: crate::event::Event::Drop
+    fn drop_outside(&mut self, data: DragData, coord: Coord);
 }
 
 #[cfg(test)]
@@ -5,14 +5,16 @@
 
 //! Widget traits
 
+use std::any::Any;
 use std::fmt;
 use std::time::Duration;
 
+use crate::draw::Transform;
 use crate::event::{Callback, CursorIcon, Handler, Manager, UpdateHandle, VoidMsg};
 use crate::geom::{Coord, Rect, Size};
 use crate::layout::{self, AxisInfo, SizeRules};
-use crate::theme::{DrawHandle, SizeHandle};
-use crate::{AlignHints, CoreData, WidgetId};
+use crate::theme::{DrawHandle, SizeHandle, ValidationState};
+use crate::{AlignHints, CoreData, WidgetId, WindowId};
 
 pub trait CloneTo {
     unsafe fn clone_to(&self, out: *mut Self);
@@ -33,7 +35,10 @@ impl<T: Clone + Sized> CloneTo for T {
 ///
 /// It is not recommended to implement this manually since breaking changes may
 /// be readily introduced (although not in patch releases).
-pub trait WidgetCore: fmt::Debug {
+///
+/// Widgets must be `'static` (i.e. may not borrow data), which allows
+/// downcasting via [`WidgetCore::as_any`].
+pub trait WidgetCore: Any + fmt::Debug {
     /// Get direct access to the [`CoreData`] providing property storage.
     fn core_data(&self) -> &CoreData;
 
@@ -55,6 +60,45 @@ pub trait WidgetCore: fmt::Debug {
         self.core_data().rect
     }
 
+    /// Set context help text (chain style)
+    ///
+    /// This text is shown when the widget is clicked in context help mode
+    /// (see [`Manager::start_help_mode`]). Where a widget has no help text,
+    /// that of its nearest ancestor is shown.
+    fn with_help<S: Into<String>>(mut self, text: S) -> Self
+    where
+        Self: Sized,
+    {
+        self.core_data_mut().help = Some(text.into());
+        self
+    }
+
+    /// Get the widget's context help text, if any
+    #[inline]
+    fn help(&self) -> Option<&str> {
+        self.core_data().help.as_deref()
+    }
+
+    /// Set a lookup key (chain style)
+    ///
+    /// The widget may later be found by this key via
+    /// [`WidgetCore::find_key`] or [`WidgetCore::lookup`] on any ancestor
+    /// (e.g. the window). Keys should be unique within a window; where not,
+    /// the first match (in order of child index) is found.
+    fn with_key<S: Into<String>>(mut self, key: S) -> Self
+    where
+        Self: Sized,
+    {
+        self.core_data_mut().key = Some(key.into());
+        self
+    }
+
+    /// Get the widget's lookup key, if any
+    #[inline]
+    fn key(&self) -> Option<&str> {
+        self.core_data().key.as_deref()
+    }
+
     /// Get the name of the widget struct
     fn widget_name(&self) -> &'static str;
 
@@ -63,6 +107,13 @@ pub trait WidgetCore: fmt::Debug {
     /// Erase type
     fn as_widget_mut(&mut self) -> &mut dyn Widget;
 
+    /// Cast to [`Any`], allowing downcasting
+    ///
+    /// See also `<dyn Widget>::downcast_ref`.
+    fn as_any(&self) -> &dyn Any;
+    /// Cast to [`Any`], allowing downcasting
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+
     /// Get the number of child widgets
     fn len(&self) -> usize;
 
@@ -128,6 +179,55 @@ pub trait WidgetCore: fmt::Debug {
         None
     }
 
+    /// Find a widget (self or a descendant) by lookup key
+    ///
+    /// See [`WidgetCore::with_key`].
+    fn find_key(&self, key: &str) -> Option<&dyn Widget> {
+        if self.key() == Some(key) {
+            return Some(self.as_widget());
+        }
+        (0..self.len())
+            .filter_map(|i| self.get(i))
+            .find_map(|w| w.find_key(key))
+    }
+
+    /// Find a widget (self or a descendant) by lookup key
+    ///
+    /// See [`WidgetCore::with_key`]. The widget is found via
+    /// [`WidgetCore::find_key`] then retrieved by its identifier, thus this
+    /// requires that the widget tree has already been configured.
+    fn find_key_mut(&mut self, key: &str) -> Option<&mut dyn Widget> {
+        let id = self.find_key(key)?.id();
+        self.find_mut(id)
+    }
+
+    /// Find a widget by lookup key and type
+    ///
+    /// Returns `None` if no widget has this `key` or if the widget found is
+    /// not of type `T`. For example:
+    /// ```ignore
+    /// let name = window.lookup::<EditBox>("username").map(|w| w.get_text());
+    /// ```
+    fn lookup<T: Widget + 'static>(&self, key: &str) -> Option<&T>
+    where
+        Self: Sized,
+    {
+        self.find_key(key).and_then(|w| w.downcast_ref())
+    }
+
+    /// Find a widget by lookup key and type
+    ///
+    /// See [`WidgetCore::lookup`].
+    ///
+    /// Warning: as with [`WidgetCore::get_mut`], changes made to a widget may
+    /// require a reconfigure or redraw action.
+    fn lookup_mut<T: Widget + 'static>(&mut self, key: &str) -> Option<&mut T>
+    where
+        Self: Sized,
+    {
+        self.find_key_mut(key).and_then(|w| w.downcast_mut())
+    }
+
     /// Walk through all widgets, calling `f` once on each.
     ///
     /// This walk is iterative (nonconcurrent), depth-first, and always calls
@@ -139,6 +239,17 @@ pub trait WidgetCore: fmt::Debug {
     /// This walk is iterative (nonconcurrent), depth-first, and always calls
     /// `f` on self *after* walking through all children.
     fn walk_mut(&mut self, f: &mut dyn FnMut(&mut dyn Widget));
+
+    /// Get the combined validation state of this widget and its descendants
+    ///
+    /// This is the most severe [`Widget::validation`] state of any widget in
+    /// the tree. For example, a submit button may be enabled only when
+    /// `form.combined_validation().is_valid()`.
+    fn combined_validation(&self) -> ValidationState {
+        let mut state = ValidationState::None;
+        self.walk(&mut |w| state = state.max(w.validation()));
+        state
+    }
 }
 
 /// Positioning and drawing routines for widgets
@@ -198,6 +309,21 @@ pub trait Layout: WidgetCore {
         Some(self.id())
     }
 
+    /// Get the clip rect and transform applied when drawing a child
+    ///
+    /// The child is identified by its `id`. Widgets drawing a child with an
+    /// offset or transform (via [`DrawHandle::clip_region`] or
+    /// [`DrawHandle::transform_region`]) should return the rect and transform
+    /// used there; the default implementation returns `None`, meaning that
+    /// the child is drawn directly.
+    ///
+    /// This is used to map the rects of descendants to window coordinates
+    /// (see [`crate::event::ManagerState::redraw_region`]).
+    #[inline]
+    fn child_region(&self, _id: WidgetId) -> Option<(Rect, Transform)> {
+        None
+    }
+
     /// Draw a widget
     ///
     /// This method is called to draw each visible widget (and should not
@@ -236,11 +362,26 @@ pub trait Widget: Layout {
     /// Configure widget
     ///
     /// Widgets are *configured* on window creation and when
-    /// [`kas::TkAction::Reconfigure`] is sent.
+    /// [`kas::TkAction::Reconfigure`] is sent. Widgets added to a tree at
+    /// run-time are configured by the reconfigure this implies.
     ///
     /// This method is called immediately after assigning `self.core_data().id`.
+    /// Widgets should register update handles, timers and accelerator keys
+    /// here, since all such registrations are cleared on reconfigure.
     fn configure(&mut self, _: &mut Manager) {}
 
+    /// Detach widget
+    ///
+    /// This method is called when a widget is removed from a widget tree (see
+    /// [`Manager::detach`]), once for each widget in the removed sub-tree and
+    /// after the widget's own children.
+    ///
+    /// The widget's id remains valid during this call. Registrations made
+    /// through the [`Manager`] (update handles, timers, accelerator keys,
+    /// focus and grabs) are removed automatically; widgets need only release
+    /// resources held elsewhere.
+    fn detach(&mut self, _: &mut Manager) {}
+
     /// Update the widget via a timer
     ///
     /// This method is called on scheduled updates (see [`update_on_timer`]).
@@ -259,15 +400,18 @@ pub trait Widget: Layout {
 
     /// Update the widget via an update handle
     ///
-    /// This method is called on triggered updates (see [`update_on_handle`]).
-    /// The source handle is specified via the [`UpdateHandle`] parameter.
+    /// This method is called on triggered updates (see [`update_on_handle`])
+    /// by the default handler of [`Event::HandleUpdate`],
+    /// [`Manager::handle_generic`]. The source handle is specified via the
+    /// [`UpdateHandle`] parameter.
     ///
     /// A user-defined payload is passed. Interpretation of this payload is
     /// user-defined and unfortunately not type safe.
     ///
-    /// This method being called does not imply a redraw.
+    /// The widget is redrawn after this method is called.
     ///
     /// [`update_on_handle`]: Manager::update_on_handle
+    /// [`Event::HandleUpdate`]: crate::event::Event::HandleUpdate
     fn update_handle(&mut self, _mgr: &mut Manager, _handle: UpdateHandle, _payload: u64) {}
 
     /// Is this widget navigable via Tab key?
@@ -281,6 +425,32 @@ pub trait Widget: Layout {
     fn cursor_icon(&self) -> CursorIcon {
         CursorIcon::Default
     }
+
+    /// Validation state of this widget's input
+    ///
+    /// Input widgets may report whether their value is valid; see also
+    /// [`WidgetCore::combined_validation`].
+    fn validation(&self) -> ValidationState {
+        ValidationState::None
+    }
+}
+
+impl<'a> dyn Widget + 'a {
+    /// Downcast to a concrete widget type
+    ///
+    /// Returns `None` if this widget is not of type `T`.
+    #[inline]
+    pub fn downcast_ref<T: Widget + 'static>(&self) -> Option<&T> {
+        self.as_any().downcast_ref()
+    }
+
+    /// Downcast to a concrete widget type
+    ///
+    /// Returns `None` if this widget is not of type `T`.
+    #[inline]
+    pub fn downcast_mut<T: Widget + 'static>(&mut self) -> Option<&mut T> {
+        self.as_any_mut().downcast_mut()
+    }
 }
 
 /// Trait to describe the type needed by the layout implementation.
@@ -311,6 +481,18 @@ pub trait Window: Widget + Handler<Msg = VoidMsg> {
     /// Get the window title
     fn title(&self) -> &str;
 
+    /// Get the window name
+    ///
+    /// If named, toolkits may save the window's placement (size, position,
+    /// etc.) when closed and restore it when a window of the same name is
+    /// next opened; see [`WindowPlacements`](crate::data::WindowPlacements).
+    /// Names should be unique within an application.
+    ///
+    /// By default, windows are unnamed.
+    fn name(&self) -> Option<&str> {
+        None
+    }
+
     /// Adjust the size of the window, repositioning widgets.
     fn resize(
         &mut self,
@@ -329,4 +511,22 @@ pub trait Window: Widget + Handler<Msg = VoidMsg> {
 
     /// Trigger a callback (see `iter_callbacks`).
     fn trigger_callback(&mut self, index: usize, mgr: &mut Manager);
+
+    /// Inspect a message pushed via [`Manager::push_msg`]
+    ///
+    /// The toolkit calls this on every window for each pushed message. By
+    /// default, messages are ignored.
+    fn inspect_msg(&mut self, _mgr: &mut Manager, _msg: &dyn Any) {}
+
+    /// Show this window as a modal window
+    ///
+    /// Input to the current window is blocked until this window is closed
+    /// (see [`Manager::add_modal_window`]). This returns immediately; dialogs
+    /// return their result via a [`DialogReply`](crate::widget::DialogReply).
+    fn show_modal(self, mgr: &mut Manager) -> WindowId
+    where
+        Self: Sized + 'static,
+    {
+        mgr.add_modal_window(Box::new(self))
+    }
 }
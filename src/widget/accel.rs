@@ -0,0 +1,179 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Accelerator (mnemonic) labels
+
+use std::ops::Range;
+
+use crate::class::HasText;
+use crate::event::{Manager, VirtualKeyCode};
+use crate::geom::Rect;
+use crate::layout::{AxisInfo, SizeRules};
+use crate::macros::Widget;
+use crate::theme::{DrawHandle, SizeHandle, TextClass, TextProperties, UnderlineStyle};
+use crate::{Align, AlignHints, CoreData, Layout, WidgetCore};
+
+/// Text with an optional mnemonic
+///
+/// A mnemonic is specified by prefixing a letter or digit with `&` (use `&&`
+/// for a literal `&`). Only the first mnemonic is used.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct AccelString {
+    text: String,
+    key: Option<VirtualKeyCode>,
+    // Byte range of the mnemonic character within text
+    range: Range<usize>,
+}
+
+impl AccelString {
+    /// Parse mnemonic markup
+    pub fn parse(label: &str) -> Self {
+        let mut text = String::with_capacity(label.len());
+        let mut key = None;
+        let mut range = 0..0;
+        let mut chars = label.chars();
+        while let Some(c) = chars.next() {
+            if c != '&' {
+                text.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some(c) => {
+                    if c != '&' && key.is_none() {
+                        key = key_for_char(c);
+                        if key.is_some() {
+                            range = text.len()..text.len() + c.len_utf8();
+                        }
+                    }
+                    text.push(c);
+                }
+                None => text.push('&'),
+            }
+        }
+        AccelString { text, key, range }
+    }
+
+    /// Get the text, without markup
+    #[inline]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Get the mnemonic key, if any
+    #[inline]
+    pub fn key(&self) -> Option<VirtualKeyCode> {
+        self.key
+    }
+
+    /// Draw the text, underlining the mnemonic if accelerators are shown
+    pub fn draw(
+        &self,
+        draw_handle: &mut dyn DrawHandle,
+        mgr: &Manager,
+        rect: Rect,
+        props: TextProperties,
+    ) {
+        draw_handle.text(rect, &self.text, props);
+        if self.key.is_some() && mgr.show_accel_labels() {
+            let range = self.range.clone();
+            draw_handle.text_underline(rect, &self.text, range, UnderlineStyle::Solid, props);
+        }
+    }
+}
+
+fn key_for_char(c: char) -> Option<VirtualKeyCode> {
+    use VirtualKeyCode::*;
+    const LETTERS: [VirtualKeyCode; 26] = [
+        A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z,
+    ];
+    const DIGITS: [VirtualKeyCode; 10] =
+        [Key0, Key1, Key2, Key3, Key4, Key5, Key6, Key7, Key8, Key9];
+    let c = c.to_ascii_uppercase();
+    match c {
+        'A'..='Z' => Some(LETTERS[(c as u8 - b'A') as usize]),
+        '0'..='9' => Some(DIGITS[(c as u8 - b'0') as usize]),
+        _ => None,
+    }
+}
+
+/// A text label with a mnemonic
+///
+/// This is as [`Label`](super::Label), except that the text may specify a
+/// mnemonic by prefixing a letter or digit with `&` (use `&&` for a literal
+/// `&`). The mnemonic character is underlined while accelerator keys are shown
+/// (see [`Manager::show_accel_labels`]).
+///
+/// The label does not register its key; the widget owning the label should
+/// do so for the widget to be activated (see [`AccelLabel::key`]).
+#[widget]
+#[handler]
+#[derive(Clone, Default, Debug, Widget)]
+pub struct AccelLabel {
+    #[core]
+    core: CoreData,
+    halign: Align,
+    valign: Align,
+    text: AccelString,
+}
+
+impl Layout for AccelLabel {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let rules = size_handle.text_bound(self.text.text(), TextClass::Label, axis);
+        if axis.is_horizontal() {
+            self.core_data_mut().rect.size.0 = rules.ideal_size();
+        } else {
+            self.core_data_mut().rect.size.1 = rules.ideal_size();
+        }
+        rules
+    }
+
+    fn set_rect(&mut self, size_handle: &mut dyn SizeHandle, rect: Rect, align: AlignHints) {
+        let direction = size_handle.layout_direction();
+        self.halign = direction.halign(align.horiz.unwrap_or(Align::Begin));
+        self.valign = align.vert.unwrap_or(Align::Centre);
+        self.core_data_mut().rect = rect;
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &Manager) {
+        let props = TextProperties {
+            class: TextClass::Label,
+            horiz: self.halign,
+            vert: self.valign,
+        };
+        self.text.draw(draw_handle, mgr, self.core.rect, props);
+    }
+}
+
+impl AccelLabel {
+    /// Construct, parsing mnemonic markup
+    pub fn new<T: AsRef<str>>(label: T) -> Self {
+        AccelLabel {
+            core: Default::default(),
+            halign: Default::default(),
+            valign: Default::default(),
+            text: AccelString::parse(label.as_ref()),
+        }
+    }
+
+    /// Get the mnemonic key, if any
+    #[inline]
+    pub fn key(&self) -> Option<VirtualKeyCode> {
+        self.text.key()
+    }
+}
+
+impl HasText for AccelLabel {
+    fn get_text(&self) -> &str {
+        self.text.text()
+    }
+
+    /// Set text, parsing mnemonic markup
+    ///
+    /// Note: the mnemonic key is only registered on reconfigure.
+    fn set_string(&mut self, mgr: &mut Manager, text: String) {
+        self.text = AccelString::parse(&text);
+        mgr.redraw(self.id());
+    }
+}
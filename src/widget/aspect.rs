@@ -0,0 +1,136 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Aspect ratio constraint
+
+use crate::event::{Event, Handler, Manager, Response};
+use crate::geom::{Coord, Rect, Size};
+use crate::layout::{AxisInfo, SizeRules};
+use crate::macros::Widget;
+use crate::theme::{DrawHandle, SizeHandle};
+use crate::{Align, AlignHints, Direction};
+use crate::{CoreData, Layout, TkAction, Widget, WidgetCore, WidgetId};
+
+/// A wrapper sizing its child to a fixed aspect ratio
+///
+/// The `ratio` is width divided by height; for example `16.0 / 9.0`. The child
+/// is given the largest rect of this ratio which fits within the allocated
+/// rect, placed according to alignment hints (by default, centred). This is
+/// intended for content such as images and video.
+///
+/// When the width is known, the ideal height is derived from the ratio.
+#[derive(Clone, Debug, Widget)]
+pub struct AspectRatio<W: Widget> {
+    #[core]
+    core: CoreData,
+    #[widget]
+    child: W,
+    ratio: f32,
+}
+
+impl<W: Widget> AspectRatio<W> {
+    /// Construct with the given `ratio` (width / height)
+    ///
+    /// Panics if `ratio` is not positive and finite.
+    #[inline]
+    pub fn new(ratio: f32, child: W) -> Self {
+        assert!(ratio > 0.0 && ratio.is_finite());
+        AspectRatio {
+            core: Default::default(),
+            child,
+            ratio,
+        }
+    }
+
+    /// Get the aspect ratio
+    #[inline]
+    pub fn ratio(&self) -> f32 {
+        self.ratio
+    }
+
+    /// Set the aspect ratio
+    ///
+    /// Panics if `ratio` is not positive and finite.
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action).
+    pub fn set_ratio(&mut self, mgr: &mut Manager, ratio: f32) {
+        assert!(ratio > 0.0 && ratio.is_finite());
+        self.ratio = ratio;
+        mgr.send_action(TkAction::Reconfigure);
+    }
+
+    /// Access the child widget
+    #[inline]
+    pub fn inner(&self) -> &W {
+        &self.child
+    }
+
+    /// Access the child widget mutably
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut W {
+        &mut self.child
+    }
+}
+
+impl<W: Widget> Widget for AspectRatio<W> {}
+
+impl<W: Widget> Layout for AspectRatio<W> {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let rules = self.child.size_rules(size_handle, axis);
+        if let Some(width) = axis.size_other_if_fixed(Direction::Vertical) {
+            let height = (width as f32 / self.ratio).round() as u32;
+            let ideal = height.max(rules.min_size());
+            return SizeRules::new(rules.min_size(), ideal, rules.stretch());
+        }
+        rules
+    }
+
+    fn set_rect(&mut self, size_handle: &mut dyn SizeHandle, rect: Rect, align: AlignHints) {
+        self.core.rect = rect;
+        let width = (rect.size.1 as f32 * self.ratio).round() as u32;
+        let size = if width <= rect.size.0 {
+            Size(width, rect.size.1)
+        } else {
+            let height = (rect.size.0 as f32 / self.ratio).round() as u32;
+            Size(rect.size.0, height.min(rect.size.1))
+        };
+        let place = |align: Option<Align>, space: u32, size: u32| {
+            let diff = (space - size) as i32;
+            match align.unwrap_or(Align::Centre) {
+                Align::Begin | Align::Stretch => 0,
+                Align::Centre => diff / 2,
+                Align::End => diff,
+            }
+        };
+        let offset = Coord(
+            place(align.horiz, rect.size.0, size.0),
+            place(align.vert, rect.size.1, size.1),
+        );
+        let rect = Rect::new(rect.pos + offset, size);
+        self.child.set_rect(size_handle, rect, align);
+    }
+
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        self.child.find_id(coord)
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &Manager) {
+        self.child.draw(draw_handle, mgr);
+    }
+}
+
+impl<W: Widget + Handler> Handler for AspectRatio<W> {
+    type Msg = <W as Handler>::Msg;
+
+    #[inline]
+    fn handle(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<Self::Msg> {
+        if id <= self.child.id() {
+            self.child.handle(mgr, id, event)
+        } else {
+            debug_assert!(id <= self.id(), "Handler::handle: bad WidgetId");
+            Response::Unhandled(event)
+        }
+    }
+}
@@ -8,31 +8,47 @@
 use smallvec::SmallVec;
 use std::fmt::Debug;
 
+use super::AccelString;
 use crate::class::HasText;
+use crate::draw::ImageData;
 use crate::event::{Action, Handler, Manager, Response, VirtualKeyCode};
-use crate::geom::{Coord, Rect};
-use crate::layout::{AxisInfo, SizeRules};
+use crate::geom::{Coord, Rect, Size};
+use crate::layout::{AxisInfo, Side, SizeRules};
 use crate::macros::Widget;
 use crate::theme::{DrawHandle, SizeHandle, TextClass, TextProperties};
 use crate::{Align, AlignHints, CoreData, Layout, Widget, WidgetCore, WidgetId};
 
-/// A push-button with a text label
+/// A push-button with a text label and/or an icon
+///
+/// Buttons may show text only ([`TextButton::new`]), an icon beside the text
+/// ([`TextButton::with_icon`]) or an icon only ([`TextButton::new_icon`]).
+/// Icons are sized by the theme (see [`SizeHandle::icon`]). All variants emit
+/// the same message on activation.
+///
+/// The label may specify a mnemonic (see [`AccelLabel`](super::AccelLabel)),
+/// which is registered as an accelerator key.
 #[derive(Clone, Debug, Default, Widget)]
-pub struct TextButton<M: Clone + Debug> {
+pub struct TextButton<M: Clone + Debug + 'static> {
     #[core]
     core: CoreData,
     keys: SmallVec<[VirtualKeyCode; 4]>,
     b_rect: Rect,
-    // text_rect: Rect,
-    label: String,
+    text_rect: Rect,
+    icon_rect: Rect,
+    label: AccelString,
+    show_label: bool,
+    icon: Option<(ImageData, Side)>,
     msg: M,
 }
 
-impl<M: Clone + Debug> Widget for TextButton<M> {
+impl<M: Clone + Debug + 'static> Widget for TextButton<M> {
     fn configure(&mut self, mgr: &mut Manager) {
         for key in &self.keys {
             mgr.add_accel_key(*key, self.id());
         }
+        if let Some(key) = self.label.key() {
+            mgr.add_accel_key(key, self.id());
+        }
     }
 
     fn allow_focus(&self) -> bool {
@@ -40,12 +56,26 @@ impl<M: Clone + Debug> Widget for TextButton<M> {
     }
 }
 
-impl<M: Clone + Debug> Layout for TextButton<M> {
+impl<M: Clone + Debug + 'static> Layout for TextButton<M> {
     fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
         let margin = size_handle.outer_margin();
         let sides = size_handle.button_surround();
-        let rules = SizeRules::fixed(axis.extract_size(sides.0 + sides.1 + margin))
-            + size_handle.text_bound(&self.label, TextClass::Button, axis);
+        let mut content = SizeRules::EMPTY;
+        if self.show_label {
+            content = size_handle.text_bound(self.label.text(), TextClass::Button, axis);
+        }
+        if let Some((_, side)) = self.icon {
+            let icon = SizeRules::fixed(axis.extract_size(size_handle.icon()));
+            if !self.show_label {
+                content = icon;
+            } else if side.is_vertical() == axis.is_vertical() {
+                let gap = axis.extract_size(size_handle.inner_margin());
+                content = content + icon + gap;
+            } else {
+                content = content.max(icon);
+            }
+        }
+        let rules = SizeRules::fixed(axis.extract_size(sides.0 + sides.1 + margin)) + content;
         if axis.is_horizontal() {
             self.core_data_mut().rect.size.0 = rules.ideal_size();
         } else {
@@ -68,15 +98,56 @@ impl<M: Clone + Debug> Layout for TextButton<M> {
             size: rect.size - margin - margin,
         };
 
-        // In theory, text rendering *should* be restricted to this rect. In
-        // practice, it sometimes overflows a tiny bit, and looks better if we
-        // do let it overflow. Since the text is centred this is okay
-        // (assuming the theme's frame is symmetric).
-        // let sides = size_handle.button_surround();
-        // self.text_rect = Rect {
-        //     pos: self.b_rect.pos + sides.0,
-        //     size: self.b_rect.size - (sides.0 + sides.1),
-        // };
+        // In theory, text rendering *should* be restricted to the content
+        // rect. In practice, it sometimes overflows a tiny bit, and looks
+        // better if we do let it overflow. Since the text is centred this is
+        // okay (assuming the theme's frame is symmetric).
+        self.text_rect = self.b_rect;
+        let (side, icon) = match self.icon {
+            Some((_, side)) => (side, size_handle.icon()),
+            None => return,
+        };
+
+        // Centre the icon and label (if shown) as a group within the content
+        let sides = size_handle.button_surround();
+        let content = Rect {
+            pos: self.b_rect.pos + sides.0,
+            size: self.b_rect.size - (sides.0 + sides.1),
+        };
+        let centre = |len: u32, avail: u32| (avail.saturating_sub(len) / 2) as i32;
+        if !self.show_label {
+            let offset = Coord(
+                centre(icon.0, content.size.0),
+                centre(icon.1, content.size.1),
+            );
+            self.icon_rect = Rect::new(content.pos + offset, icon);
+            return;
+        }
+        let text = size_handle.text_size(self.label.text(), TextClass::Button, None);
+        let gap = size_handle.inner_margin();
+        let (icon_pos, text_pos);
+        if side.is_vertical() {
+            let y = content.pos.1 + centre(icon.1 + gap.1 + text.1, content.size.1);
+            let x = content.pos.0 + centre(icon.0, content.size.0);
+            let (icon_y, text_y) = match side {
+                Side::Above => (y, y + (icon.1 + gap.1) as i32),
+                _ => (y + (text.1 + gap.1) as i32, y),
+            };
+            icon_pos = Coord(x, icon_y);
+            text_pos = Coord(content.pos.0, text_y);
+            self.text_rect = Rect::new(text_pos, Size(content.size.0, text.1));
+        } else {
+            let x = content.pos.0 + centre(icon.0 + gap.0 + text.0, content.size.0);
+            let y = content.pos.1 + centre(icon.1, content.size.1);
+            let (icon_x, text_x) = match side {
+                Side::Left => (x, x + (icon.0 + gap.0) as i32),
+                _ => (x + (text.0 + gap.0) as i32, x),
+            };
+            icon_pos = Coord(icon_x, y);
+            text_pos = Coord(text_x, content.pos.1);
+            self.text_rect = Rect::new(text_pos, Size(text.0, content.size.1));
+        }
+        self.icon_rect = Rect::new(icon_pos, icon);
     }
 
     fn find_id(&self, coord: Coord) -> Option<WidgetId> {
@@ -89,16 +160,21 @@ impl<M: Clone + Debug> Layout for TextButton<M> {
 
     fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &Manager) {
         draw_handle.button(self.b_rect, mgr.highlight_state(self.id()));
-        let props = TextProperties {
-            class: TextClass::Button,
-            horiz: Align::Centre,
-            vert: Align::Centre,
-        };
-        draw_handle.text(self.b_rect, &self.label, props);
+        if let Some((icon, _)) = self.icon.as_ref() {
+            draw_handle.image(self.icon_rect, icon);
+        }
+        if self.show_label {
+            let props = TextProperties {
+                class: TextClass::Button,
+                horiz: Align::Centre,
+                vert: Align::Centre,
+            };
+            self.label.draw(draw_handle, mgr, self.text_rect, props);
+        }
     }
 }
 
-impl<M: Clone + Debug> TextButton<M> {
+impl<M: Clone + Debug + 'static> TextButton<M> {
     /// Construct a button with a given `label` and `msg`
     ///
     /// The message `msg` is returned to the parent widget on activation. Any
@@ -110,12 +186,35 @@ impl<M: Clone + Debug> TextButton<M> {
             core: Default::default(),
             keys: SmallVec::new(),
             b_rect: Default::default(),
-            // text_rect: Default::default(),
-            label: label.into(),
+            text_rect: Default::default(),
+            icon_rect: Default::default(),
+            label: AccelString::parse(&label.into()),
+            show_label: true,
+            icon: None,
             msg,
         }
     }
 
+    /// Construct a button showing only an `icon`
+    ///
+    /// The `label` is not drawn, but is still used for its mnemonic and is
+    /// available via [`HasText`] (e.g. for a tooltip). Otherwise this is as
+    /// [`TextButton::new`].
+    pub fn new_icon<S: Into<String>>(icon: ImageData, label: S, msg: M) -> Self {
+        let mut button = TextButton::new(label, msg);
+        button.icon = Some((icon, Side::Left));
+        button.show_label = false;
+        button
+    }
+
+    /// Show an `icon` on the given `side` of the label (chain style)
+    ///
+    /// For a button without label, the `side` is ignored.
+    pub fn with_icon(mut self, icon: ImageData, side: Side) -> Self {
+        self.icon = Some((icon, side));
+        self
+    }
+
     /// Set accelerator keys (chain style)
     pub fn with_keys(mut self, keys: &[VirtualKeyCode]) -> Self {
         self.set_keys(keys);
@@ -133,18 +232,18 @@ impl<M: Clone + Debug> TextButton<M> {
     }
 }
 
-impl<M: Clone + Debug> HasText for TextButton<M> {
+impl<M: Clone + Debug + 'static> HasText for TextButton<M> {
     fn get_text(&self) -> &str {
-        &self.label
+        self.label.text()
     }
 
     fn set_string(&mut self, mgr: &mut Manager, text: String) {
-        self.label = text;
+        self.label = AccelString::parse(&text);
         mgr.redraw(self.id());
     }
 }
 
-impl<M: Clone + Debug> Handler for TextButton<M> {
+impl<M: Clone + Debug + 'static> Handler for TextButton<M> {
     type Msg = M;
 
     #[inline]
@@ -0,0 +1,87 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Canvas widget
+
+use std::fmt::{self, Debug};
+
+use crate::event::{Handler, Manager, VoidMsg};
+use crate::geom::{Coord, Rect, Size};
+use crate::layout::{AxisInfo, SizeRules, StretchPolicy};
+use crate::macros::Widget;
+use crate::theme::{DrawHandle, SizeHandle};
+use crate::{AlignHints, CoreData, Layout, Widget, WidgetCore};
+
+/// A widget drawn by a user-supplied closure
+///
+/// The closure `f(draw_handle, rect)` is called on each draw, where `rect` is
+/// the area allocated to the canvas. Drawing is clipped to `rect`. Besides the
+/// themed elements, [`DrawHandle`] supports plain shapes via
+/// [`DrawHandle::rect`] and [`DrawHandle::frame`].
+///
+/// The canvas does not track the data it visualises; call [`Canvas::redraw`]
+/// when this changes.
+///
+/// The canvas's ideal size is set on construction (in logical pixels); it may
+/// be stretched to fill available space.
+#[derive(Clone, Widget)]
+pub struct Canvas<F: Fn(&mut dyn DrawHandle, Rect) + 'static> {
+    #[core]
+    core: CoreData,
+    size: Size,
+    draw_fn: F,
+}
+
+impl<F: Fn(&mut dyn DrawHandle, Rect)> Debug for Canvas<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Canvas {{ core: {:?}, size: {:?}, ... }}",
+            self.core, self.size
+        )
+    }
+}
+
+impl<F: Fn(&mut dyn DrawHandle, Rect)> Widget for Canvas<F> {}
+
+impl<F: Fn(&mut dyn DrawHandle, Rect)> Layout for Canvas<F> {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let ideal = axis.extract_size(self.size) as f32 * size_handle.dpi_factor();
+        SizeRules::new(0, ideal.round() as u32, StretchPolicy::Maximise)
+    }
+
+    fn set_rect(&mut self, _: &mut dyn SizeHandle, rect: Rect, _: AlignHints) {
+        self.core.rect = rect;
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, _: &Manager) {
+        let rect = self.core.rect;
+        draw_handle.clip_region(rect, Coord::ZERO, &mut |handle| {
+            (self.draw_fn)(handle, rect)
+        });
+    }
+}
+
+impl<F: Fn(&mut dyn DrawHandle, Rect)> Handler for Canvas<F> {
+    type Msg = VoidMsg;
+}
+
+impl<F: Fn(&mut dyn DrawHandle, Rect)> Canvas<F> {
+    /// Construct with the given ideal `size` and draw function `f`
+    pub fn new(size: Size, f: F) -> Self {
+        Canvas {
+            core: Default::default(),
+            size,
+            draw_fn: f,
+        }
+    }
+
+    /// Request a redraw
+    ///
+    /// Call this when the data drawn by the canvas changes.
+    pub fn redraw(&self, mgr: &mut Manager) {
+        mgr.redraw(self.id());
+    }
+}
@@ -7,7 +7,7 @@
 
 use std::fmt::{self, Debug};
 
-use super::Label;
+use super::AccelLabel;
 use crate::class::HasBool;
 use crate::event::{Action, Handler, Manager, Response, VoidMsg};
 use crate::geom::Rect;
@@ -16,12 +16,27 @@ use crate::macros::Widget;
 use crate::theme::{DrawHandle, SizeHandle};
 use crate::{Align, AlignHints, CoreData, Layout, Widget, WidgetCore};
 
+/// Toggle handler for a tri-state checkbox
+///
+/// This wraps a closure receiving the new state of the checkbox, where `None`
+/// is the indeterminate (mixed) state. See [`CheckBoxBare::on_toggle_tri`].
+#[derive(Clone, Default)]
+pub struct TriState<F>(pub F);
+
 /// A bare checkbox (no label)
+///
+/// In addition to being checked or unchecked, a checkbox may be in an
+/// indeterminate (mixed) state, for example when used as the parent of a group
+/// of checkboxes with differing states. This state is usually only set
+/// programmatically (see [`CheckBoxBare::set_tri_state`]); activating a mixed
+/// checkbox checks it. Where a toggle handler is set via
+/// [`CheckBoxBare::on_toggle_tri`], activation instead cycles through
+/// unchecked, checked and mixed states.
 #[derive(Clone, Default, Widget)]
 pub struct CheckBoxBare<OT: 'static> {
     #[core]
     core: CoreData,
-    state: bool,
+    state: Option<bool>,
     on_toggle: OT,
 }
 
@@ -72,7 +87,7 @@ impl<M, OT: Fn(bool) -> M> CheckBoxBare<OT> {
     pub fn new_on(f: OT) -> Self {
         CheckBoxBare {
             core: Default::default(),
-            state: false,
+            state: Some(false),
             on_toggle: f,
         }
     }
@@ -84,7 +99,7 @@ impl CheckBoxBare<()> {
     pub fn new() -> Self {
         CheckBoxBare {
             core: Default::default(),
-            state: false,
+            state: Some(false),
             on_toggle: (),
         }
     }
@@ -101,25 +116,68 @@ impl CheckBoxBare<()> {
             on_toggle: f,
         }
     }
+
+    /// Set the event handler to be called on toggle, with tri-state support
+    ///
+    /// Activation cycles through unchecked, checked and mixed states. The
+    /// closure `f` is called with the new state of the checkbox (`None` for the
+    /// mixed state) when toggled, and the result of `f` is returned from the
+    /// event handler.
+    #[inline]
+    pub fn on_toggle_tri<M, F>(self, f: F) -> CheckBoxBare<TriState<F>>
+    where
+        F: Fn(Option<bool>) -> M,
+    {
+        CheckBoxBare {
+            core: self.core,
+            state: self.state,
+            on_toggle: TriState(f),
+        }
+    }
 }
 
 impl<OT: 'static> CheckBoxBare<OT> {
     /// Set the initial state of the checkbox.
     #[inline]
     pub fn state(mut self, state: bool) -> Self {
+        self.state = Some(state);
+        self
+    }
+
+    /// Set the initial state of the checkbox, where `None` is the mixed state
+    #[inline]
+    pub fn with_tri_state(mut self, state: Option<bool>) -> Self {
         self.state = state;
         self
     }
+
+    /// Get the state of the checkbox, where `None` is the mixed state
+    #[inline]
+    pub fn get_tri_state(&self) -> Option<bool> {
+        self.state
+    }
+
+    /// Set the state of the checkbox, where `None` is the mixed state
+    pub fn set_tri_state(&mut self, mgr: &mut Manager, state: Option<bool>) {
+        self.state = state;
+        mgr.redraw(self.id());
+    }
+
+    /// Toggle the state: the mixed state becomes checked
+    fn toggle(&mut self, mgr: &mut Manager) {
+        self.state = Some(self.state != Some(true));
+        mgr.redraw(self.id());
+    }
 }
 
 impl<H> HasBool for CheckBoxBare<H> {
+    /// Get the state; the mixed state is reported as `false`
     fn get_bool(&self) -> bool {
-        self.state
+        self.state == Some(true)
     }
 
     fn set_bool(&mut self, mgr: &mut Manager, state: bool) {
-        self.state = state;
-        mgr.redraw(self.id());
+        self.set_tri_state(mgr, Some(state));
     }
 }
 
@@ -134,8 +192,7 @@ impl Handler for CheckBoxBare<()> {
     fn handle_action(&mut self, mgr: &mut Manager, action: Action) -> Response<VoidMsg> {
         match action {
             Action::Activate => {
-                self.state = !self.state;
-                mgr.redraw(self.id());
+                self.toggle(mgr);
                 Response::None
             }
             a @ _ => Response::unhandled_action(a),
@@ -154,9 +211,32 @@ impl<M, H: Fn(bool) -> M> Handler for CheckBoxBare<H> {
     fn handle_action(&mut self, mgr: &mut Manager, action: Action) -> Response<M> {
         match action {
             Action::Activate => {
-                self.state = !self.state;
+                self.toggle(mgr);
+                ((self.on_toggle)(self.get_bool())).into()
+            }
+            a => Response::unhandled_action(a),
+        }
+    }
+}
+
+impl<M, H: Fn(Option<bool>) -> M> Handler for CheckBoxBare<TriState<H>> {
+    type Msg = M;
+
+    #[inline]
+    fn activation_via_press(&self) -> bool {
+        true
+    }
+
+    fn handle_action(&mut self, mgr: &mut Manager, action: Action) -> Response<M> {
+        match action {
+            Action::Activate => {
+                self.state = match self.state {
+                    Some(false) => Some(true),
+                    Some(true) => None,
+                    None => Some(false),
+                };
                 mgr.redraw(self.id());
-                ((self.on_toggle)(self.state)).into()
+                ((self.on_toggle.0)(self.state)).into()
             }
             a @ _ => Response::unhandled_action(a),
         }
@@ -164,11 +244,13 @@ impl<M, H: Fn(bool) -> M> Handler for CheckBoxBare<H> {
 }
 
 /// A checkable box with optional label
+///
+/// The label may specify a mnemonic (see [`AccelLabel`]).
 // TODO: use a generic wrapper for CheckBox and RadioBox?
 #[layout(horizontal, area=checkbox)]
-#[widget]
 #[handler(substitutions = (OT = ()))]
 #[handler(msg = M, generics = <M: From<VoidMsg>> where OT: Fn(bool) -> M)]
+#[handler(msg = M, substitutions = (OT = TriState<F>), generics = <M: From<VoidMsg>, F: 'static> where F: Fn(Option<bool>) -> M)]
 #[derive(Clone, Default, Widget)]
 pub struct CheckBox<OT: 'static> {
     #[core]
@@ -178,7 +260,7 @@ pub struct CheckBox<OT: 'static> {
     #[widget]
     checkbox: CheckBoxBare<OT>,
     #[widget]
-    label: Label,
+    label: AccelLabel,
 }
 
 impl<H> Debug for CheckBox<H> {
@@ -191,6 +273,14 @@ impl<H> Debug for CheckBox<H> {
     }
 }
 
+impl<OT: 'static> Widget for CheckBox<OT> {
+    fn configure(&mut self, mgr: &mut Manager) {
+        if let Some(key) = self.label.key() {
+            mgr.add_accel_key(key, self.checkbox.id());
+        }
+    }
+}
+
 impl<M, OT: Fn(bool) -> M> CheckBox<OT> {
     /// Construct a checkbox with a given `label` which calls `f` when toggled.
     ///
@@ -207,7 +297,7 @@ impl<M, OT: Fn(bool) -> M> CheckBox<OT> {
             core: Default::default(),
             layout_data: Default::default(),
             checkbox: CheckBoxBare::new_on(f),
-            label: Label::new(label),
+            label: AccelLabel::new(label.to_string()),
         }
     }
 }
@@ -223,7 +313,7 @@ impl CheckBox<()> {
             core: Default::default(),
             layout_data: Default::default(),
             checkbox: CheckBoxBare::new(),
-            label: Label::new(label),
+            label: AccelLabel::new(label.to_string()),
         }
     }
 
@@ -240,6 +330,22 @@ impl CheckBox<()> {
             label: self.label,
         }
     }
+
+    /// Set the event handler to be called on toggle, with tri-state support
+    ///
+    /// See [`CheckBoxBare::on_toggle_tri`].
+    #[inline]
+    pub fn on_toggle_tri<M, F>(self, f: F) -> CheckBox<TriState<F>>
+    where
+        F: Fn(Option<bool>) -> M,
+    {
+        CheckBox {
+            core: self.core,
+            layout_data: self.layout_data,
+            checkbox: self.checkbox.on_toggle_tri(f),
+            label: self.label,
+        }
+    }
 }
 
 impl<OT: 'static> CheckBox<OT> {
@@ -249,6 +355,25 @@ impl<OT: 'static> CheckBox<OT> {
         self.checkbox = self.checkbox.state(state);
         self
     }
+
+    /// Set the initial state of the checkbox, where `None` is the mixed state
+    #[inline]
+    pub fn with_tri_state(mut self, state: Option<bool>) -> Self {
+        self.checkbox = self.checkbox.with_tri_state(state);
+        self
+    }
+
+    /// Get the state of the checkbox, where `None` is the mixed state
+    #[inline]
+    pub fn get_tri_state(&self) -> Option<bool> {
+        self.checkbox.get_tri_state()
+    }
+
+    /// Set the state of the checkbox, where `None` is the mixed state
+    #[inline]
+    pub fn set_tri_state(&mut self, mgr: &mut Manager, state: Option<bool>) {
+        self.checkbox.set_tri_state(mgr, state);
+    }
 }
 
 impl<H> HasBool for CheckBox<H> {
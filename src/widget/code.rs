@@ -0,0 +1,305 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Code display widgets
+
+use super::ScrollArea;
+use crate::class::HasText;
+use crate::event::{Event, Handler, Manager, Response, VoidMsg};
+use crate::geom::{Coord, Rect, Size};
+use crate::layout::{AxisInfo, SizeRules, StretchPolicy};
+use crate::macros::Widget;
+use crate::theme::{DrawHandle, SizeHandle, TextClass, TextProperties};
+use crate::{Align, AlignHints, CoreData, Layout, TkAction, Widget, WidgetCore, WidgetId};
+
+/// A label displaying code (or other pre-formatted text)
+///
+/// Text is drawn as [`TextClass::Monospace`]: white-space is preserved, tabs
+/// are expanded to the [tab width](CodeLabel::with_tab_width) and lines are not
+/// wrapped. Optionally, [line numbers](CodeLabel::with_line_numbers) are shown
+/// in a gutter on the left.
+///
+/// This widget is always sized to fit its content; see [`CodeView`] for a
+/// scrollable version.
+#[widget]
+#[handler]
+#[derive(Clone, Debug, Widget)]
+pub struct CodeLabel {
+    #[core]
+    core: CoreData,
+    text: String,
+    /// Text with tabs expanded
+    display: String,
+    /// Line numbers, one per line
+    numbers: String,
+    tab_width: usize,
+    line_numbers: bool,
+    gutter_width: u32,
+    gutter_rect: Rect,
+    text_rect: Rect,
+}
+
+impl Default for CodeLabel {
+    fn default() -> Self {
+        CodeLabel::new("")
+    }
+}
+
+impl CodeLabel {
+    /// Construct, with the given text
+    pub fn new<T: ToString>(text: T) -> Self {
+        let mut label = CodeLabel {
+            core: Default::default(),
+            text: text.to_string(),
+            display: String::new(),
+            numbers: String::new(),
+            tab_width: 4,
+            line_numbers: false,
+            gutter_width: 0,
+            gutter_rect: Rect::default(),
+            text_rect: Rect::default(),
+        };
+        label.prepare();
+        label
+    }
+
+    /// Set the tab width, in characters (chain style)
+    ///
+    /// By default, tabs are expanded to multiples of 4 characters.
+    pub fn with_tab_width(mut self, tab_width: usize) -> Self {
+        self.tab_width = tab_width.max(1);
+        self.prepare();
+        self
+    }
+
+    /// Show line numbers (chain style)
+    ///
+    /// By default, line numbers are not shown.
+    pub fn with_line_numbers(mut self, line_numbers: bool) -> Self {
+        self.line_numbers = line_numbers;
+        self
+    }
+
+    /// True if line numbers are shown
+    #[inline]
+    pub fn has_line_numbers(&self) -> bool {
+        self.line_numbers
+    }
+
+    /// Get the number of lines
+    pub fn num_lines(&self) -> usize {
+        self.display.lines().count().max(1)
+    }
+
+    /// Get the length of the longest line, in characters
+    fn max_line_len(&self) -> usize {
+        let lines = self.display.lines();
+        lines.map(|line| line.chars().count()).max().unwrap_or(0)
+    }
+
+    fn prepare(&mut self) {
+        self.display = expand_tabs(&self.text, self.tab_width);
+        let numbers: Vec<_> = (1..=self.num_lines()).map(|n| n.to_string()).collect();
+        self.numbers = numbers.join("\n");
+    }
+}
+
+impl HasText for CodeLabel {
+    fn get_text(&self) -> &str {
+        &self.text
+    }
+
+    fn set_string(&mut self, mgr: &mut Manager, text: String) {
+        self.text = text;
+        self.prepare();
+        mgr.send_action(TkAction::Reconfigure);
+    }
+}
+
+impl Layout for CodeLabel {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let class = TextClass::Monospace;
+        let size = if axis.is_horizontal() {
+            let char_width = size_handle.char_width(class);
+            let width = |chars: usize| (chars as f32 * char_width).ceil() as u32;
+            self.gutter_width = if self.line_numbers {
+                let digits = self.num_lines().to_string().len();
+                width(digits) + size_handle.outer_margin().0
+            } else {
+                0
+            };
+            self.gutter_width + width(self.max_line_len())
+        } else {
+            size_handle.line_height(class) * self.num_lines() as u32
+        };
+        let margin = axis.extract_size(size_handle.inner_margin());
+        SizeRules::new(size, size, StretchPolicy::LowUtility) + SizeRules::fixed(2 * margin)
+    }
+
+    fn set_rect(&mut self, size_handle: &mut dyn SizeHandle, rect: Rect, _: AlignHints) {
+        self.core.rect = rect;
+        let margin = size_handle.inner_margin();
+        let pos = rect.pos + Coord::from(margin);
+        let size = Size(
+            rect.size.0.saturating_sub(2 * margin.0),
+            rect.size.1.saturating_sub(2 * margin.1),
+        );
+
+        // The gutter includes a margin between line numbers and text
+        let gap = size_handle.outer_margin().0.min(self.gutter_width);
+        let gutter = self.gutter_width.min(size.0);
+        self.gutter_rect = Rect::new(pos, Size(gutter.saturating_sub(gap), size.1));
+        self.text_rect = Rect::new(pos + Coord(gutter as i32, 0), Size(size.0 - gutter, size.1));
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, _: &Manager) {
+        let mut props = TextProperties {
+            class: TextClass::Monospace,
+            horiz: Align::End,
+            vert: Align::Begin,
+        };
+        if self.line_numbers {
+            draw_handle.text(self.gutter_rect, &self.numbers, props);
+        }
+        props.horiz = Align::Begin;
+        draw_handle.text(self.text_rect, &self.display, props);
+    }
+}
+
+/// A scrollable view over code (or other pre-formatted text)
+///
+/// This is a [`CodeLabel`] within a [`ScrollArea`]: scroll bars are shown when
+/// the text does not fit, e.g. for long lines. This may be used as a basis for
+/// log viewers and editors.
+#[derive(Clone, Debug, Widget)]
+pub struct CodeView {
+    #[core]
+    core: CoreData,
+    #[widget]
+    area: ScrollArea<CodeLabel>,
+}
+
+impl CodeView {
+    /// Construct, with the given text
+    pub fn new<T: ToString>(text: T) -> Self {
+        CodeView {
+            core: Default::default(),
+            area: ScrollArea::new(CodeLabel::new(text)),
+        }
+    }
+
+    /// Set the tab width, in characters (chain style)
+    ///
+    /// See [`CodeLabel::with_tab_width`].
+    pub fn with_tab_width(mut self, tab_width: usize) -> Self {
+        let label = self.area.inner_mut();
+        *label = std::mem::take(label).with_tab_width(tab_width);
+        self
+    }
+
+    /// Show line numbers (chain style)
+    ///
+    /// See [`CodeLabel::with_line_numbers`].
+    pub fn with_line_numbers(mut self, line_numbers: bool) -> Self {
+        let label = self.area.inner_mut();
+        *label = std::mem::take(label).with_line_numbers(line_numbers);
+        self
+    }
+
+    /// Access inner widget directly
+    #[inline]
+    pub fn inner(&self) -> &CodeLabel {
+        self.area.inner()
+    }
+
+    /// Access the scroll area
+    #[inline]
+    pub fn area(&self) -> &ScrollArea<CodeLabel> {
+        &self.area
+    }
+
+    /// Access the scroll area mutably
+    ///
+    /// This may be used to scroll the view, e.g. to the last line of a log.
+    #[inline]
+    pub fn area_mut(&mut self) -> &mut ScrollArea<CodeLabel> {
+        &mut self.area
+    }
+}
+
+impl HasText for CodeView {
+    fn get_text(&self) -> &str {
+        self.area.inner().get_text()
+    }
+
+    fn set_string(&mut self, mgr: &mut Manager, text: String) {
+        self.area.inner_mut().set_string(mgr, text);
+    }
+}
+
+impl Widget for CodeView {}
+
+impl Layout for CodeView {
+    #[inline]
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        self.area.size_rules(size_handle, axis)
+    }
+
+    fn set_rect(&mut self, size_handle: &mut dyn SizeHandle, rect: Rect, align: AlignHints) {
+        self.core.rect = rect;
+        self.area.set_rect(size_handle, rect, align);
+    }
+
+    #[inline]
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        self.area.find_id(coord)
+    }
+
+    #[inline]
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &Manager) {
+        self.area.draw(draw_handle, mgr);
+    }
+}
+
+impl Handler for CodeView {
+    type Msg = VoidMsg;
+
+    #[inline]
+    fn handle(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<Self::Msg> {
+        if id <= self.area.id() {
+            self.area.handle(mgr, id, event)
+        } else {
+            debug_assert!(id == self.id(), "Handler::handle: bad WidgetId");
+            Response::Unhandled(event)
+        }
+    }
+}
+
+/// Expand tabs to spaces, aligning to multiples of `tab_width` characters
+fn expand_tabs(text: &str, tab_width: usize) -> String {
+    if !text.contains('\t') {
+        return text.to_string();
+    }
+    let mut result = String::with_capacity(text.len());
+    let mut column = 0;
+    for c in text.chars() {
+        match c {
+            '\t' => {
+                let n = tab_width - column % tab_width;
+                result.push_str(&" ".repeat(n));
+                column += n;
+            }
+            '\n' => {
+                result.push(c);
+                column = 0;
+            }
+            c => {
+                result.push(c);
+                column += 1;
+            }
+        }
+    }
+    result
+}
@@ -0,0 +1,197 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Size constraints
+
+use crate::event::{Event, Handler, Manager, Response};
+use crate::geom::{Coord, Rect, Size};
+use crate::layout::{AxisInfo, SizeRules, StretchPolicy};
+use crate::macros::Widget;
+use crate::theme::{DrawHandle, SizeHandle};
+use crate::{Align, AlignHints, CoreData, Layout, TkAction, Widget, WidgetCore, WidgetId};
+
+/// A wrapper constraining the size of its child
+///
+/// Minimum and maximum sizes are given in logical pixels (scaled by the DPI
+/// factor). These take precedence over the child's own size requirements; the
+/// child may therefore be given less than its minimum size (e.g. to cap the
+/// width of an [`EditBox`](crate::widget::EditBox)).
+///
+/// Where more space than the maximum size is available, the child is placed
+/// according to alignment hints (by default, centred).
+#[derive(Clone, Debug, Widget)]
+pub struct SizeConstraint<W: Widget> {
+    #[core]
+    core: CoreData,
+    #[widget]
+    child: W,
+    min: Size,
+    max: Size,
+}
+
+impl<W: Widget> SizeConstraint<W> {
+    /// Construct, without constraints
+    #[inline]
+    pub fn new(child: W) -> Self {
+        SizeConstraint {
+            core: Default::default(),
+            child,
+            min: Size::ZERO,
+            max: Size::uniform(u32::MAX),
+        }
+    }
+
+    /// Set the minimum size (chain style)
+    #[inline]
+    pub fn with_min_size(mut self, min: Size) -> Self {
+        self.min = min;
+        self
+    }
+
+    /// Set the maximum size (chain style)
+    #[inline]
+    pub fn with_max_size(mut self, max: Size) -> Self {
+        self.max = max;
+        self
+    }
+
+    /// Set the minimum width (chain style)
+    #[inline]
+    pub fn with_min_width(mut self, width: u32) -> Self {
+        self.min.0 = width;
+        self
+    }
+
+    /// Set the minimum height (chain style)
+    #[inline]
+    pub fn with_min_height(mut self, height: u32) -> Self {
+        self.min.1 = height;
+        self
+    }
+
+    /// Set the maximum width (chain style)
+    #[inline]
+    pub fn with_max_width(mut self, width: u32) -> Self {
+        self.max.0 = width;
+        self
+    }
+
+    /// Set the maximum height (chain style)
+    #[inline]
+    pub fn with_max_height(mut self, height: u32) -> Self {
+        self.max.1 = height;
+        self
+    }
+
+    /// Get the minimum size
+    #[inline]
+    pub fn min_size(&self) -> Size {
+        self.min
+    }
+
+    /// Get the maximum size
+    #[inline]
+    pub fn max_size(&self) -> Size {
+        self.max
+    }
+
+    /// Set the minimum and maximum sizes
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action).
+    pub fn set_constraints(&mut self, mgr: &mut Manager, min: Size, max: Size) {
+        self.min = min;
+        self.max = max;
+        mgr.send_action(TkAction::Reconfigure);
+    }
+
+    /// Access the child widget
+    #[inline]
+    pub fn inner(&self) -> &W {
+        &self.child
+    }
+
+    /// Access the child widget mutably
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut W {
+        &mut self.child
+    }
+
+    // Get scaled (min, max) sizes
+    fn scaled(&self, size_handle: &dyn SizeHandle) -> (Size, Size) {
+        let factor = size_handle.dpi_factor();
+        let scale = |x: u32| match x {
+            u32::MAX => x,
+            x => (x as f32 * factor).round() as u32,
+        };
+        let min = Size(scale(self.min.0), scale(self.min.1));
+        let max = Size(scale(self.max.0), scale(self.max.1));
+        (min, max.max(min))
+    }
+}
+
+impl<W: Widget> Widget for SizeConstraint<W> {}
+
+impl<W: Widget> Layout for SizeConstraint<W> {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, mut axis: AxisInfo) -> SizeRules {
+        let (min, max) = self.scaled(size_handle);
+        if let Some(other) = axis.other() {
+            let max_other = if axis.is_horizontal() { max.1 } else { max.0 };
+            axis.sub_other(other.saturating_sub(max_other));
+        }
+        let (min, max) = (axis.extract_size(min), axis.extract_size(max));
+        let rules = self.child.size_rules(size_handle, axis);
+
+        let a = rules.min_size().max(min).min(max);
+        let b = rules.ideal_size().max(min).min(max);
+        let stretch = if b >= max {
+            StretchPolicy::Fixed
+        } else {
+            rules.stretch()
+        };
+        SizeRules::new(a, b, stretch)
+    }
+
+    fn set_rect(&mut self, size_handle: &mut dyn SizeHandle, rect: Rect, align: AlignHints) {
+        self.core.rect = rect;
+        let (_, max) = self.scaled(size_handle);
+        let size = rect.size.min(max);
+        let place = |align: Option<Align>, space: u32, size: u32| {
+            let diff = (space - size) as i32;
+            match align.unwrap_or(Align::Centre) {
+                Align::Begin | Align::Stretch => 0,
+                Align::Centre => diff / 2,
+                Align::End => diff,
+            }
+        };
+        let offset = Coord(
+            place(align.horiz, rect.size.0, size.0),
+            place(align.vert, rect.size.1, size.1),
+        );
+        let rect = Rect::new(rect.pos + offset, size);
+        self.child.set_rect(size_handle, rect, align);
+    }
+
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        self.child.find_id(coord)
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &Manager) {
+        self.child.draw(draw_handle, mgr);
+    }
+}
+
+impl<W: Widget + Handler> Handler for SizeConstraint<W> {
+    type Msg = <W as Handler>::Msg;
+
+    #[inline]
+    fn handle(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<Self::Msg> {
+        if id <= self.child.id() {
+            self.child.handle(mgr, id, event)
+        } else {
+            debug_assert!(id <= self.id(), "Handler::handle: bad WidgetId");
+            Response::Unhandled(event)
+        }
+    }
+}
@@ -0,0 +1,456 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Date and time pickers
+
+use std::any::Any;
+use std::iter;
+use std::rc::Rc;
+
+use super::{Label, SpinBox, TextButton};
+use crate::class::HasText;
+use crate::data::{Date, DateLocale, EnglishLocale, Time, Weekday};
+use crate::event::{Event, Handler, Manager, Response};
+use crate::geom::{Coord, Rect};
+use crate::layout::{self, AxisInfo, GridChildInfo, RulesSetter, RulesSolver, SizeRules};
+use crate::macros::{VoidMsg, Widget};
+use crate::theme::{DrawHandle, SizeHandle};
+use crate::{
+    Align, AlignHints, CoreData, Layout, LayoutData, TkAction, Widget, WidgetCore, WidgetId,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, VoidMsg)]
+enum Nav {
+    Prev,
+    Next,
+}
+
+/// A calendar for selecting a date
+///
+/// This displays one month at a time, with buttons to move to the previous or
+/// next month. Clicking a day selects it and returns the [`Date`] as a message.
+///
+/// Month names, weekday headings and the first day of the week are taken from
+/// a [`DateLocale`] (by default, [`EnglishLocale`]); see
+/// [`DatePicker::with_locale`].
+///
+/// KAS does not yet support pop-ups, thus the calendar is displayed inline.
+#[layout(grid)]
+#[handler(msg = Date)]
+#[derive(Clone, Debug, Widget)]
+pub struct DatePicker {
+    #[core]
+    core: CoreData,
+    #[layout_data]
+    layout_data: <Self as LayoutData>::Data,
+    #[widget(row = 0, col = 0, handler = navigate)]
+    prev: TextButton<Nav>,
+    #[widget(row = 0, col = 1, halign = centre)]
+    heading: Label,
+    #[widget(row = 0, col = 2, handler = navigate)]
+    next: TextButton<Nav>,
+    #[widget(row = 1, col = 0, cspan = 3, handler = select)]
+    grid: DayGrid,
+    shown: Date,
+    selected: Option<Date>,
+    locale: Rc<dyn DateLocale>,
+}
+
+impl Widget for DatePicker {}
+
+impl DatePicker {
+    /// Construct with `date` selected
+    pub fn new(date: Date) -> Self {
+        let locale = Rc::new(EnglishLocale::default());
+        let shown = Date::new(date.year(), date.month(), 1).unwrap();
+        DatePicker {
+            core: Default::default(),
+            layout_data: Default::default(),
+            prev: TextButton::new("<", Nav::Prev),
+            heading: Label::new(locale.month_heading(shown.year(), shown.month())),
+            next: TextButton::new(">", Nav::Next),
+            grid: DayGrid::new(shown, Some(date.day()), &*locale),
+            shown,
+            selected: Some(date),
+            locale,
+        }
+    }
+
+    /// Set the locale (chain style)
+    pub fn with_locale<L: DateLocale + 'static>(mut self, locale: L) -> Self {
+        let (year, month) = (self.shown.year(), self.shown.month());
+        self.heading = Label::new(locale.month_heading(year, month));
+        self.grid = DayGrid::new(self.shown, self.selected_day(), &locale);
+        self.locale = Rc::new(locale);
+        self
+    }
+
+    /// Get the selected date
+    #[inline]
+    pub fn selected(&self) -> Option<Date> {
+        self.selected
+    }
+
+    /// Set the selected date
+    ///
+    /// The calendar shows the month of `date`, if any. No message is returned.
+    pub fn set_selected(&mut self, mgr: &mut Manager, date: Option<Date>) {
+        self.selected = date;
+        match date {
+            Some(date) => self.show_month(mgr, date.year(), date.month()),
+            None => self.grid.set_selected(mgr, None),
+        }
+    }
+
+    /// Show the given `month` (`1..=12`) of `year`
+    ///
+    /// Panics if `month` is out of range.
+    pub fn show_month(&mut self, mgr: &mut Manager, year: i32, month: u8) {
+        self.shown = Date::new(year, month, 1).expect("DatePicker::show_month: bad month");
+        let heading = self.locale.month_heading(year, month);
+        self.heading.set_string(mgr, heading);
+        let selected = self.selected_day();
+        self.grid
+            .set_month(mgr, self.shown, selected, &*self.locale);
+    }
+
+    /// Day of the selected date, if within the shown month
+    fn selected_day(&self) -> Option<u8> {
+        let shown = self.shown;
+        self.selected
+            .filter(|date| (date.year(), date.month()) == (shown.year(), shown.month()))
+            .map(|date| date.day())
+    }
+
+    fn navigate(&mut self, mgr: &mut Manager, nav: Nav) -> Response<Date> {
+        let shown = match nav {
+            Nav::Prev => self.shown.add_months(-1),
+            Nav::Next => self.shown.add_months(1),
+        };
+        self.show_month(mgr, shown.year(), shown.month());
+        Response::None
+    }
+
+    fn select(&mut self, mgr: &mut Manager, day: u8) -> Response<Date> {
+        let date = match Date::new(self.shown.year(), self.shown.month(), day) {
+            Some(date) => date,
+            None => return Response::None,
+        };
+        self.selected = Some(date);
+        self.grid.set_selected(mgr, Some(day));
+        Response::Msg(date)
+    }
+}
+
+/// Weekday headings and day buttons of one month
+///
+/// Returns the day of the month as a message when a day is clicked.
+#[derive(Clone, Debug)]
+struct DayGrid {
+    core: CoreData,
+    headings: Vec<Label>,
+    days: Vec<TextButton<u8>>,
+    // cell index of the first day of the month
+    offset: usize,
+    selected: Option<u8>,
+    data: layout::DynGridStorage,
+}
+
+// We implement this manually, because the derive implementation cannot handle
+// vectors of child widgets.
+impl WidgetCore for DayGrid {
+    #[inline]
+    fn core_data(&self) -> &CoreData {
+        &self.core
+    }
+    #[inline]
+    fn core_data_mut(&mut self) -> &mut CoreData {
+        &mut self.core
+    }
+
+    #[inline]
+    fn widget_name(&self) -> &'static str {
+        "DayGrid"
+    }
+
+    #[inline]
+    fn as_widget(&self) -> &dyn Widget {
+        self
+    }
+    #[inline]
+    fn as_widget_mut(&mut self) -> &mut dyn Widget {
+        self
+    }
+    #[inline]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    #[inline]
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.headings.len() + self.days.len()
+    }
+    fn get(&self, index: usize) -> Option<&dyn Widget> {
+        match index.checked_sub(self.headings.len()) {
+            None => Some(self.headings[index].as_widget()),
+            Some(i) => self.days.get(i).map(|w| w.as_widget()),
+        }
+    }
+    fn get_mut(&mut self, index: usize) -> Option<&mut dyn Widget> {
+        match index.checked_sub(self.headings.len()) {
+            None => Some(self.headings[index].as_widget_mut()),
+            Some(i) => self.days.get_mut(i).map(|w| w.as_widget_mut()),
+        }
+    }
+
+    fn walk(&self, f: &mut dyn FnMut(&dyn Widget)) {
+        for heading in &self.headings {
+            heading.walk(f);
+        }
+        for day in &self.days {
+            day.walk(f);
+        }
+        f(self)
+    }
+    fn walk_mut(&mut self, f: &mut dyn FnMut(&mut dyn Widget)) {
+        for heading in &mut self.headings {
+            heading.walk_mut(f);
+        }
+        for day in &mut self.days {
+            day.walk_mut(f);
+        }
+        f(self)
+    }
+}
+
+impl Widget for DayGrid {}
+
+fn child_info(cell: usize) -> GridChildInfo {
+    let (col, row) = (cell % 7, cell / 7);
+    GridChildInfo {
+        col,
+        col_end: col + 1,
+        col_span_index: usize::MAX,
+        row,
+        row_end: row + 1,
+        row_span_index: usize::MAX,
+    }
+}
+
+impl Layout for DayGrid {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let margin = axis.extract_size(size_handle.outer_margin());
+        // A heading row plus six rows of days, enough for any month
+        let mut solver =
+            layout::GridSolver::<[u32; 7], [u32; 7], [SizeRules; 0], [SizeRules; 0], _>::new(
+                axis,
+                (7, 7),
+                &mut self.data,
+            );
+        for (n, heading) in self.headings.iter_mut().enumerate() {
+            solver.for_child(&mut self.data, child_info(n), |axis| {
+                heading.size_rules(size_handle, axis) + 2 * margin
+            });
+        }
+        for (n, day) in self.days.iter_mut().enumerate() {
+            let cell = 7 + self.offset + n;
+            solver.for_child(&mut self.data, child_info(cell), |axis| {
+                day.size_rules(size_handle, axis)
+            });
+        }
+        solver.finish(&mut self.data, iter::empty(), iter::empty())
+    }
+
+    fn set_rect(&mut self, size_handle: &mut dyn SizeHandle, rect: Rect, _: AlignHints) {
+        self.core.rect = rect;
+        let margin = size_handle.outer_margin();
+        let mut setter = layout::GridSetter::<[u32; 7], [u32; 7], _>::new(
+            rect,
+            layout::Margins::ZERO,
+            (7, 7),
+            &mut self.data,
+        );
+        let direction = size_handle.layout_direction();
+        let outer = rect;
+
+        for (n, heading) in self.headings.iter_mut().enumerate() {
+            let rect = direction.mirror(setter.child_rect(child_info(n)), outer);
+            let rect = Rect {
+                pos: rect.pos + margin,
+                size: rect.size - margin - margin,
+            };
+            let align = AlignHints::new(Some(Align::Centre), Some(Align::Centre));
+            heading.set_rect(size_handle, rect, align);
+        }
+        for (n, day) in self.days.iter_mut().enumerate() {
+            let rect = setter.child_rect(child_info(7 + self.offset + n));
+            let rect = direction.mirror(rect, outer);
+            day.set_rect(size_handle, rect, AlignHints::NONE);
+        }
+    }
+
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        for day in &self.days {
+            if day.rect().contains(coord) {
+                return day.find_id(coord);
+            }
+        }
+        Some(self.id())
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &Manager) {
+        for heading in &self.headings {
+            heading.draw(draw_handle, mgr);
+        }
+        if let Some(day) = self.selected {
+            if let Some(button) = self.days.get(day as usize - 1) {
+                draw_handle.selection_box(button.rect());
+            }
+        }
+        for day in &self.days {
+            day.draw(draw_handle, mgr);
+        }
+    }
+}
+
+impl Handler for DayGrid {
+    type Msg = u8;
+
+    fn handle(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<u8> {
+        for heading in &self.headings {
+            if id <= heading.id() {
+                // Headings do not handle input
+                return Response::Unhandled(event);
+            }
+        }
+        for day in &mut self.days {
+            if id <= day.id() {
+                return day.handle(mgr, id, event);
+            }
+        }
+        debug_assert!(id == self.id(), "Handler::handle: bad WidgetId");
+        Response::Unhandled(event)
+    }
+}
+
+impl DayGrid {
+    /// Construct for the month of `first` (the first day of the month)
+    fn new(first: Date, selected: Option<u8>, locale: &dyn DateLocale) -> Self {
+        let mut day = locale.first_day_of_week();
+        let headings = (0..7)
+            .map(|_| {
+                let label = Label::new(locale.weekday_name(day));
+                day = day.succ();
+                label
+            })
+            .collect();
+        let days = (1..=first.month_len())
+            .map(|day| TextButton::new(day.to_string(), day))
+            .collect();
+        DayGrid {
+            core: Default::default(),
+            headings,
+            days,
+            offset: Self::offset(first, locale.first_day_of_week()),
+            selected,
+            data: Default::default(),
+        }
+    }
+
+    fn offset(first: Date, first_day: Weekday) -> usize {
+        (first.weekday().index() + 7 - first_day.index()) % 7
+    }
+
+    fn set_month(
+        &mut self,
+        mgr: &mut Manager,
+        first: Date,
+        selected: Option<u8>,
+        locale: &dyn DateLocale,
+    ) {
+        let len = first.month_len() as usize;
+        if self.days.len() > len {
+            for day in &mut self.days[len..] {
+                mgr.detach(day);
+            }
+            self.days.truncate(len);
+        }
+        for day in self.days.len()..len {
+            let day = (day + 1) as u8;
+            self.days.push(TextButton::new(day.to_string(), day));
+        }
+        self.offset = Self::offset(first, locale.first_day_of_week());
+        self.selected = selected;
+        // Days have moved
+        mgr.send_action(TkAction::Reconfigure);
+    }
+
+    fn set_selected(&mut self, mgr: &mut Manager, selected: Option<u8>) {
+        if selected != self.selected {
+            self.selected = selected;
+            mgr.redraw(self.id());
+        }
+    }
+}
+
+/// A widget for selecting a time of day
+///
+/// This consists of spin boxes for the hour (24-hour clock) and minute. Each
+/// change returns the new [`Time`] as a message.
+#[layout(horizontal)]
+#[handler(msg = Time)]
+#[derive(Clone, Debug, Widget)]
+pub struct TimePicker {
+    #[core]
+    core: CoreData,
+    #[layout_data]
+    layout_data: <Self as LayoutData>::Data,
+    #[widget(handler = changed)]
+    hour: SpinBox<u8>,
+    #[widget(valign = centre)]
+    separator: Label,
+    #[widget(handler = changed)]
+    minute: SpinBox<u8>,
+}
+
+impl Widget for TimePicker {}
+
+impl TimePicker {
+    /// Construct with the given initial `time`
+    pub fn new(time: Time) -> Self {
+        TimePicker {
+            core: Default::default(),
+            layout_data: Default::default(),
+            hour: SpinBox::new(0, 23, 1)
+                .with_value(time.hour())
+                .with_wrap(true),
+            separator: Label::new(":"),
+            minute: SpinBox::new(0, 59, 1)
+                .with_value(time.minute())
+                .with_wrap(true),
+        }
+    }
+
+    /// Get the selected time
+    pub fn time(&self) -> Time {
+        Time::new(self.hour.value(), self.minute.value()).unwrap()
+    }
+
+    /// Set the time
+    ///
+    /// No message is returned.
+    pub fn set_time(&mut self, mgr: &mut Manager, time: Time) {
+        self.hour.set_value(mgr, time.hour());
+        self.minute.set_value(mgr, time.minute());
+    }
+
+    fn changed(&mut self, _: &mut Manager, _: u8) -> Response<Time> {
+        Response::Msg(self.time())
+    }
+}
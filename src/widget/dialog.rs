@@ -7,18 +7,154 @@
 //!
 //! KAS dialog boxes are pre-configured windows, usually allowing some
 //! customisation.
+//!
+//! Dialogs are usually opened with [`Window::show_modal`], which blocks input
+//! to the current window until the dialog is closed. Since KAS is event
+//! driven, this returns immediately; the dialog's result is delivered through
+//! a [`DialogReply`]:
+//!
+//! ```ignore
+//! // In some event handler:
+//! let dialog = ConfirmBox::new("Quit", "Discard unsaved changes?");
+//! let reply = dialog.reply();
+//! mgr.update_on_handle(reply.handle(), self.id());
+//! self.reply = Some(reply);
+//! dialog.show_modal(mgr);
+//!
+//! // In Handler::update_handle:
+//! if let Some(Confirm::Ok) = self.reply.as_ref().and_then(|r| r.take()) {
+//!     mgr.send_action(TkAction::CloseAll);
+//! }
+//! ```
 
-use crate::event::{Callback, Manager, Response, VoidMsg};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::class::HasText;
+use crate::draw::ImageData;
+use crate::event::{Callback, Manager, Response, UpdateHandle, VoidMsg};
 use crate::geom::Size;
 use crate::layout;
 use crate::macros::{VoidMsg, Widget};
 use crate::theme::SizeHandle;
-use crate::widget::{Label, TextButton};
+use crate::widget::{Column, EditBox, Image, Label, TextButton};
 use crate::{CoreData, TkAction, Window};
 
-#[derive(Clone, Debug, VoidMsg)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, VoidMsg)]
 enum DialogButton {
     Close,
+    Ok,
+    Cancel,
+    Yes,
+    No,
+}
+
+/// The result of a [`ConfirmBox`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Confirm {
+    Ok,
+    Cancel,
+}
+
+/// The result of a [`QuestionBox`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Yes,
+    No,
+    Cancel,
+}
+
+/// The reply of a dialog, of type `T`
+///
+/// This is obtained from a dialog before it is shown (e.g. via
+/// [`ConfirmBox::reply`]) and may be cloned. When the dialog is closed, the
+/// result is stored and the [`UpdateHandle`] is triggered; subscribed widgets
+/// (see [`Manager::update_on_handle`]) may then [`take`](DialogReply::take)
+/// the result. Closing the dialog's window without choosing an option yields
+/// the "cancel" result.
+#[derive(Clone, Debug)]
+pub struct DialogReply<T> {
+    handle: UpdateHandle,
+    value: Rc<RefCell<Option<T>>>,
+}
+
+impl<T> DialogReply<T> {
+    fn new() -> Self {
+        DialogReply {
+            handle: UpdateHandle::new(),
+            value: Default::default(),
+        }
+    }
+
+    /// Get the handle triggered when a result is available
+    #[inline]
+    pub fn handle(&self) -> UpdateHandle {
+        self.handle
+    }
+
+    /// Take the result, if available
+    pub fn take(&self) -> Option<T> {
+        self.value.borrow_mut().take()
+    }
+}
+
+/// The sending side of a [`DialogReply`]
+#[derive(Clone, Debug)]
+struct Replier<T> {
+    reply: DialogReply<T>,
+    sent: bool,
+}
+
+impl<T> Replier<T> {
+    fn new() -> Self {
+        Replier {
+            reply: DialogReply::new(),
+            sent: false,
+        }
+    }
+
+    /// Send a value and close the window; only the first value is sent
+    fn send(&mut self, mgr: &mut Manager, value: T) {
+        if !self.sent {
+            self.sent = true;
+            *self.reply.value.borrow_mut() = Some(value);
+            mgr.trigger_update(self.reply.handle, 0);
+        }
+        mgr.send_action(TkAction::Close);
+    }
+}
+
+// Implement Window for a dialog with fields `title` and `replier`, sending
+// `$cancel` if closed before a reply was sent
+macro_rules! impl_dialog_window {
+    ($ty:ty, $cancel:expr) => {
+        impl Window for $ty {
+            fn title(&self) -> &str {
+                &self.title
+            }
+
+            fn resize(
+                &mut self,
+                size_handle: &mut dyn SizeHandle,
+                size: Size,
+            ) -> (Option<Size>, Option<Size>) {
+                let (min, max) = layout::solve(self, size_handle, size);
+                (Some(min), Some(max))
+            }
+
+            fn callbacks(&self) -> Vec<(usize, Callback)> {
+                vec![(0, Callback::Close)]
+            }
+            fn final_callback(
+                &self,
+            ) -> Option<&'static dyn Fn(Box<dyn kas::Window>, &mut Manager)> {
+                None
+            }
+            fn trigger_callback(&mut self, _index: usize, mgr: &mut Manager) {
+                self.replier.send(mgr, $cancel);
+            }
+        }
+    };
 }
 
 /// A simple message box.
@@ -44,15 +180,15 @@ impl MessageBox {
             core: Default::default(),
             layout_data: Default::default(),
             title: title.to_string(),
-            label: Label::new(message),
+            label: Label::new(message).selectable(true),
             button: TextButton::new("Ok", DialogButton::Close),
         }
     }
 
     fn handle_button(&mut self, mgr: &mut Manager, msg: DialogButton) -> Response<VoidMsg> {
-        match msg {
-            DialogButton::Close => mgr.send_action(TkAction::Close),
-        };
+        if msg == DialogButton::Close {
+            mgr.send_action(TkAction::Close);
+        }
         Response::None
     }
 }
@@ -80,3 +216,344 @@ impl Window for MessageBox {
     }
     fn trigger_callback(&mut self, _index: usize, _: &mut Manager) {}
 }
+
+/// A message box with "Ok" and "Cancel" buttons
+///
+/// The result is a [`Confirm`] value; see [`ConfirmBox::reply`].
+#[widget]
+#[layout(grid)]
+#[handler]
+#[derive(Clone, Debug, Widget)]
+pub struct ConfirmBox {
+    #[core]
+    core: CoreData,
+    #[layout_data]
+    layout_data: <Self as kas::LayoutData>::Data,
+    title: String,
+    #[widget(row = 0, col = 0, cspan = 2)]
+    label: Label,
+    #[widget(row = 1, col = 0, handler = handle_button)]
+    ok: TextButton<DialogButton>,
+    #[widget(row = 1, col = 1, handler = handle_button)]
+    cancel: TextButton<DialogButton>,
+    replier: Replier<Confirm>,
+}
+
+impl ConfirmBox {
+    /// Construct
+    pub fn new<T: ToString, M: ToString>(title: T, message: M) -> Self {
+        ConfirmBox {
+            core: Default::default(),
+            layout_data: Default::default(),
+            title: title.to_string(),
+            label: Label::new(message),
+            ok: TextButton::new("Ok", DialogButton::Ok),
+            cancel: TextButton::new("Cancel", DialogButton::Cancel),
+            replier: Replier::new(),
+        }
+    }
+
+    /// Get the reply handle
+    pub fn reply(&self) -> DialogReply<Confirm> {
+        self.replier.reply.clone()
+    }
+
+    fn handle_button(&mut self, mgr: &mut Manager, msg: DialogButton) -> Response<VoidMsg> {
+        let value = match msg {
+            DialogButton::Ok => Confirm::Ok,
+            _ => Confirm::Cancel,
+        };
+        self.replier.send(mgr, value);
+        Response::None
+    }
+}
+
+impl_dialog_window!(ConfirmBox, Confirm::Cancel);
+
+/// A message box with "Yes", "No" and "Cancel" buttons
+///
+/// The result is an [`Answer`]; see [`QuestionBox::reply`].
+#[widget]
+#[layout(grid)]
+#[handler]
+#[derive(Clone, Debug, Widget)]
+pub struct QuestionBox {
+    #[core]
+    core: CoreData,
+    #[layout_data]
+    layout_data: <Self as kas::LayoutData>::Data,
+    title: String,
+    #[widget(row = 0, col = 0, cspan = 3)]
+    label: Label,
+    #[widget(row = 1, col = 0, handler = handle_button)]
+    yes: TextButton<DialogButton>,
+    #[widget(row = 1, col = 1, handler = handle_button)]
+    no: TextButton<DialogButton>,
+    #[widget(row = 1, col = 2, handler = handle_button)]
+    cancel: TextButton<DialogButton>,
+    replier: Replier<Answer>,
+}
+
+impl QuestionBox {
+    /// Construct
+    pub fn new<T: ToString, M: ToString>(title: T, message: M) -> Self {
+        QuestionBox {
+            core: Default::default(),
+            layout_data: Default::default(),
+            title: title.to_string(),
+            label: Label::new(message),
+            yes: TextButton::new("Yes", DialogButton::Yes),
+            no: TextButton::new("No", DialogButton::No),
+            cancel: TextButton::new("Cancel", DialogButton::Cancel),
+            replier: Replier::new(),
+        }
+    }
+
+    /// Get the reply handle
+    pub fn reply(&self) -> DialogReply<Answer> {
+        self.replier.reply.clone()
+    }
+
+    fn handle_button(&mut self, mgr: &mut Manager, msg: DialogButton) -> Response<VoidMsg> {
+        let value = match msg {
+            DialogButton::Yes => Answer::Yes,
+            DialogButton::No => Answer::No,
+            _ => Answer::Cancel,
+        };
+        self.replier.send(mgr, value);
+        Response::None
+    }
+}
+
+impl_dialog_window!(QuestionBox, Answer::Cancel);
+
+fn activate(_: &str) -> DialogButton {
+    DialogButton::Ok
+}
+
+/// A dialog prompting for a line of text
+///
+/// The result is `Some(text)` if confirmed (by the "Ok" button or pressing
+/// Enter) or `None` if cancelled; see [`InputBox::reply`].
+#[widget]
+#[layout(grid)]
+#[handler]
+#[derive(Clone, Debug, Widget)]
+pub struct InputBox {
+    #[core]
+    core: CoreData,
+    #[layout_data]
+    layout_data: <Self as kas::LayoutData>::Data,
+    title: String,
+    #[widget(row = 0, col = 0, cspan = 2)]
+    label: Label,
+    #[widget(row = 1, col = 0, cspan = 2, handler = handle_button)]
+    edit: EditBox<fn(&str) -> DialogButton>,
+    #[widget(row = 2, col = 0, handler = handle_button)]
+    ok: TextButton<DialogButton>,
+    #[widget(row = 2, col = 1, handler = handle_button)]
+    cancel: TextButton<DialogButton>,
+    replier: Replier<Option<String>>,
+}
+
+impl InputBox {
+    /// Construct, with the given `prompt` and initial `text`
+    pub fn new<T: ToString, P: ToString, S: Into<String>>(title: T, prompt: P, text: S) -> Self {
+        InputBox {
+            core: Default::default(),
+            layout_data: Default::default(),
+            title: title.to_string(),
+            label: Label::new(prompt),
+            edit: EditBox::new(text).on_activate(activate as fn(&str) -> DialogButton),
+            ok: TextButton::new("Ok", DialogButton::Ok),
+            cancel: TextButton::new("Cancel", DialogButton::Cancel),
+            replier: Replier::new(),
+        }
+    }
+
+    /// Get the reply handle
+    pub fn reply(&self) -> DialogReply<Option<String>> {
+        self.replier.reply.clone()
+    }
+
+    fn handle_button(&mut self, mgr: &mut Manager, msg: DialogButton) -> Response<VoidMsg> {
+        let value = match msg {
+            DialogButton::Ok => Some(self.edit.get_text().to_string()),
+            _ => None,
+        };
+        self.replier.send(mgr, value);
+        Response::None
+    }
+}
+
+impl_dialog_window!(InputBox, None);
+
+/// A link activated in an [`AboutBox`]
+///
+/// This is pushed via [`Manager::push_msg`]; applications wishing to open
+/// links (e.g. in a web browser) should register a window inspector (see
+/// [`crate::widget::Window::add_inspector`]).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OpenLink(pub String);
+
+/// An "about" dialog
+///
+/// This shows the application's name and version, an optional icon, other
+/// metadata (description, copyright, license) and links, followed by
+/// additional information (e.g. runtime details) and the KAS version.
+/// For example:
+/// ```ignore
+/// AboutBox::new(env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"))
+///     .with_description(env!("CARGO_PKG_DESCRIPTION"))
+///     .with_license("Apache-2.0")
+///     .with_link("Homepage", env!("CARGO_PKG_HOMEPAGE"))
+///     .show_modal(mgr);
+/// ```
+///
+/// Activating a link pushes an [`OpenLink`] message.
+#[widget]
+#[layout(vertical)]
+#[handler]
+#[derive(Clone, Debug, Widget)]
+pub struct AboutBox {
+    #[core]
+    core: CoreData,
+    #[layout_data]
+    layout_data: <Self as kas::LayoutData>::Data,
+    title: String,
+    description: Option<String>,
+    copyright: Option<String>,
+    license: Option<String>,
+    info: Vec<(String, String)>,
+    urls: Vec<(String, String)>,
+    #[widget]
+    icon: Image,
+    #[widget]
+    heading: Label,
+    #[widget]
+    details: Label,
+    #[widget(handler = handle_link)]
+    links: Column<TextButton<usize>>,
+    #[widget(handler = handle_button)]
+    button: TextButton<DialogButton>,
+}
+
+impl AboutBox {
+    /// Construct, with the application's `name` and `version`
+    pub fn new<N: ToString, V: ToString>(name: N, version: V) -> Self {
+        let name = name.to_string();
+        let mut dialog = AboutBox {
+            core: Default::default(),
+            layout_data: Default::default(),
+            title: format!("About {}", name),
+            description: None,
+            copyright: None,
+            license: None,
+            info: vec![],
+            urls: vec![],
+            icon: Image::from_rgba(Size::ZERO, vec![]),
+            heading: Label::new(format!("{} {}", name, version.to_string())),
+            details: Label::default(),
+            links: Column::new(vec![]),
+            button: TextButton::new("Close", DialogButton::Close),
+        };
+        dialog.update_details();
+        dialog
+    }
+
+    /// Set the application icon (chain style)
+    pub fn with_icon(mut self, icon: ImageData) -> Self {
+        self.icon = Image::new(icon);
+        self
+    }
+
+    /// Set a short description (chain style)
+    pub fn with_description<S: ToString>(mut self, text: S) -> Self {
+        self.description = Some(text.to_string());
+        self.update_details();
+        self
+    }
+
+    /// Set the copyright notice (chain style)
+    pub fn with_copyright<S: ToString>(mut self, text: S) -> Self {
+        self.copyright = Some(text.to_string());
+        self.update_details();
+        self
+    }
+
+    /// Set the license (chain style)
+    pub fn with_license<S: ToString>(mut self, text: S) -> Self {
+        self.license = Some(text.to_string());
+        self.update_details();
+        self
+    }
+
+    /// Add a link (chain style)
+    ///
+    /// This is shown as a button with the given `label`.
+    pub fn with_link<L: ToString, U: ToString>(mut self, label: L, url: U) -> Self {
+        self.urls.push((label.to_string(), url.to_string()));
+        let buttons = self.urls.iter().enumerate();
+        let buttons = buttons.map(|(i, (label, _))| TextButton::new(label.clone(), i));
+        self.links = Column::new(buttons.collect());
+        self
+    }
+
+    /// Add a line of information (chain style)
+    ///
+    /// This may be used for runtime details, such as the graphics backend.
+    pub fn with_info<K: ToString, V: ToString>(mut self, key: K, value: V) -> Self {
+        self.info.push((key.to_string(), value.to_string()));
+        self.update_details();
+        self
+    }
+
+    fn update_details(&mut self) {
+        let mut lines: Vec<String> = vec![];
+        lines.extend(self.description.iter().cloned());
+        lines.extend(self.copyright.iter().cloned());
+        if let Some(ref license) = self.license {
+            lines.push(format!("License: {}", license));
+        }
+        for (key, value) in &self.info {
+            lines.push(format!("{}: {}", key, value));
+        }
+        lines.push(format!("KAS version: {}", env!("CARGO_PKG_VERSION")));
+        self.details = Label::new(lines.join("\n"));
+    }
+
+    fn handle_link(&mut self, mgr: &mut Manager, index: usize) -> Response<VoidMsg> {
+        mgr.push_msg(OpenLink(self.urls[index].1.clone()));
+        Response::None
+    }
+
+    fn handle_button(&mut self, mgr: &mut Manager, msg: DialogButton) -> Response<VoidMsg> {
+        if msg == DialogButton::Close {
+            mgr.send_action(TkAction::Close);
+        }
+        Response::None
+    }
+}
+
+impl Window for AboutBox {
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    fn resize(
+        &mut self,
+        size_handle: &mut dyn SizeHandle,
+        size: Size,
+    ) -> (Option<Size>, Option<Size>) {
+        let (min, max) = layout::solve(self, size_handle, size);
+        (Some(min), Some(max))
+    }
+
+    fn callbacks(&self) -> Vec<(usize, Callback)> {
+        Vec::new()
+    }
+    fn final_callback(&self) -> Option<&'static dyn Fn(Box<dyn kas::Window>, &mut Manager)> {
+        None
+    }
+    fn trigger_callback(&mut self, _index: usize, _: &mut Manager) {}
+}
@@ -0,0 +1,239 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Collapsible section
+
+use std::time::Duration;
+
+use crate::animate::{Animation, Easing, FRAME};
+use crate::class::HasText;
+use crate::event::{Action, Event, Handler, Manager, Response};
+use crate::geom::{Coord, Rect, Size};
+use crate::layout::{AxisInfo, SizeRules};
+use crate::macros::Widget;
+use crate::theme::{DrawHandle, SizeHandle, TextClass, TextProperties};
+use crate::{Align, AlignHints, CoreData, Layout, TkAction, Widget, WidgetCore, WidgetId};
+
+/// Duration of the reveal animation
+const REVEAL: Duration = Duration::from_millis(150);
+
+/// A collapsible section
+///
+/// This has a header (title with a disclosure indicator), which when clicked
+/// or activated shows or hides the child widget. Since the space occupied
+/// changes, toggling causes the window to be reconfigured.
+///
+/// When expanded, the child is revealed from the top over a short animation.
+///
+/// Note: while collapsed, the child is not drawn and does not receive mouse
+/// input, but may still receive keyboard focus.
+#[derive(Clone, Debug, Widget)]
+pub struct Expander<W: Widget> {
+    #[core]
+    core: CoreData,
+    title: String,
+    expanded: bool,
+    // Fraction of the child revealed
+    reveal: Option<Animation<f32>>,
+    header_rect: Rect,
+    #[widget]
+    child: W,
+}
+
+impl<W: Widget> Expander<W> {
+    /// Construct a collapsed section with the given title
+    #[inline]
+    pub fn new<T: ToString>(title: T, child: W) -> Self {
+        Expander {
+            core: Default::default(),
+            title: title.to_string(),
+            expanded: false,
+            reveal: None,
+            header_rect: Rect::default(),
+            child,
+        }
+    }
+
+    /// Set the initial state (chain style)
+    #[inline]
+    pub fn with_expanded(mut self, expanded: bool) -> Self {
+        self.expanded = expanded;
+        self
+    }
+
+    /// True if expanded
+    #[inline]
+    pub fn is_expanded(&self) -> bool {
+        self.expanded
+    }
+
+    /// Expand or collapse
+    pub fn set_expanded(&mut self, mgr: &mut Manager, expanded: bool) {
+        if expanded == self.expanded {
+            return;
+        }
+        self.expanded = expanded;
+        if expanded {
+            let reveal = Animation::new(0.0, 1.0, REVEAL);
+            self.reveal = Some(reveal.with_easing(Easing::EaseOut));
+            mgr.request_frame(self.id());
+        } else {
+            self.reveal = None;
+        }
+        mgr.send_action(TkAction::Reconfigure);
+    }
+
+    /// Access inner widget directly
+    #[inline]
+    pub fn inner(&self) -> &W {
+        &self.child
+    }
+
+    /// Access inner widget directly
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut W {
+        &mut self.child
+    }
+
+    fn header_text(&self) -> String {
+        let indicator = if self.expanded { '▾' } else { '▸' };
+        format!("{} {}", indicator, self.title)
+    }
+}
+
+impl<W: Widget> Widget for Expander<W> {
+    fn configure(&mut self, mgr: &mut Manager) {
+        // Reconfiguring clears scheduled updates
+        if self.reveal.is_some() {
+            mgr.request_frame(self.id());
+        }
+    }
+
+    fn allow_focus(&self) -> bool {
+        true
+    }
+
+    fn update_timer(&mut self, mgr: &mut Manager) -> Option<Duration> {
+        mgr.redraw(self.id());
+        match self.reveal {
+            Some(ref reveal) if !reveal.is_finished() => Some(FRAME),
+            _ => {
+                self.reveal = None;
+                None
+            }
+        }
+    }
+}
+
+impl<W: Widget> Layout for Expander<W> {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let margin = size_handle.inner_margin();
+        let text = self.header_text();
+        let header = size_handle.text_bound(&text, TextClass::Label, axis)
+            + axis.extract_size(margin + margin);
+        if axis.is_vertical() {
+            self.header_rect.size.1 = header.ideal_size();
+        }
+        if !self.expanded {
+            return header;
+        }
+
+        let child = self.child.size_rules(size_handle, axis);
+        if axis.is_horizontal() {
+            header.max(child)
+        } else {
+            let spacing = axis.extract_size(size_handle.outer_margin());
+            header + spacing + child
+        }
+    }
+
+    fn set_rect(&mut self, size_handle: &mut dyn SizeHandle, rect: Rect, align: AlignHints) {
+        self.core.rect = rect;
+        let height = self.header_rect.size.1.min(rect.size.1);
+        self.header_rect = Rect::new(rect.pos, Size(rect.size.0, height));
+
+        if self.expanded {
+            let spacing = size_handle.outer_margin().1;
+            let offset = (height + spacing).min(rect.size.1);
+            let child_rect = Rect {
+                pos: rect.pos + Coord(0, offset as i32),
+                size: Size(rect.size.0, rect.size.1 - offset),
+            };
+            self.child.set_rect(size_handle, child_rect, align);
+        }
+    }
+
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        if self.expanded && self.child.rect().contains(coord) {
+            self.child.find_id(coord)
+        } else {
+            Some(self.id())
+        }
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &Manager) {
+        draw_handle.interaction_frame(self.header_rect, mgr.highlight_state(self.id()));
+        let props = TextProperties {
+            class: TextClass::Label,
+            horiz: Align::Begin,
+            vert: Align::Centre,
+        };
+        draw_handle.text(self.header_rect, &self.header_text(), props);
+
+        if !self.expanded {
+            return;
+        }
+        match self.reveal {
+            Some(ref reveal) => {
+                // Reveal the child progressively from the top
+                let mut rect = self.child.rect();
+                rect.size.1 = (rect.size.1 as f32 * reveal.value()) as u32;
+                draw_handle.clip_region(rect, Coord::ZERO, &mut |handle| {
+                    self.child.draw(handle, mgr)
+                });
+            }
+            None => self.child.draw(draw_handle, mgr),
+        }
+    }
+}
+
+impl<W: Widget + Handler> Handler for Expander<W> {
+    type Msg = <W as Handler>::Msg;
+
+    #[inline]
+    fn activation_via_press(&self) -> bool {
+        true
+    }
+
+    fn handle_action(&mut self, mgr: &mut Manager, action: Action) -> Response<Self::Msg> {
+        match action {
+            Action::Activate => {
+                self.set_expanded(mgr, !self.expanded);
+                Response::None
+            }
+            a => Response::unhandled_action(a),
+        }
+    }
+
+    fn handle(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<Self::Msg> {
+        if id <= self.child.id() {
+            self.child.handle(mgr, id, event)
+        } else {
+            debug_assert!(id == self.id(), "Handler::handle: bad WidgetId");
+            Manager::handle_generic(self, mgr, event)
+        }
+    }
+}
+
+impl<W: Widget> HasText for Expander<W> {
+    fn get_text(&self) -> &str {
+        &self.title
+    }
+
+    fn set_string(&mut self, mgr: &mut Manager, text: String) {
+        self.title = text;
+        mgr.send_action(TkAction::Reconfigure);
+    }
+}
@@ -0,0 +1,327 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Flow layout
+
+use std::any::Any;
+use std::ops::{Index, IndexMut};
+
+use crate::event::{Event, Handler, Manager, Response};
+use crate::geom::{Coord, Rect, Size};
+use crate::layout::{AxisInfo, SizeRules, StretchPolicy};
+use crate::theme::{DrawHandle, SizeHandle};
+use crate::{AlignHints, CoreData, Direction, Layout, TkAction, Widget, WidgetCore, WidgetId};
+
+/// A flow of boxed widgets
+///
+/// This is parameterised over handler message type.
+///
+/// See documentation of [`FlowBox`] type.
+pub type BoxFlowBox<M> = FlowBox<Box<dyn Handler<Msg = M>>>;
+
+/// A container arranging children in wrapping rows
+///
+/// Children are placed left-to-right at their ideal width, starting a new
+/// row when the available width is exhausted; each row is as tall as its
+/// tallest child. This is useful for things like lists of tags.
+///
+/// Where the parent layout supplies the width when querying the height (see
+/// [`AxisInfo`]), the height required by the wrapped rows is reported;
+/// otherwise the ideal height is that of a single row.
+#[derive(Clone, Default, Debug)]
+pub struct FlowBox<W: Widget> {
+    core: CoreData,
+    widgets: Vec<W>,
+    widths: Vec<SizeRules>,
+    heights: Vec<SizeRules>,
+}
+
+// We implement this manually, because the derive implementation cannot handle
+// vectors of child widgets.
+impl<W: Widget> WidgetCore for FlowBox<W> {
+    #[inline]
+    fn core_data(&self) -> &CoreData {
+        &self.core
+    }
+    #[inline]
+    fn core_data_mut(&mut self) -> &mut CoreData {
+        &mut self.core
+    }
+
+    #[inline]
+    fn widget_name(&self) -> &'static str {
+        "FlowBox"
+    }
+
+    #[inline]
+    fn as_widget(&self) -> &dyn Widget {
+        self
+    }
+    #[inline]
+    fn as_widget_mut(&mut self) -> &mut dyn Widget {
+        self
+    }
+    #[inline]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    #[inline]
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.widgets.len()
+    }
+    #[inline]
+    fn get(&self, index: usize) -> Option<&dyn Widget> {
+        self.widgets.get(index).map(|w| w.as_widget())
+    }
+    #[inline]
+    fn get_mut(&mut self, index: usize) -> Option<&mut dyn Widget> {
+        self.widgets.get_mut(index).map(|w| w.as_widget_mut())
+    }
+
+    fn walk(&self, f: &mut dyn FnMut(&dyn Widget)) {
+        for child in &self.widgets {
+            child.walk(f);
+        }
+        f(self)
+    }
+    fn walk_mut(&mut self, f: &mut dyn FnMut(&mut dyn Widget)) {
+        for child in &mut self.widgets {
+            child.walk_mut(f);
+        }
+        f(self)
+    }
+}
+
+impl<W: Widget> Widget for FlowBox<W> {}
+
+impl<W: Widget> Layout for FlowBox<W> {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let len = self.widgets.len();
+        let width = axis.size_other_if_fixed(Direction::Vertical);
+        self.widths.resize(len, SizeRules::EMPTY);
+        self.heights.resize(len, SizeRules::EMPTY);
+        for (n, child) in self.widgets.iter_mut().enumerate() {
+            if axis.is_horizontal() {
+                self.widths[n] = child.size_rules(size_handle, axis);
+                continue;
+            }
+            // Children are sized for the width allocated by set_rect
+            let axis = match width {
+                Some(w) => {
+                    let width = self.widths[n].ideal_size().min(w);
+                    AxisInfo::new(Direction::Vertical, Some(width))
+                }
+                None => axis,
+            };
+            self.heights[n] = child.size_rules(size_handle, axis);
+        }
+
+        let rules = if axis.is_horizontal() {
+            &self.widths
+        } else {
+            &self.heights
+        };
+        let stretch = rules
+            .iter()
+            .map(|r| r.stretch())
+            .fold(StretchPolicy::Filler, |a, b| a.max(b));
+
+        if axis.is_horizontal() {
+            // Minimum: one child per row; ideal: all children in one row
+            let min = self.widths.iter().map(|r| r.min_size()).max();
+            let ideal = self.widths.iter().map(|r| r.ideal_size()).sum();
+            return SizeRules::new(min.unwrap_or(0), ideal, stretch);
+        }
+
+        let rows = width
+            .map(|w| self.rows(w))
+            .unwrap_or_else(|| vec![(0, len)]);
+        let (mut min, mut ideal) = (0, 0);
+        for (start, end) in rows {
+            let row = &self.heights[start..end];
+            min += row.iter().map(|r| r.min_size()).max().unwrap_or(0);
+            ideal += row.iter().map(|r| r.ideal_size()).max().unwrap_or(0);
+        }
+        SizeRules::new(min, ideal, stretch)
+    }
+
+    fn set_rect(&mut self, size_handle: &mut dyn SizeHandle, rect: Rect, _: AlignHints) {
+        self.core.rect = rect;
+        let direction = size_handle.layout_direction();
+        let mut pos = rect.pos;
+        for (start, end) in self.rows(rect.size.0) {
+            let height = self.heights[start..end]
+                .iter()
+                .map(|r| r.ideal_size())
+                .max()
+                .unwrap_or(0);
+            pos.0 = rect.pos.0;
+            for n in start..end {
+                let width = self.widths[n].ideal_size().min(rect.size.0);
+                let child_rect = Rect::new(pos, Size(width, height));
+                let child_rect = direction.mirror(child_rect, rect);
+                let align = AlignHints::default();
+                self.widgets[n].set_rect(size_handle, child_rect, align);
+                pos.0 += width as i32;
+            }
+            pos.1 += height as i32;
+        }
+    }
+
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        for child in &self.widgets {
+            if child.rect().contains(coord) {
+                return child.find_id(coord);
+            }
+        }
+        if self.rect().contains(coord) {
+            Some(self.id())
+        } else {
+            None
+        }
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &Manager) {
+        for child in &self.widgets {
+            child.draw(draw_handle, mgr);
+        }
+    }
+}
+
+impl<W: Widget + Handler> Handler for FlowBox<W> {
+    type Msg = <W as Handler>::Msg;
+
+    fn handle(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<Self::Msg> {
+        for child in &mut self.widgets {
+            if id <= child.id() {
+                return child.handle(mgr, id, event);
+            }
+        }
+        debug_assert!(id == self.id(), "Handler::handle: bad WidgetId");
+        Response::Unhandled(event)
+    }
+}
+
+impl<W: Widget> FlowBox<W> {
+    /// Construct a new instance
+    pub fn new(widgets: Vec<W>) -> Self {
+        FlowBox {
+            core: Default::default(),
+            widgets,
+            widths: vec![],
+            heights: vec![],
+        }
+    }
+
+    /// True if there are no child widgets
+    pub fn is_empty(&self) -> bool {
+        self.widgets.is_empty()
+    }
+
+    /// Returns the number of child widgets
+    pub fn len(&self) -> usize {
+        self.widgets.len()
+    }
+
+    /// Remove all child widgets
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action) if any widget is
+    /// removed.
+    pub fn clear(&mut self, mgr: &mut Manager) {
+        if !self.widgets.is_empty() {
+            for widget in &mut self.widgets {
+                mgr.detach(widget);
+            }
+            mgr.send_action(TkAction::Reconfigure);
+        }
+        self.widgets.clear();
+    }
+
+    /// Append a child widget
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action).
+    pub fn push(&mut self, mgr: &mut Manager, widget: W) {
+        self.widgets.push(widget);
+        mgr.send_action(TkAction::Reconfigure);
+    }
+
+    /// Remove the last child widget
+    ///
+    /// Returns `None` if there are no children.
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action) if any widget is
+    /// removed.
+    pub fn pop(&mut self, mgr: &mut Manager) -> Option<W> {
+        let mut r = self.widgets.pop();
+        if let Some(widget) = r.as_mut() {
+            mgr.detach(widget);
+            mgr.send_action(TkAction::Reconfigure);
+        }
+        r
+    }
+
+    /// Inserts a child widget position `index`
+    ///
+    /// Panics if `index > len`.
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action).
+    pub fn insert(&mut self, mgr: &mut Manager, index: usize, widget: W) {
+        self.widgets.insert(index, widget);
+        mgr.send_action(TkAction::Reconfigure);
+    }
+
+    /// Removes the child widget at position `index`
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action).
+    pub fn remove(&mut self, mgr: &mut Manager, index: usize) -> W {
+        let mut r = self.widgets.remove(index);
+        mgr.detach(&mut r);
+        mgr.send_action(TkAction::Reconfigure);
+        r
+    }
+
+    // Split children into rows of the given width, as (start, end) indices
+    fn rows(&self, width: u32) -> Vec<(usize, usize)> {
+        let len = self.widgets.len().min(self.widths.len());
+        let len = len.min(self.heights.len());
+        let mut rows = vec![];
+        let mut start = 0;
+        let mut x = 0;
+        for n in 0..len {
+            let w = self.widths[n].ideal_size().min(width);
+            if n > start && x + w > width {
+                rows.push((start, n));
+                start = n;
+                x = 0;
+            }
+            x += w;
+        }
+        if start < len {
+            rows.push((start, len));
+        }
+        rows
+    }
+}
+
+impl<W: Widget> Index<usize> for FlowBox<W> {
+    type Output = W;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.widgets[index]
+    }
+}
+
+impl<W: Widget> IndexMut<usize> for FlowBox<W> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.widgets[index]
+    }
+}
@@ -0,0 +1,291 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Form layout
+
+use std::any::Any;
+use std::iter;
+
+use super::AccelLabel;
+use crate::event::{Action, Event, Handler, Manager, Response, VoidMsg};
+use crate::geom::{Coord, Rect};
+use crate::layout::{self, AxisInfo, GridChildInfo, RulesSetter, RulesSolver, SizeRules};
+use crate::theme::{DrawHandle, SizeHandle};
+use crate::{Align, AlignHints, CoreData, Layout, Widget, WidgetCore, WidgetId};
+
+/// A form of boxed fields
+///
+/// This is parameterised over handler message type.
+///
+/// See documentation of [`Form`] type.
+pub type BoxForm<M> = Form<Box<dyn Handler<Msg = M>>>;
+
+/// A two-column layout of labelled fields
+///
+/// Each row consists of a label (right-aligned, in the first column) and a
+/// field widget (in the second column). Rows are evenly spaced.
+///
+/// A label may specify a *mnemonic* by prefixing a letter or digit with `&`
+/// (use `&&` for a literal `&`). Pressing the mnemonic key activates the field,
+/// as does clicking the label; for an [`EditBox`](super::EditBox) this gives
+/// the field character focus. See [`AccelLabel`].
+#[derive(Clone, Default, Debug)]
+pub struct Form<W: Widget> {
+    core: CoreData,
+    rows: Vec<(AccelLabel, W)>,
+    press_label: Option<usize>,
+    data: layout::DynGridStorage,
+}
+
+// We implement this manually, because the derive implementation cannot handle
+// vectors of child widgets.
+impl<W: Widget> WidgetCore for Form<W> {
+    #[inline]
+    fn core_data(&self) -> &CoreData {
+        &self.core
+    }
+    #[inline]
+    fn core_data_mut(&mut self) -> &mut CoreData {
+        &mut self.core
+    }
+
+    #[inline]
+    fn widget_name(&self) -> &'static str {
+        "Form"
+    }
+
+    #[inline]
+    fn as_widget(&self) -> &dyn Widget {
+        self
+    }
+    #[inline]
+    fn as_widget_mut(&mut self) -> &mut dyn Widget {
+        self
+    }
+    #[inline]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    #[inline]
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        2 * self.rows.len()
+    }
+    fn get(&self, index: usize) -> Option<&dyn Widget> {
+        self.rows.get(index / 2).map(|row| match index % 2 {
+            0 => row.0.as_widget(),
+            _ => row.1.as_widget(),
+        })
+    }
+    fn get_mut(&mut self, index: usize) -> Option<&mut dyn Widget> {
+        self.rows.get_mut(index / 2).map(|row| match index % 2 {
+            0 => row.0.as_widget_mut(),
+            _ => row.1.as_widget_mut(),
+        })
+    }
+
+    fn walk(&self, f: &mut dyn FnMut(&dyn Widget)) {
+        for row in &self.rows {
+            row.0.walk(f);
+            row.1.walk(f);
+        }
+        f(self)
+    }
+    fn walk_mut(&mut self, f: &mut dyn FnMut(&mut dyn Widget)) {
+        for row in &mut self.rows {
+            row.0.walk_mut(f);
+            row.1.walk_mut(f);
+        }
+        f(self)
+    }
+}
+
+impl<W: Widget> Widget for Form<W> {
+    fn configure(&mut self, mgr: &mut Manager) {
+        for row in &self.rows {
+            if let Some(key) = row.0.key() {
+                mgr.add_accel_key(key, row.1.id());
+            }
+        }
+    }
+}
+
+fn child_info(col: usize, row: usize) -> GridChildInfo {
+    GridChildInfo {
+        col,
+        col_end: col + 1,
+        col_span_index: usize::MAX,
+        row,
+        row_end: row + 1,
+        row_span_index: usize::MAX,
+    }
+}
+
+impl<W: Widget> Layout for Form<W> {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        // Each cell is surrounded by a margin, giving consistent spacing
+        let margin = axis.extract_size(size_handle.outer_margin());
+        let mut solver =
+            layout::GridSolver::<[u32; 2], Vec<u32>, [SizeRules; 0], [SizeRules; 0], _>::new(
+                axis,
+                (2, self.rows.len()),
+                &mut self.data,
+            );
+        for (n, row) in self.rows.iter_mut().enumerate() {
+            solver.for_child(&mut self.data, child_info(0, n), |axis| {
+                row.0.size_rules(size_handle, axis) + 2 * margin
+            });
+            solver.for_child(&mut self.data, child_info(1, n), |axis| {
+                row.1.size_rules(size_handle, axis) + 2 * margin
+            });
+        }
+        solver.finish(&mut self.data, iter::empty(), iter::empty())
+    }
+
+    fn set_rect(&mut self, size_handle: &mut dyn SizeHandle, rect: Rect, _: AlignHints) {
+        self.core.rect = rect;
+        let margin = size_handle.outer_margin();
+        let mut setter = layout::GridSetter::<[u32; 2], Vec<u32>, _>::new(
+            rect,
+            layout::Margins::ZERO,
+            (2, self.rows.len()),
+            &mut self.data,
+        );
+
+        let direction = size_handle.layout_direction();
+        let outer = rect;
+        let shrink = |rect: Rect| Rect {
+            pos: direction.mirror(rect, outer).pos + margin,
+            size: rect.size - margin - margin,
+        };
+        for (n, row) in self.rows.iter_mut().enumerate() {
+            let label_rect = shrink(setter.child_rect(child_info(0, n)));
+            let label_align = AlignHints::new(Some(Align::End), Some(Align::Centre));
+            row.0.set_rect(size_handle, label_rect, label_align);
+            let field_rect = shrink(setter.child_rect(child_info(1, n)));
+            row.1.set_rect(size_handle, field_rect, AlignHints::NONE);
+        }
+    }
+
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        for row in &self.rows {
+            if row.0.rect().contains(coord) {
+                return row.0.find_id(coord);
+            } else if row.1.rect().contains(coord) {
+                return row.1.find_id(coord);
+            }
+        }
+        Some(self.id())
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &Manager) {
+        for row in &self.rows {
+            row.0.draw(draw_handle, mgr);
+            row.1.draw(draw_handle, mgr);
+        }
+    }
+}
+
+impl<W: Widget + Handler> Handler for Form<W>
+where
+    W::Msg: From<VoidMsg>,
+{
+    type Msg = <W as Handler>::Msg;
+
+    fn handle(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<Self::Msg> {
+        for n in 0..self.rows.len() {
+            let row = &mut self.rows[n];
+            if id <= row.0.id() {
+                // Labels do not handle input; a click on the label activates
+                // the corresponding field.
+                let event = match row.0.handle(mgr, id, event) {
+                    Response::Unhandled(event) => event,
+                    r => return Response::from(r),
+                };
+                return match event {
+                    Event::PressStart { source, coord, .. } if source.is_primary() => {
+                        if mgr.request_press_grab(source, &row.0, coord, None) {
+                            self.press_label = Some(n);
+                        }
+                        Response::None
+                    }
+                    Event::PressMove { .. } if self.press_label == Some(n) => Response::None,
+                    Event::PressEnd { end_id, .. } if self.press_label == Some(n) => {
+                        self.press_label = None;
+                        if end_id == Some(row.0.id()) {
+                            let field_id = row.1.id();
+                            row.1.handle(mgr, field_id, Event::Action(Action::Activate))
+                        } else {
+                            Response::None
+                        }
+                    }
+                    e => Response::Unhandled(e),
+                };
+            } else if id <= row.1.id() {
+                return row.1.handle(mgr, id, event);
+            }
+        }
+        debug_assert!(id == self.id(), "Handler::handle: bad WidgetId");
+        Response::Unhandled(event)
+    }
+}
+
+impl<W: Widget> Form<W> {
+    /// Construct an empty form
+    pub fn new() -> Self {
+        Form {
+            core: Default::default(),
+            rows: vec![],
+            press_label: None,
+            data: Default::default(),
+        }
+    }
+
+    /// Add a row (chain style)
+    ///
+    /// The `label` may include a mnemonic (see [`Form`] documentation).
+    pub fn with_row<S: AsRef<str>>(mut self, label: S, field: W) -> Self {
+        self.rows.push((AccelLabel::new(label), field));
+        self
+    }
+
+    /// True if there are no rows
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Returns the number of rows
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Access the field of row `index`
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn field(&self, index: usize) -> &W {
+        &self.rows[index].1
+    }
+
+    /// Mutably access the field of row `index`
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn field_mut(&mut self, index: usize) -> &mut W {
+        &mut self.rows[index].1
+    }
+
+    /// True if no field is in an error state
+    ///
+    /// See [`WidgetCore::combined_validation`].
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        self.combined_validation().is_valid()
+    }
+}
@@ -0,0 +1,167 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Framed container
+
+use crate::class::HasText;
+use crate::event::{Event, Handler, Manager, Response};
+use crate::geom::{Coord, Rect, Size};
+use crate::layout::{AxisInfo, SizeRules};
+use crate::macros::Widget;
+use crate::theme::{DrawHandle, SizeHandle, TextClass, TextProperties};
+use crate::{Align, AlignHints, CoreData, Layout, TkAction, Widget, WidgetCore, WidgetId};
+
+/// A frame around a single child, with an optional caption
+///
+/// This is sometimes called a *group box*. The caption (if not empty) is drawn
+/// above the frame. The child is separated from the frame by the theme's inner
+/// margin.
+#[derive(Clone, Debug, Widget)]
+pub struct Frame<W: Widget> {
+    #[core]
+    core: CoreData,
+    caption: String,
+    caption_rect: Rect,
+    frame_rect: Rect,
+    #[widget]
+    child: W,
+}
+
+impl<W: Widget> Frame<W> {
+    /// Construct a frame around a child widget, without caption
+    #[inline]
+    pub fn new(child: W) -> Self {
+        Frame {
+            core: Default::default(),
+            caption: String::new(),
+            caption_rect: Rect::default(),
+            frame_rect: Rect::default(),
+            child,
+        }
+    }
+
+    /// Set the caption (chain style)
+    #[inline]
+    pub fn with_caption<T: ToString>(mut self, caption: T) -> Self {
+        self.caption = caption.to_string();
+        self
+    }
+
+    /// Access inner widget directly
+    #[inline]
+    pub fn inner(&self) -> &W {
+        &self.child
+    }
+
+    /// Access inner widget directly
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut W {
+        &mut self.child
+    }
+}
+
+impl<W: Widget> Widget for Frame<W> {}
+
+impl<W: Widget> Layout for Frame<W> {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, mut axis: AxisInfo) -> SizeRules {
+        let frame = size_handle.outer_frame();
+        let margin = size_handle.inner_margin();
+        let surround = frame.0 + frame.1 + margin + margin;
+        axis.sub_other(if axis.is_horizontal() {
+            surround.1
+        } else {
+            surround.0
+        });
+        let surround = axis.extract_size(surround);
+        let mut rules = self.child.size_rules(size_handle, axis) + surround;
+        if !self.caption.is_empty() {
+            let caption = size_handle.text_bound(&self.caption, TextClass::Label, axis);
+            if axis.is_horizontal() {
+                let indent = axis.extract_size(frame.0 + frame.1);
+                rules = rules.max(caption + indent);
+            } else {
+                self.caption_rect.size.1 = caption.ideal_size();
+                rules += caption;
+            }
+        }
+        rules
+    }
+
+    fn set_rect(&mut self, size_handle: &mut dyn SizeHandle, rect: Rect, align: AlignHints) {
+        self.core.rect = rect;
+        let frame = size_handle.outer_frame();
+        let mut frame_rect = rect;
+        if !self.caption.is_empty() {
+            // Caption text is indented to align with the frame's inner edge
+            let (first, last) = frame;
+            let height = self.caption_rect.size.1.min(rect.size.1);
+            self.caption_rect = Rect {
+                pos: rect.pos + Coord(first.0 as i32, 0),
+                size: Size(rect.size.0.saturating_sub(first.0 + last.0), height),
+            };
+            frame_rect.pos.1 += height as i32;
+            frame_rect.size.1 -= height;
+        }
+        self.frame_rect = frame_rect;
+
+        let margin = size_handle.inner_margin();
+        let child_rect = Rect {
+            pos: frame_rect.pos + frame.0 + margin,
+            size: frame_rect.size - (frame.0 + frame.1 + margin + margin),
+        };
+        self.child.set_rect(size_handle, child_rect, align);
+    }
+
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        if self.child.rect().contains(coord) {
+            self.child.find_id(coord)
+        } else {
+            Some(self.id())
+        }
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &Manager) {
+        if !self.caption.is_empty() {
+            let props = TextProperties {
+                class: TextClass::Label,
+                horiz: Align::Begin,
+                vert: Align::Centre,
+            };
+            draw_handle.text(self.caption_rect, &self.caption, props);
+        }
+        draw_handle.outer_frame(self.frame_rect);
+        self.child.draw(draw_handle, mgr);
+    }
+}
+
+impl<W: Widget + Handler> Handler for Frame<W> {
+    type Msg = <W as Handler>::Msg;
+
+    #[inline]
+    fn handle(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<Self::Msg> {
+        if id <= self.child.id() {
+            self.child.handle(mgr, id, event)
+        } else {
+            debug_assert!(id == self.id(), "Handler::handle: bad WidgetId");
+            Response::Unhandled(event)
+        }
+    }
+}
+
+impl<W: Widget> HasText for Frame<W> {
+    fn get_text(&self) -> &str {
+        &self.caption
+    }
+
+    fn set_string(&mut self, mgr: &mut Manager, text: String) {
+        if text.is_empty() != self.caption.is_empty() {
+            // Space for the caption is allocated by layout
+            mgr.send_action(TkAction::Reconfigure);
+        } else {
+            mgr.redraw(self.id());
+        }
+        self.caption = text;
+    }
+}
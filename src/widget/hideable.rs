@@ -0,0 +1,156 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Visibility control
+
+use crate::event::{Event, Handler, Manager, Response};
+use crate::geom::{Coord, Rect};
+use crate::layout::{AxisInfo, SizeRules};
+use crate::macros::Widget;
+use crate::theme::{DrawHandle, SizeHandle};
+use crate::{AlignHints, CoreData, Layout, TkAction, Widget, WidgetCore, WidgetId};
+
+/// Layout of a hidden [`Hideable`] widget
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum HideMode {
+    /// The hidden widget is removed from layout, occupying no space
+    #[default]
+    Collapse,
+    /// The hidden widget keeps its space, which is left empty
+    Invisible,
+}
+
+/// A wrapper allowing its child to be hidden
+///
+/// A hidden child is not drawn and does not receive events. Depending on the
+/// [`HideMode`], it either occupies no space or leaves its space empty. Since
+/// a collapsed child changes the layout, hiding or showing it causes the
+/// window to be reconfigured.
+///
+/// Note: parent layouts may still reserve margins around a collapsed child,
+/// and a hidden child may still receive keyboard focus.
+#[derive(Clone, Debug, Widget)]
+pub struct Hideable<W: Widget> {
+    #[core]
+    core: CoreData,
+    #[widget]
+    child: W,
+    hidden: bool,
+    mode: HideMode,
+}
+
+impl<W: Widget> Hideable<W> {
+    /// Construct, with the child visible
+    #[inline]
+    pub fn new(child: W) -> Self {
+        Hideable {
+            core: Default::default(),
+            child,
+            hidden: false,
+            mode: HideMode::default(),
+        }
+    }
+
+    /// Set the initial state (chain style)
+    #[inline]
+    pub fn with_hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
+    }
+
+    /// Set the hide mode (chain style)
+    ///
+    /// By default, [`HideMode::Collapse`] is used.
+    #[inline]
+    pub fn with_mode(mut self, mode: HideMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// True if the child is hidden
+    #[inline]
+    pub fn is_hidden(&self) -> bool {
+        self.hidden
+    }
+
+    /// Get the hide mode
+    #[inline]
+    pub fn mode(&self) -> HideMode {
+        self.mode
+    }
+
+    /// Hide or show the child
+    pub fn set_hidden(&mut self, mgr: &mut Manager, hidden: bool) {
+        if hidden == self.hidden {
+            return;
+        }
+        self.hidden = hidden;
+        mgr.send_action(match self.mode {
+            HideMode::Collapse => TkAction::Reconfigure,
+            HideMode::Invisible => TkAction::RegionMoved,
+        });
+    }
+
+    /// Access the child widget
+    #[inline]
+    pub fn inner(&self) -> &W {
+        &self.child
+    }
+
+    /// Access the child widget mutably
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut W {
+        &mut self.child
+    }
+
+    fn is_collapsed(&self) -> bool {
+        self.hidden && self.mode == HideMode::Collapse
+    }
+}
+
+impl<W: Widget> Widget for Hideable<W> {}
+
+impl<W: Widget> Layout for Hideable<W> {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        if self.is_collapsed() {
+            return SizeRules::EMPTY;
+        }
+        self.child.size_rules(size_handle, axis)
+    }
+
+    fn set_rect(&mut self, size_handle: &mut dyn SizeHandle, rect: Rect, align: AlignHints) {
+        self.core.rect = rect;
+        if !self.is_collapsed() {
+            self.child.set_rect(size_handle, rect, align);
+        }
+    }
+
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        if self.hidden {
+            return None;
+        }
+        self.child.find_id(coord)
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &Manager) {
+        if !self.hidden {
+            self.child.draw(draw_handle, mgr);
+        }
+    }
+}
+
+impl<W: Widget + Handler> Handler for Hideable<W> {
+    type Msg = <W as Handler>::Msg;
+
+    #[inline]
+    fn handle(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<Self::Msg> {
+        if id <= self.child.id() && !self.hidden {
+            self.child.handle(mgr, id, event)
+        } else {
+            debug_assert!(id <= self.id(), "Handler::handle: bad WidgetId");
+            Response::Unhandled(event)
+        }
+    }
+}
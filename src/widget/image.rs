@@ -0,0 +1,218 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Image widget
+
+use crate::draw::{ImageCache, ImageData, ImageStatus};
+use crate::event::{Manager, UpdateHandle};
+use crate::geom::{Coord, Rect, Size};
+use crate::layout::{AxisInfo, SizeRules, StretchPolicy};
+use crate::macros::Widget;
+use crate::theme::{DrawHandle, SizeHandle};
+use crate::{Align, AlignHints, CoreData, Layout, TkAction, Widget, WidgetCore};
+
+/// Scaling of an [`Image`] within its allocated area
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ImageScaling {
+    /// Scale to fit within the area, preserving aspect ratio
+    #[default]
+    Fit,
+    /// Scale to cover the whole area, preserving aspect ratio
+    ///
+    /// Parts of the image outside the area are clipped.
+    Fill,
+    /// Scale to exactly fill the area, ignoring aspect ratio
+    Stretch,
+    /// Scale by the largest integer factor fitting within the area
+    ///
+    /// This is intended for pixel art. The image is never shrunk.
+    Integer,
+}
+
+/// A raster image
+///
+/// The image's natural size (in pixels) is its ideal size. When allocated a
+/// different size, it is scaled according to its [`ImageScaling`] mode and
+/// positioned according to alignment hints (centred by default).
+///
+/// An image may be loaded asynchronously via [`Image::from_cache`].
+#[handler]
+#[derive(Clone, Debug, Widget)]
+pub struct Image {
+    #[core]
+    core: CoreData,
+    image: ImageData,
+    scaling: ImageScaling,
+    draw_rect: Rect,
+    /// Cache and key of an image not yet loaded
+    pending: Option<(ImageCache, String)>,
+}
+
+impl Widget for Image {
+    fn configure(&mut self, mgr: &mut Manager) {
+        if let Some((cache, _)) = self.pending.as_ref() {
+            mgr.update_on_handle(cache.update_handle(), self.id());
+        }
+    }
+
+    fn update_handle(&mut self, mgr: &mut Manager, _: UpdateHandle, _: u64) {
+        let status = match self.pending.as_ref() {
+            Some((cache, key)) => cache.request(key),
+            None => return,
+        };
+        match status {
+            ImageStatus::Loading => (),
+            ImageStatus::Ready(image) => {
+                self.pending = None;
+                self.set_image(mgr, image);
+            }
+            ImageStatus::Failed(_) => self.pending = None,
+        }
+    }
+}
+
+impl Layout for Image {
+    fn size_rules(&mut self, _: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let size = axis.extract_size(self.image.size());
+        match self.scaling {
+            ImageScaling::Integer => SizeRules::new(size, size, StretchPolicy::LowUtility),
+            _ => SizeRules::new(0, size, StretchPolicy::LowUtility),
+        }
+    }
+
+    fn set_rect(&mut self, _: &mut dyn SizeHandle, rect: Rect, align: AlignHints) {
+        self.core.rect = rect;
+        let size = self.scaled_size(rect.size);
+        let place = |align: Option<Align>, space: u32, size: u32| {
+            let diff = space as i32 - size as i32;
+            match align.unwrap_or(Align::Centre) {
+                Align::Begin | Align::Stretch => 0,
+                Align::Centre => diff / 2,
+                Align::End => diff,
+            }
+        };
+        let offset = Coord(
+            place(align.horiz, rect.size.0, size.0),
+            place(align.vert, rect.size.1, size.1),
+        );
+        self.draw_rect = Rect::new(rect.pos + offset, size);
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, _: &Manager) {
+        let rect = self.core.rect;
+        if self.draw_rect.size.0 <= rect.size.0 && self.draw_rect.size.1 <= rect.size.1 {
+            draw_handle.image(self.draw_rect, &self.image);
+        } else {
+            draw_handle.clip_region(rect, Coord::ZERO, &mut |handle| {
+                handle.image(self.draw_rect, &self.image)
+            });
+        }
+    }
+}
+
+impl Image {
+    /// Construct from image data
+    pub fn new(image: ImageData) -> Self {
+        Image {
+            core: Default::default(),
+            image,
+            scaling: ImageScaling::default(),
+            draw_rect: Rect::default(),
+            pending: None,
+        }
+    }
+
+    /// Construct from an image `key` of an [`ImageCache`]
+    ///
+    /// If not already cached, the image is loaded asynchronously; until then
+    /// (or if loading fails), the widget is empty.
+    pub fn from_cache<S: Into<String>>(cache: ImageCache, key: S) -> Self {
+        let key = key.into();
+        match cache.request(&key) {
+            ImageStatus::Ready(image) => Image::new(image),
+            ImageStatus::Loading => {
+                let mut image = Image::new(ImageData::from_rgba(Size::ZERO, vec![]));
+                image.pending = Some((cache, key));
+                image
+            }
+            ImageStatus::Failed(_) => Image::new(ImageData::from_rgba(Size::ZERO, vec![])),
+        }
+    }
+
+    /// Construct from a raw RGBA buffer
+    ///
+    /// See [`ImageData::from_rgba`].
+    pub fn from_rgba(size: Size, rgba: Vec<u8>) -> Self {
+        Image::new(ImageData::from_rgba(size, rgba))
+    }
+
+    /// Load from a file
+    ///
+    /// See [`ImageData::from_path`].
+    #[cfg(feature = "image")]
+    pub fn from_path<P: AsRef<std::path::Path>>(path: P) -> Result<Self, image::ImageError> {
+        ImageData::from_path(path).map(Image::new)
+    }
+
+    /// Set the scaling mode (chain style)
+    #[inline]
+    pub fn with_scaling(mut self, scaling: ImageScaling) -> Self {
+        self.scaling = scaling;
+        self
+    }
+
+    /// Get the scaling mode
+    #[inline]
+    pub fn scaling(&self) -> ImageScaling {
+        self.scaling
+    }
+
+    /// Set the scaling mode
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action).
+    pub fn set_scaling(&mut self, mgr: &mut Manager, scaling: ImageScaling) {
+        self.scaling = scaling;
+        mgr.send_action(TkAction::Reconfigure);
+    }
+
+    /// Access the image data
+    #[inline]
+    pub fn image(&self) -> &ImageData {
+        &self.image
+    }
+
+    /// Replace the image data
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action) if the image size
+    /// changed, otherwise a redraw. Any image pending from an [`ImageCache`] is
+    /// forgotten.
+    pub fn set_image(&mut self, mgr: &mut Manager, image: ImageData) {
+        self.pending = None;
+        let resize = image.size() != self.image.size();
+        self.image = image;
+        if resize {
+            mgr.send_action(TkAction::Reconfigure);
+        } else {
+            mgr.redraw(self.id());
+        }
+    }
+
+    /// Size of the image as drawn within `space`
+    fn scaled_size(&self, space: Size) -> Size {
+        let size = self.image.size();
+        if size.0 == 0 || size.1 == 0 {
+            return Size::ZERO;
+        }
+        let (iw, ih) = (size.0 as f32, size.1 as f32);
+        let (w, h) = (space.0 as f32, space.1 as f32);
+        let scale = match self.scaling {
+            ImageScaling::Stretch => return space,
+            ImageScaling::Fit => (w / iw).min(h / ih),
+            ImageScaling::Fill => (w / iw).max(h / ih),
+            ImageScaling::Integer => (w / iw).min(h / ih).floor().max(1.0),
+        };
+        Size((iw * scale).round() as u32, (ih * scale).round() as u32)
+    }
+}
@@ -5,7 +5,9 @@
 
 //! Dynamic widgets
 
+use std::any::Any;
 use std::iter;
+use std::ops::{Index, IndexMut};
 
 use crate::event::{Event, Handler, Manager, Response};
 use crate::geom::Coord;
@@ -64,6 +66,8 @@ pub type BoxList<D, M> = List<D, Box<dyn Handler<Msg = M>>>;
 /// Drawing and event handling is O(log n) in the number of children (assuming
 /// only a small number are visible at any one time).
 ///
+/// Widgets removed from the list are [detached](Manager::detach).
+///
 /// For fixed configurations of child widgets, [`make_widget`] can be used
 /// instead. [`make_widget`] has the advantage that it can support child widgets
 /// of multiple types without allocation and via static dispatch, but the
@@ -77,6 +81,7 @@ pub struct List<D: Directional, W: Widget> {
     widgets: Vec<W>,
     data: layout::DynRowStorage,
     direction: D,
+    margins: Margins,
 }
 
 // We implement this manually, because the derive implementation cannot handle
@@ -104,6 +109,14 @@ impl<D: Directional, W: Widget> WidgetCore for List<D, W> {
     fn as_widget_mut(&mut self) -> &mut dyn Widget {
         self
     }
+    #[inline]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    #[inline]
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
 
     #[inline]
     fn len(&self) -> usize {
@@ -146,21 +159,32 @@ impl<D: Directional, W: Widget> Layout for List<D, W> {
                 child.size_rules(size_handle, axis)
             });
         }
-        solver.finish(&mut self.data, iter::empty(), iter::empty())
+        let rules = solver.finish(&mut self.data, iter::empty(), iter::empty());
+
+        let margins = self.margins.scaled(size_handle.dpi_factor());
+        let len = self.widgets.len() as u32;
+        let (cols, rows) = if self.direction.is_horizontal() {
+            (len, 1)
+        } else {
+            (1, len)
+        };
+        rules + margins.size_rules(axis, cols, rows)
     }
 
     fn set_rect(&mut self, size_handle: &mut dyn SizeHandle, rect: Rect, _: AlignHints) {
         self.core.rect = rect;
         let mut setter = layout::RowSetter::<D, Vec<u32>, _>::new(
             rect,
-            Margins::ZERO,
+            self.margins.scaled(size_handle.dpi_factor()),
             (self.direction, self.widgets.len()),
             &mut self.data,
         );
 
+        let direction = size_handle.layout_direction();
         for (n, child) in self.widgets.iter_mut().enumerate() {
             let align = AlignHints::default();
-            child.set_rect(size_handle, setter.child_rect(n), align);
+            let child_rect = direction.mirror(setter.child_rect(n), rect);
+            child.set_rect(size_handle, child_rect, align);
         }
     }
 
@@ -209,6 +233,7 @@ impl<D: Directional + Default, W: Widget> List<D, W> {
             widgets,
             data: Default::default(),
             direction: Default::default(),
+            margins: Margins::ZERO,
         }
     }
 }
@@ -221,9 +246,36 @@ impl<D: Directional, W: Widget> List<D, W> {
             widgets,
             data: Default::default(),
             direction,
+            margins: Margins::ZERO,
         }
     }
 
+    /// Set margins (chain style)
+    ///
+    /// This sets the size of the margin around the list and the spacing
+    /// between children, in logical pixels (scaled by the DPI factor). By
+    /// default these are zero, since child widgets usually include their own
+    /// margins (see [`SizeHandle::outer_margin`]).
+    pub fn with_margins(mut self, margins: Margins) -> Self {
+        self.margins = margins;
+        self
+    }
+
+    /// Get margins
+    pub fn margins(&self) -> Margins {
+        self.margins
+    }
+
+    /// Set margins
+    ///
+    /// See [`List::with_margins`].
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action).
+    pub fn set_margins(&mut self, mgr: &mut Manager, margins: Margins) {
+        self.margins = margins;
+        mgr.send_action(TkAction::Reconfigure);
+    }
+
     /// True if there are no child widgets
     pub fn is_empty(&self) -> bool {
         self.widgets.is_empty()
@@ -251,6 +303,9 @@ impl<D: Directional, W: Widget> List<D, W> {
     /// removed.
     pub fn clear(&mut self, mgr: &mut Manager) {
         if !self.widgets.is_empty() {
+            for widget in &mut self.widgets {
+                mgr.detach(widget);
+            }
             mgr.send_action(TkAction::Reconfigure);
         }
         self.widgets.clear();
@@ -272,10 +327,12 @@ impl<D: Directional, W: Widget> List<D, W> {
     /// Triggers a [reconfigure action](Manager::send_action) if any widget is
     /// removed.
     pub fn pop(&mut self, mgr: &mut Manager) -> Option<W> {
-        if !self.widgets.is_empty() {
+        let mut r = self.widgets.pop();
+        if let Some(widget) = r.as_mut() {
+            mgr.detach(widget);
             mgr.send_action(TkAction::Reconfigure);
         }
-        self.widgets.pop()
+        r
     }
 
     /// Inserts a child widget position `index`
@@ -294,7 +351,8 @@ impl<D: Directional, W: Widget> List<D, W> {
     ///
     /// Triggers a [reconfigure action](Manager::send_action).
     pub fn remove(&mut self, mgr: &mut Manager, index: usize) -> W {
-        let r = self.widgets.remove(index);
+        let mut r = self.widgets.remove(index);
+        mgr.detach(&mut r);
         mgr.send_action(TkAction::Reconfigure);
         r
     }
@@ -309,6 +367,7 @@ impl<D: Directional, W: Widget> List<D, W> {
     // we somehow test "has compatible size"?
     pub fn replace(&mut self, mgr: &mut Manager, index: usize, mut widget: W) -> W {
         std::mem::swap(&mut widget, &mut self.widgets[index]);
+        mgr.detach(&mut widget);
         mgr.send_action(TkAction::Reconfigure);
         widget
     }
@@ -333,6 +392,9 @@ impl<D: Directional, W: Widget> List<D, W> {
         if l0 == len {
             return;
         } else if l0 > len {
+            for widget in &mut self.widgets[len..] {
+                mgr.detach(widget);
+            }
             self.widgets.truncate(len);
         } else {
             self.widgets.reserve(len);
@@ -349,11 +411,33 @@ impl<D: Directional, W: Widget> List<D, W> {
     ///
     /// Triggers a [reconfigure action](Manager::send_action) if any widgets
     /// are removed.
-    pub fn retain<F: FnMut(&W) -> bool>(&mut self, mgr: &mut Manager, f: F) {
+    pub fn retain<F: FnMut(&W) -> bool>(&mut self, mgr: &mut Manager, mut f: F) {
         let len = self.widgets.len();
-        self.widgets.retain(f);
+        let mut i = 0;
+        while i < self.widgets.len() {
+            if f(&self.widgets[i]) {
+                i += 1;
+            } else {
+                let mut widget = self.widgets.remove(i);
+                mgr.detach(&mut widget);
+            }
+        }
         if len != self.widgets.len() {
             mgr.send_action(TkAction::Reconfigure);
         }
     }
 }
+
+impl<D: Directional, W: Widget> Index<usize> for List<D, W> {
+    type Output = W;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.widgets[index]
+    }
+}
+
+impl<D: Directional, W: Widget> IndexMut<usize> for List<D, W> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.widgets[index]
+    }
+}
@@ -8,24 +8,79 @@
 //! KAS provides these common widget types for convenience.
 //! All these widgets can be implemented in user-code.
 
+mod accel;
+mod aspect;
 mod button;
+mod canvas;
 mod checkbox;
+mod code;
+mod constraint;
+mod date;
 mod dialog;
+mod expander;
 mod filler;
+mod flow;
+mod form;
+mod frame;
+mod hideable;
+mod image;
 mod list;
+mod notify;
+mod opacity;
 mod radiobox;
+mod recent;
 mod scroll;
 mod scrollbar;
+mod separator;
+mod spinbox;
+mod splitter;
+mod stack;
+#[cfg(feature = "svg")]
+mod svg;
 mod text;
+mod textarea;
+mod toolbar;
+mod validated;
+mod view;
 mod window;
+mod zoom;
 
+pub use self::image::{Image, ImageScaling};
+pub use accel::AccelLabel;
+pub(crate) use accel::AccelString;
+pub use aspect::AspectRatio;
 pub use button::TextButton;
-pub use checkbox::{CheckBox, CheckBoxBare};
-pub use dialog::MessageBox;
+pub use canvas::Canvas;
+pub use checkbox::{CheckBox, CheckBoxBare, TriState};
+pub use code::{CodeLabel, CodeView};
+pub use constraint::SizeConstraint;
+pub use date::{DatePicker, TimePicker};
+pub use dialog::{
+    AboutBox, Answer, Confirm, ConfirmBox, DialogReply, InputBox, MessageBox, OpenLink, QuestionBox,
+};
+pub use expander::Expander;
 pub use filler::Filler;
+pub use flow::{BoxFlowBox, FlowBox};
+pub use form::{BoxForm, Form};
+pub use frame::Frame;
+pub use hideable::{HideMode, Hideable};
 pub use list::{BoxColumn, BoxList, BoxRow, Column, List, Row};
-pub use radiobox::{RadioBox, RadioBoxBare};
-pub use scroll::ScrollRegion;
+pub use notify::{Notifications, Notifier};
+pub use opacity::Opacity;
+pub use radiobox::{RadioBox, RadioBoxBare, RadioGroup};
+pub use recent::{OpenFile, RecentFilesList};
+pub use scroll::{Overscroll, ScrollArea, ScrollRegion};
 pub use scrollbar::ScrollBar;
-pub use text::{EditBox, Label};
+pub use separator::Separator;
+pub use spinbox::{SpinBox, SpinValue};
+pub use splitter::{ColumnSplitter, RowSplitter, Splitter};
+pub use stack::{BoxStack, Stack};
+#[cfg(feature = "svg")]
+pub use svg::Svg;
+pub use text::{EditBox, Label, MaxLength, RichLabel, SpellChecker, Validator};
+pub use textarea::TextArea;
+pub use toolbar::{ActionDesc, ActionSet, Toolbar};
+pub use validated::Validated;
+pub use view::{ListView, SingleView};
 pub use window::Window;
+pub use zoom::ZoomView;
@@ -0,0 +1,342 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Notification popups
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use crate::animate::FRAME;
+use crate::event::{Event, Handler, Manager, Response, UpdateHandle};
+use crate::geom::{Coord, Rect, Size};
+use crate::layout::{AxisInfo, SizeRules};
+use crate::macros::Widget;
+use crate::theme::{DrawHandle, SizeHandle, TextClass, TextProperties};
+use crate::{Align, AlignHints, CoreData, Layout, Widget, WidgetCore, WidgetId};
+
+/// Duration over which a notification fades out before removal
+const FADE: Duration = Duration::from_millis(300);
+
+/// A handle used to post notifications
+///
+/// This is obtained from [`Notifications::notifier`] and may be cloned and
+/// used from any event handler (in the same window or another).
+#[derive(Clone, Debug)]
+pub struct Notifier {
+    handle: UpdateHandle,
+    queue: Rc<RefCell<Vec<String>>>,
+}
+
+impl Notifier {
+    /// Post a notification
+    pub fn notify<S: Into<String>>(&self, mgr: &mut Manager, text: S) {
+        self.queue.borrow_mut().push(text.into());
+        mgr.trigger_update(self.handle, 0);
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Toast {
+    text: String,
+    expires: Instant,
+    size: Size,
+    rect: Rect,
+}
+
+/// A wrapper showing transient notifications over its child
+///
+/// Notifications are drawn in a corner of this widget (by default the bottom
+/// right), stacked with the newest nearest the corner, and removed after a
+/// timeout or when clicked. Usually this wraps the whole content of a window.
+///
+/// Notifications may be posted directly via [`Notifications::notify`] or from
+/// elsewhere via a [`Notifier`].
+///
+/// Note: text is drawn above all other content, thus text of the child is not
+/// hidden by notifications drawn over it.
+#[derive(Clone, Debug, Widget)]
+pub struct Notifications<W: Widget> {
+    #[core]
+    core: CoreData,
+    notifier: Notifier,
+    timeout: Duration,
+    corner: (Align, Align),
+    margin: Size,
+    surround: (Size, Size),
+    toasts: Vec<Toast>,
+    #[widget]
+    child: W,
+}
+
+impl<W: Widget> Notifications<W> {
+    /// Construct around a child widget
+    #[inline]
+    pub fn new(child: W) -> Self {
+        Notifications {
+            core: Default::default(),
+            notifier: Notifier {
+                handle: UpdateHandle::new(),
+                queue: Default::default(),
+            },
+            timeout: Duration::from_secs(5),
+            corner: (Align::End, Align::End),
+            margin: Size::ZERO,
+            surround: (Size::ZERO, Size::ZERO),
+            toasts: vec![],
+            child,
+        }
+    }
+
+    /// Set the time for which notifications are shown (chain style)
+    ///
+    /// The default is five seconds.
+    #[inline]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set the corner in which notifications are shown (chain style)
+    ///
+    /// `Align::Begin` is left/top; `Align::End` is right/bottom. `Centre` and
+    /// `Stretch` centre notifications on that axis (vertically, they are
+    /// stacked downwards from the centre).
+    #[inline]
+    pub fn with_corner(mut self, horiz: Align, vert: Align) -> Self {
+        self.corner = (horiz, vert);
+        self
+    }
+
+    /// Get a [`Notifier`] for this widget
+    #[inline]
+    pub fn notifier(&self) -> Notifier {
+        self.notifier.clone()
+    }
+
+    /// Show a notification
+    pub fn notify<S: Into<String>>(&mut self, mgr: &mut Manager, text: S) {
+        let text = text.into();
+        let max_width = self.core.rect.size.0 / 2;
+        let size = mgr.size_handle(|size_handle| {
+            let frame = size_handle.outer_frame();
+            let pad = size_handle.inner_margin();
+            let text_size = size_handle.text_size(&text, TextClass::Label, Some(max_width));
+            (
+                size_handle.outer_margin(),
+                (frame.0 + pad, frame.1 + pad),
+                text_size,
+            )
+        });
+        let size = if let Some((margin, surround, text_size)) = size {
+            self.margin = margin;
+            self.surround = surround;
+            text_size + surround.0 + surround.1
+        } else {
+            Size::ZERO
+        };
+
+        self.toasts.push(Toast {
+            text,
+            expires: Instant::now() + self.timeout,
+            size,
+            rect: Rect::default(),
+        });
+        self.arrange();
+        mgr.update_on_timer(self.next_update(), self.id());
+        mgr.redraw(self.id());
+    }
+
+    /// Number of notifications currently shown
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.toasts.len()
+    }
+
+    /// True if no notifications are shown
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.toasts.is_empty()
+    }
+
+    /// Remove all notifications
+    pub fn clear(&mut self, mgr: &mut Manager) {
+        if !self.toasts.is_empty() {
+            self.toasts.clear();
+            mgr.redraw(self.id());
+        }
+    }
+
+    /// Access inner widget directly
+    #[inline]
+    pub fn inner(&self) -> &W {
+        &self.child
+    }
+
+    /// Access inner widget directly
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut W {
+        &mut self.child
+    }
+
+    // Position notifications, stacking away from the corner
+    fn arrange(&mut self) {
+        let rect = self.core.rect;
+        let m = self.margin;
+        let end = rect.pos + rect.size;
+        let mut y = match self.corner.1 {
+            Align::Begin => rect.pos.1 + m.1 as i32,
+            Align::Centre | Align::Stretch => rect.pos.1 + rect.size.1 as i32 / 2,
+            Align::End => end.1 - m.1 as i32,
+        };
+        for toast in self.toasts.iter_mut().rev() {
+            let w = toast.size.0.min(rect.size.0.saturating_sub(2 * m.0));
+            let h = toast.size.1;
+            let x = match self.corner.0 {
+                Align::Begin => rect.pos.0 + m.0 as i32,
+                Align::Centre | Align::Stretch => rect.pos.0 + (rect.size.0 - w) as i32 / 2,
+                Align::End => end.0 - (m.0 + w) as i32,
+            };
+            let pos = match self.corner.1 {
+                Align::End => {
+                    y -= h as i32;
+                    let pos = Coord(x, y);
+                    y -= m.1 as i32;
+                    pos
+                }
+                _ => {
+                    let pos = Coord(x, y);
+                    y += (h + m.1) as i32;
+                    pos
+                }
+            };
+            toast.rect = Rect::new(pos, Size(w, h));
+        }
+    }
+
+    // Time until the next expiry or start of fade
+    fn next_update(&self) -> Duration {
+        let now = Instant::now();
+        let mut next = Duration::from_secs(3600);
+        for toast in &self.toasts {
+            let remaining = toast.expires.saturating_duration_since(now);
+            let wait = if remaining > FADE {
+                remaining - FADE
+            } else {
+                // Fading: update every frame
+                FRAME
+            };
+            next = next.min(wait);
+        }
+        next.max(FRAME)
+    }
+}
+
+impl<W: Widget> Widget for Notifications<W> {
+    fn configure(&mut self, mgr: &mut Manager) {
+        mgr.update_on_handle(self.notifier.handle, self.id());
+        if !self.toasts.is_empty() {
+            mgr.update_on_timer(self.next_update(), self.id());
+        }
+    }
+
+    fn update_handle(&mut self, mgr: &mut Manager, _: UpdateHandle, _: u64) {
+        let queue = std::mem::take(&mut *self.notifier.queue.borrow_mut());
+        for text in queue {
+            self.notify(mgr, text);
+        }
+    }
+
+    fn update_timer(&mut self, mgr: &mut Manager) -> Option<Duration> {
+        let now = Instant::now();
+        let len = self.toasts.len();
+        self.toasts.retain(|toast| toast.expires > now);
+        if self.toasts.len() != len {
+            self.arrange();
+        }
+        mgr.redraw(self.id());
+        if self.toasts.is_empty() {
+            None
+        } else {
+            Some(self.next_update())
+        }
+    }
+}
+
+impl<W: Widget> Layout for Notifications<W> {
+    #[inline]
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        self.child.size_rules(size_handle, axis)
+    }
+
+    fn set_rect(&mut self, size_handle: &mut dyn SizeHandle, rect: Rect, align: AlignHints) {
+        self.core.rect = rect;
+        self.child.set_rect(size_handle, rect, align);
+        self.arrange();
+    }
+
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        if self.toasts.iter().any(|toast| toast.rect.contains(coord)) {
+            return Some(self.id());
+        }
+        self.child.find_id(coord)
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &Manager) {
+        self.child.draw(draw_handle, mgr);
+        if self.toasts.is_empty() {
+            return;
+        }
+
+        let now = Instant::now();
+        let props = TextProperties {
+            class: TextClass::Label,
+            horiz: Align::Begin,
+            vert: Align::Centre,
+        };
+        let surround = self.surround;
+        // Use a new clip region to draw over all content of the child
+        draw_handle.clip_region(self.core.rect, Coord::ZERO, &mut |handle| {
+            for toast in &self.toasts {
+                let remaining = toast.expires.saturating_duration_since(now);
+                let opacity = remaining.as_secs_f32() / FADE.as_secs_f32();
+                handle.with_opacity(opacity.min(1.0), &mut |handle| {
+                    handle.popup_box(toast.rect);
+                    let text_rect = Rect {
+                        pos: toast.rect.pos + surround.0,
+                        size: toast.rect.size - (surround.0 + surround.1),
+                    };
+                    handle.text(text_rect, &toast.text, props);
+                });
+            }
+        });
+    }
+}
+
+impl<W: Widget + Handler> Handler for Notifications<W> {
+    type Msg = <W as Handler>::Msg;
+
+    fn handle(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<Self::Msg> {
+        if id <= self.child.id() {
+            return self.child.handle(mgr, id, event);
+        }
+        debug_assert!(id == self.id(), "Handler::handle: bad WidgetId");
+        match event {
+            Event::PressStart { coord, .. } => {
+                // A click dismisses the notification
+                match self.toasts.iter().position(|t| t.rect.contains(coord)) {
+                    Some(index) => {
+                        self.toasts.remove(index);
+                        self.arrange();
+                        mgr.redraw(self.id());
+                        Response::None
+                    }
+                    None => Response::Unhandled(event),
+                }
+            }
+            e => Manager::handle_generic(self, mgr, e),
+        }
+    }
+}
@@ -0,0 +1,174 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Opacity control
+
+use std::time::Duration;
+
+use crate::animate::{Animation, Easing, FRAME};
+use crate::event::{Event, Handler, Manager, Response};
+use crate::geom::{Coord, Rect};
+use crate::layout::{AxisInfo, SizeRules};
+use crate::macros::Widget;
+use crate::theme::{DrawHandle, SizeHandle};
+use crate::{AlignHints, CoreData, Layout, Widget, WidgetCore, WidgetId};
+
+/// A wrapper drawing its child with reduced opacity
+///
+/// Opacity is a value in the range `[0, 1]`, where 0 is fully transparent and
+/// 1 is fully opaque. Opacity of nested wrappers combines multiplicatively.
+///
+/// Opacity may be changed immediately via [`Opacity::set_opacity`] or animated
+/// via [`Opacity::fade_to`]. Opacity affects drawing only: the child still
+/// occupies space and receives events even when fully transparent.
+#[derive(Clone, Debug, Widget)]
+pub struct Opacity<W: Widget> {
+    #[core]
+    core: CoreData,
+    #[widget]
+    child: W,
+    opacity: f32,
+    fade: Option<Animation<f32>>,
+}
+
+impl<W: Widget> Opacity<W> {
+    /// Construct, with full opacity
+    #[inline]
+    pub fn new(child: W) -> Self {
+        Opacity::new_with_opacity(child, 1.0)
+    }
+
+    /// Construct with the given initial `opacity`
+    #[inline]
+    pub fn new_with_opacity(child: W, opacity: f32) -> Self {
+        Opacity {
+            core: Default::default(),
+            child,
+            opacity: clamp(opacity),
+            fade: None,
+        }
+    }
+
+    /// Get the current opacity
+    #[inline]
+    pub fn opacity(&self) -> f32 {
+        self.opacity
+    }
+
+    /// True if a fade transition is in progress
+    #[inline]
+    pub fn is_fading(&self) -> bool {
+        self.fade.is_some()
+    }
+
+    /// Set the opacity
+    ///
+    /// This cancels any fade in progress.
+    pub fn set_opacity(&mut self, mgr: &mut Manager, opacity: f32) {
+        self.fade = None;
+        let opacity = clamp(opacity);
+        if opacity != self.opacity {
+            self.opacity = opacity;
+            mgr.redraw(self.id());
+        }
+    }
+
+    /// Fade to the `target` opacity over the given `duration`
+    ///
+    /// Opacity is interpolated linearly from its current value. This replaces
+    /// any fade already in progress.
+    pub fn fade_to(&mut self, mgr: &mut Manager, target: f32, duration: Duration) {
+        let target = clamp(target);
+        if duration == Duration::from_secs(0) {
+            return self.set_opacity(mgr, target);
+        }
+        let fade = Animation::new(self.opacity, target, duration);
+        self.fade = Some(fade.with_easing(Easing::Linear));
+        mgr.request_frame(self.id());
+    }
+
+    /// Fade in to full opacity over the given `duration`
+    #[inline]
+    pub fn fade_in(&mut self, mgr: &mut Manager, duration: Duration) {
+        self.fade_to(mgr, 1.0, duration);
+    }
+
+    /// Fade out to full transparency over the given `duration`
+    #[inline]
+    pub fn fade_out(&mut self, mgr: &mut Manager, duration: Duration) {
+        self.fade_to(mgr, 0.0, duration);
+    }
+
+    /// Access the child widget
+    #[inline]
+    pub fn inner(&self) -> &W {
+        &self.child
+    }
+
+    /// Access the child widget mutably
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut W {
+        &mut self.child
+    }
+}
+
+fn clamp(opacity: f32) -> f32 {
+    opacity.clamp(0.0, 1.0)
+}
+
+impl<W: Widget> Widget for Opacity<W> {
+    fn update_timer(&mut self, mgr: &mut Manager) -> Option<Duration> {
+        let fade = self.fade.as_ref()?;
+        mgr.redraw(self.id());
+        if fade.is_finished() {
+            self.opacity = fade.target();
+            self.fade = None;
+            None
+        } else {
+            self.opacity = fade.value();
+            Some(FRAME)
+        }
+    }
+}
+
+impl<W: Widget> Layout for Opacity<W> {
+    #[inline]
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        self.child.size_rules(size_handle, axis)
+    }
+
+    #[inline]
+    fn set_rect(&mut self, size_handle: &mut dyn SizeHandle, rect: Rect, align: AlignHints) {
+        self.core.rect = rect;
+        self.child.set_rect(size_handle, rect, align);
+    }
+
+    #[inline]
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        self.child.find_id(coord)
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &Manager) {
+        if self.opacity >= 1.0 {
+            self.child.draw(draw_handle, mgr);
+        } else if self.opacity > 0.0 {
+            draw_handle.with_opacity(self.opacity, &mut |handle| self.child.draw(handle, mgr));
+        }
+    }
+}
+
+impl<W: Widget + Handler> Handler for Opacity<W> {
+    type Msg = <W as Handler>::Msg;
+
+    #[inline]
+    fn handle(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<Self::Msg> {
+        if id <= self.child.id() {
+            self.child.handle(mgr, id, event)
+        } else {
+            debug_assert!(id == self.id(), "Handler::handle: bad WidgetId");
+            Response::Unhandled(event)
+        }
+    }
+}
@@ -8,14 +8,17 @@
 use std::convert::TryFrom;
 use std::fmt::{self, Debug};
 
-use super::Label;
+use log::warn;
+
+use super::{AccelLabel, List};
 use crate::class::HasBool;
-use crate::event::{Action, Handler, Manager, Response, UpdateHandle, VoidMsg};
+use crate::event::{Action, Event, Handler, Manager, Response, UpdateHandle, VoidMsg};
 use crate::geom::Rect;
 use crate::layout::{AxisInfo, SizeRules};
 use crate::macros::Widget;
 use crate::theme::{DrawHandle, SizeHandle};
-use crate::{Align, AlignHints, CoreData, Layout, Widget, WidgetCore, WidgetId};
+use crate::{Align, AlignHints, CoreData, Directional, Layout, LayoutData};
+use crate::{Widget, WidgetCore, WidgetId};
 
 /// A bare radiobox (no label)
 #[derive(Clone, Widget)]
@@ -43,7 +46,13 @@ impl<OT: 'static> Widget for RadioBoxBare<OT> {
     }
 
     fn update_handle(&mut self, mgr: &mut Manager, _: UpdateHandle, payload: u64) {
-        let id = WidgetId::try_from(payload).unwrap();
+        let id = match WidgetId::try_from(payload) {
+            Ok(id) => id,
+            Err(()) => {
+                warn!("RadioBox: ignoring update with invalid payload {}", payload);
+                return;
+            }
+        };
         let state = id == self.id();
         if state != self.state {
             self.state = state;
@@ -199,8 +208,9 @@ impl<M, H: Fn(WidgetId) -> M> Handler for RadioBoxBare<H> {
 }
 
 /// A radiobox with optional label
+///
+/// The label may specify a mnemonic (see [`AccelLabel`]).
 #[layout(horizontal, area=radiobox)]
-#[widget]
 #[handler(substitutions = (OT = ()))]
 #[handler(msg = M, generics = <M: From<VoidMsg>> where OT: Fn(WidgetId) -> M)]
 #[derive(Clone, Widget)]
@@ -212,7 +222,7 @@ pub struct RadioBox<OT: 'static> {
     #[widget]
     radiobox: RadioBoxBare<OT>,
     #[widget]
-    label: Label,
+    label: AccelLabel,
 }
 
 impl<H> Debug for RadioBox<H> {
@@ -225,6 +235,14 @@ impl<H> Debug for RadioBox<H> {
     }
 }
 
+impl<OT: 'static> Widget for RadioBox<OT> {
+    fn configure(&mut self, mgr: &mut Manager) {
+        if let Some(key) = self.label.key() {
+            mgr.add_accel_key(key, self.radiobox.id());
+        }
+    }
+}
+
 impl<M, OT: Fn(WidgetId) -> M> RadioBox<OT> {
     /// Construct a radiobox with a given `label` which calls `f` when toggled.
     ///
@@ -241,7 +259,7 @@ impl<M, OT: Fn(WidgetId) -> M> RadioBox<OT> {
             core: Default::default(),
             layout_data: Default::default(),
             radiobox: RadioBoxBare::new_on(f, handle),
-            label: Label::new(label),
+            label: AccelLabel::new(label.to_string()),
         }
     }
 }
@@ -260,7 +278,7 @@ impl RadioBox<()> {
             core: Default::default(),
             layout_data: Default::default(),
             radiobox: RadioBoxBare::new(handle),
-            label: Label::new(label),
+            label: AccelLabel::new(label.to_string()),
         }
     }
 
@@ -299,3 +317,147 @@ impl<H> HasBool for RadioBox<H> {
         self.radiobox.set_bool(mgr, state);
     }
 }
+
+/// A group of labelled radioboxes, at most one of which is checked
+///
+/// All radioboxes in the group share a single [`UpdateHandle`], thus checking
+/// one unchecks all others. The index of the newly checked radiobox is returned
+/// as a message whenever the user changes the selection.
+#[layout(single)]
+#[derive(Clone, Debug, Widget)]
+pub struct RadioGroup<D: Directional> {
+    #[core]
+    core: CoreData,
+    #[layout_data]
+    layout_data: <Self as LayoutData>::Data,
+    #[widget]
+    list: List<D, RadioBox<()>>,
+    handle: UpdateHandle,
+    selected: Option<usize>,
+}
+
+impl<D: Directional> Widget for RadioGroup<D> {}
+
+impl<D: Directional + Default> RadioGroup<D> {
+    /// Construct a group with the given `labels`, with none checked
+    ///
+    /// This constructor is available where the direction is determined by the
+    /// type: for `D: Directional + Default`. In other cases, use
+    /// [`RadioGroup::new_with_direction`].
+    pub fn new<T: ToString>(labels: Vec<T>) -> Self {
+        Self::new_with_direction(D::default(), labels)
+    }
+}
+
+impl<D: Directional> RadioGroup<D> {
+    /// Construct a group with explicit direction, with none checked
+    pub fn new_with_direction<T: ToString>(direction: D, labels: Vec<T>) -> Self {
+        let handle = UpdateHandle::new();
+        let boxes = labels
+            .into_iter()
+            .map(|label| RadioBox::new(handle, label))
+            .collect();
+        RadioGroup {
+            core: Default::default(),
+            layout_data: Default::default(),
+            list: List::new_with_direction(direction, boxes),
+            handle,
+            selected: None,
+        }
+    }
+
+    /// Set the initially checked radiobox (chain style)
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn with_selected(mut self, index: usize) -> Self {
+        if let Some(old) = self.selected {
+            self.list[old].radiobox.state = false;
+        }
+        self.list[index].radiobox.state = true;
+        self.selected = Some(index);
+        self
+    }
+
+    /// True if the group is empty
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// Returns the number of radioboxes
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    /// Get the index of the checked radiobox, if any
+    #[inline]
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    /// Check the radiobox at `index`, unchecking all others
+    ///
+    /// No message is returned. If `index` is out of bounds, a warning is
+    /// logged and the selection is unchanged.
+    pub fn set_selected(&mut self, mgr: &mut Manager, index: usize) {
+        if index >= self.list.len() {
+            warn!("RadioGroup::set_selected: no radiobox at index {}", index);
+        } else if self.selected != Some(index) {
+            self.selected = Some(index);
+            self.list[index].set_bool(mgr, true);
+        }
+    }
+
+    /// Uncheck all radioboxes
+    pub fn clear_selected(&mut self, mgr: &mut Manager) {
+        if let Some(index) = self.selected.take() {
+            self.list[index].set_bool(mgr, false);
+        }
+    }
+
+    /// Append a radiobox with the given `label`
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action).
+    pub fn push<T: ToString>(&mut self, mgr: &mut Manager, label: T) {
+        self.list.push(mgr, RadioBox::new(self.handle, label));
+    }
+
+    /// Remove the last radiobox, if any
+    ///
+    /// Returns true if a radiobox was removed. Triggers a
+    /// [reconfigure action](Manager::send_action).
+    pub fn pop(&mut self, mgr: &mut Manager) -> bool {
+        let removed = self.list.pop(mgr).is_some();
+        if self.selected.map(|i| i >= self.list.len()).unwrap_or(false) {
+            self.selected = None;
+        }
+        removed
+    }
+}
+
+impl<D: Directional> Handler for RadioGroup<D> {
+    type Msg = usize;
+
+    fn handle(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<usize> {
+        if id <= self.list.id() {
+            match self.list.handle(mgr, id, event) {
+                Response::None => (),
+                Response::Unhandled(event) => return Response::Unhandled(event),
+                // Radioboxes in a group have no message; nothing to forward
+                Response::Msg(_) => (),
+            }
+            // Find which radiobox (if any) is newly checked
+            match (0..self.list.len()).find(|i| self.list[*i].get_bool()) {
+                Some(index) if self.selected != Some(index) => {
+                    self.selected = Some(index);
+                    Response::Msg(index)
+                }
+                _ => Response::None,
+            }
+        } else {
+            debug_assert!(id == self.id(), "Handler::handle: bad WidgetId");
+            Response::Unhandled(event)
+        }
+    }
+}
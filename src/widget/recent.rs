@@ -0,0 +1,92 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Recently-used files widget
+
+use std::path::{Path, PathBuf};
+
+use super::{Column, TextButton};
+use crate::class::HasText;
+use crate::data::{RecentFiles, SharedRc};
+use crate::event::{Manager, Response, UpdateHandle};
+use crate::macros::{VoidMsg, Widget};
+use crate::{CoreData, LayoutData, TkAction, Widget, WidgetCore};
+
+/// Message requesting that a file be opened
+#[derive(Clone, Debug, PartialEq, Eq, VoidMsg)]
+pub struct OpenFile(pub PathBuf);
+
+/// A list of recently-used files
+///
+/// This displays the entries of a shared [`RecentFiles`] list as a column of
+/// buttons (labelled by file name), updated whenever the list is changed via
+/// [`SharedRc::update`]. Activating an entry returns an [`OpenFile`] message.
+///
+/// This widget does not modify the list; the application should call
+/// [`RecentFiles::add`] after successfully opening a file.
+#[layout(single)]
+#[handler(msg = OpenFile)]
+#[derive(Clone, Debug, Widget)]
+pub struct RecentFilesList {
+    #[core]
+    core: CoreData,
+    #[layout_data]
+    layout_data: <Self as LayoutData>::Data,
+    #[widget(handler = open)]
+    list: Column<TextButton<usize>>,
+    recent: SharedRc<RecentFiles>,
+}
+
+impl Widget for RecentFilesList {
+    fn configure(&mut self, mgr: &mut Manager) {
+        mgr.update_on_handle(self.recent.handle(), self.id());
+    }
+
+    fn update_handle(&mut self, mgr: &mut Manager, _: UpdateHandle, _: u64) {
+        let labels = Self::labels(&self.recent.borrow());
+        (self.list).resize_with(mgr, labels.len(), |i| TextButton::new("", i));
+        for (i, label) in labels.into_iter().enumerate() {
+            self.list[i].set_text(mgr, label);
+        }
+        // Labels may have changed size
+        mgr.send_action(TkAction::Reconfigure);
+    }
+}
+
+impl RecentFilesList {
+    /// Construct a view over the shared list `recent`
+    pub fn new(recent: SharedRc<RecentFiles>) -> Self {
+        let labels = Self::labels(&recent.borrow());
+        let buttons = (labels.into_iter().enumerate())
+            .map(|(i, label)| TextButton::new(label, i))
+            .collect();
+        RecentFilesList {
+            core: Default::default(),
+            layout_data: Default::default(),
+            list: Column::new(buttons),
+            recent,
+        }
+    }
+
+    /// Access the shared list
+    pub fn recent(&self) -> &SharedRc<RecentFiles> {
+        &self.recent
+    }
+
+    fn labels(recent: &RecentFiles) -> Vec<String> {
+        let label = |path: &Path| match path.file_name() {
+            Some(name) => name.to_string_lossy().into_owned(),
+            None => path.to_string_lossy().into_owned(),
+        };
+        recent.iter().map(|entry| label(&entry.path)).collect()
+    }
+
+    fn open(&mut self, _: &mut Manager, index: usize) -> Response<OpenFile> {
+        match self.recent.borrow().get(index) {
+            Some(entry) => Response::Msg(OpenFile(entry.path.clone())),
+            None => Response::None,
+        }
+    }
+}
@@ -6,9 +6,14 @@
 //! Scroll region
 
 use std::fmt::Debug;
+use std::time::{Duration, Instant};
 
 use super::ScrollBar;
-use crate::event::{Action, CursorIcon, Event, Handler, Manager, Response, ScrollDelta};
+use crate::animate::{Animation, Easing, FRAME};
+use crate::draw::Transform;
+use crate::event::{
+    Action, CursorIcon, Event, Handler, Manager, PressSource, Response, ScrollDelta,
+};
 use crate::geom::{Coord, Rect, Size};
 use crate::layout::{AxisInfo, SizeRules};
 use crate::macros::Widget;
@@ -18,25 +23,41 @@ use crate::{CoreData, Layout, TkAction, Widget, WidgetCore, WidgetId};
 
 /// A scrollable region
 ///
-/// This region supports scrolling via mouse wheel and drag.
+/// This region supports scrolling via mouse wheel and drag. Drags released
+/// while moving continue scrolling with decaying velocity.
 /// Optionally, it can have scroll bars (see [`ScrollRegion::show_bars`] and
 /// [`ScrollRegion::with_bars`]).
 ///
 /// Scroll regions translate their contents by an `offset`, which has a
 /// minimum value of [`Coord::ZERO`] and a maximum value of
 /// [`ScrollRegion::max_offset`].
-#[widget]
+///
+/// Optionally, feedback may be shown when scrolling reaches an edge (see
+/// [`ScrollRegion::with_overscroll`]), and discrete scrolling (mouse wheel
+/// steps, page steps via the scroll bars and [`ScrollRegion::scroll_to`]) may
+/// be animated (see [`ScrollRegion::with_smooth_scroll`]).
 #[derive(Clone, Debug, Default, Widget)]
 pub struct ScrollRegion<W: Widget> {
     #[core]
     core: CoreData,
     min_child_size: Size,
+    inner_pos: Coord,
     inner_size: Size,
     max_offset: Coord,
     offset: Coord,
     scroll_rate: f32,
     auto_bars: bool,
     show_bars: (bool, bool),
+    overscroll: Overscroll,
+    edge_width: u32,
+    pull: Coord,
+    release: Option<Release>,
+    glow: Option<Glow>,
+    drag_time: Option<Instant>,
+    velocity: (f32, f32),
+    kinetic: Option<Kinetic>,
+    smooth: bool,
+    scroll_anim: Option<Animation<Coord>>,
     #[widget]
     horiz_bar: ScrollBar<Horizontal>,
     #[widget]
@@ -45,6 +66,61 @@ pub struct ScrollRegion<W: Widget> {
     child: W,
 }
 
+/// Feedback shown when scrolling reaches an edge of a [`ScrollRegion`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Overscroll {
+    /// No feedback
+    #[default]
+    None,
+    /// A glow along the edge reached, fading out
+    Glow,
+    /// Touch drags stretch the content beyond the edge, springing back on
+    /// release; other scrolling shows a glow
+    Elastic,
+}
+
+/// Duration of the edge glow
+const GLOW_DURATION: Duration = Duration::from_millis(500);
+/// Duration of the elastic spring-back
+const RELEASE_DURATION: Duration = Duration::from_millis(300);
+/// Time constant of kinetic scrolling velocity decay (seconds)
+const KINETIC_DECAY: f32 = 0.325;
+/// Kinetic scrolling stops below this speed (pixels per second)
+const KINETIC_MIN_SPEED: f32 = 20.0;
+/// A drag held still for this long before release does not scroll on
+const KINETIC_HOLD: Duration = Duration::from_millis(50);
+/// Duration of smooth scrolling animations
+const SMOOTH_DURATION: Duration = Duration::from_millis(150);
+
+#[derive(Clone, Debug)]
+struct Glow {
+    // Sign of each component indicates the edge (if any) on that axis
+    edges: Coord,
+    start: Instant,
+    alpha: f32,
+}
+
+#[derive(Clone, Debug)]
+struct Release {
+    start: Instant,
+    from: Coord,
+}
+
+#[derive(Clone, Debug)]
+struct Kinetic {
+    // Velocity in pixels per second, in the sense of drag deltas
+    velocity: (f32, f32),
+    last: Instant,
+    // Fractional pixels not yet scrolled
+    rem: (f32, f32),
+}
+
+/// Damp elastic overscroll, limiting to a quarter of the region size
+fn damp(pull: i32, size: u32) -> i32 {
+    let limit = (size / 4) as i32;
+    (pull / 2).max(-limit).min(limit)
+}
+
 impl<W: Widget> ScrollRegion<W> {
     /// Construct a new scroll region around a child widget
     #[inline]
@@ -52,12 +128,23 @@ impl<W: Widget> ScrollRegion<W> {
         ScrollRegion {
             core: Default::default(),
             min_child_size: Size::ZERO,
+            inner_pos: Coord::ZERO,
             inner_size: Size::ZERO,
             max_offset: Coord::ZERO,
             offset: Coord::ZERO,
             scroll_rate: 30.0,
             auto_bars: false,
             show_bars: (false, false),
+            overscroll: Overscroll::None,
+            edge_width: 0,
+            pull: Coord::ZERO,
+            release: None,
+            glow: None,
+            drag_time: None,
+            velocity: (0.0, 0.0),
+            kinetic: None,
+            smooth: false,
+            scroll_anim: None,
             horiz_bar: ScrollBar::new(),
             vert_bar: ScrollBar::new(),
             child,
@@ -67,10 +154,10 @@ impl<W: Widget> ScrollRegion<W> {
     /// Auto-enable bars
     ///
     /// If enabled, this automatically enables/disables scroll bars when
-    /// resized.
+    /// resized, showing each only when the content does not fit on that axis.
     ///
     /// This has the side-effect of reserving enough space for scroll bars even
-    /// when not required.
+    /// when not required. See also [`ScrollArea`].
     #[inline]
     pub fn with_auto_bars(mut self, enable: bool) -> Self {
         self.auto_bars = enable;
@@ -84,6 +171,27 @@ impl<W: Widget> ScrollRegion<W> {
         self
     }
 
+    /// Set feedback shown when scrolling reaches an edge
+    ///
+    /// By default, no feedback is shown.
+    #[inline]
+    pub fn with_overscroll(mut self, overscroll: Overscroll) -> Self {
+        self.overscroll = overscroll;
+        self
+    }
+
+    /// Enable smooth scrolling (chain style)
+    ///
+    /// If enabled, mouse wheel steps, page steps via the scroll bars and
+    /// [`ScrollRegion::scroll_to`] animate the offset over a short duration
+    /// instead of jumping. Drags and [`ScrollRegion::set_offset`] are not
+    /// animated. By default, this is disabled.
+    #[inline]
+    pub fn with_smooth_scroll(mut self, smooth: bool) -> Self {
+        self.smooth = smooth;
+        self
+    }
+
     /// Set which scroll bars are visible
     #[inline]
     pub fn show_bars(&mut self, horiz: bool, vert: bool) {
@@ -116,21 +224,286 @@ impl<W: Widget> ScrollRegion<W> {
 
     /// Set the scroll offset
     ///
+    /// Scroll bar values are updated to match. Any smooth scrolling animation
+    /// is cancelled.
+    ///
     /// Returns true if the offset is not identical to the old offset.
-    #[inline]
     pub fn set_offset(&mut self, mgr: &mut Manager, offset: Coord) -> bool {
+        self.scroll_anim = None;
+        self.move_to(mgr, offset)
+    }
+
+    /// Scroll such that `rect` is visible
+    ///
+    /// The `rect` is in the child's coordinate space (as given by
+    /// [`WidgetCore::rect`] for descendants). The offset is changed by the
+    /// minimum amount, animating if smooth scrolling is enabled.
+    ///
+    /// Returns true if the offset will change.
+    pub fn scroll_to(&mut self, mgr: &mut Manager, rect: Rect) -> bool {
+        let target = self.scroll_target();
+        let start = rect.pos - self.inner_pos;
+        let end = start + Coord::from(rect.size);
+        let view = Coord::from(self.inner_size);
+        let fit = |offset: i32, start: i32, end: i32, view: i32| {
+            if start < offset || end - start > view {
+                start
+            } else if end > offset + view {
+                end - view
+            } else {
+                offset
+            }
+        };
+        let target = Coord(
+            fit(target.0, start.0, end.0, view.0),
+            fit(target.1, start.1, end.1, view.1),
+        );
+        self.scroll_towards(mgr, target)
+    }
+
+    /// The offset being scrolled to
+    ///
+    /// This is the current offset unless smooth scrolling is in progress.
+    fn scroll_target(&self) -> Coord {
+        match self.scroll_anim {
+            Some(ref anim) => anim.target(),
+            None => self.offset,
+        }
+    }
+
+    /// Scroll to `target`, animating if smooth scrolling is enabled
+    ///
+    /// Returns true if the offset will change.
+    fn scroll_towards(&mut self, mgr: &mut Manager, target: Coord) -> bool {
+        if !self.smooth {
+            return self.set_offset(mgr, target);
+        }
+        let target = target.max(Coord::ZERO).min(self.max_offset);
+        if target == self.scroll_target() {
+            return false;
+        }
+        let anim = Animation::new(self.offset, target, SMOOTH_DURATION);
+        self.scroll_anim = Some(anim.with_easing(Easing::EaseOut));
+        mgr.request_frame(self.id());
+        true
+    }
+
+    /// Scroll by `delta` in a discrete step (e.g. from a mouse wheel)
+    ///
+    /// Returns true if the offset will change.
+    fn scroll_step(&mut self, mgr: &mut Manager, delta: Coord) -> bool {
+        if !self.smooth {
+            return self.scroll_by(mgr, delta);
+        }
+        let target = self.scroll_target() - delta;
+        let moved = self.scroll_towards(mgr, target);
+        let clamped = target.max(Coord::ZERO).min(self.max_offset);
+        self.start_glow(mgr, target - clamped);
+        moved
+    }
+
+    /// Set the offset, without affecting animations
+    fn move_to(&mut self, mgr: &mut Manager, offset: Coord) -> bool {
         let offset = offset.max(Coord::ZERO).min(self.max_offset);
         if offset != self.offset {
             self.offset = offset;
+            self.horiz_bar.set_value(mgr, offset.0 as u32);
+            self.vert_bar.set_value(mgr, offset.1 as u32);
             mgr.send_action(TkAction::RegionMoved);
             return true;
         }
         false
     }
+
+    /// Scroll by `delta`, with feedback on reaching an edge
+    ///
+    /// Returns true if the offset changed.
+    fn scroll_by(&mut self, mgr: &mut Manager, delta: Coord) -> bool {
+        let target = self.offset - delta;
+        let moved = self.set_offset(mgr, target);
+        self.start_glow(mgr, target - self.offset);
+        moved
+    }
+
+    /// Drag by `delta`, stretching beyond edges for elastic touch drags
+    fn drag_by(&mut self, mgr: &mut Manager, source: PressSource, delta: Coord) {
+        let elastic = match source {
+            PressSource::Touch(_) => self.overscroll == Overscroll::Elastic,
+            PressSource::Mouse(_) => false,
+        };
+        if !elastic {
+            self.scroll_by(mgr, delta);
+            return;
+        }
+
+        let target = self.offset + self.pull - delta;
+        self.set_offset(mgr, target);
+        let mut pull = target - self.offset;
+        if self.max_offset.0 <= 0 {
+            pull.0 = 0;
+        }
+        if self.max_offset.1 <= 0 {
+            pull.1 = 0;
+        }
+        if pull != self.pull {
+            self.pull = pull;
+            mgr.redraw(self.id());
+        }
+    }
+
+    /// Track drag velocity, using a smoothed estimate
+    fn track_velocity(&mut self, delta: Coord) {
+        let now = Instant::now();
+        if let Some(time) = self.drag_time {
+            let dt = (now - time).as_secs_f32().max(0.001);
+            let v = (delta.0 as f32 / dt, delta.1 as f32 / dt);
+            self.velocity = (
+                0.8 * v.0 + 0.2 * self.velocity.0,
+                0.8 * v.1 + 0.2 * self.velocity.1,
+            );
+        } else {
+            self.velocity = (0.0, 0.0);
+        }
+        self.drag_time = Some(now);
+    }
+
+    /// End a drag: spring back elastic overscroll or continue kinetically
+    fn release(&mut self, mgr: &mut Manager) {
+        let drag_time = self.drag_time.take();
+        if self.pull != Coord::ZERO {
+            self.release = Some(Release {
+                start: Instant::now(),
+                from: self.pull,
+            });
+            mgr.request_frame(self.id());
+            return;
+        }
+
+        let moving = drag_time
+            .map(|time| time.elapsed() < KINETIC_HOLD)
+            .unwrap_or(false);
+        let v = self.velocity;
+        if moving && v.0.hypot(v.1) >= KINETIC_MIN_SPEED {
+            self.kinetic = Some(Kinetic {
+                velocity: v,
+                last: Instant::now(),
+                rem: (0.0, 0.0),
+            });
+            mgr.request_frame(self.id());
+        }
+    }
+
+    /// Advance kinetic scrolling; returns true while still moving
+    fn kinetic_step(&mut self, mgr: &mut Manager) -> bool {
+        let mut k = match self.kinetic.take() {
+            Some(k) => k,
+            None => return false,
+        };
+        let now = Instant::now();
+        let dt = (now - k.last).as_secs_f32();
+        k.last = now;
+
+        let decay = (-dt / KINETIC_DECAY).exp();
+        k.velocity = (k.velocity.0 * decay, k.velocity.1 * decay);
+        let d = (k.rem.0 + k.velocity.0 * dt, k.rem.1 + k.velocity.1 * dt);
+        let delta = Coord(d.0 as i32, d.1 as i32);
+        k.rem = (d.0 - delta.0 as f32, d.1 - delta.1 as f32);
+
+        // Stop on reaching an edge (scroll_by shows any feedback)
+        if delta != Coord::ZERO && !self.scroll_by(mgr, delta) {
+            return false;
+        }
+        if k.velocity.0.hypot(k.velocity.1) < KINETIC_MIN_SPEED {
+            return false;
+        }
+        self.kinetic = Some(k);
+        true
+    }
+
+    /// Show a glow if `excess` scrolls beyond an edge
+    fn start_glow(&mut self, mgr: &mut Manager, excess: Coord) {
+        if self.overscroll == Overscroll::None {
+            return;
+        }
+        // Only show feedback on axes which can scroll
+        let edges = Coord(
+            if self.max_offset.0 > 0 {
+                excess.0.signum()
+            } else {
+                0
+            },
+            if self.max_offset.1 > 0 {
+                excess.1.signum()
+            } else {
+                0
+            },
+        );
+        if edges != Coord::ZERO {
+            self.glow = Some(Glow {
+                edges,
+                start: Instant::now(),
+                alpha: 1.0,
+            });
+            mgr.request_frame(self.id());
+            mgr.redraw(self.id());
+        }
+    }
+
+    /// Offset of drawn content, including elastic stretch
+    fn draw_offset(&self) -> Coord {
+        let stretch = Coord(
+            damp(self.pull.0, self.inner_size.0),
+            damp(self.pull.1, self.inner_size.1),
+        );
+        self.offset + stretch
+    }
+}
+
+impl<W: Widget> Widget for ScrollRegion<W> {
+    fn update_timer(&mut self, mgr: &mut Manager) -> Option<Duration> {
+        let mut animating = self.kinetic_step(mgr);
+        if let Some(anim) = self.scroll_anim.take() {
+            self.move_to(mgr, anim.value());
+            if !anim.is_finished() {
+                self.scroll_anim = Some(anim);
+                animating = true;
+            }
+        }
+        if let Some(ref mut glow) = self.glow {
+            let t = glow.start.elapsed().as_secs_f32() / GLOW_DURATION.as_secs_f32();
+            if t < 1.0 {
+                glow.alpha = 1.0 - t;
+                animating = true;
+            } else {
+                self.glow = None;
+            }
+        }
+        if let Some(ref release) = self.release {
+            let t = release.start.elapsed().as_secs_f32() / RELEASE_DURATION.as_secs_f32();
+            if t < 1.0 {
+                let f = (1.0 - t) * (1.0 - t);
+                let from = release.from;
+                self.pull = Coord((from.0 as f32 * f) as i32, (from.1 as f32 * f) as i32);
+                animating = true;
+            } else {
+                self.pull = Coord::ZERO;
+                self.release = None;
+            }
+        }
+        mgr.redraw(self.id());
+        if animating {
+            Some(FRAME)
+        } else {
+            None
+        }
+    }
 }
 
 impl<W: Widget> Layout for ScrollRegion<W> {
-    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, mut axis: AxisInfo) -> SizeRules {
+        if axis.is_vertical() && self.show_bars.1 {
+            axis.sub_other(size_handle.scrollbar().0);
+        }
         let mut rules = self.child.size_rules(size_handle, axis);
         if axis.is_horizontal() {
             self.min_child_size.0 = rules.min_size();
@@ -153,15 +526,21 @@ impl<W: Widget> Layout for ScrollRegion<W> {
     fn set_rect(&mut self, size_handle: &mut dyn SizeHandle, rect: Rect, _: AlignHints) {
         self.core.rect = rect;
         // We use simplified layout code here
-        let pos = rect.pos;
+        let mut pos = rect.pos;
         self.inner_size = rect.size;
         let width = size_handle.scrollbar().0;
+        self.edge_width = width;
 
         if self.auto_bars {
-            self.show_bars = (
-                self.min_child_size.0 + width > rect.size.0,
-                self.min_child_size.1 + width > rect.size.1,
-            );
+            // Showing one bar reduces space on the other axis, which may in
+            // turn require the other bar.
+            let min = self.min_child_size;
+            let (mut horiz, mut vert) = (false, false);
+            for _ in 0..2 {
+                horiz = min.0 > rect.size.0.saturating_sub(if vert { width } else { 0 });
+                vert = min.1 > rect.size.1.saturating_sub(if horiz { width } else { 0 });
+            }
+            self.show_bars = (horiz, vert);
         }
         if self.show_bars.0 {
             self.inner_size.1 -= width;
@@ -169,6 +548,12 @@ impl<W: Widget> Layout for ScrollRegion<W> {
         if self.show_bars.1 {
             self.inner_size.0 -= width;
         }
+        // In right-to-left mode the vertical bar is on the left
+        let rtl = size_handle.layout_direction().is_rtl();
+        if rtl && self.show_bars.1 {
+            pos.0 += width as i32;
+        }
+        self.inner_pos = pos;
 
         let child_size = self.inner_size.max(self.min_child_size);
         let child_rect = Rect::new(pos, child_size);
@@ -178,20 +563,25 @@ impl<W: Widget> Layout for ScrollRegion<W> {
         self.offset = self.offset.max(Coord::ZERO).min(self.max_offset);
 
         if self.show_bars.0 {
-            let pos = Coord(pos.0, pos.1 + self.inner_size.1 as i32);
+            let pos = Coord(rect.pos.0, pos.1 + self.inner_size.1 as i32);
             let size = Size(self.core.rect.size.0, width);
             self.horiz_bar
                 .set_rect(size_handle, Rect { pos, size }, AlignHints::NONE);
             self.horiz_bar
-                .set_limits(self.max_offset.0 as u32, rect.size.0);
+                .set_limits(self.max_offset.0 as u32, self.inner_size.0);
         }
         if self.show_bars.1 {
-            let pos = Coord(pos.0 + self.inner_size.0 as i32, pos.1);
+            let x = if rtl {
+                rect.pos.0
+            } else {
+                pos.0 + self.inner_size.0 as i32
+            };
+            let pos = Coord(x, pos.1);
             let size = Size(width, self.core.rect.size.1);
             self.vert_bar
                 .set_rect(size_handle, Rect { pos, size }, AlignHints::NONE);
             self.vert_bar
-                .set_limits(self.max_offset.1 as u32, rect.size.1);
+                .set_limits(self.max_offset.1 as u32, self.inner_size.1);
         }
     }
 
@@ -205,6 +595,16 @@ impl<W: Widget> Layout for ScrollRegion<W> {
         }
     }
 
+    fn child_region(&self, id: WidgetId) -> Option<(Rect, Transform)> {
+        if id != self.child.id() {
+            return None;
+        }
+        let rect = Rect::new(self.inner_pos, self.inner_size);
+        let offset = self.draw_offset();
+        let transform = Transform::translation(-offset.0 as f32, -offset.1 as f32);
+        Some((rect, transform))
+    }
+
     fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &Manager) {
         if self.show_bars.0 {
             self.horiz_bar.draw(draw_handle, mgr);
@@ -213,12 +613,36 @@ impl<W: Widget> Layout for ScrollRegion<W> {
             self.vert_bar.draw(draw_handle, mgr);
         }
         let rect = Rect {
-            pos: self.core.rect.pos,
+            pos: self.inner_pos,
             size: self.inner_size,
         };
-        draw_handle.clip_region(rect, self.offset, &mut |handle| {
+        draw_handle.clip_region(rect, self.draw_offset(), &mut |handle| {
             self.child.draw(handle, mgr)
         });
+
+        if let Some(ref glow) = self.glow {
+            let w = self.edge_width.min(rect.size.0).min(rect.size.1);
+            let mut strips = Vec::with_capacity(2);
+            if glow.edges.0 != 0 {
+                let mut pos = rect.pos;
+                if glow.edges.0 > 0 {
+                    pos.0 += (rect.size.0 - w) as i32;
+                }
+                strips.push(Rect::new(pos, Size(w, rect.size.1)));
+            }
+            if glow.edges.1 != 0 {
+                let mut pos = rect.pos;
+                if glow.edges.1 > 0 {
+                    pos.1 += (rect.size.1 - w) as i32;
+                }
+                strips.push(Rect::new(pos, Size(rect.size.0, w)));
+            }
+            draw_handle.with_opacity(glow.alpha, &mut |handle| {
+                for strip in &strips {
+                    handle.scroll_edge(*strip);
+                }
+            });
+        }
     }
 }
 
@@ -234,50 +658,68 @@ impl<W: Widget + Handler> Handler for ScrollRegion<W> {
                     }
                     ScrollDelta::PixelDelta(d) => d,
                 };
-                if w.set_offset(mgr, w.offset - d) {
-                    w.horiz_bar.set_value(mgr, w.offset.0 as u32);
-                    w.vert_bar.set_value(mgr, w.offset.1 as u32);
+                if w.scroll_step(mgr, d) {
                     Response::None
                 } else {
                     Response::unhandled_action(Action::Scroll(delta))
                 }
             }
-            Event::PressStart { source, coord } if source.is_primary() => {
+            Event::PressStart { source, coord, .. } if source.is_primary() => {
+                w.release = None;
+                w.kinetic = None;
+                w.scroll_anim = None;
+                w.drag_time = None;
                 mgr.request_press_grab(source, w, coord, Some(CursorIcon::Grabbing));
                 Response::None
             }
             e @ _ => Response::Unhandled(e),
         };
 
+        if id <= self.vert_bar.id() {
+            if let Event::Action(Action::Scroll(delta)) = event {
+                // Scroll over a bar in content units, not bar units
+                return unhandled(self, mgr, Event::Action(Action::Scroll(delta)));
+            }
+        }
+
         if id <= self.horiz_bar.id() {
             return match Response::<Self::Msg>::try_from(self.horiz_bar.handle(mgr, id, event)) {
                 Ok(Response::Unhandled(event)) => unhandled(self, mgr, event),
                 Ok(r) => r,
-                Err(msg) => {
+                Err(msg) if self.horiz_bar.is_dragging() => {
                     self.set_offset(mgr, Coord(msg as i32, self.offset.1));
                     Response::None
                 }
+                Err(msg) => {
+                    let target = Coord(msg as i32, self.scroll_target().1);
+                    self.scroll_towards(mgr, target);
+                    Response::None
+                }
             };
         } else if id <= self.vert_bar.id() {
             return match Response::<Self::Msg>::try_from(self.vert_bar.handle(mgr, id, event)) {
                 Ok(Response::Unhandled(event)) => unhandled(self, mgr, event),
                 Ok(r) => r,
-                Err(msg) => {
+                Err(msg) if self.vert_bar.is_dragging() => {
                     self.set_offset(mgr, Coord(self.offset.0, msg as i32));
                     Response::None
                 }
+                Err(msg) => {
+                    let target = Coord(self.scroll_target().0, msg as i32);
+                    self.scroll_towards(mgr, target);
+                    Response::None
+                }
             };
         } else if id == self.id() {
             return match event {
-                Event::PressMove { delta, .. } => {
-                    if self.set_offset(mgr, self.offset - delta) {
-                        self.horiz_bar.set_value(mgr, self.offset.0 as u32);
-                        self.vert_bar.set_value(mgr, self.offset.1 as u32);
-                    }
+                Event::PressMove { source, delta, .. } => {
+                    self.track_velocity(delta);
+                    self.drag_by(mgr, source, delta);
                     Response::None
                 }
                 Event::PressEnd { .. } => {
                     // consume due to request
+                    self.release(mgr);
                     Response::None
                 }
                 e @ _ => Response::Unhandled(e),
@@ -285,10 +727,18 @@ impl<W: Widget + Handler> Handler for ScrollRegion<W> {
         }
 
         let event = match event {
-            a @ Event::Action(_) => a,
-            Event::PressStart { source, coord } => Event::PressStart {
+            a @ Event::Action(_)
+            | a @ Event::Timer
+            | a @ Event::Message(_)
+            | a @ Event::HandleUpdate { .. } => a,
+            Event::PressStart {
+                source,
+                coord,
+                clicks,
+            } => Event::PressStart {
                 source,
                 coord: coord + self.offset,
+                clicks,
             },
             Event::PressMove {
                 source,
@@ -308,6 +758,25 @@ impl<W: Widget + Handler> Handler for ScrollRegion<W> {
                 end_id,
                 coord: coord + self.offset,
             },
+            Event::Pan {
+                coord,
+                delta,
+                scale,
+                rotation,
+            } => Event::Pan {
+                coord: coord + self.offset,
+                delta,
+                scale,
+                rotation,
+            },
+            Event::LongPress { source, coord } => Event::LongPress {
+                source,
+                coord: coord + self.offset,
+            },
+            Event::Drop { data, coord } => Event::Drop {
+                data,
+                coord: coord + self.offset,
+            },
         };
 
         match self.child.handle(mgr, id, event) {
@@ -317,3 +786,131 @@ impl<W: Widget + Handler> Handler for ScrollRegion<W> {
         }
     }
 }
+
+/// A scrollable region with automatic scroll bars
+///
+/// This is a [`ScrollRegion`] with auto-enabled bars (see
+/// [`ScrollRegion::with_auto_bars`]): each scroll bar is shown only when the
+/// content does not fit on that axis. Bar values are kept synchronised with
+/// the scroll offset, whether scrolled via the bars, mouse wheel, drag or
+/// [`ScrollArea::set_offset`].
+#[derive(Clone, Debug, Widget)]
+pub struct ScrollArea<W: Widget> {
+    #[core]
+    core: CoreData,
+    #[widget]
+    region: ScrollRegion<W>,
+}
+
+impl<W: Widget> ScrollArea<W> {
+    /// Construct a new scroll area around a child widget
+    #[inline]
+    pub fn new(child: W) -> Self {
+        ScrollArea {
+            core: Default::default(),
+            region: ScrollRegion::new(child).with_auto_bars(true),
+        }
+    }
+
+    /// Set feedback shown when scrolling reaches an edge
+    ///
+    /// See [`ScrollRegion::with_overscroll`].
+    #[inline]
+    pub fn with_overscroll(mut self, overscroll: Overscroll) -> Self {
+        self.region = self.region.with_overscroll(overscroll);
+        self
+    }
+
+    /// Enable smooth scrolling (chain style)
+    ///
+    /// See [`ScrollRegion::with_smooth_scroll`].
+    #[inline]
+    pub fn with_smooth_scroll(mut self, smooth: bool) -> Self {
+        self.region = self.region.with_smooth_scroll(smooth);
+        self
+    }
+
+    /// Access inner widget directly
+    #[inline]
+    pub fn inner(&self) -> &W {
+        self.region.inner()
+    }
+
+    /// Access inner widget directly
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut W {
+        self.region.inner_mut()
+    }
+
+    /// Access the scroll region
+    #[inline]
+    pub fn region(&self) -> &ScrollRegion<W> {
+        &self.region
+    }
+
+    /// Get the maximum offset
+    #[inline]
+    pub fn max_offset(&self) -> Coord {
+        self.region.max_offset()
+    }
+
+    /// Get the current offset
+    #[inline]
+    pub fn offset(&self) -> Coord {
+        self.region.offset()
+    }
+
+    /// Set the scroll offset
+    ///
+    /// Returns true if the offset is not identical to the old offset.
+    #[inline]
+    pub fn set_offset(&mut self, mgr: &mut Manager, offset: Coord) -> bool {
+        self.region.set_offset(mgr, offset)
+    }
+
+    /// Scroll such that `rect` is visible
+    ///
+    /// See [`ScrollRegion::scroll_to`].
+    #[inline]
+    pub fn scroll_to(&mut self, mgr: &mut Manager, rect: Rect) -> bool {
+        self.region.scroll_to(mgr, rect)
+    }
+}
+
+impl<W: Widget> Widget for ScrollArea<W> {}
+
+impl<W: Widget> Layout for ScrollArea<W> {
+    #[inline]
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        self.region.size_rules(size_handle, axis)
+    }
+
+    fn set_rect(&mut self, size_handle: &mut dyn SizeHandle, rect: Rect, align: AlignHints) {
+        self.core.rect = rect;
+        self.region.set_rect(size_handle, rect, align);
+    }
+
+    #[inline]
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        self.region.find_id(coord)
+    }
+
+    #[inline]
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &Manager) {
+        self.region.draw(draw_handle, mgr);
+    }
+}
+
+impl<W: Widget + Handler> Handler for ScrollArea<W> {
+    type Msg = <W as Handler>::Msg;
+
+    #[inline]
+    fn handle(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<Self::Msg> {
+        if id <= self.region.id() {
+            self.region.handle(mgr, id, event)
+        } else {
+            debug_assert!(id == self.id(), "Handler::handle: bad WidgetId");
+            Response::Unhandled(event)
+        }
+    }
+}
@@ -6,19 +6,39 @@
 //! `ScrollBar` control
 
 use std::fmt::Debug;
+use std::time::Duration;
 
-use crate::event::{CursorIcon, Event, Handler, Manager, PressSource, Response};
+use crate::event::{
+    Action, ControlKey, CursorIcon, Event, Handler, Manager, PressSource, Response, ScrollDelta,
+};
 use crate::geom::Rect;
 use crate::layout::{AxisInfo, SizeRules, StretchPolicy};
 use crate::macros::Widget;
 use crate::theme::{DrawHandle, SizeHandle};
-use crate::{AlignHints, CoreData, Directional, Layout, WidgetCore, WidgetId};
+use crate::{AlignHints, CoreData, Directional, Layout, Widget, WidgetCore, WidgetId};
+
+/// Delay before a held press on the track starts repeating
+const REPEAT_DELAY: Duration = Duration::from_millis(400);
+/// Interval between repeated page steps while the track is held
+const REPEAT_INTERVAL: Duration = Duration::from_millis(50);
 
 /// A scroll bar
 ///
 /// Scroll bars allow user-input of a value between 0 and a defined maximum,
 /// and allow the size of the handle to be specified.
-#[widget]
+///
+/// The handle may be dragged. Pressing the track either side of the handle
+/// moves the value by one page (the handle value) towards the pointer,
+/// repeating while the press is held.
+///
+/// The value may also be adjusted with the mouse wheel or touchpad: line
+/// deltas adjust the value by the [line step](ScrollBar::with_line_step) while
+/// pixel deltas move the handle. Where the value is not changed (e.g. at the
+/// end of the range), the scroll action is returned unhandled.
+///
+/// Scroll bars may receive keyboard focus; while focussed, the arrow keys
+/// adjust the value by the line step, Page Up/Down by one page and Home/End
+/// move to either end of the range.
 #[derive(Clone, Debug, Default, Widget)]
 pub struct ScrollBar<D: Directional> {
     #[core]
@@ -30,8 +50,13 @@ pub struct ScrollBar<D: Directional> {
     handle_value: u32, // contract: > 0
     max_value: u32,
     value: u32,
+    line_step: u32,
     press_source: Option<PressSource>,
     press_offset: i32,
+    /// True while paging via a held press on the track
+    paging: bool,
+    /// Last pointer position while paging
+    pointer: i32,
 }
 
 impl<D: Directional + Default> ScrollBar<D> {
@@ -57,8 +82,11 @@ impl<D: Directional> ScrollBar<D> {
             handle_value: 1,
             max_value: 0,
             value: 0,
+            line_step: 1,
             press_source: None,
             press_offset: 0,
+            paging: false,
+            pointer: 0,
         }
     }
 
@@ -71,6 +99,15 @@ impl<D: Directional> ScrollBar<D> {
         self
     }
 
+    /// Set the value step per line scrolled (chain style)
+    ///
+    /// By default this is 1.
+    #[inline]
+    pub fn with_line_step(mut self, step: u32) -> Self {
+        self.line_step = step;
+        self
+    }
+
     /// Set the page limits
     ///
     /// The `max_value` parameter specifies the maximum possible value.
@@ -100,6 +137,12 @@ impl<D: Directional> ScrollBar<D> {
         self.value
     }
 
+    /// True while the handle is being dragged
+    #[inline]
+    pub fn is_dragging(&self) -> bool {
+        self.press_source.is_some() && !self.paging
+    }
+
     /// Set the value
     pub fn set_value(&mut self, mgr: &mut Manager, value: u32) {
         let value = value.min(self.max_value);
@@ -109,6 +152,54 @@ impl<D: Directional> ScrollBar<D> {
         }
     }
 
+    // true if not equal to old value
+    fn scroll_by(&mut self, mgr: &mut Manager, delta: ScrollDelta) -> bool {
+        let is_vert = self.direction.is_vertical();
+        match delta {
+            ScrollDelta::LineDelta(x, y) => {
+                // Scrolling up or left reduces the value
+                let lines = if is_vert { -y } else { x };
+                let d = (lines * self.line_step as f32).round() as i64;
+                self.step_by(mgr, d)
+            }
+            ScrollDelta::PixelDelta(d) => {
+                let d = if is_vert { d.1 } else { d.0 };
+                let position = (self.position() as i32 - d).max(0) as u32;
+                self.set_position(mgr, position)
+            }
+        }
+    }
+
+    // true if not equal to old value
+    fn step_by(&mut self, mgr: &mut Manager, delta: i64) -> bool {
+        let value = (self.value as i64 + delta)
+            .max(0)
+            .min(self.max_value as i64) as u32;
+        if value != self.value {
+            self.value = value;
+            mgr.redraw(self.id());
+            return true;
+        }
+        false
+    }
+
+    // Move by one page towards the pointer; true if changed
+    fn page_towards_pointer(&mut self, mgr: &mut Manager) -> bool {
+        let offset = match self.direction.is_vertical() {
+            false => self.core.rect.pos.0,
+            true => self.core.rect.pos.1,
+        };
+        let h_start = offset + self.position() as i32;
+        let page = self.handle_value as i64;
+        if self.pointer < h_start {
+            self.step_by(mgr, -page)
+        } else if self.pointer >= h_start + self.handle_len as i32 {
+            self.step_by(mgr, page)
+        } else {
+            false
+        }
+    }
+
     #[inline]
     fn len(&self) -> u32 {
         match self.direction.is_vertical() {
@@ -157,6 +248,16 @@ impl<D: Directional> ScrollBar<D> {
     }
 }
 
+impl<D: Directional> Widget for ScrollBar<D> {
+    fn allow_focus(&self) -> bool {
+        true
+    }
+
+    fn cursor_icon(&self) -> CursorIcon {
+        CursorIcon::Grab
+    }
+}
+
 impl<D: Directional> Layout for ScrollBar<D> {
     fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
         let (thickness, _, min_len) = size_handle.scrollbar();
@@ -195,6 +296,40 @@ impl<D: Directional> Layout for ScrollBar<D> {
 impl<D: Directional> Handler for ScrollBar<D> {
     type Msg = u32;
 
+    fn handle_action(&mut self, mgr: &mut Manager, action: Action) -> Response<Self::Msg> {
+        match action {
+            Action::Scroll(delta) => {
+                if self.scroll_by(mgr, delta) {
+                    Response::Msg(self.value)
+                } else {
+                    Response::unhandled_action(Action::Scroll(delta))
+                }
+            }
+            Action::ControlKey(key) => {
+                let is_vert = self.direction.is_vertical();
+                let line = self.line_step as i64;
+                let page = self.handle_value as i64;
+                let delta = match key {
+                    ControlKey::Up if is_vert => -line,
+                    ControlKey::Down if is_vert => line,
+                    ControlKey::Left if !is_vert => -line,
+                    ControlKey::Right if !is_vert => line,
+                    ControlKey::PageUp => -page,
+                    ControlKey::PageDown => page,
+                    ControlKey::Home => -(self.max_value as i64),
+                    ControlKey::End => self.max_value as i64,
+                    key => return Response::unhandled_action(Action::ControlKey(key)),
+                };
+                if self.step_by(mgr, delta) {
+                    Response::Msg(self.value)
+                } else {
+                    Response::None
+                }
+            }
+            a => Response::unhandled_action(a),
+        }
+    }
+
     fn handle(&mut self, mgr: &mut Manager, _: WidgetId, event: Event) -> Response<Self::Msg> {
         match event {
             Event::PressStart { source, coord, .. } => {
@@ -215,16 +350,20 @@ impl<D: Directional> Handler for ScrollBar<D> {
 
                 if pointer >= h_start && pointer < h_start + self.handle_len as i32 {
                     // coord is on the scroll handle
+                    self.paging = false;
                     self.press_offset = position - pointer;
                     Response::None
                 } else {
-                    // coord is not on the handle; we move the bar immediately
-                    self.press_offset = -offset - (self.handle_len / 2) as i32;
-                    let position = (pointer + self.press_offset).max(0) as u32;
-                    let moved = self.set_position(mgr, position);
-                    debug_assert!(moved);
-                    mgr.redraw(self.id());
-                    Response::Msg(self.value)
+                    // coord is on the track; we page towards the pointer,
+                    // repeating while held
+                    self.paging = true;
+                    self.pointer = pointer;
+                    mgr.event_on_timer(REPEAT_DELAY, self.id());
+                    if self.page_towards_pointer(mgr) {
+                        Response::Msg(self.value)
+                    } else {
+                        Response::None
+                    }
                 }
             }
             Event::PressMove { source, coord, .. } if Some(source) == self.press_source => {
@@ -232,6 +371,10 @@ impl<D: Directional> Handler for ScrollBar<D> {
                     false => coord.0,
                     true => coord.1,
                 };
+                if self.paging {
+                    self.pointer = pointer;
+                    return Response::None;
+                }
                 let position = (pointer + self.press_offset).max(0) as u32;
                 if self.set_position(mgr, position) {
                     mgr.redraw(self.id());
@@ -242,8 +385,20 @@ impl<D: Directional> Handler for ScrollBar<D> {
             }
             Event::PressEnd { source, .. } if Some(source) == self.press_source => {
                 self.press_source = None;
+                if self.paging {
+                    self.paging = false;
+                    mgr.cancel_timer_event(self.id());
+                }
                 Response::None
             }
+            Event::Timer if self.paging => {
+                mgr.event_on_timer(REPEAT_INTERVAL, self.id());
+                if self.page_towards_pointer(mgr) {
+                    Response::Msg(self.value)
+                } else {
+                    Response::None
+                }
+            }
             e @ _ => Manager::handle_generic(self, mgr, e),
         }
     }
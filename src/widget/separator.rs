@@ -0,0 +1,82 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Separator widget
+
+use crate::event::Manager;
+use crate::geom::Rect;
+use crate::layout::{AxisInfo, SizeRules, StretchPolicy};
+use crate::macros::Widget;
+use crate::theme::{DrawHandle, SizeHandle};
+use crate::{AlignHints, CoreData, Directional, Layout};
+
+/// A separator line
+///
+/// This is a thin line along direction `D` (e.g. a [`Horizontal`] separator
+/// is a horizontal line, for use in a column), useful for visually splitting
+/// toolbars, menus and lists. It stretches along its axis and has thickness
+/// given by [`SizeHandle::separator`].
+///
+/// [`Horizontal`]: crate::Horizontal
+#[widget]
+#[handler]
+#[derive(Clone, Debug, Default, Widget)]
+pub struct Separator<D: Directional> {
+    #[core]
+    core: CoreData,
+    direction: D,
+    line: Rect,
+}
+
+impl<D: Directional + Default> Separator<D> {
+    /// Construct a separator
+    #[inline]
+    pub fn new() -> Self {
+        Separator::new_with_direction(D::default())
+    }
+}
+
+impl<D: Directional> Separator<D> {
+    /// Construct a separator with the given direction
+    #[inline]
+    pub fn new_with_direction(direction: D) -> Self {
+        Separator {
+            core: Default::default(),
+            direction,
+            line: Rect::default(),
+        }
+    }
+}
+
+impl<D: Directional> Layout for Separator<D> {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        if self.direction.is_vertical() == axis.is_vertical() {
+            SizeRules::new(0, 0, StretchPolicy::Filler)
+        } else {
+            SizeRules::fixed(size_handle.separator())
+        }
+    }
+
+    fn set_rect(&mut self, size_handle: &mut dyn SizeHandle, rect: Rect, _: AlignHints) {
+        self.core.rect = rect;
+        // Centre the line, should we be given more space than requested
+        let thickness = size_handle.separator();
+        let mut line = rect;
+        if self.direction.is_horizontal() {
+            let thickness = thickness.min(rect.size.1);
+            line.pos.1 += (rect.size.1 - thickness) as i32 / 2;
+            line.size.1 = thickness;
+        } else {
+            let thickness = thickness.min(rect.size.0);
+            line.pos.0 += (rect.size.0 - thickness) as i32 / 2;
+            line.size.0 = thickness;
+        }
+        self.line = line;
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, _: &Manager) {
+        draw_handle.separator(self.line);
+    }
+}
@@ -0,0 +1,256 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Spin box
+
+use std::fmt::Debug;
+use std::str::FromStr;
+
+use super::{EditBox, TextButton, Validator};
+use crate::class::HasText;
+use crate::event::{Action, ControlKey, Event, Handler, Manager, Response, ScrollDelta};
+use crate::macros::Widget;
+use crate::{CoreData, LayoutData, Widget, WidgetCore, WidgetId};
+
+/// Types usable as the value of a [`SpinBox`]
+///
+/// This is implemented for all built-in integer and floating-point types.
+/// Integer steps saturate at the bounds of the type.
+pub trait SpinValue: Copy + PartialOrd + Debug + FromStr + ToString + 'static {
+    /// Add `step` to `self`
+    fn add_step(self, step: Self) -> Self;
+
+    /// Subtract `step` from `self`
+    fn sub_step(self, step: Self) -> Self;
+}
+
+macro_rules! impl_spin_int {
+    ($($t:ty),*) => {
+        $(impl SpinValue for $t {
+            #[inline]
+            fn add_step(self, step: Self) -> Self {
+                self.saturating_add(step)
+            }
+            #[inline]
+            fn sub_step(self, step: Self) -> Self {
+                self.saturating_sub(step)
+            }
+        })*
+    };
+}
+impl_spin_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+macro_rules! impl_spin_float {
+    ($($t:ty),*) => {
+        $(impl SpinValue for $t {
+            #[inline]
+            fn add_step(self, step: Self) -> Self {
+                self + step
+            }
+            #[inline]
+            fn sub_step(self, step: Self) -> Self {
+                self - step
+            }
+        })*
+    };
+}
+impl_spin_float!(f32, f64);
+
+#[derive(Clone, Copy, Debug)]
+enum SpinStep {
+    Down,
+    Up,
+}
+
+/// Validator for the text of a [`SpinBox`]
+#[derive(Clone, Debug)]
+struct SpinValidator<T: SpinValue> {
+    min: T,
+    max: T,
+}
+
+impl<T: SpinValue> SpinValidator<T> {
+    fn parse(&self, text: &str) -> Option<T> {
+        T::from_str(text.trim())
+            .ok()
+            .filter(|v| self.min <= *v && *v <= self.max)
+    }
+}
+
+impl<T: SpinValue> Validator for SpinValidator<T> {
+    fn is_valid(&self, text: &str) -> bool {
+        self.parse(text).is_some()
+    }
+}
+
+/// A numeric input with increment and decrement buttons
+///
+/// The value may be edited as text, stepped with the `−` and `+` buttons, or
+/// stepped with the `Up` and `Down` keys or mouse wheel while the edit box has
+/// focus. Text input is committed on activation (`Enter`); text which does not
+/// parse or is out of range is shown as an error.
+///
+/// When wrapping is enabled (see [`SpinBox::with_wrap`]), stepping beyond
+/// one end of the range moves to the other end; otherwise values are clamped.
+///
+/// A message containing the new value is returned whenever the value changes.
+#[layout(horizontal)]
+#[derive(Clone, Debug, Widget)]
+pub struct SpinBox<T: SpinValue> {
+    #[core]
+    core: CoreData,
+    #[layout_data]
+    layout_data: <Self as LayoutData>::Data,
+    #[widget]
+    edit: EditBox<fn(&str)>,
+    #[widget]
+    down: TextButton<SpinStep>,
+    #[widget]
+    up: TextButton<SpinStep>,
+    value: T,
+    validator: SpinValidator<T>,
+    step: T,
+    wrap: bool,
+}
+
+impl<T: SpinValue> Widget for SpinBox<T> {}
+
+impl<T: SpinValue> SpinBox<T> {
+    /// Construct over the range `min..=max`, with the given `step`
+    ///
+    /// The initial value is `min`.
+    pub fn new(min: T, max: T, step: T) -> Self {
+        let validator = SpinValidator { min, max };
+        SpinBox {
+            core: Default::default(),
+            layout_data: Default::default(),
+            edit: Self::make_edit(min, &validator),
+            down: TextButton::new("−", SpinStep::Down),
+            up: TextButton::new("+", SpinStep::Up),
+            value: min,
+            validator,
+            step,
+            wrap: false,
+        }
+    }
+
+    fn make_edit(value: T, validator: &SpinValidator<T>) -> EditBox<fn(&str)> {
+        let on_activate: fn(&str) = |_| ();
+        EditBox::new(value.to_string())
+            .on_activate(on_activate)
+            .with_validator(validator.clone())
+    }
+
+    /// Set the initial value (chain style)
+    ///
+    /// The value is clamped to the range.
+    pub fn with_value(mut self, value: T) -> Self {
+        self.value = self.clamp(value);
+        self.edit = Self::make_edit(self.value, &self.validator);
+        self
+    }
+
+    /// Enable or disable wrapping (chain style)
+    pub fn with_wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Get the current value
+    #[inline]
+    pub fn value(&self) -> T {
+        self.value
+    }
+
+    /// Set the value
+    ///
+    /// The value is clamped to the range. No message is returned.
+    pub fn set_value(&mut self, mgr: &mut Manager, value: T) {
+        self.value = self.clamp(value);
+        self.edit.set_string(mgr, self.value.to_string());
+    }
+
+    fn clamp(&self, value: T) -> T {
+        if value < self.validator.min {
+            self.validator.min
+        } else if value > self.validator.max {
+            self.validator.max
+        } else {
+            value
+        }
+    }
+
+    /// Apply a new value, returning a message if changed
+    fn update(&mut self, mgr: &mut Manager, value: T) -> Response<T> {
+        self.edit.set_string(mgr, value.to_string());
+        if value != self.value {
+            self.value = value;
+            value.into()
+        } else {
+            Response::None
+        }
+    }
+
+    /// Commit text input
+    fn commit(&mut self, mgr: &mut Manager) -> Response<T> {
+        let value = self.validator.parse(self.edit.get_text());
+        self.update(mgr, value.unwrap_or(self.value))
+    }
+
+    fn spin(&mut self, mgr: &mut Manager, step: SpinStep) -> Response<T> {
+        let (min, max) = (self.validator.min, self.validator.max);
+        let value = self.validator.parse(self.edit.get_text());
+        let value = value.unwrap_or(self.value);
+        let value = match step {
+            SpinStep::Down if self.wrap && value <= min => max,
+            SpinStep::Down => self.clamp(value.sub_step(self.step)),
+            SpinStep::Up if self.wrap && value >= max => min,
+            SpinStep::Up => self.clamp(value.add_step(self.step)),
+        };
+        self.update(mgr, value)
+    }
+
+    fn handle_unhandled(&mut self, mgr: &mut Manager, event: Event) -> Response<T> {
+        match event {
+            Event::Action(Action::ControlKey(ControlKey::Down)) => self.spin(mgr, SpinStep::Down),
+            Event::Action(Action::ControlKey(ControlKey::Up)) => self.spin(mgr, SpinStep::Up),
+            Event::Action(Action::Scroll(ScrollDelta::LineDelta(_, y))) if y < 0.0 => {
+                self.spin(mgr, SpinStep::Down)
+            }
+            Event::Action(Action::Scroll(ScrollDelta::LineDelta(_, y))) if y > 0.0 => {
+                self.spin(mgr, SpinStep::Up)
+            }
+            e => Response::Unhandled(e),
+        }
+    }
+}
+
+impl<T: SpinValue> Handler for SpinBox<T> {
+    type Msg = T;
+
+    fn handle(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<T> {
+        if id <= self.edit.id() {
+            match self.edit.handle(mgr, id, event) {
+                Response::None => Response::None,
+                Response::Unhandled(event) => self.handle_unhandled(mgr, event),
+                Response::Msg(()) => self.commit(mgr),
+            }
+        } else if id <= self.up.id() {
+            let r = if id <= self.down.id() {
+                self.down.handle(mgr, id, event)
+            } else {
+                self.up.handle(mgr, id, event)
+            };
+            match Response::<T>::try_from(r) {
+                Ok(Response::Unhandled(event)) => self.handle_unhandled(mgr, event),
+                Ok(r) => r,
+                Err(step) => self.spin(mgr, step),
+            }
+        } else {
+            debug_assert!(id == self.id(), "Handler::handle: bad WidgetId");
+            self.handle_unhandled(mgr, event)
+        }
+    }
+}
@@ -0,0 +1,519 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Splitter: a row/column with adjustable divisions
+
+use std::any::Any;
+
+use crate::event::{
+    Action, ControlKey, CursorIcon, Event, Handler, Manager, PressSource, Response,
+};
+use crate::geom::{Coord, Rect, Size};
+use crate::layout::{AxisInfo, RowPositionSolver, SizeRules};
+use crate::theme::{DrawHandle, SizeHandle};
+use crate::{AlignHints, Direction, Directional, Horizontal, LayoutDirection, Vertical};
+use crate::{CoreData, Layout, TkAction, Widget, WidgetCore, WidgetId};
+
+/// A row splitter
+///
+/// See documentation of [`Splitter`] type.
+pub type RowSplitter<W> = Splitter<Horizontal, W>;
+
+/// A column splitter
+///
+/// See documentation of [`Splitter`] type.
+pub type ColumnSplitter<W> = Splitter<Vertical, W>;
+
+/// A row/column with draggable dividers
+///
+/// This is similar to [`List`], except that the user may adjust the size
+/// allocated to each child by dragging the handles placed between children.
+///
+/// Initially, space is allocated according to the child widgets' size rules.
+/// Once the user moves a handle, the relative size of each child is retained
+/// as a *weight* and used to allocate space on future resizes. In all cases
+/// the minimum size of each child is respected.
+///
+/// When the splitter has keyboard focus, the arrow keys along its direction
+/// move the selected handle (the last handle dragged, initially the first),
+/// while the arrow keys across its direction select the previous or next
+/// handle. Home and End move the selected handle as far as possible.
+///
+/// As with [`List`], rows are mirrored in right-to-left mode: the first child
+/// is placed on the right.
+///
+/// [`List`]: crate::widget::List
+#[derive(Clone, Default, Debug)]
+pub struct Splitter<D: Directional, W: Widget> {
+    core: CoreData,
+    widgets: Vec<W>,
+    handles: Vec<Rect>,
+    handle_size: u32,
+    // Rules along the main axis for each child, followed by the total
+    rules: Vec<SizeRules>,
+    // Empty unless the user has adjusted the split
+    weights: Vec<f32>,
+    press_source: Option<PressSource>,
+    press_handle: usize,
+    press_offset: i32,
+    direction: D,
+    layout_direction: LayoutDirection,
+}
+
+// We implement this manually, because the derive implementation cannot handle
+// vectors of child widgets.
+impl<D: Directional, W: Widget> WidgetCore for Splitter<D, W> {
+    #[inline]
+    fn core_data(&self) -> &CoreData {
+        &self.core
+    }
+    #[inline]
+    fn core_data_mut(&mut self) -> &mut CoreData {
+        &mut self.core
+    }
+
+    #[inline]
+    fn widget_name(&self) -> &'static str {
+        "Splitter"
+    }
+
+    #[inline]
+    fn as_widget(&self) -> &dyn Widget {
+        self
+    }
+    #[inline]
+    fn as_widget_mut(&mut self) -> &mut dyn Widget {
+        self
+    }
+    #[inline]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    #[inline]
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.widgets.len()
+    }
+    #[inline]
+    fn get(&self, index: usize) -> Option<&dyn Widget> {
+        self.widgets.get(index).map(|w| w.as_widget())
+    }
+    #[inline]
+    fn get_mut(&mut self, index: usize) -> Option<&mut dyn Widget> {
+        self.widgets.get_mut(index).map(|w| w.as_widget_mut())
+    }
+
+    fn walk(&self, f: &mut dyn FnMut(&dyn Widget)) {
+        for child in &self.widgets {
+            child.walk(f);
+        }
+        f(self)
+    }
+    fn walk_mut(&mut self, f: &mut dyn FnMut(&mut dyn Widget)) {
+        for child in &mut self.widgets {
+            child.walk_mut(f);
+        }
+        f(self)
+    }
+}
+
+impl<D: Directional, W: Widget> Widget for Splitter<D, W> {
+    fn allow_focus(&self) -> bool {
+        !self.handles.is_empty()
+    }
+
+    fn cursor_icon(&self) -> CursorIcon {
+        // Only handles report our own id, so this only applies to handles
+        match self.direction.is_horizontal() {
+            true => CursorIcon::ColResize,
+            false => CursorIcon::RowResize,
+        }
+    }
+}
+
+impl<D: Directional, W: Widget> Layout for Splitter<D, W> {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let along = axis.is_vertical() == self.direction.is_vertical();
+        if along {
+            self.handle_size = size_handle.scrollbar().0;
+            self.rules.clear();
+        }
+
+        let mut rules = SizeRules::EMPTY;
+        for child in &mut self.widgets {
+            let child_rules = child.size_rules(size_handle, axis);
+            if along {
+                self.rules.push(child_rules);
+                rules += child_rules;
+            } else {
+                rules = rules.max(child_rules);
+            }
+        }
+
+        if along {
+            self.rules.push(rules);
+            if self.widgets.len() > 1 {
+                rules = rules + self.handle_size * (self.widgets.len() as u32 - 1);
+            }
+        }
+        rules
+    }
+
+    fn set_rect(&mut self, size_handle: &mut dyn SizeHandle, rect: Rect, _: AlignHints) {
+        self.core.rect = rect;
+        self.handles.clear();
+        let n = self.widgets.len();
+        if n == 0 {
+            return;
+        }
+
+        let is_horiz = self.direction.is_horizontal();
+        let total = if is_horiz { rect.size.0 } else { rect.size.1 };
+        let avail = total.saturating_sub(self.handle_size * (n as u32 - 1));
+        let mut sizes = vec![0; n];
+        if self.weights.len() == n {
+            self.solve_weighted(&mut sizes, avail);
+        } else {
+            SizeRules::solve_seq(&mut sizes, &self.rules, avail);
+        }
+
+        // Children are positioned left-to-right, then mirrored if required
+        let direction = size_handle.layout_direction();
+        self.layout_direction = direction;
+        let mut pos = rect.pos;
+        for (i, child) in self.widgets.iter_mut().enumerate() {
+            let mut crect = rect;
+            crect.pos = pos;
+            if is_horiz {
+                crect.size.0 = sizes[i];
+                pos.0 += sizes[i] as i32;
+            } else {
+                crect.size.1 = sizes[i];
+                pos.1 += sizes[i] as i32;
+            }
+            let crect = direction.mirror(crect, rect);
+            child.set_rect(size_handle, crect, AlignHints::default());
+
+            if i + 1 < n {
+                let mut hrect = rect;
+                hrect.pos = pos;
+                if is_horiz {
+                    hrect.size.0 = self.handle_size;
+                    pos.0 += self.handle_size as i32;
+                } else {
+                    hrect.size.1 = self.handle_size;
+                    pos.1 += self.handle_size as i32;
+                }
+                self.handles.push(direction.mirror(hrect, rect));
+            }
+        }
+    }
+
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        let solver = RowPositionSolver::new(self.direction);
+        if let Some(child) = solver.find_child(&self.widgets, coord) {
+            return child.find_id(coord);
+        }
+        Some(self.id())
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &Manager) {
+        let solver = RowPositionSolver::new(self.direction);
+        solver.for_children(&self.widgets, draw_handle.target_rect(), |w| {
+            w.draw(draw_handle, mgr)
+        });
+
+        // Handles are drawn as a scrollbar handle oriented across our direction
+        let dir = match self.direction.is_horizontal() {
+            true => Direction::Vertical,
+            false => Direction::Horizontal,
+        };
+        // Only the selected handle shows keyboard focus
+        let hl = mgr.highlight_state(self.id());
+        for (i, rect) in self.handles.iter().enumerate() {
+            let mut hl = hl;
+            hl.key_focus &= i == self.press_handle;
+            draw_handle.scrollbar(*rect, *rect, dir, hl);
+        }
+    }
+}
+
+impl<D: Directional, W: Widget + Handler> Handler for Splitter<D, W> {
+    type Msg = <W as Handler>::Msg;
+
+    fn handle(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<Self::Msg> {
+        if id != self.id() {
+            for child in &mut self.widgets {
+                if id <= child.id() {
+                    return child.handle(mgr, id, event);
+                }
+            }
+            debug_assert!(false, "Handler::handle: bad WidgetId");
+            return Response::Unhandled(event);
+        }
+
+        match event {
+            Event::PressStart { source, coord, .. } if source.is_primary() => {
+                let index = match self.handles.iter().position(|h| h.contains(coord)) {
+                    Some(index) => index,
+                    None => return Response::None,
+                };
+                let icon = self.cursor_icon();
+                if mgr.request_press_grab(source, self, coord, Some(icon)) {
+                    self.press_source = Some(source);
+                    self.press_handle = index;
+                    let handle = self.logical(self.handles[index]);
+                    self.press_offset = self.axis_pos(handle.pos) - self.logical_pos(coord);
+                }
+                Response::None
+            }
+            Event::PressMove { source, coord, .. } if Some(source) == self.press_source => {
+                let pos = self.logical_pos(coord) + self.press_offset;
+                self.move_handle(mgr, self.press_handle, pos);
+                Response::None
+            }
+            Event::PressEnd { source, .. } if Some(source) == self.press_source => {
+                self.press_source = None;
+                Response::None
+            }
+            Event::Action(Action::ControlKey(key)) => match self.control_key(mgr, key) {
+                true => Response::None,
+                false => Response::unhandled_action(Action::ControlKey(key)),
+            },
+            e => Manager::handle_generic(self, mgr, e),
+        }
+    }
+}
+
+impl<D: Directional + Default, W: Widget> Splitter<D, W> {
+    /// Construct a new instance
+    ///
+    /// This constructor is available where the direction is determined by the
+    /// type: for `D: Directional + Default`. In other cases, use
+    /// [`Splitter::new_with_direction`].
+    pub fn new(widgets: Vec<W>) -> Self {
+        Splitter::new_with_direction(Default::default(), widgets)
+    }
+}
+
+impl<D: Directional, W: Widget> Splitter<D, W> {
+    /// Construct a new instance with explicit direction
+    pub fn new_with_direction(direction: D, widgets: Vec<W>) -> Self {
+        Splitter {
+            core: Default::default(),
+            widgets,
+            handles: vec![],
+            handle_size: 0,
+            rules: vec![],
+            weights: vec![],
+            press_source: None,
+            press_handle: 0,
+            press_offset: 0,
+            direction,
+            layout_direction: LayoutDirection::default(),
+        }
+    }
+
+    /// True if there are no child widgets
+    pub fn is_empty(&self) -> bool {
+        self.widgets.is_empty()
+    }
+
+    /// Returns the number of child widgets
+    pub fn len(&self) -> usize {
+        self.widgets.len()
+    }
+
+    /// Get the relative size of each child, if adjusted by the user
+    ///
+    /// Returns an empty slice if the user has not adjusted the split.
+    pub fn weights(&self) -> &[f32] {
+        &self.weights
+    }
+
+    /// Append a child widget
+    ///
+    /// Any user adjustments of the split are reset.
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action).
+    pub fn push(&mut self, mgr: &mut Manager, widget: W) {
+        self.widgets.push(widget);
+        self.weights.clear();
+        mgr.send_action(TkAction::Reconfigure);
+    }
+
+    /// Remove the last child widget
+    ///
+    /// Returns `None` if there are no children. Otherwise, any user
+    /// adjustments of the split are reset.
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action) if any widget is
+    /// removed.
+    pub fn pop(&mut self, mgr: &mut Manager) -> Option<W> {
+        let mut r = self.widgets.pop();
+        if let Some(widget) = r.as_mut() {
+            mgr.detach(widget);
+            self.weights.clear();
+            mgr.send_action(TkAction::Reconfigure);
+        }
+        r
+    }
+
+    fn axis_pos(&self, coord: Coord) -> i32 {
+        match self.direction.is_horizontal() {
+            true => coord.0,
+            false => coord.1,
+        }
+    }
+
+    // Map a rect between left-to-right (logical) and displayed positions
+    fn logical(&self, rect: Rect) -> Rect {
+        self.layout_direction.mirror(rect, self.core.rect)
+    }
+
+    // Get the logical position of `coord` along our axis
+    fn logical_pos(&self, coord: Coord) -> i32 {
+        self.axis_pos(self.logical(Rect::new(coord, Size::ZERO)).pos)
+    }
+
+    fn axis_size(&self, rect: Rect) -> u32 {
+        match self.direction.is_horizontal() {
+            true => rect.size.0,
+            false => rect.size.1,
+        }
+    }
+
+    // Allocate sizes according to weights, respecting minimum sizes
+    fn solve_weighted(&self, sizes: &mut [u32], avail: u32) {
+        let mut used = 0;
+        for (i, size) in sizes.iter_mut().enumerate() {
+            let min = self.rules[i].min_size();
+            *size = ((self.weights[i] * avail as f32) as u32).max(min);
+            used += *size;
+        }
+
+        // Take any excess from the last children first
+        for i in (0..sizes.len()).rev() {
+            if used <= avail {
+                break;
+            }
+            let min = self.rules[i].min_size();
+            let take = (sizes[i] - min).min(used - avail);
+            sizes[i] -= take;
+            used -= take;
+        }
+
+        // Give any space remaining due to rounding to the last child
+        if used < avail {
+            sizes[sizes.len() - 1] += avail - used;
+        }
+    }
+
+    // Adjust the selected handle via the keyboard
+    //
+    // Returns false if the key is not used.
+    fn control_key(&mut self, mgr: &mut Manager, key: ControlKey) -> bool {
+        let index = self.press_handle;
+        if index >= self.handles.len() {
+            return false;
+        }
+        let is_horiz = self.direction.is_horizontal();
+        let mut step = self.handle_size.max(1) as i32;
+        if is_horiz && self.layout_direction.is_rtl() {
+            step = -step;
+        }
+        let pos = self.axis_pos(self.logical(self.handles[index]).pos);
+        match key {
+            ControlKey::Left if is_horiz => self.move_handle(mgr, index, pos - step),
+            ControlKey::Right if is_horiz => self.move_handle(mgr, index, pos + step),
+            ControlKey::Up if !is_horiz => self.move_handle(mgr, index, pos - step),
+            ControlKey::Down if !is_horiz => self.move_handle(mgr, index, pos + step),
+            ControlKey::Up | ControlKey::Left if index > 0 => {
+                self.press_handle -= 1;
+                mgr.redraw(self.id());
+            }
+            ControlKey::Down | ControlKey::Right if index + 1 < self.handles.len() => {
+                self.press_handle += 1;
+                mgr.redraw(self.id());
+            }
+            ControlKey::Home => self.move_handle(mgr, index, i32::MIN / 2),
+            ControlKey::End => self.move_handle(mgr, index, i32::MAX / 2),
+            _ => return false,
+        }
+        true
+    }
+
+    // Move handle `index` to position `pos` and recompute weights
+    fn move_handle(&mut self, mgr: &mut Manager, index: usize, pos: i32) {
+        let first = self.logical(self.widgets[index].rect());
+        let second = self.logical(self.widgets[index + 1].rect());
+        let start = self.axis_pos(first.pos);
+        let end = self.axis_pos(second.pos) + self.axis_size(second) as i32;
+        let len = (end - start) as u32 - self.handle_size;
+
+        let min0 = self.rules[index].min_size();
+        let min1 = self.rules[index + 1].min_size();
+        let size0 = ((pos.saturating_sub(start)).max(0) as u32)
+            .min(len.saturating_sub(min1))
+            .max(min0);
+        if size0 == self.axis_size(first) {
+            return;
+        }
+        let size1 = len.saturating_sub(size0);
+
+        let mut sizes: Vec<u32> = self
+            .widgets
+            .iter()
+            .map(|w| self.axis_size(w.rect()))
+            .collect();
+        sizes[index] = size0;
+        sizes[index + 1] = size1;
+
+        let total: u32 = sizes.iter().sum();
+        if total == 0 {
+            return;
+        }
+        self.weights = sizes
+            .iter()
+            .map(|size| *size as f32 / total as f32)
+            .collect();
+
+        // Only the two neighbours of the handle and the handle itself move
+        let (mut rect0, mut hrect, mut rect1) = (first, self.logical(self.handles[index]), second);
+        if self.direction.is_horizontal() {
+            rect0.size.0 = size0;
+            hrect.pos.0 = start + size0 as i32;
+            rect1.pos.0 = hrect.pos.0 + self.handle_size as i32;
+            rect1.size.0 = size1;
+        } else {
+            rect0.size.1 = size0;
+            hrect.pos.1 = start + size0 as i32;
+            rect1.pos.1 = hrect.pos.1 + self.handle_size as i32;
+            rect1.size.1 = size1;
+        }
+        let (rect0, hrect, rect1) = (
+            self.logical(rect0),
+            self.logical(hrect),
+            self.logical(rect1),
+        );
+        let (w0, w1) = self.widgets.split_at_mut(index + 1);
+        let (w0, w1) = (&mut w0[index], &mut w1[0]);
+        let done = mgr.size_handle(|size_handle| {
+            w0.set_rect(size_handle, rect0, AlignHints::default());
+            w1.set_rect(size_handle, rect1, AlignHints::default());
+        });
+        if done.is_some() {
+            self.handles[index] = hrect;
+            mgr.send_action(TkAction::RegionMoved);
+        } else {
+            // Without a size handle, child rects can only be adjusted via a resize
+            mgr.send_action(TkAction::Reconfigure);
+        }
+    }
+}
@@ -0,0 +1,258 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! A stack
+
+use std::any::Any;
+
+use crate::event::{Event, Handler, Manager, Response};
+use crate::geom::{Coord, Rect};
+use crate::layout::{AxisInfo, SizeRules};
+use crate::theme::{DrawHandle, SizeHandle};
+use crate::{AlignHints, CoreData, Layout, TkAction, Widget, WidgetCore, WidgetId};
+
+/// A stack of boxed widgets
+///
+/// This is parameterised over handler message type.
+///
+/// See documentation of [`Stack`] type.
+pub type BoxStack<M> = Stack<Box<dyn Handler<Msg = M>>>;
+
+/// A stack of widgets
+///
+/// A stack consists of a number of child widgets, all of which are sized and
+/// positioned over the same region. Only the *active* child is drawn or
+/// receives events addressed by coordinate; the others are hidden. This is
+/// useful for implementing wizards and tabbed pages.
+///
+/// The stack's size requirements are the maximum of those of all children,
+/// hence changing the active child does not require a resize.
+///
+/// Inactive children are hidden from [`WidgetCore::walk`],
+/// [`WidgetCore::find`] and [`WidgetCore::find_key`] (thus from keyboard
+/// navigation and lookup by key) and do not receive events. They are still configured and receive update-handle and timer
+/// notifications (delivered via [`WidgetCore::walk_mut`] and
+/// [`WidgetCore::find_mut`]).
+#[derive(Clone, Default, Debug)]
+pub struct Stack<W: Widget> {
+    core: CoreData,
+    widgets: Vec<W>,
+    active: usize,
+}
+
+// We implement this manually, because the derive implementation cannot handle
+// vectors of child widgets.
+impl<W: Widget> WidgetCore for Stack<W> {
+    #[inline]
+    fn core_data(&self) -> &CoreData {
+        &self.core
+    }
+    #[inline]
+    fn core_data_mut(&mut self) -> &mut CoreData {
+        &mut self.core
+    }
+
+    #[inline]
+    fn widget_name(&self) -> &'static str {
+        "Stack"
+    }
+
+    #[inline]
+    fn as_widget(&self) -> &dyn Widget {
+        self
+    }
+    #[inline]
+    fn as_widget_mut(&mut self) -> &mut dyn Widget {
+        self
+    }
+    #[inline]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    #[inline]
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.widgets.len()
+    }
+    #[inline]
+    fn get(&self, index: usize) -> Option<&dyn Widget> {
+        self.widgets.get(index).map(|w| w.as_widget())
+    }
+    #[inline]
+    fn get_mut(&mut self, index: usize) -> Option<&mut dyn Widget> {
+        self.widgets.get_mut(index).map(|w| w.as_widget_mut())
+    }
+
+    fn find(&self, id: WidgetId) -> Option<&dyn Widget> {
+        if id == self.id() {
+            return Some(self.as_widget());
+        }
+        match self.child_index(id) {
+            Some(index) if index == self.active => self.widgets[index].find(id),
+            _ => None,
+        }
+    }
+
+    fn find_key(&self, key: &str) -> Option<&dyn Widget> {
+        if self.key() == Some(key) {
+            return Some(self.as_widget());
+        }
+        self.widgets.get(self.active)?.find_key(key)
+    }
+
+    fn walk(&self, f: &mut dyn FnMut(&dyn Widget)) {
+        if let Some(child) = self.widgets.get(self.active) {
+            child.walk(f);
+        }
+        f(self)
+    }
+    fn walk_mut(&mut self, f: &mut dyn FnMut(&mut dyn Widget)) {
+        for child in &mut self.widgets {
+            child.walk_mut(f);
+        }
+        f(self)
+    }
+}
+
+impl<W: Widget> Widget for Stack<W> {}
+
+impl<W: Widget> Layout for Stack<W> {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let mut rules = SizeRules::EMPTY;
+        for child in &mut self.widgets {
+            rules = rules.max(child.size_rules(size_handle, axis));
+        }
+        rules
+    }
+
+    fn set_rect(&mut self, size_handle: &mut dyn SizeHandle, rect: Rect, align: AlignHints) {
+        self.core.rect = rect;
+        for child in &mut self.widgets {
+            child.set_rect(size_handle, rect, align);
+        }
+    }
+
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        if let Some(child) = self.widgets.get(self.active) {
+            return child.find_id(coord);
+        }
+        Some(self.id())
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &Manager) {
+        if let Some(child) = self.widgets.get(self.active) {
+            child.draw(draw_handle, mgr);
+        }
+    }
+}
+
+impl<W: Widget + Handler> Handler for Stack<W> {
+    type Msg = <W as Handler>::Msg;
+
+    fn handle(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<Self::Msg> {
+        match self.child_index(id) {
+            Some(index) if index == self.active => self.widgets[index].handle(mgr, id, event),
+            // Inactive children are hidden and do not receive events
+            Some(_) => Response::Unhandled(event),
+            None => {
+                debug_assert!(id == self.id(), "Handler::handle: bad WidgetId");
+                Response::Unhandled(event)
+            }
+        }
+    }
+}
+
+impl<W: Widget> Stack<W> {
+    /// Construct a new instance
+    ///
+    /// The first child (if any) is initially active.
+    pub fn new(widgets: Vec<W>) -> Self {
+        Stack {
+            core: Default::default(),
+            widgets,
+            active: 0,
+        }
+    }
+
+    /// Get the index of the child containing the widget `id`, if any
+    fn child_index(&self, id: WidgetId) -> Option<usize> {
+        self.widgets.iter().position(|child| id <= child.id())
+    }
+
+    /// Get the index of the active child
+    ///
+    /// This may be out of bounds if the stack is empty.
+    #[inline]
+    pub fn active(&self) -> usize {
+        self.active
+    }
+
+    /// Set the active child
+    ///
+    /// If `index` is out of bounds, no child is drawn.
+    ///
+    /// Focus and grabs held by the previously active child are released (see
+    /// [`Manager::hide`]). Triggers a
+    /// [region-moved action](Manager::send_action) if the active child
+    /// changes.
+    pub fn set_active(&mut self, mgr: &mut Manager, index: usize) {
+        if self.active != index {
+            if let Some(child) = self.widgets.get(self.active) {
+                mgr.hide(child);
+            }
+            self.active = index;
+            mgr.send_action(TkAction::RegionMoved);
+        }
+    }
+
+    /// True if there are no child widgets
+    pub fn is_empty(&self) -> bool {
+        self.widgets.is_empty()
+    }
+
+    /// Returns the number of child widgets
+    pub fn len(&self) -> usize {
+        self.widgets.len()
+    }
+
+    /// Append a child widget
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action).
+    pub fn push(&mut self, mgr: &mut Manager, widget: W) {
+        self.widgets.push(widget);
+        mgr.send_action(TkAction::Reconfigure);
+    }
+
+    /// Remove the last child widget
+    ///
+    /// Returns `None` if there are no children.
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action) if any widget is
+    /// removed.
+    pub fn pop(&mut self, mgr: &mut Manager) -> Option<W> {
+        let mut r = self.widgets.pop();
+        if let Some(widget) = r.as_mut() {
+            mgr.detach(widget);
+            mgr.send_action(TkAction::Reconfigure);
+        }
+        r
+    }
+
+    /// Replace the child at `index`
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action).
+    pub fn replace(&mut self, mgr: &mut Manager, index: usize, mut widget: W) -> W {
+        std::mem::swap(&mut widget, &mut self.widgets[index]);
+        mgr.detach(&mut widget);
+        mgr.send_action(TkAction::Reconfigure);
+        widget
+    }
+}
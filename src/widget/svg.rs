@@ -0,0 +1,160 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! SVG image widget
+
+use std::fmt::{self, Debug};
+use std::path::Path;
+use std::rc::Rc;
+
+use log::warn;
+use nsvg::{SvgImage, Units};
+
+use crate::draw::ImageData;
+use crate::event::Manager;
+use crate::geom::{Coord, Rect, Size};
+use crate::layout::{AxisInfo, SizeRules, StretchPolicy};
+use crate::macros::Widget;
+use crate::theme::{DrawHandle, SizeHandle};
+use crate::{Align, AlignHints, CoreData, Layout, TkAction};
+
+/// Resolution used to interpret physical units (e.g. `mm`) in SVG documents
+const SVG_DPI: f32 = 96.0;
+
+/// A vector image
+///
+/// The image is rasterised on the CPU at the size allocated to the widget, and
+/// re-rasterised when this size changes (including on DPI changes), thus it
+/// remains sharp at any scale. The aspect ratio is preserved; the image is
+/// positioned according to alignment hints (centred by default).
+///
+/// The image's ideal size is its natural size (in logical pixels) scaled by
+/// the DPI factor.
+///
+/// This widget requires the `svg` feature.
+#[widget]
+#[handler]
+#[derive(Clone, Widget)]
+pub struct Svg {
+    #[core]
+    core: CoreData,
+    svg: Rc<SvgImage>,
+    raster: Option<ImageData>,
+    draw_rect: Rect,
+}
+
+impl Debug for Svg {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Svg {{ core: {:?}, size: {:?}, draw_rect: {:?}, ... }}",
+            self.core,
+            self.natural_size(),
+            self.draw_rect
+        )
+    }
+}
+
+impl Layout for Svg {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let (w, h) = self.natural_size();
+        let dpi_factor = size_handle.dpi_factor();
+        let size = Size(
+            (w * dpi_factor).round() as u32,
+            (h * dpi_factor).round() as u32,
+        );
+        SizeRules::new(0, axis.extract_size(size), StretchPolicy::LowUtility)
+    }
+
+    fn set_rect(&mut self, _: &mut dyn SizeHandle, rect: Rect, align: AlignHints) {
+        self.core.rect = rect;
+        let (w, h) = self.natural_size();
+        let scale = if w > 0.0 && h > 0.0 {
+            (rect.size.0 as f32 / w).min(rect.size.1 as f32 / h)
+        } else {
+            0.0
+        };
+        let size = Size((w * scale).round() as u32, (h * scale).round() as u32);
+
+        let place = |align: Option<Align>, space: u32, size: u32| {
+            let diff = space as i32 - size as i32;
+            match align.unwrap_or(Align::Centre) {
+                Align::Begin | Align::Stretch => 0,
+                Align::Centre => diff / 2,
+                Align::End => diff,
+            }
+        };
+        let offset = Coord(
+            place(align.horiz, rect.size.0, size.0),
+            place(align.vert, rect.size.1, size.1),
+        );
+        self.draw_rect = Rect::new(rect.pos + offset, size);
+
+        let resize = self.raster.as_ref().map(|r| r.size()) != Some(size);
+        if resize {
+            self.raster = self.rasterise(scale);
+        }
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, _: &Manager) {
+        if let Some(raster) = self.raster.as_ref() {
+            // The raster size may differ from draw_rect by rounding; we draw
+            // at the raster size to avoid blurring.
+            let rect = Rect::new(self.draw_rect.pos, raster.size());
+            draw_handle.image(rect, raster);
+        }
+    }
+}
+
+impl Svg {
+    /// Construct from a parsed image
+    fn from_image(svg: SvgImage) -> Self {
+        Svg {
+            core: Default::default(),
+            svg: Rc::new(svg),
+            raster: None,
+            draw_rect: Rect::default(),
+        }
+    }
+
+    /// Construct from SVG data
+    pub fn new(data: &str) -> Result<Self, nsvg::Error> {
+        nsvg::parse_str(data, Units::Pixel, SVG_DPI).map(Svg::from_image)
+    }
+
+    /// Load from a file
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, nsvg::Error> {
+        nsvg::parse_file(path.as_ref(), Units::Pixel, SVG_DPI).map(Svg::from_image)
+    }
+
+    /// Replace the image with new SVG data
+    ///
+    /// On success, triggers a [reconfigure action](Manager::send_action).
+    pub fn set_svg(&mut self, mgr: &mut Manager, data: &str) -> Result<(), nsvg::Error> {
+        self.svg = Rc::new(nsvg::parse_str(data, Units::Pixel, SVG_DPI)?);
+        self.raster = None;
+        mgr.send_action(TkAction::Reconfigure);
+        Ok(())
+    }
+
+    /// Natural size in logical pixels
+    fn natural_size(&self) -> (f32, f32) {
+        (self.svg.width(), self.svg.height())
+    }
+
+    fn rasterise(&self, scale: f32) -> Option<ImageData> {
+        if scale <= 0.0 {
+            return None;
+        }
+        match self.svg.rasterize_to_raw_rgba(scale) {
+            Ok((w, h, rgba)) if w > 0 && h > 0 => Some(ImageData::from_rgba(Size(w, h), rgba)),
+            Ok(_) => None,
+            Err(e) => {
+                warn!("Svg: failed to rasterise: {}", e);
+                None
+            }
+        }
+    }
+}
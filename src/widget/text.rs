@@ -6,18 +6,34 @@
 //! Text widgets
 
 use std::fmt::{self, Debug};
+use std::ops::Range;
+use std::rc::Rc;
 
 use crate::class::{Editable, HasText};
-use crate::event::{Action, CursorIcon, Handler, Manager, Response, VoidMsg};
+use crate::event::{
+    Action, ControlKey, CursorIcon, Event, Handler, Manager, PressSource, Response, VoidMsg,
+};
+use crate::geom::{Coord, Size};
 use crate::layout::{AxisInfo, SizeRules};
 use crate::macros::Widget;
-use crate::theme::{DrawHandle, SizeHandle, TextClass, TextProperties};
-use crate::{Align, AlignHints, CoreData, Layout, Widget, WidgetCore};
+use crate::text::{MarkupError, RichText};
+use crate::theme::{
+    DrawHandle, SizeHandle, TextClass, TextProperties, UnderlineStyle, ValidationState,
+};
+use crate::{Align, AlignHints, CoreData, Layout, TkAction, Widget, WidgetCore, WidgetId};
 use kas::geom::Rect;
 
 /// A simple text label
-#[widget]
-#[handler]
+///
+/// Text is wrapped to the allocated width, thus the label's height depends on
+/// its width. Where the parent layout supplies the width when querying the
+/// height (see [`AxisInfo`]), the label's height accounts for wrapping, and
+/// paragraphs reflow when resized.
+///
+/// Optionally, text may be [selectable](Label::selectable): the user may then
+/// select text with the mouse and copy it to the clipboard (without editing).
+/// This is useful for things like log viewers and error messages. Selection
+/// by mouse does not account for wrapped lines.
 #[derive(Clone, Default, Debug, Widget)]
 pub struct Label {
     #[core]
@@ -25,6 +41,22 @@ pub struct Label {
     halign: Align,
     valign: Align,
     text: String,
+    selectable: bool,
+    /// Cursor position (byte index)
+    cursor: usize,
+    /// Selection anchor; selection is empty when `anchor == cursor`
+    anchor: usize,
+    press_source: Option<PressSource>,
+}
+
+impl Widget for Label {
+    fn cursor_icon(&self) -> CursorIcon {
+        if self.selectable {
+            CursorIcon::Text
+        } else {
+            CursorIcon::Default
+        }
+    }
 }
 
 impl Layout for Label {
@@ -38,8 +70,9 @@ impl Layout for Label {
         rules
     }
 
-    fn set_rect(&mut self, _size_handle: &mut dyn SizeHandle, rect: Rect, align: AlignHints) {
-        self.halign = align.horiz.unwrap_or(Align::Begin);
+    fn set_rect(&mut self, size_handle: &mut dyn SizeHandle, rect: Rect, align: AlignHints) {
+        let direction = size_handle.layout_direction();
+        self.halign = direction.halign(align.horiz.unwrap_or(Align::Begin));
         self.valign = align.vert.unwrap_or(Align::Centre);
         self.core_data_mut().rect = rect;
     }
@@ -50,7 +83,13 @@ impl Layout for Label {
             horiz: self.halign,
             vert: self.valign,
         };
-        draw_handle.text(self.core.rect, &self.text, props);
+        let (start, end) = self.selection();
+        if start != end {
+            draw_handle.text_selection(self.core.rect, &self.text, start..end, props);
+            draw_handle.text_selected(self.core.rect, &self.text, start..end, props);
+        } else {
+            draw_handle.text(self.core.rect, &self.text, props);
+        }
     }
 }
 
@@ -62,8 +101,67 @@ impl Label {
             halign: Default::default(),
             valign: Default::default(),
             text: text.to_string(),
+            selectable: false,
+            cursor: 0,
+            anchor: 0,
+            press_source: None,
+        }
+    }
+
+    /// Set whether text is selectable (chain style)
+    ///
+    /// By default, text is not selectable.
+    pub fn selectable(mut self, selectable: bool) -> Self {
+        self.selectable = selectable;
+        self
+    }
+
+    /// True if text is selectable
+    #[inline]
+    pub fn is_selectable(&self) -> bool {
+        self.selectable
+    }
+
+    /// Get the selected range (byte indices)
+    ///
+    /// If nothing is selected, the range is empty.
+    pub fn selection(&self) -> (usize, usize) {
+        if self.anchor <= self.cursor {
+            (self.anchor, self.cursor)
+        } else {
+            (self.cursor, self.anchor)
         }
     }
+
+    /// Get the selected text
+    pub fn selected_text(&self) -> &str {
+        let (start, end) = self.selection();
+        &self.text[start..end]
+    }
+
+    /// Find the text index nearest to `coord`
+    fn index_at(&self, mgr: &mut Manager, coord: Coord) -> Option<usize> {
+        let text = &self.text;
+        let rect = self.core.rect;
+        let (halign, valign) = (self.halign, self.valign);
+        mgr.size_handle(|size_handle| {
+            let size = size_handle.text_size(text, TextClass::Label, Some(rect.size.0));
+            let offset = |align: Align, space: u32, size: u32| {
+                let diff = space.saturating_sub(size) as i32;
+                match align {
+                    Align::Begin | Align::Stretch => 0,
+                    Align::Centre => diff / 2,
+                    Align::End => diff,
+                }
+            };
+            let pos = rect.pos
+                + Coord(
+                    offset(halign, rect.size.0, size.0),
+                    offset(valign, rect.size.1, size.1),
+                );
+            text_index(size_handle, text, TextClass::Label, coord - pos)
+        })
+    }
 }
 
 impl<T> From<T> for Label
@@ -76,6 +174,10 @@ where
             halign: Default::default(),
             valign: Default::default(),
             text: String::from(text),
+            selectable: false,
+            cursor: 0,
+            anchor: 0,
+            press_source: None,
         }
     }
 }
@@ -87,16 +189,179 @@ impl HasText for Label {
 
     fn set_string(&mut self, mgr: &mut Manager, text: String) {
         self.text = text;
+        self.cursor = 0;
+        self.anchor = 0;
+        mgr.redraw(self.id());
+    }
+}
+
+impl Handler for Label {
+    type Msg = VoidMsg;
+
+    fn handle_action(&mut self, mgr: &mut Manager, action: Action) -> Response<VoidMsg> {
+        match action {
+            Action::ReceivedCharacter('\u{01}') if self.selectable => {
+                // select all
+                self.anchor = 0;
+                self.cursor = self.text.len();
+                mgr.redraw(self.id());
+                Response::None
+            }
+            Action::ReceivedCharacter('\u{03}') if self.selectable => {
+                // copy
+                if self.cursor != self.anchor {
+                    mgr.set_clipboard(self.selected_text().to_string());
+                }
+                Response::None
+            }
+            a => Response::unhandled_action(a),
+        }
+    }
+
+    fn handle(&mut self, mgr: &mut Manager, _: WidgetId, event: Event) -> Response<VoidMsg> {
+        match event {
+            Event::PressStart {
+                source,
+                coord,
+                clicks,
+            } if self.selectable && source.is_primary() => {
+                if mgr.request_press_grab(source, self, coord, None) {
+                    self.press_source = None;
+                    mgr.request_char_focus(self.id());
+                    if let Some(index) = self.index_at(mgr, coord) {
+                        if clicks >= 2 {
+                            let (start, end) = if clicks == 2 {
+                                word_range(&self.text, index)
+                            } else {
+                                line_range(&self.text, index)
+                            };
+                            self.anchor = start;
+                            self.cursor = end;
+                        } else {
+                            self.press_source = Some(source);
+                            self.cursor = index;
+                            if !mgr.modifiers().shift {
+                                self.anchor = index;
+                            }
+                        }
+                    }
+                }
+                Response::None
+            }
+            Event::PressMove { source, coord, .. } if Some(source) == self.press_source => {
+                if let Some(index) = self.index_at(mgr, coord) {
+                    if index != self.cursor {
+                        self.cursor = index;
+                        mgr.redraw(self.id());
+                    }
+                }
+                Response::None
+            }
+            Event::PressEnd { source, .. } if Some(source) == self.press_source => {
+                self.press_source = None;
+                Response::None
+            }
+            e => Manager::handle_generic(self, mgr, e),
+        }
+    }
+}
+
+/// A text label with styled spans
+///
+/// This is as [`Label`], except that spans of text may be drawn bold, italic,
+/// in another colour or at another font size (see [`RichText`]). Text may be
+/// constructed directly or parsed from markup via [`RichLabel::from_markup`].
+///
+/// Support for styling depends on the theme; where a style is not supported,
+/// text is drawn in the standard style.
+#[widget]
+#[handler]
+#[derive(Clone, Default, Debug, Widget)]
+pub struct RichLabel {
+    #[core]
+    core: CoreData,
+    halign: Align,
+    valign: Align,
+    text: RichText,
+}
+
+impl Layout for RichLabel {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let rules = size_handle.rich_text_bound(&self.text, TextClass::Label, axis);
+        if axis.is_horizontal() {
+            self.core_data_mut().rect.size.0 = rules.ideal_size();
+        } else {
+            self.core_data_mut().rect.size.1 = rules.ideal_size();
+        }
+        rules
+    }
+
+    fn set_rect(&mut self, size_handle: &mut dyn SizeHandle, rect: Rect, align: AlignHints) {
+        let direction = size_handle.layout_direction();
+        self.halign = direction.halign(align.horiz.unwrap_or(Align::Begin));
+        self.valign = align.vert.unwrap_or(Align::Centre);
+        self.core_data_mut().rect = rect;
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, _: &Manager) {
+        let props = TextProperties {
+            class: TextClass::Label,
+            horiz: self.halign,
+            vert: self.valign,
+        };
+        draw_handle.rich_text(self.core.rect, &self.text, props);
+    }
+}
+
+impl RichLabel {
+    /// Construct a new instance
+    pub fn new(text: RichText) -> Self {
+        RichLabel {
+            core: Default::default(),
+            halign: Default::default(),
+            valign: Default::default(),
+            text,
+        }
+    }
+
+    /// Construct from markup
+    ///
+    /// See [`RichText::parse`] for the supported syntax.
+    pub fn from_markup(markup: &str) -> Result<Self, MarkupError> {
+        RichText::parse(markup).map(RichLabel::new)
+    }
+
+    /// Get the text
+    pub fn rich_text(&self) -> &RichText {
+        &self.text
+    }
+
+    /// Set the text
+    ///
+    /// Triggers a [reconfigure action](Manager::send_action).
+    pub fn set_rich_text(&mut self, mgr: &mut Manager, text: RichText) {
+        self.text = text;
+        mgr.send_action(TkAction::Reconfigure);
+    }
+}
+
+impl HasText for RichLabel {
+    fn get_text(&self) -> &str {
+        self.text.as_str()
+    }
+
+    fn set_string(&mut self, mgr: &mut Manager, text: String) {
+        self.text = RichText::from(text);
         mgr.redraw(self.id());
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
-enum LastEdit {
+pub(super) enum LastEdit {
     None,
     Insert,
     Backspace,
-    Clear,
+    Delete,
     Paste,
 }
 
@@ -106,7 +371,299 @@ impl Default for LastEdit {
     }
 }
 
+/// Byte index of the char preceding `pos`
+pub(super) fn prev_char(text: &str, pos: usize) -> usize {
+    text[..pos]
+        .char_indices()
+        .next_back()
+        .map(|(i, _)| i)
+        .unwrap_or(0)
+}
+
+/// Byte index of the char following `pos`
+pub(super) fn next_char(text: &str, pos: usize) -> usize {
+    text[pos..]
+        .chars()
+        .next()
+        .map(|c| pos + c.len_utf8())
+        .unwrap_or(pos)
+}
+
+/// Byte index of the start of the word preceding `pos`
+pub(super) fn prev_word(text: &str, pos: usize) -> usize {
+    let mut iter = text[..pos].char_indices().rev().peekable();
+    while let Some((_, c)) = iter.peek() {
+        if c.is_alphanumeric() {
+            break;
+        }
+        iter.next();
+    }
+    let mut pos = iter.peek().map(|(i, _)| *i).unwrap_or(0);
+    for (i, c) in iter {
+        if !c.is_alphanumeric() {
+            break;
+        }
+        pos = i;
+    }
+    pos
+}
+
+/// Byte index of the end of the word following `pos`
+pub(super) fn next_word(text: &str, pos: usize) -> usize {
+    let mut iter = text[pos..].char_indices().peekable();
+    while let Some((_, c)) = iter.peek() {
+        if c.is_alphanumeric() {
+            break;
+        }
+        iter.next();
+    }
+    for (i, c) in iter {
+        if !c.is_alphanumeric() {
+            return pos + i;
+        }
+    }
+    text.len()
+}
+
+/// Byte range of the word at `pos`
+///
+/// If `pos` is not within or adjacent to a word, this is the char at `pos`.
+fn word_range(text: &str, pos: usize) -> (usize, usize) {
+    let is_word = |c: Option<char>| c.map(|c| c.is_alphanumeric()).unwrap_or(false);
+    if !is_word(text[pos..].chars().next()) && !is_word(text[..pos].chars().next_back()) {
+        return (pos, next_char(text, pos));
+    }
+    let start = text[..pos]
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| c.is_alphanumeric())
+        .last()
+        .map(|(i, _)| i)
+        .unwrap_or(pos);
+    let end = text[pos..]
+        .char_indices()
+        .find(|(_, c)| !c.is_alphanumeric())
+        .map(|(i, _)| pos + i)
+        .unwrap_or(text.len());
+    (start, end)
+}
+
+/// Byte range of the line at `pos`, excluding the line break
+fn line_range(text: &str, pos: usize) -> (usize, usize) {
+    let start = text[..pos].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let end = text[pos..]
+        .find('\n')
+        .map(|i| pos + i)
+        .unwrap_or(text.len());
+    (start, end)
+}
+
+/// Input validation for [`EditBox`]
+///
+/// All methods have default implementations accepting any input.
+pub trait Validator {
+    /// Transform inserted text
+    ///
+    /// This is called on each typed character or pasted string before
+    /// insertion, and may modify `input` (e.g. to change case or remove
+    /// unwanted characters).
+    fn transform(&self, _input: &mut String) {}
+
+    /// Accept or reject an edit
+    ///
+    /// This is called with the text as it would be after each edit; if this
+    /// returns false, the edit is discarded.
+    fn accept(&self, _new_text: &str) -> bool {
+        true
+    }
+
+    /// Check whether the text is valid
+    ///
+    /// Unlike [`Validator::accept`], this does not prevent edits; instead
+    /// invalid text is drawn in an error state. This allows intermediate
+    /// states while typing (e.g. an incomplete date).
+    fn is_valid(&self, _text: &str) -> bool {
+        true
+    }
+}
+
+/// Closures are used as an edit filter (see [`Validator::accept`])
+impl<F: Fn(&str) -> bool> Validator for F {
+    fn accept(&self, new_text: &str) -> bool {
+        self(new_text)
+    }
+}
+
+/// A [`Validator`] limiting text length (in chars)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MaxLength(pub usize);
+
+impl Validator for MaxLength {
+    fn accept(&self, new_text: &str) -> bool {
+        new_text.chars().count() <= self.0
+    }
+}
+
+/// Spell checking for [`EditBox`] and [`TextArea`](super::TextArea)
+///
+/// Misspelled words are drawn with a wavy underline.
+pub trait SpellChecker {
+    /// Find misspelled words
+    ///
+    /// This is called with some part of the text, consisting of one or more
+    /// whole lines, and should return the byte ranges (relative to `text`) of
+    /// misspelled words.
+    fn check(&self, text: &str) -> Vec<Range<usize>>;
+
+    /// Suggest replacements for a misspelled `word`
+    ///
+    /// The default implementation returns no suggestions.
+    fn suggestions(&self, _word: &str) -> Vec<String> {
+        vec![]
+    }
+}
+
+/// Spell-check state of a text widget
+///
+/// This tracks misspelled ranges across edits: on update, the edited region is
+/// found by comparison with the previous text, ranges outside this region are
+/// kept (shifted as required) and only the affected lines are re-checked.
+#[derive(Clone, Default)]
+pub(super) struct SpellCheck {
+    checker: Option<Rc<dyn SpellChecker>>,
+    checked: String,
+    misspelled: Vec<Range<usize>>,
+}
+
+impl SpellCheck {
+    pub fn set_checker(&mut self, checker: Rc<dyn SpellChecker>, text: &str) {
+        self.checker = Some(checker);
+        self.checked.clear();
+        self.misspelled.clear();
+        self.recheck(text, 0, text.len());
+        self.checked = text.to_string();
+    }
+
+    /// Update after the text changed
+    pub fn update(&mut self, text: &str) {
+        if self.checker.is_none() || text == self.checked {
+            return;
+        }
+        let old = &self.checked;
+
+        // Find the edited region: old[start..old_end] → text[start..new_end]
+        let mut start = old
+            .bytes()
+            .zip(text.bytes())
+            .take_while(|(a, b)| a == b)
+            .count();
+        while !text.is_char_boundary(start) {
+            start -= 1;
+        }
+        let max_suffix = old.len().min(text.len()) - start;
+        let mut suffix = old
+            .bytes()
+            .rev()
+            .zip(text.bytes().rev())
+            .take(max_suffix)
+            .take_while(|(a, b)| a == b)
+            .count();
+        while !text.is_char_boundary(text.len() - suffix) {
+            suffix -= 1;
+        }
+        let old_end = old.len() - suffix;
+        let new_end = text.len() - suffix;
+
+        // Keep ranges outside the edit, shifting those after it
+        let mut misspelled = Vec::with_capacity(self.misspelled.len());
+        for r in self.misspelled.drain(..) {
+            if r.end < start {
+                misspelled.push(r);
+            } else if r.start > old_end {
+                misspelled.push((r.start + new_end - old_end)..(r.end + new_end - old_end));
+            }
+        }
+        self.misspelled = misspelled;
+
+        // Re-check whole lines affected by the edit
+        let line_start = text[..start].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let line_end = text[new_end..]
+            .find('\n')
+            .map(|i| new_end + i)
+            .unwrap_or(text.len());
+        self.recheck(text, line_start, line_end);
+        self.checked = text.to_string();
+    }
+
+    /// Check `text[start..end]`, replacing any existing ranges within
+    fn recheck(&mut self, text: &str, start: usize, end: usize) {
+        let checker = match self.checker.as_ref() {
+            Some(checker) => checker,
+            None => return,
+        };
+        self.misspelled.retain(|r| r.end < start || r.start > end);
+        let found = checker.check(&text[start..end]);
+        self.misspelled.extend(
+            found
+                .into_iter()
+                .map(|r| (start + r.start)..(start + r.end)),
+        );
+        self.misspelled.sort_by_key(|r| r.start);
+    }
+
+    /// Get the misspelled range containing `index`, if any
+    pub fn misspelling_at(&self, index: usize) -> Option<Range<usize>> {
+        self.misspelled
+            .iter()
+            .find(|r| r.start <= index && index <= r.end)
+            .cloned()
+    }
+
+    /// Get suggestions for the misspelled word at `index`, if any
+    pub fn suggestions_at(&self, text: &str, index: usize) -> Option<(Range<usize>, Vec<String>)> {
+        let checker = self.checker.as_ref()?;
+        let range = self.misspelling_at(index)?;
+        let suggestions = checker.suggestions(&text[range.clone()]);
+        Some((range, suggestions))
+    }
+
+    /// Underline misspelled words
+    ///
+    /// The drawn `text` starts at byte `offset` of the checked text.
+    pub fn draw(
+        &self,
+        draw_handle: &mut dyn DrawHandle,
+        rect: Rect,
+        text: &str,
+        offset: usize,
+        props: TextProperties,
+    ) {
+        for r in &self.misspelled {
+            if r.end <= offset {
+                continue;
+            }
+            let start = r.start.saturating_sub(offset);
+            let end = r.end - offset;
+            draw_handle.text_underline(rect, text, start..end, UnderlineStyle::Wavy, props);
+        }
+    }
+}
+
 /// An editable, single-line text box.
+///
+/// The cursor may be moved with the arrow keys (by word when `Ctrl` is held)
+/// and `Home`/`End`, or placed by clicking. Text may be selected by holding
+/// `Shift` while moving the cursor or by dragging with the mouse;
+/// double-click selects a word and triple-click a line. Standard shortcuts
+/// for select-all, cut, copy, paste and undo are supported.
+///
+/// Input may be filtered and validated via [`EditBox::with_filter`] or
+/// [`EditBox::with_validator`].
+///
+/// A [`SpellChecker`] may be set via [`EditBox::with_spell_checker`].
+/// Applications may present replacements for a misspelled word (e.g. in a
+/// context menu) using [`EditBox::suggestions_at`] and
+/// [`EditBox::replace_range`].
 #[derive(Clone, Default, Widget)]
 pub struct EditBox<H: 'static> {
     #[core]
@@ -115,8 +672,19 @@ pub struct EditBox<H: 'static> {
     editable: bool,
     multi_line: bool,
     text: String,
-    old_state: Option<String>,
+    placeholder: String,
+    /// Cursor position (byte index)
+    cursor: usize,
+    /// Selection anchor; selection is empty when `anchor == cursor`
+    anchor: usize,
+    press_source: Option<PressSource>,
+    /// Input method pre-edit text, displayed at the cursor
+    preedit: String,
+    old_state: Option<(String, usize)>,
     last_edit: LastEdit,
+    validator: Option<Rc<dyn Validator>>,
+    error: bool,
+    spell: SpellCheck,
     on_activate: H,
 }
 
@@ -124,8 +692,8 @@ impl<H> Debug for EditBox<H> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "EditBox {{ core: {:?}, editable: {:?}, text: {:?}, ... }}",
-            self.core, self.editable, self.text
+            "EditBox {{ core: {:?}, editable: {:?}, text: {:?}, cursor: {:?}, anchor: {:?}, error: {:?}, ... }}",
+            self.core, self.editable, self.text, self.cursor, self.anchor, self.error
         )
     }
 }
@@ -138,19 +706,26 @@ impl<H: 'static> Widget for EditBox<H> {
     fn cursor_icon(&self) -> CursorIcon {
         CursorIcon::Text
     }
+
+    fn validation(&self) -> ValidationState {
+        if self.error {
+            ValidationState::Error
+        } else {
+            ValidationState::None
+        }
+    }
 }
 
 impl<H: 'static> Layout for EditBox<H> {
     fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
-        let class = if self.multi_line {
-            TextClass::EditMulti
-        } else {
-            TextClass::Edit
-        };
         let sides = size_handle.edit_surround();
         let margin = size_handle.inner_margin();
-        let rules = SizeRules::fixed(axis.extract_size(sides.0 + sides.1 + margin))
-            + size_handle.text_bound(&self.text, class, axis);
+        let mut bound = size_handle.text_bound(&self.text, self.class(), axis);
+        if !self.placeholder.is_empty() {
+            let class = TextClass::Placeholder;
+            bound = bound.max(size_handle.text_bound(&self.placeholder, class, axis));
+        }
+        let rules = SizeRules::fixed(axis.extract_size(sides.0 + sides.1 + margin)) + bound;
         if axis.is_horizontal() {
             self.core_data_mut().rect.size.0 = rules.ideal_size();
         } else {
@@ -178,40 +753,66 @@ impl<H: 'static> Layout for EditBox<H> {
     }
 
     fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &Manager) {
-        let class = if self.multi_line {
-            TextClass::EditMulti
-        } else {
-            TextClass::Edit
-        };
         let highlights = mgr.highlight_state(self.id());
-        draw_handle.edit_box(self.core.rect, highlights);
+        draw_handle.edit_box(self.core.rect, self.error, highlights);
         let props = TextProperties {
-            class,
+            class: self.class(),
             horiz: Align::Begin,
             vert: Align::Begin,
         };
-        let mut text = &self.text;
-        let mut _string;
-        if highlights.char_focus {
-            _string = self.text.clone();
-            _string.push('|');
-            text = &_string;
+        let (sel_start, sel_end) = self.selection();
+        if highlights.char_focus && !self.preedit.is_empty() {
+            let mut text = self.text.clone();
+            text.insert_str(self.cursor, &self.preedit);
+            let end = self.cursor + self.preedit.len();
+            draw_handle.text(self.text_rect, &text, props);
+            let range = self.cursor..end;
+            draw_handle.text_underline(self.text_rect, &text, range, UnderlineStyle::Solid, props);
+            if mgr.caret_visible(self.id()) {
+                draw_handle.text_caret(self.text_rect, &text, end, props);
+            }
+        } else if highlights.char_focus {
+            draw_handle.text_selection(self.text_rect, &self.text, sel_start..sel_end, props);
+            draw_handle.text_selected(self.text_rect, &self.text, sel_start..sel_end, props);
+            (self.spell).draw(draw_handle, self.text_rect, &self.text, 0, props);
+            if mgr.caret_visible(self.id()) {
+                draw_handle.text_caret(self.text_rect, &self.text, self.cursor, props);
+            }
+        } else if self.text.is_empty() {
+            let props = TextProperties {
+                class: TextClass::Placeholder,
+                ..props
+            };
+            draw_handle.text(self.text_rect, &self.placeholder, props);
+        } else {
+            draw_handle.text_selection(self.text_rect, &self.text, sel_start..sel_end, props);
+            draw_handle.text_selected(self.text_rect, &self.text, sel_start..sel_end, props);
+            (self.spell).draw(draw_handle, self.text_rect, &self.text, 0, props);
         }
-        draw_handle.text(self.text_rect, text, props);
     }
 }
 
 impl EditBox<()> {
     /// Construct an `EditBox` with the given inital `text`.
     pub fn new<S: Into<String>>(text: S) -> Self {
+        let text = text.into();
+        let len = text.len();
         EditBox {
             core: Default::default(),
             text_rect: Default::default(),
             editable: true,
             multi_line: false,
-            text: text.into(),
+            text,
+            placeholder: String::new(),
+            cursor: len,
+            anchor: len,
+            press_source: None,
+            preedit: String::new(),
             old_state: None,
             last_edit: LastEdit::None,
+            validator: None,
+            error: false,
+            spell: Default::default(),
             on_activate: (),
         }
     }
@@ -230,8 +831,16 @@ impl EditBox<()> {
             editable: self.editable,
             multi_line: self.multi_line,
             text: self.text,
+            placeholder: self.placeholder,
+            cursor: self.cursor,
+            anchor: self.anchor,
+            press_source: self.press_source,
+            preedit: self.preedit,
             old_state: self.old_state,
             last_edit: self.last_edit,
+            validator: self.validator,
+            error: self.error,
+            spell: self.spell,
             on_activate: f,
         }
     }
@@ -250,81 +859,464 @@ impl<H> EditBox<H> {
         self
     }
 
+    /// Set placeholder text (chain style)
+    ///
+    /// This hint is shown when the text is empty and the `EditBox` does not
+    /// have character focus.
+    pub fn with_placeholder<S: Into<String>>(mut self, placeholder: S) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    /// Set an edit filter (chain style)
+    ///
+    /// The closure `f` is called with the text as it would be after each edit;
+    /// if it returns false, the edit is discarded. For example, a field
+    /// accepting only digits:
+    /// ```
+    /// # use kas::widget::EditBox;
+    /// let edit = EditBox::new("0").with_filter(|s: &str| s.chars().all(|c| c.is_ascii_digit()));
+    /// ```
+    pub fn with_filter<F: Fn(&str) -> bool + 'static>(self, f: F) -> Self {
+        self.with_validator(f)
+    }
+
+    /// Set a [`Validator`] (chain style)
+    ///
+    /// This replaces any existing filter or validator.
+    pub fn with_validator<V: Validator + 'static>(mut self, validator: V) -> Self {
+        self.error = !validator.is_valid(&self.text);
+        self.validator = Some(Rc::new(validator));
+        self
+    }
+
+    /// Set a [`SpellChecker`] (chain style)
+    pub fn with_spell_checker<C: SpellChecker + 'static>(mut self, checker: C) -> Self {
+        self.spell.set_checker(Rc::new(checker), &self.text);
+        self
+    }
+
+    /// Get the misspelled word at byte `index` and suggested replacements
+    ///
+    /// Returns `None` if there is no misspelled word at `index`.
+    pub fn suggestions_at(&self, index: usize) -> Option<(Range<usize>, Vec<String>)> {
+        self.spell.suggestions_at(&self.text, index)
+    }
+
+    /// Replace the text in the given byte `range`
+    ///
+    /// This may be used to apply a spelling suggestion. The edit may be undone
+    /// and is not subject to the [`Validator`]. The cursor is placed after the
+    /// replacement.
+    pub fn replace_range(&mut self, mgr: &mut Manager, range: Range<usize>, replacement: &str) {
+        self.save_state(LastEdit::None);
+        self.text.replace_range(range.clone(), replacement);
+        self.cursor = range.start + replacement.len();
+        self.anchor = self.cursor;
+        if let Some(validator) = self.validator.as_ref() {
+            self.error = !validator.is_valid(&self.text);
+        }
+        self.spell.update(&self.text);
+        mgr.redraw(self.id());
+    }
+
+    /// True if the text is invalid according to the [`Validator`]
+    #[inline]
+    pub fn has_error(&self) -> bool {
+        self.error
+    }
+
+    /// Get the cursor position (byte index)
+    #[inline]
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Get the selected range (byte indices)
+    ///
+    /// If nothing is selected, the range is empty and starts at the cursor.
+    pub fn selection(&self) -> (usize, usize) {
+        if self.anchor <= self.cursor {
+            (self.anchor, self.cursor)
+        } else {
+            (self.cursor, self.anchor)
+        }
+    }
+
+    /// Get the selected text
+    pub fn selected_text(&self) -> &str {
+        let (start, end) = self.selection();
+        &self.text[start..end]
+    }
+
+    /// Select the whole text
+    pub fn select_all(&mut self, mgr: &mut Manager) {
+        self.anchor = 0;
+        self.cursor = self.text.len();
+        mgr.redraw(self.id());
+    }
+
+    fn class(&self) -> TextClass {
+        if self.multi_line {
+            TextClass::EditMulti
+        } else {
+            TextClass::Edit
+        }
+    }
+
+    fn save_state(&mut self, edit: LastEdit) {
+        if edit == LastEdit::None || self.last_edit != edit {
+            self.old_state = Some((self.text.clone(), self.cursor));
+        }
+        self.last_edit = edit;
+    }
+
+    /// Delete the selection, returning true if anything was deleted
+    fn delete_selection(&mut self) -> bool {
+        let (start, end) = self.selection();
+        self.text.replace_range(start..end, "");
+        self.cursor = start;
+        self.anchor = start;
+        start != end
+    }
+
+    /// Insert `s`, subject to [`Validator::transform`]
+    fn insert_str(&mut self, s: &str) {
+        let mut s = s.to_string();
+        if let Some(validator) = self.validator.as_ref() {
+            validator.transform(&mut s);
+        }
+        self.delete_selection();
+        self.text.insert_str(self.cursor, &s);
+        self.cursor += s.len();
+        self.anchor = self.cursor;
+    }
+
+    /// Find the text index nearest to `coord`
+    ///
+    /// This measures text using the toolkit's [`SizeHandle`], if available.
+    fn index_at(&self, mgr: &mut Manager, coord: Coord) -> Option<usize> {
+        let class = self.class();
+        let text = &self.text;
+        let rel = coord - self.text_rect.pos;
+        mgr.size_handle(|size_handle| text_index(size_handle, text, class, rel))
+    }
+
+    /// Register the caret position with the event manager
+    fn update_caret(&self, mgr: &mut Manager) {
+        if !mgr.char_focus(self.id()) {
+            return;
+        }
+        let class = self.class();
+        let text = &self.text;
+        let line_start = text[..self.cursor].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let line = text[..line_start].matches('\n').count() as u32;
+        let prefix = format!("{}{}|", &text[line_start..self.cursor], self.preedit);
+        let caret = mgr.size_handle(|size_handle| {
+            let marker = size_handle.text_size("|", class, None).0;
+            let x = size_handle.text_size(&prefix, class, None).0 - marker;
+            let line_height = size_handle.line_height(class);
+            (Coord(x as i32, (line * line_height) as i32), line_height)
+        });
+        if let Some((offset, line_height)) = caret {
+            let rect = Rect::new(self.text_rect.pos + offset, Size(1, line_height));
+            mgr.set_caret(self.id(), rect);
+        }
+    }
+
+    /// Handle a control key, returning false if not used
+    fn control_key(&mut self, mgr: &mut Manager, key: ControlKey) -> bool {
+        let modifiers = mgr.modifiers();
+        let pos = self.cursor;
+        let state = (self.cursor, self.anchor);
+        self.cursor = match key {
+            ControlKey::Left if modifiers.ctrl => prev_word(&self.text, pos),
+            ControlKey::Left => prev_char(&self.text, pos),
+            ControlKey::Right if modifiers.ctrl => next_word(&self.text, pos),
+            ControlKey::Right => next_char(&self.text, pos),
+            ControlKey::Home => 0,
+            ControlKey::End => self.text.len(),
+            _ => return false,
+        };
+        if !modifiers.shift {
+            self.anchor = self.cursor;
+        }
+        self.last_edit = LastEdit::None;
+        if (self.cursor, self.anchor) != state {
+            mgr.redraw(self.id());
+        }
+        true
+    }
+
     fn received_char(&mut self, mgr: &mut Manager, c: char) -> bool {
+        let prev = self.prev_state();
+        if self.edit(mgr, c) {
+            return true;
+        }
+        self.finish_edit(mgr, prev);
+        false
+    }
+
+    /// Insert dropped text at `coord`
+    fn drop_text(&mut self, mgr: &mut Manager, text: &str, coord: Coord) {
+        let prev = self.prev_state();
+        self.save_state(LastEdit::Paste);
+        if let Some(index) = self.index_at(mgr, coord) {
+            self.cursor = index;
+            self.anchor = index;
+        }
+        self.insert_str(single_line(text));
+        self.finish_edit(mgr, prev);
+    }
+
+    /// Insert text committed by an input method
+    fn commit_text(&mut self, mgr: &mut Manager, text: &str) {
         if !self.editable {
-            return false;
+            return;
+        }
+        let prev = self.prev_state();
+        self.save_state(LastEdit::Insert);
+        self.insert_str(if self.multi_line {
+            text
+        } else {
+            single_line(text)
+        });
+        self.finish_edit(mgr, prev);
+    }
+
+    /// State to restore should the validator reject an edit
+    fn prev_state(&self) -> Option<(String, usize, usize)> {
+        self.validator
+            .as_ref()
+            .map(|_| (self.text.clone(), self.cursor, self.anchor))
+    }
+
+    /// Validate an edit, reverting to `prev` if not accepted
+    fn finish_edit(&mut self, mgr: &mut Manager, prev: Option<(String, usize, usize)>) {
+        if let (Some(validator), Some(prev)) = (self.validator.as_ref(), prev) {
+            if self.text != prev.0 && !validator.accept(&self.text) {
+                self.text = prev.0;
+                self.cursor = prev.1;
+                self.anchor = prev.2;
+            }
+            self.error = !validator.is_valid(&self.text);
         }
+        self.spell.update(&self.text);
+        mgr.redraw(self.id());
+    }
 
-        // TODO: Text selection and editing (see Unicode std. section 5.11)
-        // Note that it may make sense to implement text shaping first.
-        // For now we just filter control characters and append the rest.
-        if c < '\u{20}' || (c >= '\u{7f}' && c <= '\u{9f}') {
-            match c {
-                '\u{03}' /* copy */ => {
-                    // we don't yet have selection support, so just copy everything
-                    mgr.set_clipboard(self.text.clone());
+    /// Apply an edit, returning true on activation
+    fn edit(&mut self, mgr: &mut Manager, c: char) -> bool {
+        match c {
+            '\u{01}' /* select all */ => {
+                self.anchor = 0;
+                self.cursor = self.text.len();
+            }
+            '\u{03}' /* copy */ => {
+                if self.cursor != self.anchor {
+                    mgr.set_clipboard(self.selected_text().to_string());
                 }
-                '\u{08}' /* backspace */  => {
-                    if self.last_edit != LastEdit::Backspace {
-                        self.old_state = Some(self.text.clone());
-                        self.last_edit = LastEdit::Backspace;
-                    }
-                    self.text.pop();
+            }
+            '\u{0D}' /* carriage return (\r) */ => return true,
+            _ if !self.editable => return false,
+            '\u{08}' /* backspace */  => {
+                self.save_state(LastEdit::Backspace);
+                if !self.delete_selection() && self.cursor > 0 {
+                    let start = if mgr.modifiers().ctrl {
+                        prev_word(&self.text, self.cursor)
+                    } else {
+                        prev_char(&self.text, self.cursor)
+                    };
+                    self.text.replace_range(start..self.cursor, "");
+                    self.cursor = start;
+                    self.anchor = start;
                 }
-                '\u{09}' /* tab */ => (),
-                '\u{0A}' /* line feed */ => (),
-                '\u{0B}' /* vertical tab */ => (),
-                '\u{0C}' /* form feed */ => (),
-                '\u{0D}' /* carriage return (\r) */ => return true,
-                '\u{16}' /* paste */ => {
-                    if self.last_edit != LastEdit::Paste {
-                        self.old_state = Some(self.text.clone());
-                        self.last_edit = LastEdit::Paste;
-                    }
-                    if let Some(content) = mgr.get_clipboard() {
-                        // We cut the content short on control characters and
-                        // ignore them (preventing line-breaks and ignoring any
-                        // actions such as recursive-paste).
-                        let mut end = content.len();
-                        for (i, b) in content.as_bytes().iter().cloned().enumerate() {
-                            if b < 0x20 || (b >= 0x7f && b <= 0x9f) {
-                                end = i;
-                                break;
+            }
+            '\u{16}' /* paste */ => {
+                self.save_state(LastEdit::Paste);
+                if let Some(content) = mgr.get_clipboard() {
+                    self.insert_str(single_line(&content));
+                }
+            }
+            '\u{18}' /* cut */ => {
+                if self.cursor != self.anchor {
+                    self.save_state(LastEdit::None);
+                    mgr.set_clipboard(self.selected_text().to_string());
+                    self.delete_selection();
+                }
+            }
+            '\u{1A}' /* undo and redo */ => {
+                // TODO: maintain full edit history (externally?)
+                // NOTE: undo *and* redo shortcuts map to this control char
+                if let Some((text, cursor)) = self.old_state.as_mut() {
+                    std::mem::swap(text, &mut self.text);
+                    std::mem::swap(cursor, &mut self.cursor);
+                    self.anchor = self.cursor;
+                    self.last_edit = LastEdit::None;
+                }
+            }
+            '\u{7f}' /* delete */ => {
+                self.save_state(LastEdit::Delete);
+                if !self.delete_selection() {
+                    let end = next_char(&self.text, self.cursor);
+                    self.text.replace_range(self.cursor..end, "");
+                }
+            }
+            c if c < '\u{20}' || (c >= '\u{7f}' && c <= '\u{9f}') => (),
+            c => {
+                self.save_state(LastEdit::Insert);
+                let mut buf = [0; 4];
+                self.insert_str(c.encode_utf8(&mut buf));
+            }
+        }
+        false
+    }
+
+    /// Handle an action, returning true on activation
+    fn handle_common(&mut self, mgr: &mut Manager, action: Action) -> Result<bool, Action> {
+        match action {
+            Action::Activate => {
+                mgr.request_char_focus(self.id());
+                Ok(false)
+            }
+            Action::ReceivedCharacter(c) => Ok(self.received_char(mgr, c)),
+            Action::ImePreedit(text) => {
+                self.preedit = text;
+                mgr.redraw(self.id());
+                Ok(false)
+            }
+            Action::ImeCommit(text) => {
+                self.preedit.clear();
+                self.commit_text(mgr, &text);
+                Ok(false)
+            }
+            Action::ControlKey(key) => {
+                if self.control_key(mgr, key) {
+                    Ok(false)
+                } else {
+                    Err(Action::ControlKey(key))
+                }
+            }
+            a => Err(a),
+        }
+    }
+
+    /// Handle press events, returning other events
+    fn handle_press(&mut self, mgr: &mut Manager, event: Event) -> Result<(), Event> {
+        match event {
+            Event::PressStart {
+                source,
+                coord,
+                clicks,
+            } if source.is_primary() => {
+                if mgr.request_press_grab(source, self, coord, None) {
+                    self.press_source = None;
+                    mgr.request_char_focus(self.id());
+                    if let Some(index) = self.index_at(mgr, coord) {
+                        if clicks >= 2 {
+                            // Double-click selects a word, triple-click a
+                            // line; these do not extend on drag
+                            let (start, end) = if clicks == 2 {
+                                word_range(&self.text, index)
+                            } else {
+                                line_range(&self.text, index)
+                            };
+                            self.anchor = start;
+                            self.cursor = end;
+                        } else {
+                            self.press_source = Some(source);
+                            self.cursor = index;
+                            if !mgr.modifiers().shift {
+                                self.anchor = index;
                             }
                         }
-                        self.text.push_str(&content[0..end]);
-                    }
-                }
-                '\u{1A}' /* undo and redo */ => {
-                    // TODO: maintain full edit history (externally?)
-                    // NOTE: undo *and* redo shortcuts map to this control char
-                    if let Some(state) = self.old_state.as_mut() {
-                        std::mem::swap(state, &mut self.text);
                         self.last_edit = LastEdit::None;
                     }
                 }
-                '\u{1B}' /* escape */ => (),
-                '\u{7f}' /* delete */ => {
-                    if self.last_edit != LastEdit::Clear {
-                        self.old_state = Some(self.text.clone());
-                        self.last_edit = LastEdit::Clear;
+                Ok(())
+            }
+            Event::PressMove { source, coord, .. } if Some(source) == self.press_source => {
+                if let Some(index) = self.index_at(mgr, coord) {
+                    if index != self.cursor {
+                        self.cursor = index;
+                        mgr.redraw(self.id());
                     }
-                    self.text.clear();
                 }
-                _ => (),
-            };
-        } else {
-            if self.last_edit != LastEdit::Insert {
-                self.old_state = Some(self.text.clone());
-                self.last_edit = LastEdit::Insert;
+                Ok(())
+            }
+            Event::PressEnd { source, .. } if Some(source) == self.press_source => {
+                self.press_source = None;
+                Ok(())
             }
-            self.text.push(c);
+            Event::Drop { data, coord } if self.editable => match data.text() {
+                Some(text) => {
+                    self.drop_text(mgr, text, coord);
+                    Ok(())
+                }
+                None => Err(Event::Drop { data, coord }),
+            },
+            e => Err(e),
         }
-        mgr.redraw(self.id());
-        false
     }
 }
 
+/// Find the index in `text` nearest to `rel`, relative to the text position
+///
+/// Explicit line breaks are respected for multi-line text classes; other
+/// wrapping is not accounted for.
+fn text_index(size_handle: &mut dyn SizeHandle, text: &str, class: TextClass, rel: Coord) -> usize {
+    let (start, end) = match class {
+        TextClass::Label | TextClass::EditMulti | TextClass::Monospace => {
+            let line_height = size_handle.line_height(class).max(1) as i32;
+            let line = (rel.1.max(0) / line_height) as usize;
+            let start = match line {
+                0 => 0,
+                n => text
+                    .match_indices('\n')
+                    .nth(n - 1)
+                    .map(|(i, _)| i + 1)
+                    .unwrap_or(text.len()),
+            };
+            let end = text[start..].find('\n').map(|i| start + i);
+            (start, end.unwrap_or(text.len()))
+        }
+        _ => (0, text.len()),
+    };
+
+    // Trailing white-space is not measured, hence we append a marker
+    // and subtract its width.
+    let marker = size_handle.text_size("|", class, None).0 as i32;
+    let mut best = (start, rel.0.abs());
+    for i in text[start..end]
+        .char_indices()
+        .skip(1)
+        .map(|(i, _)| start + i)
+        .chain(std::iter::once(end))
+        .filter(|i| *i > start)
+    {
+        let prefix = format!("{}|", &text[start..i]);
+        let x = size_handle.text_size(&prefix, class, None).0 as i32 - marker;
+        let dist = (x - rel.0).abs();
+        if dist < best.1 {
+            best = (i, dist);
+        } else if x > rel.0 {
+            break;
+        }
+    }
+    best.0
+}
+
+/// Cut text short on control characters
+///
+/// This prevents line-breaks and ignores any actions such as recursive-paste.
+fn single_line(text: &str) -> &str {
+    let end = (text.char_indices())
+        .find(|(_, c)| c.is_control())
+        .map(|(i, _)| i)
+        .unwrap_or(text.len());
+    &text[0..end]
+}
+
 impl<H> HasText for EditBox<H> {
     fn get_text(&self) -> &str {
         &self.text
@@ -332,6 +1324,12 @@ impl<H> HasText for EditBox<H> {
 
     fn set_string(&mut self, mgr: &mut Manager, text: String) {
         self.text = text;
+        self.cursor = self.text.len();
+        self.anchor = self.cursor;
+        if let Some(validator) = self.validator.as_ref() {
+            self.error = !validator.is_valid(&self.text);
+        }
+        self.spell.update(&self.text);
         mgr.redraw(self.id());
     }
 }
@@ -349,48 +1347,40 @@ impl<H> Editable for EditBox<H> {
 impl Handler for EditBox<()> {
     type Msg = VoidMsg;
 
-    #[inline]
-    fn activation_via_press(&self) -> bool {
-        true
-    }
-
     fn handle_action(&mut self, mgr: &mut Manager, action: Action) -> Response<VoidMsg> {
-        match action {
-            Action::Activate => {
-                mgr.request_char_focus(self.id());
-                Response::None
-            }
-            Action::ReceivedCharacter(c) => {
-                self.received_char(mgr, c);
-                Response::None
-            }
-            a @ _ => Response::unhandled_action(a),
+        match self.handle_common(mgr, action) {
+            Ok(_) => Response::None,
+            Err(a) => Response::unhandled_action(a),
         }
     }
+
+    fn handle(&mut self, mgr: &mut Manager, _: WidgetId, event: Event) -> Response<VoidMsg> {
+        let response = match self.handle_press(mgr, event) {
+            Ok(()) => Response::None,
+            Err(e) => Manager::handle_generic(self, mgr, e),
+        };
+        self.update_caret(mgr);
+        response
+    }
 }
 
 impl<M, H: Fn(&str) -> M> Handler for EditBox<H> {
     type Msg = M;
 
-    #[inline]
-    fn activation_via_press(&self) -> bool {
-        true
-    }
-
     fn handle_action(&mut self, mgr: &mut Manager, action: Action) -> Response<M> {
-        match action {
-            Action::Activate => {
-                mgr.request_char_focus(self.id());
-                Response::None
-            }
-            Action::ReceivedCharacter(c) => {
-                if self.received_char(mgr, c) {
-                    ((self.on_activate)(&self.text)).into()
-                } else {
-                    Response::None
-                }
-            }
-            a @ _ => Response::unhandled_action(a),
+        match self.handle_common(mgr, action) {
+            Ok(true) => ((self.on_activate)(&self.text)).into(),
+            Ok(false) => Response::None,
+            Err(a) => Response::unhandled_action(a),
         }
     }
+
+    fn handle(&mut self, mgr: &mut Manager, _: WidgetId, event: Event) -> Response<M> {
+        let response = match self.handle_press(mgr, event) {
+            Ok(()) => Response::None,
+            Err(e) => Manager::handle_generic(self, mgr, e),
+        };
+        self.update_caret(mgr);
+        response
+    }
 }
@@ -0,0 +1,648 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Multi-line text editor
+
+use std::fmt::{self, Debug};
+use std::ops::Range;
+use std::rc::Rc;
+
+use super::text::{next_char, next_word, prev_char, prev_word, LastEdit, SpellCheck};
+use super::SpellChecker;
+use crate::class::{Editable, HasText};
+use crate::event::{
+    Action, ControlKey, CursorIcon, Event, Handler, Manager, Response, ScrollDelta, VoidMsg,
+};
+use crate::geom::{Coord, Rect, Size};
+use crate::layout::{AxisInfo, SizeRules, StretchPolicy};
+use crate::macros::Widget;
+use crate::theme::{DrawHandle, SizeHandle, TextClass, TextProperties, UnderlineStyle};
+use crate::{Align, AlignHints, CoreData, Layout, Widget, WidgetCore, WidgetId};
+
+/// A multi-line text editor
+///
+/// Text is wrapped to the available width. The cursor may be moved with the
+/// arrow keys (by word when `Ctrl` is held), `Home`/`End` (to the start/end of
+/// the text when `Ctrl` is held) and `PageUp`/`PageDown`. Holding `Shift`
+/// while moving the cursor selects text. Standard shortcuts for select-all,
+/// cut, copy, paste and undo are supported.
+///
+/// Vertical navigation and scrolling operate on logical lines (i.e. lines
+/// separated by a line break), not on lines produced by wrapping.
+///
+/// By default this widget does not emit messages; use [`TextArea::on_change`]
+/// to receive notification of each edit.
+///
+/// A [`SpellChecker`] may be set via [`TextArea::with_spell_checker`]; see
+/// also [`TextArea::suggestions_at`] and [`TextArea::replace_range`].
+#[derive(Clone, Default, Widget)]
+pub struct TextArea<H: 'static> {
+    #[core]
+    core: CoreData,
+    text_rect: Rect,
+    editable: bool,
+    lines: (u32, u32),
+    text: String,
+    /// Cursor position (byte index)
+    cursor: usize,
+    /// Selection anchor; selection is empty when `anchor == cursor`
+    anchor: usize,
+    /// Index of the first visible line
+    first_line: usize,
+    visible_lines: usize,
+    line_height: u32,
+    /// Input method pre-edit text, displayed at the cursor
+    preedit: String,
+    old_state: Option<(String, usize)>,
+    last_edit: LastEdit,
+    spell: SpellCheck,
+    on_change: H,
+}
+
+impl<H> Debug for TextArea<H> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "TextArea {{ core: {:?}, editable: {:?}, text: {:?}, cursor: {:?}, anchor: {:?}, ... }}",
+            self.core, self.editable, self.text, self.cursor, self.anchor
+        )
+    }
+}
+
+impl<H: 'static> Widget for TextArea<H> {
+    fn allow_focus(&self) -> bool {
+        true
+    }
+
+    fn cursor_icon(&self) -> CursorIcon {
+        CursorIcon::Text
+    }
+}
+
+impl<H: 'static> Layout for TextArea<H> {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let sides = size_handle.edit_surround();
+        let margin = size_handle.inner_margin();
+        let frame = SizeRules::fixed(axis.extract_size(sides.0 + sides.1 + margin));
+        if axis.is_horizontal() {
+            frame + size_handle.text_bound(&self.text, TextClass::EditMulti, axis)
+        } else {
+            let line_height = size_handle.line_height(TextClass::EditMulti);
+            let min = line_height * self.lines.0;
+            let ideal = line_height * self.lines.1;
+            frame + SizeRules::new(min, ideal, StretchPolicy::LowUtility)
+        }
+    }
+
+    fn set_rect(&mut self, size_handle: &mut dyn SizeHandle, rect: Rect, _: AlignHints) {
+        let sides = size_handle.edit_surround();
+        self.text_rect = Rect {
+            pos: rect.pos + sides.0,
+            size: rect.size - (sides.0 + sides.1),
+        };
+        self.core.rect = rect;
+        self.line_height = size_handle.line_height(TextClass::EditMulti).max(1);
+        self.visible_lines = ((self.text_rect.size.1 / self.line_height) as usize).max(1);
+        self.ensure_cursor_visible();
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &Manager) {
+        let highlights = mgr.highlight_state(self.id());
+        draw_handle.edit_box(self.core.rect, false, highlights);
+        let props = TextProperties {
+            class: TextClass::EditMulti,
+            horiz: Align::Begin,
+            vert: Align::Begin,
+        };
+
+        // Draw from the first visible line
+        let start = self.line_start_of_index(self.first_line);
+        let text = &self.text[start..];
+        let (sel_start, sel_end) = self.selection();
+        let sel_start = sel_start.saturating_sub(start);
+        let sel_end = sel_end.saturating_sub(start);
+        let show_caret = mgr.caret_visible(self.id()) && self.cursor >= start;
+
+        let rect = self.text_rect;
+        if highlights.char_focus && !self.preedit.is_empty() && self.cursor >= start {
+            let cursor = self.cursor - start;
+            let mut text = text.to_string();
+            text.insert_str(cursor, &self.preedit);
+            let end = cursor + self.preedit.len();
+            draw_handle.clip_region(rect, Coord::ZERO, &mut |handle| {
+                handle.text(rect, &text, props);
+                handle.text_underline(rect, &text, cursor..end, UnderlineStyle::Solid, props);
+                if show_caret {
+                    handle.text_caret(rect, &text, end, props);
+                }
+            });
+            return;
+        }
+        draw_handle.clip_region(rect, Coord::ZERO, &mut |handle| {
+            handle.text_selection(rect, text, sel_start..sel_end, props);
+            handle.text_selected(rect, text, sel_start..sel_end, props);
+            (self.spell).draw(handle, rect, text, start, props);
+            if show_caret {
+                handle.text_caret(rect, text, self.cursor - start, props);
+            }
+        });
+    }
+}
+
+impl TextArea<()> {
+    /// Construct a `TextArea` with the given inital `text`.
+    pub fn new<S: Into<String>>(text: S) -> Self {
+        let text = text.into();
+        let len = text.len();
+        TextArea {
+            core: Default::default(),
+            text_rect: Default::default(),
+            editable: true,
+            lines: (3, 8),
+            text,
+            cursor: len,
+            anchor: len,
+            first_line: 0,
+            visible_lines: 1,
+            line_height: 1,
+            preedit: String::new(),
+            old_state: None,
+            last_edit: LastEdit::None,
+            spell: Default::default(),
+            on_change: (),
+        }
+    }
+
+    /// Set the event handler to be called on change.
+    ///
+    /// The closure `f` is called with the new text whenever the text is
+    /// edited. Its result is returned from the event handler.
+    ///
+    /// Technically, this consumes `self` and reconstructs another `TextArea`
+    /// with a different parameterisation.
+    pub fn on_change<R, H: Fn(&str) -> R>(self, f: H) -> TextArea<H> {
+        TextArea {
+            core: self.core,
+            text_rect: self.text_rect,
+            editable: self.editable,
+            lines: self.lines,
+            text: self.text,
+            cursor: self.cursor,
+            anchor: self.anchor,
+            first_line: self.first_line,
+            visible_lines: self.visible_lines,
+            line_height: self.line_height,
+            preedit: self.preedit,
+            old_state: self.old_state,
+            last_edit: self.last_edit,
+            spell: self.spell,
+            on_change: f,
+        }
+    }
+}
+
+impl<H> TextArea<H> {
+    /// Set whether this `TextArea` is editable.
+    pub fn editable(mut self, editable: bool) -> Self {
+        self.editable = editable;
+        self
+    }
+
+    /// Set the minimum and ideal number of visible lines
+    pub fn with_lines(mut self, min: u32, ideal: u32) -> Self {
+        self.lines = (min, ideal.max(min));
+        self
+    }
+
+    /// Set a [`SpellChecker`] (chain style)
+    pub fn with_spell_checker<C: SpellChecker + 'static>(mut self, checker: C) -> Self {
+        self.spell.set_checker(Rc::new(checker), &self.text);
+        self
+    }
+
+    /// Get the misspelled word at byte `index` and suggested replacements
+    ///
+    /// Returns `None` if there is no misspelled word at `index`.
+    pub fn suggestions_at(&self, index: usize) -> Option<(Range<usize>, Vec<String>)> {
+        self.spell.suggestions_at(&self.text, index)
+    }
+
+    /// Replace the text in the given byte `range`
+    ///
+    /// This may be used to apply a spelling suggestion. The edit may be undone.
+    /// The cursor is placed after the replacement.
+    pub fn replace_range(&mut self, mgr: &mut Manager, range: Range<usize>, replacement: &str) {
+        self.save_state(LastEdit::None);
+        self.text.replace_range(range.clone(), replacement);
+        self.cursor = range.start + replacement.len();
+        self.anchor = self.cursor;
+        self.spell.update(&self.text);
+        self.ensure_cursor_visible();
+        mgr.redraw(self.id());
+    }
+
+    /// Get the cursor position (byte index)
+    #[inline]
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Get the selected range (byte indices)
+    ///
+    /// If nothing is selected, the range is empty and starts at the cursor.
+    pub fn selection(&self) -> (usize, usize) {
+        if self.anchor <= self.cursor {
+            (self.anchor, self.cursor)
+        } else {
+            (self.cursor, self.anchor)
+        }
+    }
+
+    /// Get the selected text
+    pub fn selected_text(&self) -> &str {
+        let (start, end) = self.selection();
+        &self.text[start..end]
+    }
+
+    /// Select the whole text
+    pub fn select_all(&mut self, mgr: &mut Manager) {
+        self.anchor = 0;
+        self.cursor = self.text.len();
+        mgr.redraw(self.id());
+    }
+
+    fn save_state(&mut self, edit: LastEdit) {
+        if edit == LastEdit::None || self.last_edit != edit {
+            self.old_state = Some((self.text.clone(), self.cursor));
+        }
+        self.last_edit = edit;
+    }
+
+    /// Delete the selection, returning true if anything was deleted
+    fn delete_selection(&mut self) -> bool {
+        let (start, end) = self.selection();
+        self.text.replace_range(start..end, "");
+        self.cursor = start;
+        self.anchor = start;
+        start != end
+    }
+
+    fn insert_str(&mut self, s: &str) {
+        self.delete_selection();
+        self.text.insert_str(self.cursor, s);
+        self.cursor += s.len();
+        self.anchor = self.cursor;
+    }
+
+    fn line_start(&self, pos: usize) -> usize {
+        self.text[..pos].rfind('\n').map(|i| i + 1).unwrap_or(0)
+    }
+
+    fn line_end(&self, pos: usize) -> usize {
+        self.text[pos..]
+            .find('\n')
+            .map(|i| pos + i)
+            .unwrap_or(self.text.len())
+    }
+
+    /// Index of the logical line containing `pos`
+    fn line_index(&self, pos: usize) -> usize {
+        self.text[..pos].matches('\n').count()
+    }
+
+    /// Register the caret position with the event manager
+    fn update_caret(&self, mgr: &mut Manager) {
+        if !mgr.char_focus(self.id()) {
+            return;
+        }
+        let start = self.line_start(self.cursor);
+        let line = self.line_index(self.cursor).saturating_sub(self.first_line) as u32;
+        let prefix = format!("{}{}|", &self.text[start..self.cursor], self.preedit);
+        let x = mgr.size_handle(|size_handle| {
+            let class = TextClass::EditMulti;
+            let marker = size_handle.text_size("|", class, None).0;
+            size_handle.text_size(&prefix, class, None).0 - marker
+        });
+        if let Some(x) = x {
+            let offset = Coord(x as i32, (line * self.line_height) as i32);
+            let rect = Rect::new(self.text_rect.pos + offset, Size(1, self.line_height));
+            mgr.set_caret(self.id(), rect);
+        }
+    }
+
+    /// Start of logical line `index`, or end of text if out of range
+    fn line_start_of_index(&self, index: usize) -> usize {
+        if index == 0 {
+            return 0;
+        }
+        self.text
+            .match_indices('\n')
+            .nth(index - 1)
+            .map(|(i, _)| i + 1)
+            .unwrap_or(self.text.len())
+    }
+
+    fn num_lines(&self) -> usize {
+        self.text.matches('\n').count() + 1
+    }
+
+    /// Move `lines` logical lines from `pos`, preserving the column
+    fn move_lines(&self, pos: usize, lines: isize) -> usize {
+        let start = self.line_start(pos);
+        let column = self.text[start..pos].chars().count();
+        let line = self.line_index(pos) as isize + lines;
+        if line < 0 {
+            return 0;
+        } else if line as usize >= self.num_lines() {
+            return self.text.len();
+        }
+        let start = self.line_start_of_index(line as usize);
+        let end = self.line_end(start);
+        self.text[start..end]
+            .char_indices()
+            .nth(column)
+            .map(|(i, _)| start + i)
+            .unwrap_or(end)
+    }
+
+    fn ensure_cursor_visible(&mut self) {
+        let line = self.line_index(self.cursor);
+        if line < self.first_line {
+            self.first_line = line;
+        } else if line >= self.first_line + self.visible_lines {
+            self.first_line = line + 1 - self.visible_lines;
+        }
+    }
+
+    fn scroll(&mut self, mgr: &mut Manager, lines: isize) {
+        let max = self.num_lines().saturating_sub(1) as isize;
+        let first = (self.first_line as isize - lines).max(0).min(max) as usize;
+        if first != self.first_line {
+            self.first_line = first;
+            mgr.redraw(self.id());
+        }
+    }
+
+    fn control_key(&mut self, mgr: &mut Manager, key: ControlKey) {
+        let modifiers = mgr.modifiers();
+        let pos = self.cursor;
+        let state = (self.cursor, self.anchor, self.first_line);
+        let page = self.visible_lines.max(1) as isize;
+        self.cursor = match key {
+            ControlKey::Left if modifiers.ctrl => prev_word(&self.text, pos),
+            ControlKey::Left => prev_char(&self.text, pos),
+            ControlKey::Right if modifiers.ctrl => next_word(&self.text, pos),
+            ControlKey::Right => next_char(&self.text, pos),
+            ControlKey::Up => self.move_lines(pos, -1),
+            ControlKey::Down => self.move_lines(pos, 1),
+            ControlKey::Home if modifiers.ctrl => 0,
+            ControlKey::Home => self.line_start(pos),
+            ControlKey::End if modifiers.ctrl => self.text.len(),
+            ControlKey::End => self.line_end(pos),
+            ControlKey::PageUp => self.move_lines(pos, -page),
+            ControlKey::PageDown => self.move_lines(pos, page),
+        };
+        if !modifiers.shift {
+            self.anchor = self.cursor;
+        }
+        self.last_edit = LastEdit::None;
+        self.ensure_cursor_visible();
+        if (self.cursor, self.anchor, self.first_line) != state {
+            mgr.redraw(self.id());
+        }
+    }
+
+    /// Handle a received character, returning true if the text changed
+    fn received_char(&mut self, mgr: &mut Manager, c: char) -> bool {
+        let changed = match c {
+            '\u{01}' /* select all */ => {
+                self.anchor = 0;
+                self.cursor = self.text.len();
+                false
+            }
+            '\u{03}' /* copy */ => {
+                if self.cursor != self.anchor {
+                    mgr.set_clipboard(self.selected_text().to_string());
+                }
+                false
+            }
+            _ if !self.editable => false,
+            '\u{08}' /* backspace */ => {
+                self.save_state(LastEdit::Backspace);
+                if !self.delete_selection() && self.cursor > 0 {
+                    let start = if mgr.modifiers().ctrl {
+                        prev_word(&self.text, self.cursor)
+                    } else {
+                        prev_char(&self.text, self.cursor)
+                    };
+                    self.text.replace_range(start..self.cursor, "");
+                    self.cursor = start;
+                    self.anchor = start;
+                }
+                true
+            }
+            '\u{09}' /* tab */ => {
+                self.save_state(LastEdit::Insert);
+                self.insert_str("\t");
+                true
+            }
+            '\u{0A}' /* line feed */ | '\u{0D}' /* carriage return (\r) */ => {
+                self.save_state(LastEdit::Insert);
+                self.insert_str("\n");
+                true
+            }
+            '\u{16}' /* paste */ => {
+                self.save_state(LastEdit::Paste);
+                if let Some(content) = mgr.get_clipboard() {
+                    self.insert_str(&clean_text(&content));
+                }
+                true
+            }
+            '\u{18}' /* cut */ => {
+                if self.cursor != self.anchor {
+                    self.save_state(LastEdit::None);
+                    mgr.set_clipboard(self.selected_text().to_string());
+                    self.delete_selection();
+                    true
+                } else {
+                    false
+                }
+            }
+            '\u{1A}' /* undo and redo */ => {
+                // NOTE: undo *and* redo shortcuts map to this control char
+                if let Some((text, cursor)) = self.old_state.as_mut() {
+                    std::mem::swap(text, &mut self.text);
+                    std::mem::swap(cursor, &mut self.cursor);
+                    self.anchor = self.cursor;
+                    self.last_edit = LastEdit::None;
+                    true
+                } else {
+                    false
+                }
+            }
+            '\u{7f}' /* delete */ => {
+                self.save_state(LastEdit::Delete);
+                if !self.delete_selection() {
+                    let end = next_char(&self.text, self.cursor);
+                    self.text.replace_range(self.cursor..end, "");
+                }
+                true
+            }
+            c if c < '\u{20}' || ('\u{7f}'..='\u{9f}').contains(&c) => false,
+            c => {
+                self.save_state(LastEdit::Insert);
+                let mut buf = [0; 4];
+                self.insert_str(c.encode_utf8(&mut buf));
+                true
+            }
+        };
+        if changed {
+            self.spell.update(&self.text);
+        }
+        self.ensure_cursor_visible();
+        mgr.redraw(self.id());
+        changed
+    }
+
+    /// Insert dropped text at the cursor, returning other events
+    fn handle_drop(&mut self, mgr: &mut Manager, event: Event) -> Result<(), Event> {
+        match event {
+            Event::Drop { data, coord } if self.editable => match data.text() {
+                Some(text) => {
+                    self.save_state(LastEdit::Paste);
+                    self.insert_str(&clean_text(text));
+                    self.spell.update(&self.text);
+                    self.ensure_cursor_visible();
+                    mgr.redraw(self.id());
+                    Ok(())
+                }
+                None => Err(Event::Drop { data, coord }),
+            },
+            e => Err(e),
+        }
+    }
+
+    /// Handle an action, returning true if the text changed
+    fn handle_common(&mut self, mgr: &mut Manager, action: Action) -> bool {
+        match action {
+            Action::Activate => {
+                mgr.request_char_focus(self.id());
+                false
+            }
+            Action::ReceivedCharacter(c) => self.received_char(mgr, c),
+            Action::ImePreedit(text) => {
+                self.preedit = text;
+                mgr.redraw(self.id());
+                false
+            }
+            Action::ImeCommit(text) => {
+                self.preedit.clear();
+                if self.editable {
+                    self.save_state(LastEdit::Insert);
+                    self.insert_str(&clean_text(&text));
+                    self.spell.update(&self.text);
+                }
+                self.ensure_cursor_visible();
+                mgr.redraw(self.id());
+                self.editable
+            }
+            Action::ControlKey(key) => {
+                self.control_key(mgr, key);
+                false
+            }
+            Action::Scroll(delta) => {
+                let lines = match delta {
+                    ScrollDelta::LineDelta(_, y) => y.round() as isize,
+                    ScrollDelta::PixelDelta(d) => (d.1 / self.line_height as i32) as isize,
+                };
+                self.scroll(mgr, lines);
+                false
+            }
+        }
+    }
+}
+
+impl<H> HasText for TextArea<H> {
+    fn get_text(&self) -> &str {
+        &self.text
+    }
+
+    fn set_string(&mut self, mgr: &mut Manager, text: String) {
+        self.text = text;
+        self.cursor = self.text.len();
+        self.anchor = self.cursor;
+        self.first_line = 0;
+        self.spell.update(&self.text);
+        self.ensure_cursor_visible();
+        mgr.redraw(self.id());
+    }
+}
+
+impl<H> Editable for TextArea<H> {
+    fn is_editable(&self) -> bool {
+        self.editable
+    }
+
+    fn set_editable(&mut self, editable: bool) {
+        self.editable = editable;
+    }
+}
+
+impl Handler for TextArea<()> {
+    type Msg = VoidMsg;
+
+    #[inline]
+    fn activation_via_press(&self) -> bool {
+        true
+    }
+
+    fn handle_action(&mut self, mgr: &mut Manager, action: Action) -> Response<VoidMsg> {
+        self.handle_common(mgr, action);
+        Response::None
+    }
+
+    fn handle(&mut self, mgr: &mut Manager, _: WidgetId, event: Event) -> Response<VoidMsg> {
+        let response = match self.handle_drop(mgr, event) {
+            Ok(()) => Response::None,
+            Err(e) => Manager::handle_generic(self, mgr, e),
+        };
+        self.update_caret(mgr);
+        response
+    }
+}
+
+impl<M, H: Fn(&str) -> M> Handler for TextArea<H> {
+    type Msg = M;
+
+    #[inline]
+    fn activation_via_press(&self) -> bool {
+        true
+    }
+
+    fn handle_action(&mut self, mgr: &mut Manager, action: Action) -> Response<M> {
+        if self.handle_common(mgr, action) {
+            ((self.on_change)(&self.text)).into()
+        } else {
+            Response::None
+        }
+    }
+
+    fn handle(&mut self, mgr: &mut Manager, _: WidgetId, event: Event) -> Response<M> {
+        let response = match self.handle_drop(mgr, event) {
+            Ok(()) => ((self.on_change)(&self.text)).into(),
+            Err(e) => Manager::handle_generic(self, mgr, e),
+        };
+        self.update_caret(mgr);
+        response
+    }
+}
+
+/// Normalise line breaks and drop other control characters
+fn clean_text(text: &str) -> String {
+    text.replace("\r\n", "\n")
+        .chars()
+        .filter(|c| *c == '\n' || *c == '\t' || !c.is_control())
+        .collect()
+}
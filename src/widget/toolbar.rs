@@ -0,0 +1,334 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Declarative actions and toolbars
+
+use smallvec::SmallVec;
+use std::fmt::{self, Debug};
+use std::rc::Rc;
+
+use super::Row;
+use crate::event::{Action, Handler, Manager, Response, UpdateHandle, VirtualKeyCode};
+use crate::geom::{Coord, Rect};
+use crate::layout::{AxisInfo, SizeRules};
+use crate::macros::Widget;
+use crate::theme::{DrawHandle, SizeHandle, TextClass, TextProperties};
+use crate::{Align, AlignHints, CoreData, Layout, LayoutData, Widget, WidgetCore, WidgetId};
+
+/// Description of a single user action
+///
+/// An action is identified by an `id` of type `A`, which is the message
+/// returned when the action is activated. Descriptions are constructed in
+/// builder style:
+/// ```
+/// # use kas::widget::ActionDesc;
+/// # use kas::event::VirtualKeyCode;
+/// let save = ActionDesc::new(1, "Save")
+///     .with_icon("document-save")
+///     .with_keys(&[VirtualKeyCode::S]);
+/// ```
+#[derive(Clone)]
+pub struct ActionDesc<A> {
+    id: A,
+    label: String,
+    icon: Option<String>,
+    keys: SmallVec<[VirtualKeyCode; 4]>,
+    enabled: Option<Rc<dyn Fn() -> bool>>,
+    checked: Option<Rc<dyn Fn() -> bool>>,
+}
+
+impl<A: Debug> Debug for ActionDesc<A> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "ActionDesc {{ id: {:?}, label: {:?}, icon: {:?}, keys: {:?}, enabled: {}, checked: {} }}",
+            self.id,
+            self.label,
+            self.icon,
+            self.keys,
+            self.is_enabled(),
+            self.is_checked(),
+        )
+    }
+}
+
+impl<A> ActionDesc<A> {
+    /// Construct with the given `id` and `label`
+    pub fn new<S: Into<String>>(id: A, label: S) -> Self {
+        ActionDesc {
+            id,
+            label: label.into(),
+            icon: None,
+            keys: SmallVec::new(),
+            enabled: None,
+            checked: None,
+        }
+    }
+
+    /// Set the name of an icon (chain style)
+    ///
+    /// Icons are not yet drawn by themes; the label is used in all cases.
+    pub fn with_icon<S: Into<String>>(mut self, icon: S) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
+    /// Set accelerator keys (chain style)
+    pub fn with_keys(mut self, keys: &[VirtualKeyCode]) -> Self {
+        self.keys = SmallVec::from_slice(keys);
+        self
+    }
+
+    /// Set a predicate determining whether the action is enabled (chain style)
+    ///
+    /// Without a predicate, the action is always enabled.
+    pub fn with_enabled<F: Fn() -> bool + 'static>(mut self, f: F) -> Self {
+        self.enabled = Some(Rc::new(f));
+        self
+    }
+
+    /// Set a predicate determining whether the action is checked (chain style)
+    ///
+    /// Without a predicate, the action is not checkable.
+    pub fn with_checked<F: Fn() -> bool + 'static>(mut self, f: F) -> Self {
+        self.checked = Some(Rc::new(f));
+        self
+    }
+
+    /// Get the action identifier
+    pub fn id(&self) -> &A {
+        &self.id
+    }
+
+    /// Get the label
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Get the icon name, if any
+    pub fn icon(&self) -> Option<&str> {
+        self.icon.as_deref()
+    }
+
+    /// Get the accelerator keys
+    pub fn keys(&self) -> &[VirtualKeyCode] {
+        &self.keys
+    }
+
+    /// Evaluate the enabled predicate
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.as_ref().map(|f| f()).unwrap_or(true)
+    }
+
+    /// Evaluate the checked predicate
+    pub fn is_checked(&self) -> bool {
+        self.checked.as_ref().map(|f| f()).unwrap_or(false)
+    }
+}
+
+/// A set of user actions
+///
+/// This describes, in one place, the actions available to the user, their
+/// labels, accelerator keys and state predicates. Widgets such as [`Toolbar`]
+/// are generated from this description.
+///
+/// Enabled and checked states are re-evaluated on each call to
+/// [`ActionSet::update`], which notifies all generated widgets via an
+/// [`UpdateHandle`]. Call this whenever application state affecting these
+/// predicates changes.
+#[derive(Clone, Debug)]
+pub struct ActionSet<A> {
+    actions: Vec<ActionDesc<A>>,
+    handle: UpdateHandle,
+}
+
+impl<A> Default for ActionSet<A> {
+    fn default() -> Self {
+        ActionSet::new()
+    }
+}
+
+impl<A> ActionSet<A> {
+    /// Construct an empty set
+    pub fn new() -> Self {
+        ActionSet {
+            actions: vec![],
+            handle: UpdateHandle::new(),
+        }
+    }
+
+    /// Add an action (chain style)
+    pub fn with(mut self, action: ActionDesc<A>) -> Self {
+        self.actions.push(action);
+        self
+    }
+
+    /// Add an action
+    pub fn push(&mut self, action: ActionDesc<A>) {
+        self.actions.push(action);
+    }
+
+    /// Access the list of actions
+    pub fn actions(&self) -> &[ActionDesc<A>] {
+        &self.actions
+    }
+
+    /// Get the update handle
+    pub fn handle(&self) -> UpdateHandle {
+        self.handle
+    }
+
+    /// Re-evaluate state predicates in all generated widgets
+    pub fn update(&self, mgr: &mut Manager) {
+        mgr.trigger_update(self.handle, 0);
+    }
+}
+
+impl<A: Clone + Debug + 'static> ActionSet<A> {
+    /// Generate a toolbar with one button per action
+    pub fn toolbar(&self) -> Toolbar<A> {
+        let buttons = self
+            .actions
+            .iter()
+            .map(|action| ActionButton::new(action.clone(), self.handle))
+            .collect();
+        Toolbar {
+            core: Default::default(),
+            layout_data: Default::default(),
+            row: Row::new(buttons),
+        }
+    }
+}
+
+/// A row of buttons generated from an [`ActionSet`]
+///
+/// Activating a button yields the action's identifier as a message. Disabled
+/// actions are drawn dimmed and cannot be activated; checked actions are drawn
+/// depressed.
+#[layout(single)]
+#[handler(msg = A)]
+#[derive(Clone, Debug, Widget)]
+pub struct Toolbar<A: Clone + Debug + 'static> {
+    #[core]
+    core: CoreData,
+    #[layout_data]
+    layout_data: <Self as LayoutData>::Data,
+    #[widget]
+    row: Row<ActionButton<A>>,
+}
+
+impl<A: Clone + Debug + 'static> Widget for Toolbar<A> {}
+
+/// A button for an [`ActionDesc`]
+#[derive(Clone, Debug, Widget)]
+struct ActionButton<A: Clone + Debug + 'static> {
+    #[core]
+    core: CoreData,
+    b_rect: Rect,
+    action: ActionDesc<A>,
+    handle: UpdateHandle,
+    enabled: bool,
+    checked: bool,
+}
+
+impl<A: Clone + Debug + 'static> ActionButton<A> {
+    fn new(action: ActionDesc<A>, handle: UpdateHandle) -> Self {
+        let enabled = action.is_enabled();
+        let checked = action.is_checked();
+        ActionButton {
+            core: Default::default(),
+            b_rect: Default::default(),
+            action,
+            handle,
+            enabled,
+            checked,
+        }
+    }
+}
+
+impl<A: Clone + Debug + 'static> Widget for ActionButton<A> {
+    fn configure(&mut self, mgr: &mut Manager) {
+        for key in self.action.keys() {
+            mgr.add_accel_key(*key, self.id());
+        }
+        mgr.update_on_handle(self.handle, self.id());
+    }
+
+    fn update_handle(&mut self, mgr: &mut Manager, _: UpdateHandle, _: u64) {
+        let enabled = self.action.is_enabled();
+        let checked = self.action.is_checked();
+        if (enabled, checked) != (self.enabled, self.checked) {
+            self.enabled = enabled;
+            self.checked = checked;
+            mgr.redraw(self.id());
+        }
+    }
+
+    fn allow_focus(&self) -> bool {
+        self.enabled
+    }
+}
+
+impl<A: Clone + Debug + 'static> Layout for ActionButton<A> {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let margin = size_handle.outer_margin();
+        let sides = size_handle.button_surround();
+        SizeRules::fixed(axis.extract_size(sides.0 + sides.1 + margin))
+            + size_handle.text_bound(self.action.label(), TextClass::Button, axis)
+    }
+
+    fn set_rect(&mut self, size_handle: &mut dyn SizeHandle, rect: Rect, _: AlignHints) {
+        self.core.rect = rect;
+        let margin = size_handle.outer_margin();
+        self.b_rect = Rect {
+            pos: rect.pos + margin,
+            size: rect.size - margin - margin,
+        };
+    }
+
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        if self.b_rect.contains(coord) {
+            Some(self.id())
+        } else {
+            None
+        }
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &Manager) {
+        let mut highlights = mgr.highlight_state(self.id());
+        highlights.depress = highlights.depress || self.checked;
+        let props = TextProperties {
+            class: TextClass::Button,
+            horiz: Align::Centre,
+            vert: Align::Centre,
+        };
+        let mut draw = |handle: &mut dyn DrawHandle| {
+            handle.button(self.b_rect, highlights);
+            handle.text(self.b_rect, self.action.label(), props);
+        };
+        if self.enabled {
+            draw(draw_handle);
+        } else {
+            draw_handle.with_opacity(0.5, &mut draw);
+        }
+    }
+}
+
+impl<A: Clone + Debug + 'static> Handler for ActionButton<A> {
+    type Msg = A;
+
+    #[inline]
+    fn activation_via_press(&self) -> bool {
+        true
+    }
+
+    fn handle_action(&mut self, _: &mut Manager, action: Action) -> Response<A> {
+        match action {
+            Action::Activate if self.enabled => self.action.id.clone().into(),
+            Action::Activate => Response::None,
+            a => Response::unhandled_action(a),
+        }
+    }
+}
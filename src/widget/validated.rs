@@ -0,0 +1,229 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Validation state and messages for input widgets
+
+use std::fmt::{self, Debug};
+use std::rc::Rc;
+
+use crate::event::{Event, Handler, Manager, Response};
+use crate::geom::{Coord, Rect, Size};
+use crate::layout::{AxisInfo, SizeRules};
+use crate::macros::Widget;
+use crate::theme::{DrawHandle, SizeHandle, TextClass, ValidationState};
+use crate::{AlignHints, CoreData, Layout, TkAction, Widget, WidgetCore, WidgetId};
+
+type CheckFn<W> = Rc<dyn Fn(&W) -> (ValidationState, String)>;
+
+/// An input widget with a validation state and message
+///
+/// The theme draws a frame around the child widget, coloured according to the
+/// [`ValidationState`], and the message (if any) below it.
+///
+/// The state may be set explicitly via [`Validated::set_validation`] or
+/// computed from the child using a check function (see
+/// [`Validated::with_check`]), which is re-evaluated after each event handled
+/// by the child.
+///
+/// The combined state of all widgets in a form may be queried via
+/// [`WidgetCore::combined_validation`].
+#[derive(Clone, Widget)]
+pub struct Validated<W: Widget> {
+    #[core]
+    core: CoreData,
+    frame_rect: Rect,
+    msg_rect: Rect,
+    state: ValidationState,
+    message: String,
+    check: Option<CheckFn<W>>,
+    #[widget]
+    child: W,
+}
+
+impl<W: Widget> Debug for Validated<W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Validated {{ core: {:?}, state: {:?}, message: {:?}, child: {:?}, ... }}",
+            self.core, self.state, self.message, self.child
+        )
+    }
+}
+
+impl<W: Widget> Validated<W> {
+    /// Construct around a child widget, with no validation state
+    #[inline]
+    pub fn new(child: W) -> Self {
+        Validated {
+            core: Default::default(),
+            frame_rect: Rect::default(),
+            msg_rect: Rect::default(),
+            state: ValidationState::None,
+            message: String::new(),
+            check: None,
+            child,
+        }
+    }
+
+    /// Set the initial state and message (chain style)
+    pub fn with_validation<S: Into<String>>(mut self, state: ValidationState, message: S) -> Self {
+        self.state = state;
+        self.message = message.into();
+        self
+    }
+
+    /// Set a check function (chain style)
+    ///
+    /// The function is called with the child widget on configure and after
+    /// each event handled by the child, and returns the new state and message.
+    pub fn with_check<F>(mut self, check: F) -> Self
+    where
+        F: Fn(&W) -> (ValidationState, String) + 'static,
+    {
+        self.check = Some(Rc::new(check));
+        self
+    }
+
+    /// Access inner widget directly
+    #[inline]
+    pub fn inner(&self) -> &W {
+        &self.child
+    }
+
+    /// Access inner widget directly
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut W {
+        &mut self.child
+    }
+
+    /// Get the validation state
+    #[inline]
+    pub fn state(&self) -> ValidationState {
+        self.state
+    }
+
+    /// Get the validation message
+    #[inline]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Set the validation state and message
+    ///
+    /// An empty message is not drawn. Space for the message is reserved when
+    /// layout is computed, thus adding or removing a message causes the window
+    /// to be reconfigured.
+    pub fn set_validation<S: Into<String>>(
+        &mut self,
+        mgr: &mut Manager,
+        state: ValidationState,
+        message: S,
+    ) {
+        let message = message.into();
+        if message.is_empty() != self.message.is_empty() {
+            mgr.send_action(TkAction::Reconfigure);
+        } else if state != self.state || message != self.message {
+            mgr.redraw(self.id());
+        }
+        self.state = state;
+        self.message = message;
+    }
+
+    /// Re-evaluate the check function, if any
+    pub fn check(&mut self, mgr: &mut Manager) {
+        if let Some(check) = self.check.as_ref() {
+            let (state, message) = check(&self.child);
+            self.set_validation(mgr, state, message);
+        }
+    }
+}
+
+impl<W: Widget> Widget for Validated<W> {
+    fn configure(&mut self, _: &mut Manager) {
+        // Layout follows configuration, thus no action is required here
+        if let Some(check) = self.check.as_ref() {
+            let (state, message) = check(&self.child);
+            self.state = state;
+            self.message = message;
+        }
+    }
+
+    fn validation(&self) -> ValidationState {
+        self.state
+    }
+}
+
+impl<W: Widget> Layout for Validated<W> {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let frame = size_handle.outer_frame();
+        let frame = axis.extract_size(frame.0 + frame.1);
+        let mut rules = self.child.size_rules(size_handle, axis) + frame;
+        if !self.message.is_empty() {
+            let msg_rules = size_handle.text_bound(&self.message, TextClass::Label, axis);
+            if axis.is_horizontal() {
+                rules = rules.max(msg_rules);
+            } else {
+                let margin = axis.extract_size(size_handle.outer_margin());
+                rules = rules + msg_rules + margin;
+            }
+        }
+        rules
+    }
+
+    fn set_rect(&mut self, size_handle: &mut dyn SizeHandle, rect: Rect, align: AlignHints) {
+        self.core.rect = rect;
+        let mut frame_rect = rect;
+        if !self.message.is_empty() {
+            let margin = size_handle.outer_margin().1;
+            let class = TextClass::Label;
+            let height = size_handle
+                .text_size(&self.message, class, Some(rect.size.0))
+                .1;
+            let height = height.min(rect.size.1);
+            frame_rect.size.1 = rect.size.1.saturating_sub(height + margin);
+            let pos = Coord(rect.pos.0, rect.pos.1 + (rect.size.1 - height) as i32);
+            self.msg_rect = Rect::new(pos, Size(rect.size.0, height));
+        }
+        self.frame_rect = frame_rect;
+
+        let frame = size_handle.outer_frame();
+        let child_rect = Rect {
+            pos: frame_rect.pos + frame.0,
+            size: frame_rect.size - (frame.0 + frame.1),
+        };
+        self.child.set_rect(size_handle, child_rect, align);
+    }
+
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        if self.child.rect().contains(coord) {
+            self.child.find_id(coord)
+        } else {
+            Some(self.id())
+        }
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &Manager) {
+        self.child.draw(draw_handle, mgr);
+        draw_handle.validation_frame(self.frame_rect, self.state);
+        if !self.message.is_empty() {
+            draw_handle.validation_message(self.msg_rect, &self.message, self.state);
+        }
+    }
+}
+
+impl<W: Widget + Handler> Handler for Validated<W> {
+    type Msg = <W as Handler>::Msg;
+
+    fn handle(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<Self::Msg> {
+        if id <= self.child.id() {
+            let response = self.child.handle(mgr, id, event);
+            self.check(mgr);
+            response
+        } else {
+            debug_assert!(id == self.id(), "Handler::handle: bad WidgetId");
+            Response::Unhandled(event)
+        }
+    }
+}
@@ -0,0 +1,248 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! View widgets over data models
+
+use super::{Label, List};
+use crate::class::HasText;
+use crate::data::{ListModel, SelectionChanged, SelectionMode, SelectionModel, SingleModel};
+use crate::event::{Event, Handler, Manager, Response, UpdateHandle};
+use crate::geom::{Coord, Rect};
+use crate::layout::{AxisInfo, SizeRules};
+use crate::macros::Widget;
+use crate::theme::{DrawHandle, SizeHandle};
+use crate::{AlignHints, CoreData, Directional, Layout, LayoutData, Widget, WidgetCore, WidgetId};
+
+/// A view over a [`SingleModel`]
+///
+/// Displays the model's value as a text label, updated whenever the model
+/// notifies of a change.
+#[layout(single)]
+#[handler]
+#[derive(Clone, Debug, Widget)]
+pub struct SingleView<M: SingleModel + 'static>
+where
+    M::Item: ToString,
+{
+    #[core]
+    core: CoreData,
+    #[layout_data]
+    layout_data: <Self as LayoutData>::Data,
+    #[widget]
+    label: Label,
+    model: M,
+}
+
+impl<M: SingleModel> Widget for SingleView<M>
+where
+    M::Item: ToString,
+{
+    fn configure(&mut self, mgr: &mut Manager) {
+        if let Some(handle) = self.model.update_handle() {
+            mgr.update_on_handle(handle, self.id());
+        }
+    }
+
+    fn update_handle(&mut self, mgr: &mut Manager, _: UpdateHandle, _: u64) {
+        self.label.set_text(mgr, self.model.get());
+    }
+}
+
+impl<M: SingleModel> SingleView<M>
+where
+    M::Item: ToString,
+{
+    /// Construct a view over the given `model`
+    pub fn new(model: M) -> Self {
+        SingleView {
+            core: Default::default(),
+            layout_data: Default::default(),
+            label: Label::new(model.get()),
+            model,
+        }
+    }
+
+    /// Access the model
+    pub fn model(&self) -> &M {
+        &self.model
+    }
+}
+
+/// A view over a [`ListModel`]
+///
+/// Displays each item of the model as a text label within a row or column,
+/// updated whenever the model notifies of a change.
+///
+/// Items may be selected by clicking when a selection mode is set (see
+/// [`ListView::with_selection_mode`]); a [`SelectionChanged`] message is
+/// returned whenever the user changes the selection.
+///
+/// Changing the number of items triggers a
+/// [reconfigure action](Manager::send_action).
+#[derive(Clone, Debug, Widget)]
+pub struct ListView<D: Directional + 'static, M: ListModel + 'static>
+where
+    M::Item: ToString,
+{
+    #[core]
+    core: CoreData,
+    #[widget]
+    list: List<D, Label>,
+    model: M,
+    selection: SelectionModel,
+}
+
+impl<D: Directional, M: ListModel> Widget for ListView<D, M>
+where
+    M::Item: ToString,
+{
+    fn configure(&mut self, mgr: &mut Manager) {
+        if let Some(handle) = self.model.update_handle() {
+            mgr.update_on_handle(handle, self.id());
+        }
+    }
+
+    fn update_handle(&mut self, mgr: &mut Manager, _: UpdateHandle, _: u64) {
+        let model = &self.model;
+        self.list.resize_with(mgr, model.len(), |_| Label::new(""));
+        for i in 0..self.list.len() {
+            if let Some(item) = ListModel::get(model, i) {
+                self.list[i].set_text(mgr, item);
+            }
+        }
+        if self.selection.truncate(self.list.len()) {
+            mgr.redraw(self.id());
+        }
+    }
+}
+
+impl<D: Directional, M: ListModel> Layout for ListView<D, M>
+where
+    M::Item: ToString,
+{
+    #[inline]
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        self.list.size_rules(size_handle, axis)
+    }
+
+    #[inline]
+    fn set_rect(&mut self, size_handle: &mut dyn SizeHandle, rect: Rect, align: AlignHints) {
+        self.core.rect = rect;
+        self.list.set_rect(size_handle, rect, align);
+    }
+
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        if self.list.rect().contains(coord) {
+            self.list.find_id(coord)
+        } else {
+            Some(self.id())
+        }
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &Manager) {
+        for i in self.selection.iter() {
+            if i < self.list.len() {
+                draw_handle.selection_box(self.list[i].rect());
+            }
+        }
+        self.list.draw(draw_handle, mgr);
+    }
+}
+
+impl<D: Directional, M: ListModel> Handler for ListView<D, M>
+where
+    M::Item: ToString,
+{
+    type Msg = SelectionChanged;
+
+    fn handle(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<Self::Msg> {
+        let event = if id <= self.list.id() {
+            match self.list.handle(mgr, id, event) {
+                Response::Unhandled(event) => event,
+                r => return Response::from(r),
+            }
+        } else {
+            debug_assert!(id == self.id(), "Handler::handle: bad WidgetId");
+            event
+        };
+
+        match event {
+            Event::PressStart { source, .. } if source.is_primary() => {
+                let index = (0..self.list.len()).find(|i| self.list[*i].id() == id);
+                match index {
+                    Some(index) if self.selection.press(index, mgr.modifiers()) => {
+                        mgr.redraw(self.id());
+                        SelectionChanged.into()
+                    }
+                    Some(_) => Response::None,
+                    None => Response::Unhandled(event),
+                }
+            }
+            e if id == self.id() => Manager::handle_generic(self, mgr, e),
+            e => Response::Unhandled(e),
+        }
+    }
+}
+
+impl<D: Directional + Default, M: ListModel> ListView<D, M>
+where
+    M::Item: ToString,
+{
+    /// Construct a view over the given `model`
+    pub fn new(model: M) -> Self {
+        ListView::new_with_direction(D::default(), model)
+    }
+}
+
+impl<D: Directional, M: ListModel> ListView<D, M>
+where
+    M::Item: ToString,
+{
+    /// Construct a view over the given `model` with explicit direction
+    pub fn new_with_direction(direction: D, model: M) -> Self {
+        let labels = (0..model.len())
+            .filter_map(|i| ListModel::get(&model, i))
+            .map(Label::new)
+            .collect();
+        ListView {
+            core: Default::default(),
+            list: List::new_with_direction(direction, labels),
+            model,
+            selection: SelectionModel::default(),
+        }
+    }
+
+    /// Set the selection mode (chain style)
+    ///
+    /// By default, selection is disabled.
+    pub fn with_selection_mode(mut self, mode: SelectionMode) -> Self {
+        self.selection.set_mode(mode);
+        self
+    }
+
+    /// Access the model
+    pub fn model(&self) -> &M {
+        &self.model
+    }
+
+    /// Access the selection
+    pub fn selection(&self) -> &SelectionModel {
+        &self.selection
+    }
+
+    /// Update the selection
+    ///
+    /// The closure `f` should return true when the selection is changed (as
+    /// do the [`SelectionModel`] methods), in which case a redraw is
+    /// requested. No message is returned.
+    pub fn update_selection<F>(&mut self, mgr: &mut Manager, f: F)
+    where
+        F: FnOnce(&mut SelectionModel) -> bool,
+    {
+        if f(&mut self.selection) {
+            mgr.redraw(self.id());
+        }
+    }
+}
@@ -5,6 +5,7 @@
 
 //! Window widgets
 
+use std::any::Any;
 use std::fmt::{self, Debug};
 
 use crate::event::{Callback, Event, Handler, Manager, Response, VoidMsg};
@@ -14,6 +15,10 @@ use crate::macros::Widget;
 use crate::theme::SizeHandle;
 use crate::{CoreData, LayoutData, Widget, WidgetId};
 
+type WindowFn<W> = &'static dyn Fn(&mut W, &mut Manager);
+type Inspector<W> = &'static dyn Fn(&mut W, &mut Manager, &dyn Any);
+type FinalCallback = &'static dyn Fn(Box<dyn kas::Window>, &mut Manager);
+
 /// The main instantiation of the [`Window`] trait.
 #[widget]
 #[layout(single)]
@@ -26,10 +31,12 @@ pub struct Window<W: Widget + 'static> {
     enforce_min: bool,
     enforce_max: bool,
     title: String,
+    name: Option<String>,
     #[widget]
     w: W,
-    fns: Vec<(Callback, &'static dyn Fn(&mut W, &mut Manager))>,
-    final_callback: Option<&'static dyn Fn(Box<dyn kas::Window>, &mut Manager)>,
+    fns: Vec<(Callback, WindowFn<W>)>,
+    inspectors: Vec<Inspector<W>>,
+    final_callback: Option<FinalCallback>,
 }
 
 impl<W: Widget> Debug for Window<W> {
@@ -46,7 +53,7 @@ impl<W: Widget> Debug for Window<W> {
                 write!(f, ", ({:?}, <Fn>)", next.0)?;
             }
         }
-        write!(f, "] }}")
+        write!(f, "], inspectors: {} }}", self.inspectors.len())
     }
 }
 
@@ -58,8 +65,10 @@ impl<W: Widget + Clone> Clone for Window<W> {
             enforce_min: self.enforce_min,
             enforce_max: self.enforce_max,
             title: self.title.clone(),
+            name: self.name.clone(),
             w: self.w.clone(),
             fns: self.fns.clone(),
+            inspectors: self.inspectors.clone(),
             final_callback: self.final_callback.clone(),
         }
     }
@@ -74,12 +83,23 @@ impl<W: Widget> Window<W> {
             enforce_min: true,
             enforce_max: false,
             title: title.to_string(),
+            name: None,
             w,
             fns: Vec::new(),
+            inspectors: Vec::new(),
             final_callback: None,
         }
     }
 
+    /// Set the window name (chain style)
+    ///
+    /// Named windows may have their placement saved and restored by the
+    /// toolkit; see [`kas::Window::name`].
+    pub fn with_name<S: Into<String>>(mut self, name: S) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
     /// Configure whether min/max dimensions are forced
     ///
     /// By default, the min size is enforced but not the max.
@@ -94,6 +114,25 @@ impl<W: Widget> Window<W> {
         self.fns.push((condition, f));
     }
 
+    /// Add an inspector for messages
+    ///
+    /// Inspectors are called, in the order added, for every message returned
+    /// by this window's widget (via [`Response::Msg`]) and every message
+    /// pushed via [`Manager::push_msg`] in any window. Returned messages are
+    /// not passed any further. Inspectors may down-cast the message to the
+    /// types of interest, for example:
+    /// ```ignore
+    /// window.add_inspector(&|w, mgr, msg| {
+    ///     if let Some(SaveRequested) = msg.downcast_ref() {
+    ///         w.save(mgr);
+    ///     }
+    /// });
+    /// ```
+    /// The closure must be passed by reference.
+    pub fn add_inspector(&mut self, f: &'static dyn Fn(&mut W, &mut Manager, &dyn Any)) {
+        self.inspectors.push(f);
+    }
+
     /// Set a callback to be called when the window is closed.
     ///
     /// This callback assumes ownership of self, with the advantages and
@@ -106,20 +145,36 @@ impl<W: Widget> Window<W> {
     }
 }
 
-impl<W: Widget + Handler<Msg = VoidMsg> + 'static> Handler for Window<W> {
+impl<W: Handler + 'static> Handler for Window<W>
+where
+    W::Msg: 'static,
+{
     type Msg = VoidMsg;
 
     fn handle(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<Self::Msg> {
-        // The window itself doesn't handle events, so we can just pass through
-        self.w.handle(mgr, id, event)
+        // The window itself doesn't handle events; messages are inspected
+        match self.w.handle(mgr, id, event).try_into() {
+            Ok(r) => r,
+            Err(msg) => {
+                kas::Window::inspect_msg(self, mgr, &msg);
+                Response::None
+            }
+        }
     }
 }
 
-impl<W: Widget + Handler<Msg = VoidMsg> + 'static> kas::Window for Window<W> {
+impl<W: Handler + 'static> kas::Window for Window<W>
+where
+    W::Msg: 'static,
+{
     fn title(&self) -> &str {
         &self.title
     }
 
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
     fn resize(
         &mut self,
         size_handle: &mut dyn SizeHandle,
@@ -145,4 +200,10 @@ impl<W: Widget + Handler<Msg = VoidMsg> + 'static> kas::Window for Window<W> {
         let cb = &mut self.fns[index].1;
         cb(&mut self.w, mgr);
     }
+
+    fn inspect_msg(&mut self, mgr: &mut Manager, msg: &dyn Any) {
+        for f in &self.inspectors {
+            f(&mut self.w, mgr, msg);
+        }
+    }
 }
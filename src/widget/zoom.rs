@@ -0,0 +1,270 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Zoomable view
+
+use crate::draw::Transform;
+use crate::event::{Action, CursorIcon, Event, Handler, Manager, Response, ScrollDelta};
+use crate::geom::{Coord, Rect, Size};
+use crate::layout::{AxisInfo, SizeRules};
+use crate::macros::Widget;
+use crate::theme::{DrawHandle, SizeHandle, TextClass};
+use crate::{AlignHints, CoreData, Layout, TkAction, Widget, WidgetCore, WidgetId};
+
+/// A zoomable, pannable view over a child widget
+///
+/// The child is drawn scaled by a *zoom* factor and translated by a *pan*
+/// offset; events are mapped back to the child's coordinate space, thus the
+/// child may be interacted with as usual.
+///
+/// Input not handled by the child controls the view:
+///
+/// -   the mouse wheel (or touchpad scrolling) pans the view; with `Ctrl`
+///     held, it zooms instead
+/// -   dragging with the primary mouse button or a single finger pans the view
+/// -   a two-finger pinch zooms the view
+///
+/// The child is sized as if it were not zoomed, at least as large as the view.
+#[derive(Clone, Debug, Widget)]
+pub struct ZoomView<W: Widget> {
+    #[core]
+    core: CoreData,
+    min_child_size: Size,
+    zoom: f32,
+    pan: (f32, f32),
+    zoom_limits: (f32, f32),
+    scroll_rate: f32,
+    #[widget]
+    child: W,
+}
+
+impl<W: Widget> ZoomView<W> {
+    /// Construct a new view around a child widget
+    #[inline]
+    pub fn new(child: W) -> Self {
+        ZoomView {
+            core: Default::default(),
+            min_child_size: Size::ZERO,
+            zoom: 1.0,
+            pan: (0.0, 0.0),
+            zoom_limits: (0.1, 10.0),
+            scroll_rate: 30.0,
+            child,
+        }
+    }
+
+    /// Set the minimum and maximum zoom factors (chain style)
+    ///
+    /// By default, the zoom is limited to the range `0.1..=10.0`.
+    #[inline]
+    pub fn with_zoom_limits(mut self, min: f32, max: f32) -> Self {
+        self.zoom_limits = (min, max);
+        self.zoom = self.zoom.max(min).min(max);
+        self
+    }
+
+    /// Access inner widget directly
+    #[inline]
+    pub fn inner(&self) -> &W {
+        &self.child
+    }
+
+    /// Access inner widget directly
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut W {
+        &mut self.child
+    }
+
+    /// Get the zoom factor
+    #[inline]
+    pub fn zoom(&self) -> f32 {
+        self.zoom
+    }
+
+    /// Set the zoom factor, keeping the centre of the view fixed
+    ///
+    /// The zoom is clamped to the limits (see [`ZoomView::with_zoom_limits`]).
+    pub fn set_zoom(&mut self, mgr: &mut Manager, zoom: f32) {
+        let rect = self.core.rect;
+        let centre = rect.pos + Coord(rect.size.0 as i32 / 2, rect.size.1 as i32 / 2);
+        self.zoom_about(mgr, zoom, centre);
+    }
+
+    /// Get the pan offset
+    #[inline]
+    pub fn pan(&self) -> (f32, f32) {
+        self.pan
+    }
+
+    /// Set the pan offset
+    pub fn set_pan(&mut self, mgr: &mut Manager, pan: (f32, f32)) {
+        if pan != self.pan {
+            self.pan = pan;
+            mgr.send_action(TkAction::RegionMoved);
+        }
+    }
+
+    /// Reset zoom to 1 and pan to zero
+    pub fn reset(&mut self, mgr: &mut Manager) {
+        if (self.zoom, self.pan) != (1.0, (0.0, 0.0)) {
+            self.zoom = 1.0;
+            self.pan = (0.0, 0.0);
+            mgr.send_action(TkAction::RegionMoved);
+        }
+    }
+
+    /// Get the transform from the child's coordinate space to ours
+    ///
+    /// The child is scaled about the position of this widget, then panned.
+    pub fn transform(&self) -> Transform {
+        let pos = self.core.rect.pos;
+        Transform::scaling(self.zoom)
+            .about((pos.0 as f32, pos.1 as f32))
+            .then(Transform::translation(self.pan.0, self.pan.1))
+    }
+
+    /// Zoom, keeping the point at `centre` fixed
+    fn zoom_about(&mut self, mgr: &mut Manager, zoom: f32, centre: Coord) {
+        let zoom = zoom.max(self.zoom_limits.0).min(self.zoom_limits.1);
+        if zoom == self.zoom {
+            return;
+        }
+        let centre = (centre.0 as f32, centre.1 as f32);
+        let c = self.transform().inverse().map_point(centre);
+        let pos = self.core.rect.pos;
+        let pos = (pos.0 as f32, pos.1 as f32);
+        self.zoom = zoom;
+        self.pan = (
+            centre.0 - pos.0 - (c.0 - pos.0) * zoom,
+            centre.1 - pos.1 - (c.1 - pos.1) * zoom,
+        );
+        mgr.send_action(TkAction::RegionMoved);
+    }
+
+    /// Pan the view by `delta`
+    fn pan_by(&mut self, mgr: &mut Manager, delta: Coord) {
+        let pan = (self.pan.0 + delta.0 as f32, self.pan.1 + delta.1 as f32);
+        self.set_pan(mgr, pan);
+    }
+}
+
+impl<W: Widget> Widget for ZoomView<W> {}
+
+impl<W: Widget> Layout for ZoomView<W> {
+    fn size_rules(&mut self, size_handle: &mut dyn SizeHandle, axis: AxisInfo) -> SizeRules {
+        let mut rules = self.child.size_rules(size_handle, axis);
+        if axis.is_horizontal() {
+            self.min_child_size.0 = rules.min_size();
+        } else {
+            self.min_child_size.1 = rules.min_size();
+        }
+        let line_height = size_handle.line_height(TextClass::Label);
+        self.scroll_rate = 3.0 * line_height as f32;
+        rules.reduce_min_to(line_height);
+        rules
+    }
+
+    fn set_rect(&mut self, size_handle: &mut dyn SizeHandle, rect: Rect, _: AlignHints) {
+        self.core.rect = rect;
+        let child_size = rect.size.max(self.min_child_size);
+        let child_rect = Rect::new(rect.pos, child_size);
+        self.child
+            .set_rect(size_handle, child_rect, AlignHints::NONE);
+    }
+
+    fn find_id(&self, coord: Coord) -> Option<WidgetId> {
+        if !self.core.rect.contains(coord) {
+            return None;
+        }
+        let child_coord = self.transform().inverse().map_coord(coord);
+        if self.child.rect().contains(child_coord) {
+            if let Some(id) = self.child.find_id(child_coord) {
+                return Some(id);
+            }
+        }
+        Some(self.id())
+    }
+
+    fn child_region(&self, id: WidgetId) -> Option<(Rect, Transform)> {
+        if id == self.child.id() {
+            Some((self.core.rect, self.transform()))
+        } else {
+            None
+        }
+    }
+
+    fn draw(&self, draw_handle: &mut dyn DrawHandle, mgr: &Manager) {
+        draw_handle.transform_region(self.core.rect, self.transform(), &mut |handle| {
+            self.child.draw(handle, mgr)
+        });
+    }
+}
+
+impl<W: Widget + Handler> Handler for ZoomView<W> {
+    type Msg = <W as Handler>::Msg;
+
+    fn handle(&mut self, mgr: &mut Manager, id: WidgetId, event: Event) -> Response<Self::Msg> {
+        // Events not handled by the child, in the child's coordinate space
+        let unhandled = |w: &mut Self, mgr: &mut Manager, event| match event {
+            Event::Action(Action::Scroll(delta)) if mgr.modifiers().ctrl => {
+                let steps = match delta {
+                    ScrollDelta::LineDelta(_, y) => y,
+                    ScrollDelta::PixelDelta(d) => 3.0 * d.1 as f32 / w.scroll_rate,
+                };
+                let rect = w.core.rect;
+                let centre = rect.pos + Coord(rect.size.0 as i32 / 2, rect.size.1 as i32 / 2);
+                w.zoom_about(mgr, w.zoom * 1.1f32.powf(steps), centre);
+                Response::None
+            }
+            Event::Action(Action::Scroll(delta)) => {
+                let d = match delta {
+                    ScrollDelta::LineDelta(x, y) => (-w.scroll_rate * x, w.scroll_rate * y),
+                    ScrollDelta::PixelDelta(d) => (d.0 as f32, d.1 as f32),
+                };
+                w.set_pan(mgr, (w.pan.0 + d.0, w.pan.1 + d.1));
+                Response::None
+            }
+            Event::PressStart { source, coord, .. } if source.is_primary() => {
+                let coord = w.transform().map_coord(coord);
+                mgr.request_pan_grab(source, w, coord, Some(CursorIcon::Grabbing));
+                Response::None
+            }
+            e => Response::Unhandled(e),
+        };
+
+        if id == self.id() {
+            return match event {
+                Event::PressMove { delta, .. } => {
+                    self.pan_by(mgr, delta);
+                    Response::None
+                }
+                Event::Pan {
+                    coord,
+                    delta,
+                    scale,
+                    ..
+                } => {
+                    self.pan_by(mgr, delta);
+                    if scale != 1.0 {
+                        self.zoom_about(mgr, self.zoom * scale, coord);
+                    }
+                    Response::None
+                }
+                Event::PressEnd { .. } => Response::None,
+                e => {
+                    let e = e.transform(self.transform().inverse());
+                    unhandled(self, mgr, e)
+                }
+            };
+        }
+
+        let event = event.transform(self.transform().inverse());
+        match self.child.handle(mgr, id, event) {
+            Response::None => Response::None,
+            Response::Unhandled(event) => unhandled(self, mgr, event),
+            e => e,
+        }
+    }
+}